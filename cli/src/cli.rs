@@ -6,6 +6,7 @@ use crate::modules::node::Node;
 use crate::notifier::{Notification, Notifier};
 use crate::result::Result;
 use waglayla_daemon::{DaemonEvent, DaemonKind, Daemons};
+use waglayla_wallet_core::error::Error as WalletError;
 use waglayla_wallet_core::rpc::DynRpcApi;
 use waglayla_wallet_core::storage::{IdT, PrvKeyDataInfo};
 use waglayla_wrpc_client::WaglaylaRpcClient;
@@ -467,6 +468,19 @@ impl WaglaylaCli {
 
                                     this.term().refresh_prompt();
                                 }
+                                Events::Consolidation {
+                                    id,
+                                    summary,
+                                } => {
+                                    tprintln!(this, "{NOTIFY} {} {}: {summary}",style("consolidation".pad_to_width(8)).blue(), id.short());
+                                    this.term().refresh_prompt();
+                                }
+                                Events::ConsolidationError {
+                                    id,
+                                    message,
+                                } => {
+                                    terrorln!(this, "Consolidation {}: {message}", id.short());
+                                }
                             }
                         }
                     }
@@ -523,6 +537,22 @@ impl WaglaylaCli {
         }
     }
 
+    /// Resolves an address or an address book alias of the current network.
+    pub async fn resolve_address(&self, address_or_alias: &str) -> Result<Address> {
+        if address_or_alias.contains(':') {
+            return Ok(Address::try_from(address_or_alias)?);
+        }
+
+        let prefix = AddressPrefix::from(self.wallet().network_id()?);
+        let entry = self
+            .store()
+            .as_address_book_store()?
+            .load(prefix, address_or_alias)
+            .await?
+            .ok_or_else(|| WalletError::AddressBookEntryNotFound(address_or_alias.to_string()))?;
+        Ok(entry.address.clone())
+    }
+
     pub async fn prompt_account(&self) -> Result<Arc<dyn Account>> {
         self.select_account_with_args(false).await
     }
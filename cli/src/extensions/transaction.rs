@@ -21,6 +21,8 @@ impl TransactionTypeExtension for TransactionKind {
             TransactionKind::TransferIncoming => style(s).green().to_string(),
             TransactionKind::TransferOutgoing => style(s).red().to_string(),
             TransactionKind::Change => style(s).dim().to_string(),
+            TransactionKind::Consolidation => style(s).dim().to_string(),
+            TransactionKind::FeeBump => style(s).dim().to_string(),
         }
     }
 
@@ -140,7 +142,8 @@ impl TransactionExtension for TransactionRecord {
             TransactionData::Outgoing { fees, aggregate_input_value, transaction, payment_value, change_value, .. }
             | TransactionData::Batch { fees, aggregate_input_value, transaction, payment_value, change_value, .. }
             | TransactionData::TransferIncoming { fees, aggregate_input_value, transaction, payment_value, change_value, .. }
-            | TransactionData::TransferOutgoing { fees, aggregate_input_value, transaction, payment_value, change_value, .. } => {
+            | TransactionData::TransferOutgoing { fees, aggregate_input_value, transaction, payment_value, change_value, .. }
+            | TransactionData::Consolidation { fees, aggregate_input_value, transaction, payment_value, change_value, .. } => {
                 if let Some(payment_value) = payment_value {
                     lines.push(format!(
                         "{:>4}Payment: {}  Used: {}  Fees: {}  Change: {}  UTXOs: [{}↠{}]",
@@ -173,6 +176,20 @@ impl TransactionExtension for TransactionRecord {
                     }
                 }
             }
+            TransactionData::FeeBump { fees, aggregate_input_value, transaction, change_value, .. } => {
+                let parent =
+                    transaction.inputs.first().map(|input| input.previous_outpoint.transaction_id.to_string()).unwrap_or_default();
+                lines.push(format!("{:>4}Parent: {}", "", style(parent).dim()));
+                lines.push(format!(
+                    "{:>4}Used: {}  Fees: {}  Change: {}  UTXOs: [{}↠{}]",
+                    "",
+                    style(sompi_to_waglayla_string(*aggregate_input_value)).blue(),
+                    style(sompi_to_waglayla_string(*fees)).red(),
+                    style(sompi_to_waglayla_string(*change_value)).green(),
+                    transaction.inputs.len(),
+                    transaction.outputs.len(),
+                ));
+            }
         }
 
         lines
@@ -1,11 +1,12 @@
 use crate::imports::*;
+use waglayla_wallet_core::error::Error as WalletError;
 
 #[derive(Default, Handler)]
 #[help("Show or generate a new address for the current wallet account")]
 pub struct Address;
 
 impl Address {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<WaglaylaCli>()?;
 
         if argv.is_empty() {
@@ -21,6 +22,10 @@ impl Address {
                     tprintln!(ctx, "Generating new address for account {}", style(ident).cyan());
                     tprintln!(ctx, "{}", style(new_address).blue());
                 }
+                "book" => {
+                    argv.remove(0);
+                    self.address_book(ctx, argv).await?;
+                }
                 v => {
                     tprintln!(ctx, "unknown command: '{v}'\r\n");
                     return self.display_help(ctx, argv).await;
@@ -31,8 +36,105 @@ impl Address {
         Ok(())
     }
 
+    async fn address_book(self: &Arc<Self>, ctx: Arc<WaglaylaCli>, mut argv: Vec<String>) -> Result<()> {
+        let wallet = ctx.wallet();
+        if !wallet.is_open() {
+            return Err(Error::WalletIsNotOpen);
+        }
+        let network_id = wallet.network_id()?;
+
+        let op = if argv.is_empty() { "list".to_string() } else { argv.remove(0) };
+        match op.as_str() {
+            "list" | "search" => {
+                let search = (!argv.is_empty()).then(|| argv.join(" "));
+                let AddressBookEnumerateResponse { entries } =
+                    wallet.address_book_enumerate_call(AddressBookEnumerateRequest { network_id: Some(network_id), search }).await?;
+                if entries.is_empty() {
+                    tprintln!(ctx, "No address book entries found on {network_id}");
+                }
+                for entry in entries {
+                    let labels = if entry.labels.is_empty() { "".to_string() } else { format!(" [{}]", entry.labels.join(", ")) };
+                    tprintln!(ctx, "{}: {} {}{}", style(&entry.alias).cyan(), style(&entry.address).blue(), entry.title, labels);
+                }
+            }
+            "add" => {
+                if argv.len() < 2 {
+                    tprintln!(ctx, "usage: address book add <alias> <address> [<title>]");
+                    return Ok(());
+                }
+                let alias = argv.remove(0);
+                let address = waglayla_addresses::Address::try_from(argv.remove(0).as_str())?;
+                let entry = AddressBookEntry::try_new(&alias, &argv.join(" "), address, vec![])?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                wallet.address_book_add_call(AddressBookAddRequest { wallet_secret, entry }).await?;
+                tprintln!(ctx, "Added address book entry '{alias}'");
+            }
+            "update" => {
+                if argv.len() < 2 {
+                    tprintln!(ctx, "usage: address book update <alias> <alias|address|title|labels> [<value>]");
+                    return Ok(());
+                }
+                let alias = argv.remove(0);
+                let field = argv.remove(0);
+                let value = argv.join(" ");
+                let mut entry = ctx
+                    .store()
+                    .as_address_book_store()?
+                    .load(AddressPrefix::from(network_id), &alias)
+                    .await?
+                    .ok_or_else(|| WalletError::AddressBookEntryNotFound(alias.clone()))?
+                    .as_ref()
+                    .clone();
+                match field.as_str() {
+                    "alias" => entry.alias = value,
+                    "address" => entry.address = waglayla_addresses::Address::try_from(value.as_str())?,
+                    "title" => entry.title = value,
+                    "labels" => entry.labels = value.split(',').map(String::from).collect(),
+                    _ => {
+                        tprintln!(ctx, "unknown field '{field}', expected one of: alias, address, title, labels");
+                        return Ok(());
+                    }
+                }
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                wallet
+                    .address_book_update_call(AddressBookUpdateRequest { wallet_secret, network_id, alias: alias.clone(), entry })
+                    .await?;
+                tprintln!(ctx, "Updated address book entry '{alias}'");
+            }
+            "remove" => {
+                if argv.len() != 1 {
+                    tprintln!(ctx, "usage: address book remove <alias>");
+                    return Ok(());
+                }
+                let alias = argv.remove(0);
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                wallet.address_book_remove_call(AddressBookRemoveRequest { wallet_secret, network_id, alias: alias.clone() }).await?;
+                tprintln!(ctx, "Removed address book entry '{alias}'");
+            }
+            v => {
+                tprintln!(ctx, "unknown command: 'address book {v}'\r\n");
+                return self.clone().display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
     async fn display_help(self: Arc<Self>, ctx: Arc<WaglaylaCli>, _argv: Vec<String>) -> Result<()> {
-        ctx.term().help(&[("address [new]", "Show current or generate a new account address")], None)?;
+        ctx.term().help(
+            &[
+                ("address [new]", "Show current or generate a new account address"),
+                ("address book [list]", "List address book entries of the current network"),
+                ("address book search <text>", "Fuzzy search address book entries by alias, title, label or address"),
+                ("address book add <alias> <address> [<title>]", "Add an address book entry"),
+                (
+                    "address book update <alias> <alias|address|title|labels> <value>",
+                    "Update an address book entry (labels are comma separated)",
+                ),
+                ("address book remove <alias>", "Remove an address book entry"),
+            ],
+            None,
+        )?;
 
         Ok(())
     }
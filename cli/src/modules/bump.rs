@@ -0,0 +1,32 @@
+use crate::imports::*;
+use waglayla_consensus_core::tx::TransactionId;
+
+#[derive(Default, Handler)]
+#[help("Raise the fee rate of an unconfirmed transaction (child-pays-for-parent)")]
+pub struct Bump;
+
+impl Bump {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<WaglaylaCli>()?;
+
+        if argv.len() != 2 {
+            tprintln!(ctx, "usage: bump <transaction id> <fee rate (SOMPI per gram)>");
+            return Ok(());
+        }
+
+        let transaction_id = TransactionId::from_hex(argv[0].as_str())?;
+        let fee_rate = argv[1].parse::<f64>().map_err(|_| Error::custom(format!("invalid fee rate '{}'", argv[1])))?;
+        if !fee_rate.is_finite() || fee_rate <= 0.0 {
+            return Err(Error::custom("fee rate must be a positive number"));
+        }
+
+        let account = ctx.wallet().account()?;
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        let abortable = Abortable::default();
+        let summary = account.fee_bump(transaction_id, fee_rate, wallet_secret, payment_secret, &abortable).await?;
+
+        tprintln!(ctx, "Bump - {summary}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use crate::imports::*;
+use waglayla_wallet_core::utxo::{ConsolidationPolicy, ConsolidationWindow};
+
+#[derive(Default, Handler)]
+#[help("Configure automatic UTXO consolidation of the current account")]
+pub struct Consolidation;
+
+impl Consolidation {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<WaglaylaCli>()?;
+
+        let wallet = ctx.wallet();
+        if !wallet.is_open() {
+            return Err(Error::WalletIsNotOpen);
+        }
+
+        let account = ctx.wallet().account()?;
+        let op = if argv.is_empty() { "show".to_string() } else { argv.remove(0) };
+        match op.as_str() {
+            "show" => {
+                if let Some(policy) = account.consolidation_policy() {
+                    let state = if wallet.is_consolidation_armed(account.id()) {
+                        style("enabled").green()
+                    } else {
+                        style("inactive, run 'consolidation enable' to resume").yellow()
+                    };
+                    tprintln!(ctx, "Consolidation ({state}) - {policy}");
+                } else {
+                    tprintln!(ctx, "Automatic consolidation is not configured for this account");
+                }
+            }
+            "enable" => {
+                let policy = match self.parse_policy(&argv)? {
+                    Some(policy) => policy,
+                    None => account.consolidation_policy().ok_or_else(|| {
+                        Error::custom(
+                            "usage: consolidation enable <utxo count> [<min value>] [<max fee %>] [<max utxo value>] [<window> ...]",
+                        )
+                    })?,
+                };
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                wallet
+                    .accounts_consolidation_call(AccountsConsolidationRequest {
+                        account_id: *account.id(),
+                        wallet_secret,
+                        payment_secret,
+                        policy: Some(policy.clone()),
+                        retain_secrets: true,
+                    })
+                    .await?;
+                tprintln!(ctx, "Consolidation enabled - {policy}");
+            }
+            "disable" => {
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                wallet
+                    .accounts_consolidation_call(AccountsConsolidationRequest {
+                        account_id: *account.id(),
+                        wallet_secret,
+                        payment_secret: None,
+                        policy: None,
+                        retain_secrets: false,
+                    })
+                    .await?;
+                tprintln!(ctx, "Consolidation disabled");
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses `<utxo count> [<min value>] [<max fee %>] [<max utxo value>] [<window> ...]`
+    /// where windows are specified as `<start hour>-<end hour>`.
+    fn parse_policy(&self, argv: &[String]) -> Result<Option<ConsolidationPolicy>> {
+        let Some(utxo_count_threshold) = argv.first() else {
+            return Ok(None);
+        };
+
+        let mut policy = ConsolidationPolicy {
+            utxo_count_threshold: utxo_count_threshold
+                .parse()
+                .map_err(|_| Error::custom(format!("invalid UTXO count '{utxo_count_threshold}'")))?,
+            ..Default::default()
+        };
+
+        let (windows, args): (Vec<_>, Vec<_>) = argv[1..].iter().partition(|arg| arg.contains('-'));
+        if let Some(min_value) = args.first() {
+            policy.min_value = try_parse_required_waglayla_as_sompi_u64(Some(min_value))?;
+        }
+        if let Some(max_fee) = args.get(1) {
+            let max_fee = max_fee.trim_end_matches('%');
+            let max_fee = max_fee.parse::<f64>().map_err(|_| Error::custom(format!("invalid max fee rate '{max_fee}'")))?;
+            policy.max_fee_rate = max_fee / 100.0;
+        }
+        if let Some(max_utxo_value) = args.get(2) {
+            policy.max_utxo_value = Some(try_parse_required_nonzero_waglayla_as_sompi_u64(Some(max_utxo_value))?);
+        }
+        policy.windows =
+            windows.into_iter().map(|window| window.parse::<ConsolidationWindow>()).collect::<std::result::Result<_, _>>()?;
+
+        Ok(Some(policy))
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<WaglaylaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("consolidation [show]", "Show the consolidation policy of the current account"),
+                (
+                    "consolidation enable <utxo count> [<min value>] [<max fee %>] [<max utxo value>] [<window> ...]",
+                    "Set and enable the consolidation policy",
+                ),
+                ("consolidation disable", "Disable and remove the consolidation policy"),
+            ],
+            None,
+        )?;
+        tprintln!(ctx);
+        tprintln!(ctx, "UTXOs are consolidated into the account change address once the number of mature UTXOs");
+        tprintln!(ctx, "(valued at most <max utxo value>) reaches <utxo count> and their total value reaches");
+        tprintln!(ctx, "<min value>, as long as fees do not exceed <max fee %> (1% by default) of that value.");
+        tprintln!(ctx, "Windows restrict the consolidation to UTC hours, e.g. '22-4' (any time by default).");
+        tprintln!(ctx, "The wallet password is kept in memory while the wallet is open, 'consolidation enable'");
+        tprintln!(ctx, "without arguments resumes the stored policy after the wallet is reopened.");
+        tprintln!(ctx);
+
+        Ok(())
+    }
+}
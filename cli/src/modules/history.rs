@@ -1,7 +1,11 @@
 use crate::imports::*;
+use std::path::PathBuf;
 use waglayla_consensus_core::tx::TransactionId;
 use waglayla_wallet_core::error::Error as WalletError;
-use waglayla_wallet_core::storage::Binding;
+use waglayla_wallet_core::storage::transaction::{utc_date_to_unixtime, TransactionExportFormat};
+use waglayla_wallet_core::storage::{Binding, TransactionKind};
+use workflow_store::fs;
+
 #[derive(Default, Handler)]
 #[help("Display transaction history")]
 pub struct History;
@@ -45,6 +49,9 @@ impl History {
 
                 return Ok(());
             }
+            "export" => {
+                return self.export(&ctx, &account, argv).await;
+            }
             "list" => {
                 let last = if argv.is_empty() { None } else { argv[0].parse::<usize>().ok() };
                 (last, false)
@@ -135,12 +142,69 @@ impl History {
         Ok(())
     }
 
+    /// Exports the account transaction history into a CSV or JSON file (selected by the file extension),
+    /// optionally restricted by `from <YYYY-MM-DD>`, `to <YYYY-MM-DD>` (inclusive) and `kind <kind>[,<kind>...]`.
+    async fn export(&self, ctx: &Arc<WaglaylaCli>, account: &Arc<dyn Account>, mut argv: Vec<String>) -> Result<()> {
+        if argv.is_empty() {
+            return Err(Error::custom("usage: history export <file.csv|file.json> [from <date>] [to <date>] [kind <kind>,...]"));
+        }
+
+        let filename = PathBuf::from(argv.remove(0));
+        let format = match filename.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => TransactionExportFormat::Json,
+            _ => TransactionExportFormat::Csv,
+        };
+
+        let mut from_unixtime_msec = None;
+        let mut to_unixtime_msec = None;
+        let mut filter = None;
+        let mut args = argv.iter();
+        while let Some(arg) = args.next() {
+            let value = args.next().ok_or_else(|| Error::custom(format!("missing value for '{arg}'")))?;
+            match arg.as_str() {
+                "from" => from_unixtime_msec = Some(utc_date_to_unixtime(value)?),
+                // the end date is inclusive
+                "to" => to_unixtime_msec = Some(utc_date_to_unixtime(value)? + 24 * 60 * 60 * 1000),
+                "kind" => {
+                    filter = Some(
+                        value
+                            .split(',')
+                            .map(|kind| kind.trim().parse::<TransactionKind>())
+                            .collect::<std::result::Result<Vec<_>, _>>()?,
+                    )
+                }
+                v => return Err(Error::custom(format!("unknown export option '{v}'"))),
+            }
+        }
+
+        let TransactionsExportResponse { total, data, .. } = ctx
+            .wallet()
+            .transactions_export_call(TransactionsExportRequest {
+                account_id: *account.id(),
+                network_id: ctx.wallet().network_id()?,
+                format,
+                filter,
+                from_unixtime_msec,
+                to_unixtime_msec,
+            })
+            .await?;
+
+        fs::write_string(&filename, &data).await?;
+        tprintln!(ctx, "Exported {} transactions to '{}'", total.separated_string(), filename.display());
+
+        Ok(())
+    }
+
     async fn display_help(self: Arc<Self>, ctx: Arc<WaglaylaCli>, _argv: Vec<String>) -> Result<()> {
         ctx.term().help(
             &[
                 ("list [<last N transactions>]", "List transactions"),
                 ("details [<last N transactions>]", "List transactions with UTXO details"),
                 ("lookup <transaction id>", "Lookup transaction in the history"),
+                (
+                    "export <file.csv|file.json> [from <YYYY-MM-DD>] [to <YYYY-MM-DD>] [kind <kind>,...]",
+                    "Export transactions to a CSV or JSON file",
+                ),
             ],
             None,
         )?;
@@ -3,8 +3,10 @@ use crate::imports::*;
 pub mod account;
 pub mod address;
 pub mod broadcast;
+pub mod bump;
 pub mod close;
 pub mod connect;
+pub mod consolidation;
 #[path = "create-unsigned-tx.rs"]
 pub mod create_unsigned_tx;
 pub mod details;
@@ -25,6 +27,7 @@ pub mod mute;
 pub mod network;
 pub mod node;
 pub mod open;
+pub mod payout;
 pub mod ping;
 pub mod reload;
 pub mod rpc;
@@ -32,6 +35,7 @@ pub mod select;
 pub mod send;
 pub mod server;
 pub mod settings;
+pub mod shares;
 pub mod sign;
 pub mod start;
 pub mod stop;
@@ -40,6 +44,7 @@ pub mod sweep;
 pub mod theme;
 pub mod track;
 pub mod transfer;
+pub mod utxo;
 pub mod wallet;
 
 // this module is registered manually within
@@ -56,9 +61,9 @@ pub fn register_handlers(cli: &Arc<WaglaylaCli>) -> Result<()> {
         cli,
         cli.handlers(),
         [
-            account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, reload, select, send, server, settings, sweep, track, transfer,
-            wallet,
+            account, address, bump, close, connect, consolidation, details, disconnect, estimate, exit, export, guide, help, history, rpc, list,
+            miner, message, monitor, mute, network, node, open, payout, ping, reload, select, send, server, settings, shares, sweep,
+            track, transfer, utxo, wallet,
             // halt,
             // theme,  start, stop
         ]
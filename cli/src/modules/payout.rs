@@ -0,0 +1,163 @@
+use crate::imports::*;
+use std::path::{Path, PathBuf};
+use waglayla_consensus_core::Hash;
+use waglayla_wallet_core::tx::PayoutRecipient;
+use workflow_store::fs;
+
+#[derive(Default, Handler)]
+#[help("Pay multiple recipients listed in a CSV file")]
+pub struct Payout;
+
+/// Recipient listed in the payout CSV file.
+struct PayoutRow {
+    recipient: PayoutRecipient,
+}
+
+impl Payout {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<WaglaylaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let account = ctx.wallet().account()?;
+        let filename = PathBuf::from(&argv[0]);
+        let priority_fee_sompi = try_parse_optional_waglayla_as_sompi_i64(argv.get(1))?.unwrap_or(0);
+
+        let rows = self.load_rows(&ctx, &filename).await?;
+        let receipts_filename = PathBuf::from(format!("{}.receipts", filename.display()));
+        let mut receipts =
+            if fs::exists(&receipts_filename).await? { fs::read_to_string(&receipts_filename).await? } else { String::new() };
+        // receipts are keyed by the recipient id, identical rows are paid as many times as they are listed
+        let mut paid = HashMap::<Hash, usize>::new();
+        for id in receipts.lines().filter_map(|line| line.split(',').next().and_then(|id| id.trim().parse::<Hash>().ok())) {
+            *paid.entry(id).or_default() += 1;
+        }
+        let paid_count = paid.values().sum::<usize>();
+
+        let pending = rows
+            .into_iter()
+            .filter(|row| match paid.get_mut(&row.recipient.id()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            tprintln!(ctx, "All recipients have been paid, receipts are stored in '{}'", receipts_filename.display());
+            return Ok(());
+        }
+
+        let total = pending.iter().map(|row| row.recipient.amount).sum::<u64>();
+        if paid_count > 0 {
+            tprintln!(ctx, "Resuming payout, skipping {paid_count} previously paid recipient(s)");
+        }
+        tprintln!(ctx, "Paying {} WALA to {} recipient(s)", sompi_to_waglayla_string(total), pending.len());
+
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        let abortable = Abortable::default();
+
+        let recipients = pending.iter().map(|row| row.recipient.clone()).collect::<Vec<_>>();
+        if !receipts.is_empty() && !receipts.ends_with('\n') {
+            receipts.push('\n');
+        }
+        let receipts = Arc::new(Mutex::new(receipts));
+
+        // receipts are stored as soon as the transaction paying them is submitted,
+        // allowing an interrupted payout to be resumed
+        let ctx_ = ctx.clone();
+        let recipients_ = recipients.clone();
+        let receipts_ = receipts.clone();
+        let receipts_filename_ = receipts_filename.clone();
+        let summary = account
+            .payout(
+                recipients,
+                priority_fee_sompi.into(),
+                wallet_secret,
+                payment_secret,
+                &abortable,
+                Some(Arc::new(move |transaction_id, group| {
+                    if group.is_empty() {
+                        tprintln!(ctx_, "Submitted compound transaction {transaction_id}");
+                        return;
+                    }
+                    let mut receipts = receipts_.lock().unwrap();
+                    for receipt in group {
+                        let recipient = &recipients_[receipt.index as usize];
+                        receipts.push_str(&format!(
+                            "{},{},{},{},{}\n",
+                            receipt.recipient_id,
+                            recipient.address,
+                            sompi_to_waglayla_string(recipient.amount),
+                            receipt.transaction_id,
+                            recipient.note.as_deref().unwrap_or_default()
+                        ));
+                    }
+                    if let Err(err) = fs::write_string_sync(&receipts_filename_, &receipts) {
+                        terrorln!(ctx_, "Unable to store payout receipts: {err}");
+                    }
+                    tprintln!(ctx_, "Paid {} recipient(s) in transaction {transaction_id}", group.len());
+                })),
+            )
+            .await?;
+
+        tprintln!(ctx, "Payout - {summary}");
+        tprintln!(ctx, "Receipts are stored in '{}'", receipts_filename.display());
+        if !summary.is_complete() {
+            twarnln!(ctx, "Payout is incomplete, run the same command again to pay the remaining recipients");
+        }
+
+        Ok(())
+    }
+
+    /// Loads payout recipients from a CSV file containing `<address|alias>,<amount>[,<note>]`
+    /// lines. Empty lines, lines starting with `#` and an optional header are ignored.
+    async fn load_rows(&self, ctx: &Arc<WaglaylaCli>, filename: &Path) -> Result<Vec<PayoutRow>> {
+        let text = fs::read_to_string(filename).await?;
+
+        let mut rows = vec![];
+        for (index, text) in text.lines().enumerate() {
+            let line = index + 1;
+            let text = text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = text.splitn(3, ',').map(str::trim);
+            let address = fields.next().unwrap_or_default();
+            let amount = fields.next();
+            if line == 1 && address.eq_ignore_ascii_case("address") {
+                continue;
+            }
+
+            let address = ctx.resolve_address(address).await.map_err(|err| Error::custom(format!("line {line}: {err}")))?;
+            let amount = try_parse_required_nonzero_waglayla_as_sompi_u64(amount)
+                .map_err(|err| Error::custom(format!("line {line}: {err}")))?;
+            let note = fields.next().filter(|note| !note.is_empty()).map(String::from);
+            rows.push(PayoutRow { recipient: PayoutRecipient::new(address, amount, note) });
+        }
+
+        if rows.is_empty() {
+            return Err(Error::custom(format!("no recipients found in '{}'", filename.display())));
+        }
+
+        Ok(rows)
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<WaglaylaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(&[("payout <file.csv> [<priority fee>]", "Pay the recipients listed in a CSV file")], None)?;
+        tprintln!(ctx);
+        tprintln!(ctx, "Each line of the CSV file must contain: <address|alias>,<amount>[,<note>]");
+        tprintln!(ctx, "Payments are packed into as few transactions as possible, the priority fee");
+        tprintln!(ctx, "applies to each transaction. Transaction ids paying each recipient are stored");
+        tprintln!(ctx, "in '<file.csv>.receipts' as they are submitted, keyed by a hash of the address,");
+        tprintln!(ctx, "amount and note. If a payout is interrupted, running the same command again");
+        tprintln!(ctx, "pays only the recipients without a receipt, even if the file has been reordered.");
+        tprintln!(ctx);
+
+        Ok(())
+    }
+}
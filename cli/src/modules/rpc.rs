@@ -212,6 +212,86 @@ impl Rpc {
                 let result = rpc.get_coin_supply_call(GetCoinSupplyRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::ExportAddressBook => {
+                let result = rpc.export_address_book_call(ExportAddressBookRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionInclusionProof => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = RpcTransactionId::from_hex(argv.remove(0).as_str())?;
+                let block_hash = argv.first().map(|hash| RpcHash::from_hex(hash.as_str())).transpose()?;
+                let result = rpc.get_transaction_inclusion_proof(transaction_id, block_hash).await?;
+                let receipt = waglayla_consensus_core::merkle::TransactionReceipt::try_from(&result)?;
+                self.println(&ctx, result);
+                match receipt.verify() {
+                    Some(hash) if receipt.is_accepted() => {
+                        tprintln!(ctx, "receipt is valid, transaction accepted by chain block {hash}")
+                    }
+                    Some(hash) => tprintln!(ctx, "receipt is valid, transaction included in the past of block {hash}"),
+                    None => tprintln!(ctx, "receipt is invalid"),
+                }
+            }
+            RpcApiOps::GetUtxoSetAtBlock => {
+                if argv.len() < 2 {
+                    return Err(Error::custom("Please specify a chain block hash and at least one address"));
+                }
+                let block_hash = RpcHash::from_hex(argv.remove(0).as_str())?;
+                let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc.get_utxo_set_at_block_call(GetUtxoSetAtBlockRequest { addresses, block_hash }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetBalanceAtDaaScore => {
+                if argv.len() < 2 {
+                    return Err(Error::custom("Please specify a daa_score and at least one address"));
+                }
+                let daa_score = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse daa_score to u64"))?;
+                let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc.get_balance_at_daa_score(addresses, daa_score).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetDagExport => {
+                if argv.len() < 3 {
+                    return Err(Error::custom("Usage: rpc get-dag-export <daa|blue> <start> <end> [jsonl|dot]"));
+                }
+                let by_blue_score = match argv.remove(0).as_str() {
+                    "daa" => false,
+                    "blue" => true,
+                    _ => return Err(Error::custom("Please specify the score type of the range: 'daa' or 'blue'")),
+                };
+                let start = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse start score to u64"))?;
+                let end = argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse end score to u64"))?;
+                let blocks = rpc.get_dag_export(start, end, by_blue_score).await?;
+                let mut output = Vec::new();
+                let written = match argv.first().map(String::as_str) {
+                    None | Some("jsonl") => waglayla_consensus_core::dag_export::write_jsonl(&mut output, &blocks),
+                    Some("dot") => waglayla_consensus_core::dag_export::write_dot(&mut output, &blocks),
+                    Some(format) => return Err(Error::custom(format!("Unknown export format '{format}', expected 'jsonl' or 'dot'"))),
+                };
+                written.map_err(|err| Error::custom(err.to_string()))?;
+                ctx.term().writeln(String::from_utf8_lossy(&output).crlf());
+            }
+            RpcApiOps::GetPruningPointProof => {
+                let levels = rpc.get_pruning_point_proof().await?;
+                if levels.is_empty() {
+                    tprintln!(ctx, "The pruning point of the node is genesis, the proof is empty");
+                }
+                for (level, headers) in levels.iter().enumerate() {
+                    let (Some(first), Some(last)) = (headers.first(), headers.last()) else {
+                        tprintln!(ctx, "level {level}: no headers");
+                        continue;
+                    };
+                    tprintln!(
+                        ctx,
+                        "level {level}: {} headers, DAA scores {}..{}, last header {}",
+                        headers.len(),
+                        first.daa_score,
+                        last.daa_score,
+                        last.hash
+                    );
+                }
+            }
             RpcApiOps::GetDaaScoreTimestampEstimate => {
                 if argv.is_empty() {
                     return Err(Error::custom("Please specify a daa_score"));
@@ -1,7 +1,7 @@
 use crate::imports::*;
 
 #[derive(Default, Handler)]
-#[help("Send a Waglayla transaction to a public address")]
+#[help("Send a Waglayla transaction to a public address or a payment request URI")]
 pub struct Send;
 
 impl Send {
@@ -11,14 +11,46 @@ impl Send {
 
         let account = ctx.wallet().account()?;
 
-        if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+        if argv.is_empty() || (argv.len() < 2 && !argv[0].contains('?')) {
+            tprintln!(ctx, "usage: send <address|alias> <amount> <priority fee>");
+            tprintln!(ctx, "       send <payment request uri> [<amount>] [fee=<priority fee>]");
             return Ok(());
         }
 
-        let address = Address::try_from(argv.first().unwrap().as_str())?;
-        let amount_sompi = try_parse_required_nonzero_waglayla_as_sompi_u64(argv.get(1))?;
-        let priority_fee_sompi = try_parse_optional_waglayla_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        // payment request URIs carry the amount (unless omitted by the payee) and the payload
+        let (address, amount_sompi, priority_fee_sompi, payload) = if argv[0].contains('?') {
+            let request = argv[0].parse::<PaymentRequest>()?;
+            request.validate(&ctx.wallet().network_id()?)?;
+            if let Some(label) = &request.label {
+                tprintln!(ctx, "Payment to: {}", strip_control_characters(label));
+            }
+            if let Some(message) = &request.message {
+                tprintln!(ctx, "Message: {}", strip_control_characters(message));
+            }
+            // the priority fee must be explicit so that it can not be mistaken for the amount
+            let (priority_fee, args): (Vec<_>, Vec<_>) = argv[1..].iter().partition(|arg| arg.starts_with("fee="));
+            if priority_fee.len() > 1 || args.len() > 1 {
+                return Err(Error::custom("usage: send <payment request uri> [<amount>] [fee=<priority fee>]"));
+            }
+            let amount_sompi = match (request.amount, args.first()) {
+                (Some(amount_sompi), None) => amount_sompi,
+                (Some(amount_sompi), Some(_)) => {
+                    return Err(Error::custom(format!(
+                        "the payment request specifies the amount of {} WALA, use 'fee=<priority fee>' to set the priority fee",
+                        sompi_to_waglayla_string(amount_sompi)
+                    )));
+                }
+                (None, amount) => try_parse_required_nonzero_waglayla_as_sompi_u64(amount.copied())?,
+            };
+            let priority_fee = priority_fee.first().map(|arg| arg.trim_start_matches("fee="));
+            let priority_fee_sompi = try_parse_optional_waglayla_as_sompi_i64(priority_fee)?.unwrap_or(0);
+            (request.address, amount_sompi, priority_fee_sompi, request.payload)
+        } else {
+            let address = ctx.resolve_address(argv.first().unwrap()).await?;
+            let amount_sompi = try_parse_required_nonzero_waglayla_as_sompi_u64(argv.get(1))?;
+            let priority_fee_sompi = try_parse_optional_waglayla_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+            (address, amount_sompi, priority_fee_sompi, None)
+        };
         let outputs = PaymentOutputs::from((address.clone(), amount_sompi));
         let abortable = Abortable::default();
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
@@ -28,6 +60,7 @@ impl Send {
             .send(
                 outputs.into(),
                 priority_fee_sompi.into(),
+                payload,
                 None,
                 wallet_secret,
                 payment_secret,
@@ -45,3 +78,9 @@ impl Send {
         Ok(())
     }
 }
+
+/// Removes control characters (such as terminal escape sequences) from the
+/// payee supplied payment request fields before they are displayed.
+fn strip_control_characters(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
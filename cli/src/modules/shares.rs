@@ -0,0 +1,148 @@
+use crate::imports::*;
+use waglayla_wallet_core::storage::{split_mnemonic, verify_mnemonic_shares, MnemonicShare, MnemonicShareArgs};
+
+#[derive(Default, Handler)]
+#[help("Create or verify Shamir secret-shared mnemonic backups")]
+pub struct Shares;
+
+impl Shares {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<WaglaylaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.first().unwrap();
+        match action.as_str() {
+            "create" => {
+                if argv.len() != 3 {
+                    tprintln!(ctx, "usage: shares create <threshold> <count>");
+                    return Ok(());
+                }
+                let threshold = argv[1].parse::<u8>().map_err(|_| Error::custom(format!("invalid threshold: {}", argv[1])))?;
+                let count = argv[2].parse::<u8>().map_err(|_| Error::custom(format!("invalid share count: {}", argv[2])))?;
+                let args = MnemonicShareArgs::new(threshold, count);
+                args.validate()?;
+                self.create(ctx, args).await
+            }
+            "verify" => self.verify(ctx).await,
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                self.display_help(ctx, argv).await
+            }
+        }
+    }
+
+    async fn create(self: &Arc<Self>, ctx: Arc<WaglaylaCli>, args: MnemonicShareArgs) -> Result<()> {
+        let account = ctx.account().await?;
+        let prv_key_data_id = account.prv_key_data_id()?;
+
+        let wallet_secret = Secret::new(ctx.term().ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+        if wallet_secret.as_ref().is_empty() {
+            return Err(Error::WalletSecretRequired);
+        }
+
+        let prv_key_data = ctx.store().as_prv_key_data_store()?.load_key_data(&wallet_secret, prv_key_data_id).await?;
+        let Some(keydata) = prv_key_data else { return Err(Error::KeyDataNotFound) };
+        let payment_secret = if keydata.payload.is_encrypted() {
+            let payment_secret = Secret::new(ctx.term().ask(true, "Enter payment password: ").await?.trim().as_bytes().to_vec());
+            if payment_secret.as_ref().is_empty() {
+                return Err(Error::PaymentSecretRequired);
+            } else {
+                Some(payment_secret)
+            }
+        } else {
+            None
+        };
+
+        let prv_key_data = keydata.payload.decrypt(payment_secret.as_ref())?;
+        let Some(mnemonic) = prv_key_data.as_ref().as_mnemonic()? else {
+            return Err(Error::custom("mnemonic is not available for this private key"));
+        };
+
+        let shares = split_mnemonic(&mnemonic, &args)?;
+
+        tprintln!(ctx, "");
+        tpara!(
+            ctx,
+            "\
+            IMPORTANT: any {} of the following {} shares recover your mnemonic. \
+            Please store each share separately. Fewer than {} shares reveal nothing about the mnemonic.",
+            args.threshold,
+            args.count,
+            args.threshold,
+        );
+        if payment_secret.is_some() {
+            tpara!(
+                ctx,
+                "\
+                To recover your private key you will also need your payment password. \
+                Your payment password is permanently associated with this mnemonic.",
+            );
+        }
+        tprintln!(ctx, "");
+        for share in shares.iter() {
+            tprintln!(ctx, "share {} of {} (threshold {}):", share.number(), args.count, share.threshold());
+            tprintln!(ctx, "");
+            tprintln!(ctx, "{}", share.to_phrase().as_str());
+            tprintln!(ctx, "");
+        }
+
+        Ok(())
+    }
+
+    async fn verify(self: &Arc<Self>, ctx: Arc<WaglaylaCli>) -> Result<()> {
+        let term = ctx.term();
+
+        let mut shares: Vec<MnemonicShare> = vec![];
+        tprintln!(ctx, "Please enter mnemonic shares, one per line, <enter> when done");
+        loop {
+            let text = term.ask(true, "Share:").await?;
+            if text.trim().is_empty() {
+                break;
+            }
+            match MnemonicShare::try_from_phrase(&text) {
+                Ok(share) => {
+                    tprintln!(ctx, "share {} (threshold {}) accepted", share.number(), share.threshold());
+                    shares.push(share);
+                }
+                Err(err) => tprintln!(ctx, "{err}"),
+            }
+        }
+
+        let info = verify_mnemonic_shares(&shares)?;
+
+        tprintln!(ctx, "");
+        tprintln!(ctx, "share set is valid");
+        tprintln!(ctx, "identifier: {:04x}", info.identifier);
+        tprintln!(ctx, "threshold: {}", info.threshold);
+        tprintln!(ctx, "verified shares: {}", info.share_count);
+        tprintln!(ctx, "private key data id: {}", info.prv_key_data_id);
+
+        if let Ok(account) = ctx.account().await {
+            if let Ok(prv_key_data_id) = account.prv_key_data_id() {
+                let matches = prv_key_data_id == &info.prv_key_data_id;
+                tprintln!(ctx, "matches selected account: {}", if matches { "yes" } else { "no" });
+            }
+        }
+        tprintln!(ctx, "");
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<WaglaylaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                (
+                    "shares create <threshold> <count>",
+                    "Split the mnemonic of the selected account into <count> shares, <threshold> of which recover it",
+                ),
+                ("shares verify", "Verify a set of shares without revealing the mnemonic"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::imports::*;
+use waglayla_consensus_core::tx::{TransactionId, TransactionOutpoint};
+use waglayla_wallet_core::tx::UtxoSelection;
+
+#[derive(Default, Handler)]
+#[help("List, freeze, label and spend specific UTXOs of the current account")]
+pub struct Utxo;
+
+impl Utxo {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<WaglaylaCli>()?;
+
+        let wallet = ctx.wallet();
+        if !wallet.is_open() {
+            return Err(Error::WalletIsNotOpen);
+        }
+
+        let op = if argv.is_empty() { "list".to_string() } else { argv.remove(0) };
+        match op.as_str() {
+            "list" => {
+                let account = ctx.account().await?;
+                let UtxosEnumerateResponse { mut utxos } =
+                    wallet.utxos_enumerate_call(UtxosEnumerateRequest { account_id: *account.id() }).await?;
+                if utxos.is_empty() {
+                    tprintln!(ctx, "No UTXOs found for this account");
+                    return Ok(());
+                }
+                utxos.sort_by_key(|utxo| utxo.block_daa_score);
+                for utxo in utxos {
+                    let mut flags = vec![];
+                    if !utxo.is_mature {
+                        flags.push("pending");
+                    }
+                    if utxo.is_coinbase {
+                        flags.push("coinbase");
+                    }
+                    if utxo.frozen {
+                        flags.push("frozen");
+                    }
+                    let flags = if flags.is_empty() { "".to_string() } else { format!(" [{}]", flags.join(", ")) };
+                    let label = utxo.label.map(|label| format!(" {label}")).unwrap_or_default();
+                    tprintln!(
+                        ctx,
+                        "{}: {} WALA{}{}",
+                        style(format_outpoint(&utxo.outpoint)).cyan(),
+                        sompi_to_waglayla_string(utxo.amount),
+                        style(flags).yellow(),
+                        label
+                    );
+                }
+            }
+            "freeze" | "unfreeze" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: utxo {op} <outpoint> [<outpoint> ...]");
+                    return Ok(());
+                }
+                let outpoints = argv.iter().map(|outpoint| parse_outpoint(outpoint)).collect::<Result<Vec<_>>>()?;
+                let frozen = op == "freeze";
+                let count = outpoints.len();
+                wallet.utxos_freeze_call(UtxosFreezeRequest { outpoints, frozen }).await?;
+                tprintln!(ctx, "{} {count} UTXO(s)", if frozen { "Froze" } else { "Unfroze" });
+            }
+            "label" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: utxo label <outpoint> [<label>]");
+                    return Ok(());
+                }
+                let outpoint = parse_outpoint(&argv.remove(0))?;
+                let label = (!argv.is_empty()).then(|| argv.join(" "));
+                wallet.utxos_label_call(UtxosLabelRequest { outpoint, label }).await?;
+            }
+            "spend" => {
+                if argv.len() < 4 {
+                    tprintln!(ctx, "usage: utxo spend <address|alias> <amount> <priority fee> <outpoint> [<outpoint> ...]");
+                    return Ok(());
+                }
+                let account = ctx.wallet().account()?;
+                let address = ctx.resolve_address(&argv[0]).await?;
+                let amount_sompi = try_parse_required_nonzero_waglayla_as_sompi_u64(argv.get(1))?;
+                let priority_fee_sompi = try_parse_optional_waglayla_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+                let include = argv[3..].iter().map(|outpoint| parse_outpoint(outpoint)).collect::<Result<Vec<_>>>()?;
+                let outputs = PaymentOutputs::from((address, amount_sompi));
+                let abortable = Abortable::default();
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+
+                let (summary, _ids) = account
+                    .send(
+                        outputs.into(),
+                        priority_fee_sompi.into(),
+                        None,
+                        Some(UtxoSelection { include, exclude: vec![] }),
+                        wallet_secret,
+                        payment_secret,
+                        &abortable,
+                        None,
+                    )
+                    .await?;
+
+                tprintln!(ctx, "Send - {summary}");
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<WaglaylaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("utxo [list]", "List UTXOs of the current account"),
+                ("utxo freeze <outpoint> ...", "Freeze UTXOs, preventing them from being spent"),
+                ("utxo unfreeze <outpoint> ...", "Unfreeze previously frozen UTXOs"),
+                ("utxo label <outpoint> [<label>]", "Set or clear (if no label is supplied) a UTXO label"),
+                ("utxo spend <address|alias> <amount> <priority fee> <outpoint> ...", "Send funds spending only the specified UTXOs"),
+            ],
+            None,
+        )?;
+        tprintln!(ctx, "Outpoints are specified as <transaction id>:<index>");
+
+        Ok(())
+    }
+}
+
+fn format_outpoint(outpoint: &TransactionOutpoint) -> String {
+    format!("{}:{}", outpoint.transaction_id, outpoint.index)
+}
+
+/// Parses an outpoint specified as `<transaction id>:<index>` or `<transaction id>-<index>`.
+fn parse_outpoint(outpoint: &str) -> Result<TransactionOutpoint> {
+    let (transaction_id, index) =
+        outpoint.rsplit_once([':', '-']).ok_or_else(|| Error::custom(format!("invalid outpoint '{outpoint}'")))?;
+    Ok(TransactionOutpoint::new(TransactionId::from_hex(transaction_id)?, index.parse()?))
+}
@@ -4,6 +4,7 @@ use crate::result::Result;
 use crate::WaglaylaCli;
 use waglayla_bip32::{Language, Mnemonic};
 use waglayla_wallet_core::account::{BIP32_ACCOUNT_KIND, LEGACY_ACCOUNT_KIND, MULTISIG_ACCOUNT_KIND};
+use waglayla_wallet_core::storage::{combine_mnemonic_shares, MnemonicShare};
 use std::sync::Arc;
 
 pub async fn prompt_for_mnemonic(term: &Arc<Terminal>) -> Result<Vec<String>> {
@@ -38,6 +39,59 @@ pub async fn prompt_for_mnemonic(term: &Arc<Terminal>) -> Result<Vec<String>> {
     }
 }
 
+/// Prompts for mnemonic shares (one share per line) until the share set
+/// threshold is reached and returns the recovered mnemonic.
+pub async fn prompt_for_mnemonic_shares(term: &Arc<Terminal>) -> Result<Mnemonic> {
+    let mut shares: Vec<MnemonicShare> = vec![];
+    loop {
+        match shares.first() {
+            None => tprintln!(term, "Please enter mnemonic share (18 or 30 space separated words)"),
+            Some(first) => tprintln!(
+                term,
+                "Please enter share {} of {} (the order of shares is not important) or <enter> to abort",
+                shares.len() + 1,
+                first.threshold()
+            ),
+        }
+        let text = term.ask(true, "Share:").await?;
+        if text.trim().is_empty() {
+            return Err(Error::UserAbort);
+        }
+        let share = match MnemonicShare::try_from_phrase(&text) {
+            Ok(share) => share,
+            Err(err) => {
+                tprintln!(term, "{err}");
+                continue;
+            }
+        };
+        if shares.iter().any(|other| other.number() == share.number()) {
+            tprintln!(term, "share {} has already been entered", share.number());
+            continue;
+        }
+        shares.push(share);
+        if shares.len() >= shares[0].threshold() as usize {
+            break;
+        }
+    }
+
+    Ok(combine_mnemonic_shares(&shares)?)
+}
+
+/// Prompts for a mnemonic, either as words or, if requested, as a set of mnemonic shares.
+pub async fn prompt_for_mnemonic_or_shares(term: &Arc<Terminal>) -> Result<Mnemonic> {
+    if matches!(
+        term.ask(false, "Recover the mnemonic from shares (type 'y' to approve)?: ").await?.trim(),
+        "y" | "Y" | "YES" | "yes"
+    ) {
+        tprintln!(term, "");
+        prompt_for_mnemonic_shares(term).await
+    } else {
+        tprintln!(term, "");
+        let mnemonic = prompt_for_mnemonic(term).await?.join(" ");
+        Ok(Mnemonic::new(mnemonic.trim(), Language::English)?)
+    }
+}
+
 pub(crate) async fn import_with_mnemonic(ctx: &Arc<WaglaylaCli>, account_kind: AccountKind, additional_xpubs: &[String]) -> Result<()> {
     let wallet = ctx.wallet();
 
@@ -50,9 +104,9 @@ pub(crate) async fn import_with_mnemonic(ctx: &Arc<WaglaylaCli>, account_kind: A
     tprintln!(ctx);
     let wallet_secret = Secret::new(term.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
     tprintln!(ctx);
-    let mnemonic = prompt_for_mnemonic(&term).await?;
+    let mnemonic = prompt_for_mnemonic_or_shares(&term).await?;
     tprintln!(ctx);
-    let length = mnemonic.len();
+    let length = mnemonic.phrase().split_whitespace().count();
     match account_kind.as_ref() {
         LEGACY_ACCOUNT_KIND if length != 12 => Err(Error::Custom(format!("wrong mnemonic length ({length})"))),
         BIP32_ACCOUNT_KIND if length != 24 => Err(Error::Custom(format!("wrong mnemonic length ({length})"))),
@@ -89,9 +143,6 @@ pub(crate) async fn import_with_mnemonic(ctx: &Arc<WaglaylaCli>, account_kind: A
         }
     };
 
-    let mnemonic = mnemonic.join(" ");
-    let mnemonic = Mnemonic::new(mnemonic.trim(), Language::English)?;
-
     let account = if account_kind != MULTISIG_ACCOUNT_KIND {
         wallet.import_with_mnemonic(&wallet_secret, payment_secret.as_ref(), mnemonic, account_kind).await?
     } else {
@@ -101,12 +152,10 @@ pub(crate) async fn import_with_mnemonic(ctx: &Arc<WaglaylaCli>, account_kind: A
             "y" | "Y" | "YES" | "yes"
         ) {
             tprintln!(ctx);
-            let mnemonic = prompt_for_mnemonic(&term).await?;
+            let mnemonic = prompt_for_mnemonic_or_shares(&term).await?;
             tprintln!(ctx);
             let payment_secret = term.ask(true, "Enter payment password (optional): ").await?;
             let payment_secret = payment_secret.trim().is_not_empty().then(|| Secret::new(payment_secret.trim().as_bytes().to_vec()));
-            let mnemonic = mnemonic.join(" ");
-            let mnemonic = Mnemonic::new(mnemonic.trim(), Language::English)?;
 
             mnemonics_secrets.push((mnemonic, payment_secret));
         }
@@ -109,8 +109,8 @@ pub(crate) async fn create(ctx: &Arc<WaglaylaCli>, name: Option<&str>, import_wi
     tprintln!(ctx, "");
 
     let prv_key_data_args = if import_with_mnemonic {
-        let words = crate::wizards::import::prompt_for_mnemonic(&term).await?;
-        PrvKeyDataCreateArgs::new(None, payment_secret.clone(), Secret::from(words.join(" ")))
+        let mnemonic = crate::wizards::import::prompt_for_mnemonic_or_shares(&term).await?;
+        PrvKeyDataCreateArgs::new(None, payment_secret.clone(), Secret::from(mnemonic.phrase()))
     } else {
         PrvKeyDataCreateArgs::new(
             None,
@@ -22,11 +22,20 @@ use parking_lot::Mutex;
 use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
 use thiserror::Error;
 
+pub use stores::address_store::AddressRecord;
 pub use stores::NetAddress;
 
 const MAX_ADDRESSES: usize = 4096;
 const MAX_CONNECTION_FAILED_COUNT: u64 = 3;
 
+/// Re-advertisements of a known address refresh its `last_seen` timestamp at most once per this interval (ms)
+const LAST_SEEN_REFRESH_INTERVAL: u64 = 10 * 60 * 1000;
+
+/// Minimal interval between stale address pruning rounds (ms)
+const STALE_PRUNING_INTERVAL: u64 = 60 * 60 * 1000;
+
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
 const UPNP_DEADLINE_SEC: u64 = 2 * 60;
 const UPNP_EXTEND_PERIOD: u64 = UPNP_DEADLINE_SEC / 2;
 
@@ -51,11 +60,38 @@ pub enum UpnpError {
     GetExternalIpError(#[from] GetExternalIpError),
 }
 
+/// Defines when known addresses are considered stale and pruned from the address store
+#[derive(Clone, Copy, Debug)]
+pub struct StaleAddressPolicy {
+    /// Addresses we never successfully connected to are pruned if not advertised to us for this long (ms)
+    pub max_unconnected_age: u64,
+    /// Addresses we did connect to at some point are pruned if not seen for this long (ms)
+    pub max_connected_age: u64,
+    /// Pruning never shrinks the store below this number of addresses, so that a node which was offline
+    /// for a long while does not lose its entire address book at startup
+    pub min_retained: usize,
+}
+
+impl Default for StaleAddressPolicy {
+    fn default() -> Self {
+        Self { max_unconnected_age: 7 * DAY_MILLIS, max_connected_age: 30 * DAY_MILLIS, min_retained: 1024 }
+    }
+}
+
+impl StaleAddressPolicy {
+    fn is_stale(&self, record: &AddressRecord, now: u64) -> bool {
+        let max_age = if record.last_success > 0 { self.max_connected_age } else { self.max_unconnected_age };
+        now.saturating_sub(record.last_seen) > max_age
+    }
+}
+
 pub struct AddressManager {
     banned_address_store: DbBannedAddressesStore,
     address_store: address_store_with_cache::Store,
     config: Arc<Config>,
     local_net_addresses: Vec<NetAddress>,
+    stale_address_policy: StaleAddressPolicy,
+    last_stale_pruning: u64,
 }
 
 impl AddressManager {
@@ -65,6 +101,8 @@ impl AddressManager {
             address_store: address_store_with_cache::new(db),
             local_net_addresses: Vec::new(),
             config,
+            stale_address_policy: Default::default(),
+            last_stale_pruning: 0,
         };
 
         let extender = instance.init_local_addresses(tick_service);
@@ -252,17 +290,26 @@ impl AddressManager {
     }
 
     pub fn add_address(&mut self, address: NetAddress) {
+        self.add_address_from(address, None)
+    }
+
+    /// Adds an address advertised by the peer at `source`, or refreshes its last seen time if it is already known
+    pub fn add_address_from(&mut self, address: NetAddress, source: Option<NetAddress>) {
         if address.ip.is_loopback() || address.ip.is_unspecified() {
             debug!("[Address manager] skipping local address {}", address.ip);
             return;
         }
 
+        let now = unix_now();
         if self.address_store.has(address) {
+            if now.saturating_sub(self.address_store.get(address).last_seen) > LAST_SEEN_REFRESH_INTERVAL {
+                self.address_store.update(address, |record| record.last_seen = now);
+            }
             return;
         }
 
         // We mark `connection_failed_count` as 0 only after first success
-        self.address_store.set(address, 1);
+        self.address_store.insert(AddressRecord::new(address, 1, source, now));
     }
 
     pub fn mark_connection_failure(&mut self, address: NetAddress) {
@@ -274,16 +321,36 @@ impl AddressManager {
         if new_count > MAX_CONNECTION_FAILED_COUNT {
             self.address_store.remove(address);
         } else {
-            self.address_store.set(address, new_count);
+            let now = unix_now();
+            self.address_store.update(address, |record| {
+                record.connection_failed_count = new_count;
+                record.last_attempt = now;
+            });
         }
     }
 
     pub fn mark_connection_success(&mut self, address: NetAddress) {
-        if !self.address_store.has(address) {
-            return;
-        }
+        let now = unix_now();
+        self.address_store.update(address, |record| {
+            record.connection_failed_count = 0;
+            record.last_attempt = now;
+            record.last_success = now;
+            record.last_seen = now;
+        });
+    }
+
+    /// Records the metadata advertised by the peer at `address` during the handshake
+    pub fn set_peer_info(&mut self, address: NetAddress, protocol_version: u32, services: u64, user_agent: &str) {
+        self.address_store.update(address, |record| {
+            record.protocol_version = protocol_version;
+            record.services = services;
+            record.user_agent = user_agent.to_owned();
+        });
+    }
 
-        self.address_store.set(address, 0);
+    /// Records the last measured ping round-trip time (in milliseconds) of the peer at `address`
+    pub fn set_latency(&mut self, address: NetAddress, latency: u64) {
+        self.address_store.update(address, |record| record.latency = Some(latency));
     }
 
     pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
@@ -291,7 +358,47 @@ impl AddressManager {
     }
 
     pub fn iterate_prioritized_random_addresses(&self, exceptions: HashSet<NetAddress>) -> impl ExactSizeIterator<Item = NetAddress> {
-        self.address_store.iterate_prioritized_random_addresses(exceptions)
+        self.address_store.iterate_prioritized_random_addresses(exceptions, unix_now())
+    }
+
+    pub fn set_stale_address_policy(&mut self, policy: StaleAddressPolicy) {
+        self.stale_address_policy = policy;
+    }
+
+    /// Prunes stale addresses according to the [`StaleAddressPolicy`]. Pruning is skipped if the last round
+    /// was less than an hour ago, so this may be called on every connection manager iteration.
+    pub fn prune_stale_addresses(&mut self) {
+        let now = unix_now();
+        if now.saturating_sub(self.last_stale_pruning) < STALE_PRUNING_INTERVAL {
+            return;
+        }
+        self.last_stale_pruning = now;
+        let pruned = self.address_store.prune_stale(&self.stale_address_policy, now);
+        if pruned > 0 {
+            debug!("[Address manager] pruned {} stale addresses", pruned);
+        }
+    }
+
+    /// Returns the records of all known addresses, e.g. for seeding the address book of another node
+    pub fn get_all_address_records(&self) -> Vec<AddressRecord> {
+        self.address_store.iterate_records().cloned().collect_vec()
+    }
+
+    /// Imports address records exported by another node. Known, local and banned addresses are skipped.
+    /// Returns the number of imported addresses.
+    pub fn import_address_records(&mut self, records: impl IntoIterator<Item = AddressRecord>) -> usize {
+        let mut imported = 0;
+        for mut record in records {
+            let address = record.address;
+            if address.ip.is_loopback() || address.ip.is_unspecified() || self.address_store.has(address) || self.is_banned(address.ip)
+            {
+                continue;
+            }
+            record.connection_failed_count = record.connection_failed_count.min(MAX_CONNECTION_FAILED_COUNT);
+            self.address_store.insert(record);
+            imported += 1;
+        }
+        imported
     }
 
     pub fn ban(&mut self, ip: IpAddress) {
@@ -346,53 +453,104 @@ mod address_store_with_cache {
 
     use crate::{
         stores::{
-            address_store::{AddressesStore, DbAddressesStore, Entry},
+            address_store::{AddressRecord, AddressesStore, DbAddressesStore},
             AddressKey,
         },
-        NetAddress, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
+        NetAddress, StaleAddressPolicy, DAY_MILLIS, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
     };
+    use waglayla_core::{info, time::unix_now, warn};
+
+    /// Selection weight multiplier of addresses we have successfully connected to in the past
+    const REACHABLE_WEIGHT_FACTOR: f64 = 4.0;
 
     pub struct Store {
         db_store: DbAddressesStore,
-        addresses: HashMap<AddressKey, Entry>,
+        addresses: HashMap<AddressKey, AddressRecord>,
     }
 
     impl Store {
         fn new(db: Arc<DB>) -> Self {
             // We manage the cache ourselves on this level, so we disable the inner builtin cache
-            let db_store = DbAddressesStore::new(db, CachePolicy::Empty);
+            let mut db_store = DbAddressesStore::new(db, CachePolicy::Empty);
             let mut addresses = HashMap::new();
-            for (key, entry) in db_store.iterator().map(|res| res.unwrap()) {
-                addresses.insert(key, entry);
+            for (key, record) in db_store.iterator().map(|res| res.unwrap()) {
+                addresses.insert(key, record);
+            }
+
+            // peer addresses are not critical, a failed migration must not prevent the node from starting
+            let legacy_entries = db_store.take_legacy_entries().unwrap_or_else(|err| {
+                warn!("[Address manager] failed migrating the legacy addresses: {err}");
+                vec![]
+            });
+            if !legacy_entries.is_empty() {
+                info!("[Address manager] migrating {} addresses to the address record format", legacy_entries.len());
+                let now = unix_now();
+                for (key, entry) in legacy_entries {
+                    let record = AddressRecord::from_legacy(entry, now);
+                    db_store.set(key, record.clone()).unwrap();
+                    addresses.entry(key).or_insert(record);
+                }
             }
 
-            Self { db_store, addresses }
+            let mut store = Self { db_store, addresses };
+            store.keep_limit();
+            store
         }
 
         pub fn has(&mut self, address: NetAddress) -> bool {
             self.addresses.contains_key(&address.into())
         }
 
-        pub fn set(&mut self, address: NetAddress, connection_failed_count: u64) {
-            let entry = match self.addresses.get(&address.into()) {
-                Some(entry) => Entry { connection_failed_count, address: entry.address },
-                None => Entry { connection_failed_count, address },
-            };
-            self.db_store.set(address.into(), entry).unwrap();
-            self.addresses.insert(address.into(), entry);
+        /// Inserts a new record, or overrides the existing record of the same address
+        pub fn insert(&mut self, record: AddressRecord) {
+            let key = record.address.into();
+            self.db_store.set(key, record.clone()).unwrap();
+            self.addresses.insert(key, record);
             self.keep_limit();
         }
 
+        /// Applies `f` to the record of `address`, if it is known
+        pub fn update(&mut self, address: NetAddress, f: impl FnOnce(&mut AddressRecord)) {
+            let key = address.into();
+            if let Some(record) = self.addresses.get_mut(&key) {
+                f(record);
+                self.db_store.set(key, record.clone()).unwrap();
+            }
+        }
+
         fn keep_limit(&mut self) {
             while self.addresses.len() > MAX_ADDRESSES {
-                let to_remove =
-                    self.addresses.iter().max_by(|a, b| (a.1).connection_failed_count.cmp(&(b.1).connection_failed_count)).unwrap();
-                self.remove_by_key(*to_remove.0);
+                // Evict the address with most connection failures, preferring the least recently seen one
+                let to_remove = self
+                    .addresses
+                    .iter()
+                    .max_by_key(|(_, record)| (record.connection_failed_count, std::cmp::Reverse(record.last_seen)))
+                    .map(|(key, _)| *key)
+                    .unwrap();
+                self.remove_by_key(to_remove);
             }
         }
 
-        pub fn get(&self, address: NetAddress) -> Entry {
-            *self.addresses.get(&address.into()).unwrap()
+        /// Removes stale addresses, stalest first, as long as the store holds more than `policy.min_retained` addresses.
+        /// Returns the number of removed addresses.
+        pub fn prune_stale(&mut self, policy: &StaleAddressPolicy, now: u64) -> usize {
+            let removable = self.addresses.len().saturating_sub(policy.min_retained);
+            let stale = self
+                .addresses
+                .iter()
+                .filter(|(_, record)| policy.is_stale(record, now))
+                .sorted_by_key(|(_, record)| record.last_seen)
+                .map(|(key, _)| *key)
+                .take(removable)
+                .collect_vec();
+            for key in stale.iter().copied() {
+                self.remove_by_key(key);
+            }
+            stale.len()
+        }
+
+        pub fn get(&self, address: NetAddress) -> &AddressRecord {
+            self.addresses.get(&address.into()).unwrap()
         }
 
         pub fn remove(&mut self, address: NetAddress) {
@@ -405,12 +563,17 @@ mod address_store_with_cache {
         }
 
         pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
-            self.addresses.values().map(|entry| entry.address)
+            self.addresses.values().map(|record| record.address)
+        }
+
+        pub fn iterate_records(&self) -> impl Iterator<Item = &AddressRecord> + '_ {
+            self.addresses.values()
         }
 
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
+        /// then by the connection quality history of the address (see [`quality_weight`]).
         /// Afterwards the weights are normalized uniformly over the ip's [`PrefixBucket`] size.
         ///
         /// This ensures a distributed selection across the global network, while respecting
@@ -418,16 +581,18 @@ mod address_store_with_cache {
         ///
         /// The exact weight formula for any given ip, is as follows:
         ///```ignore
-        ///         ip_weight = (64 ^ (x - y)) / n
+        ///         ip_weight = (64 ^ (x - y)) * q / n
         ///
         ///             whereby:
         ///                 x: max allowed connection failures.
         ///                 y: connection failures of the ip.
+        ///                 q: quality weight of the ip.
         ///                 n: number of ips with the same prefix bytes.
         ///```
         pub fn iterate_prioritized_random_addresses(
             &self,
             exceptions: HashSet<NetAddress>,
+            now: u64,
         ) -> impl ExactSizeIterator<Item = NetAddress> {
             let exceptions: HashSet<AddressKey> = exceptions.into_iter().map(|addr| addr.into()).collect();
             let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
@@ -435,10 +600,10 @@ mod address_store_with_cache {
                 .addresses
                 .iter()
                 .filter(|(addr_key, _)| !exceptions.contains(addr_key))
-                .map(|(_, e)| {
-                    let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
+                .map(|(_, r)| {
+                    let count = prefix_counter.entry(r.address.prefix_bucket()).or_insert(0);
                     *count += 1;
-                    (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64), e.address)
+                    (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - r.connection_failed_count) as f64) * quality_weight(r, now), r.address)
                 })
                 .unzip();
 
@@ -461,6 +626,21 @@ mod address_store_with_cache {
         Store::new(db)
     }
 
+    /// The selection weight factor derived from the connection history of an address. Addresses we have
+    /// successfully connected to are preferred, the more so the lower their latency, while addresses which
+    /// were not seen for a while lose weight with every passing day.
+    pub fn quality_weight(record: &AddressRecord, now: u64) -> f64 {
+        let mut weight = 1.0;
+        if record.last_success > 0 {
+            weight *= REACHABLE_WEIGHT_FACTOR;
+            if let Some(latency) = record.latency {
+                weight /= 1.0 + latency as f64 / 1000.0;
+            }
+        }
+        let days_unseen = now.saturating_sub(record.last_seen) / DAY_MILLIS;
+        weight / (1 + days_unseen) as f64
+    }
+
     pub struct RandomWeightedIterator {
         weighted_index: Option<WeightedIndex<f64>>,
         remaining: usize,
@@ -536,6 +716,90 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_quality_weight() {
+            let now = 100 * DAY_MILLIS;
+            let address = NetAddress::new(IpAddress::from_str("1.2.3.4").unwrap(), 12111);
+            let fresh = AddressRecord::new(address, 1, None, now);
+            assert_eq!(quality_weight(&fresh, now), 1.0);
+
+            let reachable = AddressRecord { last_success: now, ..fresh.clone() };
+            let slow = AddressRecord { latency: Some(1000), ..reachable.clone() };
+            let fast = AddressRecord { latency: Some(50), ..reachable.clone() };
+            assert_eq!(quality_weight(&reachable, now), REACHABLE_WEIGHT_FACTOR);
+            assert!(quality_weight(&fast, now) > quality_weight(&slow, now));
+            assert!(quality_weight(&slow, now) > quality_weight(&fresh, now));
+
+            let unseen = AddressRecord { last_seen: now - 3 * DAY_MILLIS, ..fresh.clone() };
+            assert_eq!(quality_weight(&unseen, now), 0.25);
+        }
+
+        #[test]
+        fn test_address_records() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let (am, _) = AddressManager::new(config.clone(), db.1, Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            let source = NetAddress::new(IpAddress::from_str("10.0.0.1").unwrap(), 12111);
+            let reachable = NetAddress::new(IpAddress::from_str("1.1.1.1").unwrap(), 12111);
+            let unreachable = NetAddress::new(IpAddress::from_str("2.2.2.2").unwrap(), 12111);
+            am.add_address_from(reachable, Some(source));
+            am.add_address_from(unreachable, Some(source));
+            am.mark_connection_success(reachable);
+            am.set_peer_info(reachable, 7, 1, "/waglaylad:0.14.5/");
+            am.set_latency(reachable, 120);
+            am.mark_connection_failure(unreachable);
+
+            let record = am.address_store.get(reachable).clone();
+            assert_eq!(record.connection_failed_count, 0);
+            assert!(record.last_success > 0 && record.last_success == record.last_attempt);
+            assert_eq!((record.protocol_version, record.services, record.user_agent.as_str()), (7, 1, "/waglaylad:0.14.5/"));
+            assert_eq!(record.latency, Some(120));
+            assert_eq!(record.source, Some(source));
+            let record = am.address_store.get(unreachable).clone();
+            assert_eq!(record.connection_failed_count, 2);
+            assert_eq!(record.last_success, 0);
+
+            // Export and import into a fresh node
+            let exported = am.get_all_address_records();
+            assert_eq!(exported.len(), 2);
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let (other, _) = AddressManager::new(config, db.1, Arc::new(TickService::default()));
+            let mut other = other.lock();
+            assert_eq!(other.import_address_records(exported.clone()), 2);
+            assert_eq!(other.import_address_records(exported), 0);
+            assert_eq!(other.address_store.get(reachable), am.address_store.get(reachable));
+
+            // Stale pruning removes the never connected address first, and respects the retained minimum
+            let now = unix_now();
+            let policy = StaleAddressPolicy { max_unconnected_age: DAY_MILLIS, max_connected_age: 10 * DAY_MILLIS, min_retained: 0 };
+            assert_eq!(other.address_store.prune_stale(&policy, now + 2 * DAY_MILLIS), 1);
+            assert!(other.address_store.has(reachable));
+            let policy = StaleAddressPolicy { min_retained: 1, ..policy };
+            assert_eq!(other.address_store.prune_stale(&policy, now + 20 * DAY_MILLIS), 0);
+            let policy = StaleAddressPolicy { min_retained: 0, ..policy };
+            assert_eq!(other.address_store.prune_stale(&policy, now + 20 * DAY_MILLIS), 1);
+            assert!(!other.address_store.has(reachable));
+        }
+
+        #[test]
+        fn test_corrupt_legacy_entries() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            // a legacy key shorter than an address key
+            let mut key = Vec::<u8>::from(waglayla_database::registry::DatabaseStorePrefixes::Addresses);
+            key.extend([1, 2, 3]);
+            db.1.put(key, [0u8; 8]).unwrap();
+
+            let mut db_store = DbAddressesStore::new(db.1.clone(), CachePolicy::Empty);
+            assert!(matches!(db_store.take_legacy_entries(), Err(waglayla_database::prelude::StoreError::DataInconsistency(_))));
+
+            // the address manager starts regardless
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let (am, _) = AddressManager::new(config, db.1, Arc::new(TickService::default()));
+            assert_eq!(am.lock().get_all_addresses().len(), 0);
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             waglayla_core::log::try_init_logger("info");
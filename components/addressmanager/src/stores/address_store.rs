@@ -12,20 +12,66 @@ use std::{error::Error, fmt::Display, sync::Arc};
 use super::AddressKey;
 use crate::NetAddress;
 
+/// The address entry format preceding [`AddressRecord`], only read for migrating existing stores
 #[derive(Clone, Copy, Serialize, Deserialize)]
-pub struct Entry {
+pub struct LegacyEntry {
     pub connection_failed_count: u64,
     pub address: NetAddress,
 }
 
-impl MemSizeEstimator for Entry {}
+impl MemSizeEstimator for LegacyEntry {}
+
+/// A known address along with its connection quality history and the metadata advertised by the peer
+/// during the last handshake. Timestamps are unix milliseconds, where 0 stands for never.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressRecord {
+    pub address: NetAddress,
+    pub connection_failed_count: u64,
+    /// Last time the address was advertised to us or successfully connected to
+    pub last_seen: u64,
+    /// Last connection attempt
+    pub last_attempt: u64,
+    /// Last successful connection
+    pub last_success: u64,
+    /// Last measured ping round-trip time, in milliseconds
+    pub latency: Option<u64>,
+    pub protocol_version: u32,
+    pub services: u64,
+    pub user_agent: String,
+    /// The peer which advertised this address, if it was learned from address gossip
+    pub source: Option<NetAddress>,
+}
+
+impl AddressRecord {
+    pub fn new(address: NetAddress, connection_failed_count: u64, source: Option<NetAddress>, now: u64) -> Self {
+        Self {
+            address,
+            connection_failed_count,
+            last_seen: now,
+            last_attempt: 0,
+            last_success: 0,
+            latency: None,
+            protocol_version: 0,
+            services: 0,
+            user_agent: String::new(),
+            source,
+        }
+    }
+
+    /// Migrated addresses have no history, so they are considered seen at migration time
+    pub fn from_legacy(entry: LegacyEntry, now: u64) -> Self {
+        Self::new(entry.address, entry.connection_failed_count, None, now)
+    }
+}
+
+impl MemSizeEstimator for AddressRecord {}
 
 pub trait AddressesStoreReader {
-    fn get(&self, key: AddressKey) -> Result<Entry, StoreError>;
+    fn get(&self, key: AddressKey) -> Result<AddressRecord, StoreError>;
 }
 
 pub trait AddressesStore: AddressesStoreReader {
-    fn set(&mut self, key: AddressKey, entry: Entry) -> StoreResult<()>;
+    fn set(&mut self, key: AddressKey, record: AddressRecord) -> StoreResult<()>;
     #[allow(dead_code)]
     fn set_failed_count(&mut self, key: AddressKey, connection_failed_count: u64) -> StoreResult<()>;
     fn remove(&mut self, key: AddressKey) -> StoreResult<()>;
@@ -71,41 +117,56 @@ impl From<DbAddressKey> for AddressKey {
     }
 }
 
+fn parse_key<T>(iter_result: Result<(Box<[u8]>, T), Box<dyn Error>>) -> Result<(AddressKey, T), Box<dyn Error>> {
+    let (key_bytes, data) = iter_result?;
+    let address_key_slice = <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..])?;
+    Ok((DbAddressKey(address_key_slice).into(), data))
+}
+
 #[derive(Clone)]
 pub struct DbAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<DbAddressKey, Entry>,
+    access: CachedDbAccess<DbAddressKey, AddressRecord>,
+    legacy_access: CachedDbAccess<DbAddressKey, LegacyEntry>,
 }
 
 impl DbAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::Addresses.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), cache_policy, DatabaseStorePrefixes::AddressRecords.into()),
+            legacy_access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into()),
+        }
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, AddressRecord), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(parse_key)
     }
 
-    pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, Entry), Box<dyn Error>>> + '_ {
-        self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_failed_count)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
-                Ok(address_key_slice) => {
-                    let addr_key = DbAddressKey(address_key_slice);
-                    let address: AddressKey = addr_key.into();
-                    Ok((address, connection_failed_count))
-                }
-                Err(e) => Err(e.into()),
-            },
-            Err(e) => Err(e),
-        })
+    /// Removes and returns all entries stored in the legacy format
+    pub fn take_legacy_entries(&mut self) -> StoreResult<Vec<(AddressKey, LegacyEntry)>> {
+        let entries = self
+            .legacy_access
+            .iterator()
+            .map(parse_key)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| StoreError::DataInconsistency(format!("corrupt legacy address entry: {err}")))?;
+        if !entries.is_empty() {
+            self.legacy_access.delete_all(DirectDbWriter::new(&self.db))?;
+        }
+        Ok(entries)
     }
 }
 
 impl AddressesStoreReader for DbAddressesStore {
-    fn get(&self, key: AddressKey) -> Result<Entry, StoreError> {
+    fn get(&self, key: AddressKey) -> Result<AddressRecord, StoreError> {
         self.access.read(key.into())
     }
 }
 
 impl AddressesStore for DbAddressesStore {
-    fn set(&mut self, key: AddressKey, entry: Entry) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), key.into(), entry)
+    fn set(&mut self, key: AddressKey, record: AddressRecord) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), key.into(), record)
     }
 
     fn remove(&mut self, key: AddressKey) -> StoreResult<()> {
@@ -113,7 +174,7 @@ impl AddressesStore for DbAddressesStore {
     }
 
     fn set_failed_count(&mut self, key: AddressKey, connection_failed_count: u64) -> StoreResult<()> {
-        let entry = self.get(key)?;
-        self.set(key, Entry { connection_failed_count, address: entry.address })
+        let record = self.get(key)?;
+        self.set(key, AddressRecord { connection_failed_count, ..record })
     }
 }
@@ -11,7 +11,7 @@ use futures_util::future::join_all;
 use itertools::Itertools;
 use waglayla_addressmanager::{AddressManager, NetAddress};
 use waglayla_core::{debug, info, warn};
-use waglayla_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
+use waglayla_p2p_lib::{common::ProtocolError, ConnectionError, Peer, PeerRelayMode};
 use waglayla_utils::triggers::SingleTrigger;
 use parking_lot::Mutex as ParkingLotMutex;
 use rand::{seq::SliceRandom, thread_rng};
@@ -27,6 +27,7 @@ use tokio::{
 pub struct ConnectionManager {
     p2p_adaptor: Arc<waglayla_p2p_lib::Adaptor>,
     outbound_target: usize,
+    block_relay_only_target: usize,
     inbound_limit: usize,
     dns_seeders: &'static [&'static str],
     default_port: u16,
@@ -53,6 +54,7 @@ impl ConnectionManager {
     pub fn new(
         p2p_adaptor: Arc<waglayla_p2p_lib::Adaptor>,
         outbound_target: usize,
+        block_relay_only_target: usize,
         inbound_limit: usize,
         dns_seeders: &'static [&'static str],
         default_port: u16,
@@ -62,6 +64,7 @@ impl ConnectionManager {
         let manager = Arc::new(Self {
             p2p_adaptor,
             outbound_target,
+            block_relay_only_target,
             inbound_limit,
             address_manager,
             connection_requests: Default::default(),
@@ -98,6 +101,7 @@ impl ConnectionManager {
         let peers = self.p2p_adaptor.active_peers();
         let peer_by_address: HashMap<SocketAddr, Peer> = peers.into_iter().map(|peer| (peer.net_address(), peer)).collect();
 
+        self.address_manager.lock().prune_stale_addresses();
         self.handle_connection_requests(&peer_by_address).await;
         self.handle_outbound_connections(&peer_by_address).await;
         self.handle_inbound_connections(&peer_by_address).await;
@@ -160,20 +164,56 @@ impl ConnectionManager {
     }
 
     async fn handle_outbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
-        let active_outbound: HashSet<waglayla_addressmanager::NetAddress> =
+        let mut active_outbound: HashSet<waglayla_addressmanager::NetAddress> =
             peer_by_address.values().filter(|peer| peer.is_outbound()).map(|peer| peer.net_address().into()).collect();
-        if active_outbound.len() >= self.outbound_target {
-            return;
+        let (active_full_relay, active_block_relay_only) = outbound_relay_counts(peer_by_address.values());
+
+        let mut missing_connections =
+            self.connect_outbound(PeerRelayMode::Full, self.outbound_target, active_full_relay, &mut active_outbound).await;
+        missing_connections += self
+            .connect_outbound(
+                PeerRelayMode::BlockRelayOnly,
+                self.block_relay_only_target,
+                active_block_relay_only,
+                &mut active_outbound,
+            )
+            .await;
+
+        if missing_connections > 0 && !self.dns_seeders.is_empty() && !self.shutdown_signal.trigger.is_triggered() {
+            let cmgr = self.clone();
+            // DNS lookup is a blocking i/o operation, so we spawn it as a blocking task
+            let _ = tokio::task::spawn_blocking(move || {
+                cmgr.dns_seed(missing_connections); //TODO: Consider putting a number higher than `missing_connections`.
+            })
+            .await;
         }
+    }
 
-        let mut missing_connections = self.outbound_target - active_outbound.len();
-        let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_addresses(active_outbound);
+    /// Tries to reach `target` active outbound connections of the given relay mode, avoiding addresses in `active_outbound`
+    /// and adding newly connected addresses to it. Returns the number of connections still missing.
+    async fn connect_outbound(
+        self: &Arc<Self>,
+        relay_mode: PeerRelayMode,
+        target: usize,
+        active: usize,
+        active_outbound: &mut HashSet<waglayla_addressmanager::NetAddress>,
+    ) -> usize {
+        if active >= target {
+            return 0;
+        }
+
+        let kind = match relay_mode {
+            PeerRelayMode::Full => "",
+            PeerRelayMode::BlockRelayOnly => " block-relay-only",
+        };
+        let mut missing_connections = target - active;
+        let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_addresses(active_outbound.clone());
 
         let mut progressing = true;
         let mut connecting = true;
         while connecting && missing_connections > 0 {
             if self.shutdown_signal.trigger.is_triggered() {
-                return;
+                return missing_connections;
             }
             let mut addrs_to_connect = Vec::with_capacity(missing_connections);
             let mut jobs = Vec::with_capacity(missing_connections);
@@ -185,23 +225,25 @@ impl ConnectionManager {
                 let socket_addr = SocketAddr::new(net_addr.ip.into(), net_addr.port).to_string();
                 debug!("Connecting to {}", &socket_addr);
                 addrs_to_connect.push(net_addr);
-                jobs.push(self.p2p_adaptor.connect_peer(socket_addr.clone()));
+                jobs.push(self.p2p_adaptor.connect_peer_with_relay_mode(socket_addr.clone(), relay_mode));
             }
 
             if progressing && !jobs.is_empty() {
                 // Log only if progress was made
                 info!(
-                    "Connection manager: has {}/{} outgoing P2P connections, trying to obtain {} additional connection(s)...",
-                    self.outbound_target - missing_connections,
-                    self.outbound_target,
+                    "Connection manager: has {}/{} outgoing{} P2P connections, trying to obtain {} additional connection(s)...",
+                    target - missing_connections,
+                    target,
+                    kind,
                     jobs.len(),
                 );
                 progressing = false;
             } else {
                 debug!(
-                    "Connection manager: outgoing: {}/{} , connecting: {}, iterator: {}",
-                    self.outbound_target - missing_connections,
-                    self.outbound_target,
+                    "Connection manager: outgoing{}: {}/{} , connecting: {}, iterator: {}",
+                    kind,
+                    target - missing_connections,
+                    target,
                     jobs.len(),
                     addr_iter.len(),
                 );
@@ -211,6 +253,7 @@ impl ConnectionManager {
                 match res {
                     Ok(_) => {
                         self.address_manager.lock().mark_connection_success(net_addr);
+                        active_outbound.insert(net_addr);
                         missing_connections -= 1;
                         progressing = true;
                     }
@@ -226,18 +269,11 @@ impl ConnectionManager {
             }
         }
 
-        if missing_connections > 0 && !self.dns_seeders.is_empty() {
-            let cmgr = self.clone();
-            // DNS lookup is a blocking i/o operation, so we spawn it as a blocking task
-            let _ = tokio::task::spawn_blocking(move || {
-                cmgr.dns_seed(missing_connections); //TODO: Consider putting a number higher than `missing_connections`.
-            })
-            .await;
-        }
+        missing_connections
     }
 
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
-        let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
+        let active_inbound = limited_inbound_peers(peer_by_address.values(), |ip| self.p2p_adaptor.is_whitelisted(ip));
         let active_inbound_len = active_inbound.len();
         if self.inbound_limit >= active_inbound_len {
             return;
@@ -283,7 +319,7 @@ impl ConnectionManager {
     ///
     /// _GO-WAGLAYLAD: BanByIP_
     pub async fn ban(&self, ip: IpAddr) {
-        if self.ip_has_permanent_connection(ip).await {
+        if self.is_whitelisted(ip) || self.ip_has_permanent_connection(ip).await {
             return;
         }
         for peer in self.p2p_adaptor.active_peers() {
@@ -296,7 +332,14 @@ impl ConnectionManager {
 
     /// Returns whether the given address is banned.
     pub async fn is_banned(&self, address: &SocketAddr) -> bool {
-        !self.is_permanent(address).await && self.address_manager.lock().is_banned(address.ip().into())
+        !self.is_whitelisted(address.ip())
+            && !self.is_permanent(address).await
+            && self.address_manager.lock().is_banned(address.ip().into())
+    }
+
+    /// Returns whether the given IP belongs to a whitelisted network.
+    pub fn is_whitelisted(&self, ip: IpAddr) -> bool {
+        self.p2p_adaptor.is_whitelisted(&ip)
     }
 
     /// Returns whether the given address is a permanent request.
@@ -309,3 +352,70 @@ impl ConnectionManager {
         self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
     }
 }
+
+/// Returns the number of active full relay and block-relay-only outbound connections among `peers`
+fn outbound_relay_counts<'a>(peers: impl Iterator<Item = &'a Peer>) -> (usize, usize) {
+    peers.filter(|peer| peer.is_outbound()).fold((0, 0), |(full, block_relay_only), peer| {
+        if peer.is_block_relay_only() {
+            (full, block_relay_only + 1)
+        } else {
+            (full + 1, block_relay_only)
+        }
+    })
+}
+
+/// Returns the inbound peers counting towards the inbound limit. Whitelisted peers are exempt from it.
+fn limited_inbound_peers<'a>(peers: impl Iterator<Item = &'a Peer>, is_whitelisted: impl Fn(&IpAddr) -> bool) -> Vec<&'a Peer> {
+    peers.filter(|peer| !peer.is_outbound() && !is_whitelisted(&peer.net_address().ip())).collect_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use waglayla_p2p_lib::PeerProperties;
+    use waglayla_utils::networking::{IpNetwork, PeerId};
+    use std::time::Instant;
+
+    fn peer(address: &str, is_outbound: bool, relay_mode: PeerRelayMode) -> Peer {
+        Peer::new(
+            PeerId::default(),
+            address.parse().unwrap(),
+            is_outbound,
+            relay_mode,
+            Instant::now(),
+            Arc::new(PeerProperties::default()),
+            0,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn test_outbound_relay_counts() {
+        let peers = [
+            peer("10.0.0.1:16111", true, PeerRelayMode::Full),
+            peer("10.0.0.2:16111", true, PeerRelayMode::BlockRelayOnly),
+            peer("10.0.0.3:16111", true, PeerRelayMode::BlockRelayOnly),
+            peer("10.0.0.4:16111", false, PeerRelayMode::Full),
+        ];
+        assert_eq!(outbound_relay_counts(peers.iter()), (1, 2));
+
+        // Block-relay-only connections only
+        let peers =
+            [peer("10.0.0.2:16111", true, PeerRelayMode::BlockRelayOnly), peer("10.0.0.2:16112", true, PeerRelayMode::BlockRelayOnly)];
+        assert_eq!(outbound_relay_counts(peers.iter()), (0, 2));
+    }
+
+    #[test]
+    fn test_whitelisted_peers_are_exempt_from_inbound_limit() {
+        let whitelist: IpNetwork = "192.168.1.0/24".parse().unwrap();
+        let peers = [
+            peer("192.168.1.7:16111", false, PeerRelayMode::Full),
+            peer("192.168.2.7:16111", false, PeerRelayMode::Full),
+            peer("10.0.0.1:16111", false, PeerRelayMode::Full),
+            peer("192.168.1.8:16111", true, PeerRelayMode::Full),
+        ];
+        let limited = limited_inbound_peers(peers.iter(), |ip| whitelist.contains(ip));
+        let addresses = limited.iter().map(|peer| peer.net_address().to_string()).collect_vec();
+        assert_eq!(addresses, vec!["192.168.2.7:16111", "10.0.0.1:16111"]);
+    }
+}
@@ -6,7 +6,7 @@ use std::{ops::Deref, sync::Arc};
 
 pub use tokio::task::spawn_blocking;
 
-use waglayla_consensus_core::{acceptance_data::AcceptanceData, api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus}, block::Block, BlockHashSet, blockstatus::BlockStatus, BlueWorkType, ChainPath, daa_score_timestamp::DaaScoreTimestamp, errors::consensus::ConsensusResult, Hash, header::Header, pruning::{PruningPointProof, PruningPointsList, PruningPointTrustedData}, trusted::{ExternalGhostdagData, TrustedBlock}, tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry}};
+use waglayla_consensus_core::{acceptance_data::AcceptanceData, api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus}, block::Block, BlockHashSet, blockstatus::BlockStatus, BlueWorkType, ChainPath, daa_score_timestamp::DaaScoreTimestamp, dag_export::{DagExportBlock, DagExportRange}, errors::consensus::ConsensusResult, Hash, header::Header, pruning::{PruningPointProof, PruningPointsList, PruningPointTrustedData}, trusted::{ExternalGhostdagData, TrustedBlock}, tx::{MutableTransaction, ScriptPublicKeys, Transaction, TransactionOutpoint, UtxoEntry}, utxo::utxo_collection::UtxoCollection};
 use waglayla_utils::sync::rwlock::*;
 
 use crate::BlockProcessingBatch;
@@ -265,6 +265,25 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_virtual_utxos(from_outpoint, chunk_size, skip_first)).await
     }
 
+    /// Reconstructs the UTXOs of the given script public keys at a past selected chain block. Only supported by archival nodes.
+    ///
+    /// See [`ConsensusApi::get_utxos_at_chain_block`]
+    pub async fn async_get_utxos_at_chain_block(
+        &self,
+        chain_block: Hash,
+        script_public_keys: ScriptPublicKeys,
+    ) -> ConsensusResult<UtxoCollection> {
+        self.clone().spawn_blocking(move |c| c.get_utxos_at_chain_block(chain_block, &script_public_keys)).await
+    }
+
+    pub async fn async_get_chain_block_at_daa_score(&self, daa_score: u64) -> ConsensusResult<Hash> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_at_daa_score(daa_score)).await
+    }
+
+    pub async fn async_export_dag(&self, range: DagExportRange, max_blocks: usize) -> ConsensusResult<Vec<DagExportBlock>> {
+        self.clone().spawn_blocking(move |c| c.export_dag(range, max_blocks)).await
+    }
+
     pub async fn async_get_tips(&self) -> Vec<Hash> {
         self.clone().spawn_blocking(|c| c.get_tips()).await
     }
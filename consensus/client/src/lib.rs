@@ -18,12 +18,14 @@ cfg_if::cfg_if! {
         mod sign;
         mod script;
         mod serializable;
+        mod receipt;
 
 
         pub use header::*;
         pub use input::*;
         pub use transaction::*;
         pub use serializable::*;
+        pub use receipt::*;
         pub use vtx::*;
         pub use hash::*;
         // pub use signing::*;
@@ -0,0 +1,85 @@
+use crate::error::Error;
+use crate::header::Header;
+use crate::result::Result;
+use crate::transaction::Transaction;
+use js_sys::Object;
+use waglayla_consensus_core::header as native;
+use waglayla_consensus_core::merkle::{TransactionInclusionProof, TransactionReceipt};
+use waglayla_consensus_core::tx as cctx;
+use waglayla_hashes::Hash;
+use waglayla_merkle::MerkleWitness;
+use wasm_bindgen::prelude::*;
+use workflow_wasm::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TRANSACTION_RECEIPT: &'static str = r#"
+/**
+ * The sibling hashes on the path from a leaf to the merkle root, ordered from the leaf level up.
+ * 
+ * @category Consensus
+ */
+export interface IMerkleWitness {
+    index: number;
+    siblings: HexString[];
+}
+
+/**
+ * A receipt proving a transaction is included in a block, linked through a chain of
+ * headers to a selected chain block which optionally proves the transaction was accepted.
+ * Receipts are produced by the `getTransactionInclusionProof` RPC method.
+ * 
+ * @category Consensus
+ */
+export interface ITransactionReceipt {
+    transaction: ITransaction | Transaction;
+    blockHash: HexString;
+    merkleWitness: IMerkleWitness;
+    headers: Array<IHeader | Header>;
+    acceptanceWitness?: IMerkleWitness;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ITransactionReceipt")]
+    pub type ITransactionReceipt;
+}
+
+fn merkle_witness_from_object(object: &Object) -> Result<MerkleWitness> {
+    let index = object.get_u32("index")?;
+    let siblings = object.get_vec("siblings")?.into_iter().map(|hash| Ok(hash.try_into_owned()?)).collect::<Result<Vec<Hash>>>()?;
+    Ok(MerkleWitness::new(index, siblings))
+}
+
+impl TryFrom<ITransactionReceipt> for TransactionReceipt {
+    type Error = Error;
+    fn try_from(value: ITransactionReceipt) -> Result<Self> {
+        let Some(object) = Object::try_from(value.as_ref()) else {
+            return Err(Error::custom("transaction receipt must be an object"));
+        };
+        let transaction = Transaction::try_owned_from(object.get_value("transaction")?)?;
+        let inclusion = TransactionInclusionProof {
+            block_hash: object.get_value("blockHash")?.try_into_owned().map_err(|err| Error::convert("blockHash", err))?,
+            transaction: cctx::Transaction::from(&transaction),
+            witness: merkle_witness_from_object(&object.get_object("merkleWitness")?)?,
+        };
+        let headers = object
+            .get_vec("headers")?
+            .into_iter()
+            .map(|header| Ok(Header::try_owned_from(header)?.inner().clone()))
+            .collect::<Result<Vec<native::Header>>>()?;
+        let acceptance_witness = object.try_get_object("acceptanceWitness")?.map(|x| merkle_witness_from_object(&x)).transpose()?;
+        Ok(TransactionReceipt::new(inclusion, headers, acceptance_witness))
+    }
+}
+
+/// Verifies a transaction receipt with no access to a node. Returns the hash of the
+/// selected chain block the receipt leads to, which should then be checked to be a
+/// chain block of a trusted node (e.g. using the `getBlock` RPC method).
+/// Throws if the receipt is invalid.
+/// @category Consensus
+#[wasm_bindgen(js_name = verifyTransactionReceipt)]
+pub fn verify_transaction_receipt_js(receipt: ITransactionReceipt) -> Result<String> {
+    let receipt = TransactionReceipt::try_from(receipt)?;
+    receipt.verify().map(|hash| hash.to_string()).ok_or_else(|| Error::custom("invalid transaction receipt"))
+}
@@ -10,6 +10,7 @@ pub struct ProcessingCounters {
     pub txs_counts: AtomicU64,
     pub chain_block_counts: AtomicU64,
     pub mass_counts: AtomicU64,
+    pub assumed_valid_txs_counts: AtomicU64,
 }
 
 impl ProcessingCounters {
@@ -23,6 +24,7 @@ impl ProcessingCounters {
             txs_counts: self.txs_counts.load(Ordering::Relaxed),
             chain_block_counts: self.chain_block_counts.load(Ordering::Relaxed),
             mass_counts: self.mass_counts.load(Ordering::Relaxed),
+            assumed_valid_txs_counts: self.assumed_valid_txs_counts.load(Ordering::Relaxed),
         }
     }
 }
@@ -37,6 +39,7 @@ pub struct ProcessingCountersSnapshot {
     pub txs_counts: u64,
     pub chain_block_counts: u64,
     pub mass_counts: u64,
+    pub assumed_valid_txs_counts: u64,
 }
 
 impl core::ops::Sub for &ProcessingCountersSnapshot {
@@ -52,6 +55,7 @@ impl core::ops::Sub for &ProcessingCountersSnapshot {
             txs_counts: self.txs_counts.saturating_sub(rhs.txs_counts),
             chain_block_counts: self.chain_block_counts.saturating_sub(rhs.chain_block_counts),
             mass_counts: self.mass_counts.saturating_sub(rhs.mass_counts),
+            assumed_valid_txs_counts: self.assumed_valid_txs_counts.saturating_sub(rhs.assumed_valid_txs_counts),
         }
     }
 }
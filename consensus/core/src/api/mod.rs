@@ -8,6 +8,7 @@ use crate::{
     blockstatus::BlockStatus,
     coinbase::MinerData,
     daa_score_timestamp::DaaScoreTimestamp,
+    dag_export::{DagExportBlock, DagExportRange},
     errors::{
         block::{BlockProcessResult, RuleError},
         coinbase::CoinbaseResult,
@@ -16,14 +17,15 @@ use crate::{
         tx::TxResult,
     },
     header::Header,
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofLevelStats},
     trusted::{ExternalGhostdagData, TrustedBlock},
-    tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    tx::{MutableTransaction, ScriptPublicKeys, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_collection::UtxoCollection,
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use waglayla_hashes::Hash;
 
-pub use self::stats::{BlockCount, ConsensusStats};
+pub use self::stats::{BlockCount, ConsensusStats, ReorgHistogram, ReorgStats};
 
 pub mod counters;
 pub mod stats;
@@ -172,6 +174,23 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the UTXOs of `script_public_keys` within the UTXO set of the selected chain block `chain_block`, i.e., after
+    /// accepting its mergeset. The state is reconstructed by reverting the UTXO diffs of the chain blocks on the way
+    /// from the virtual, hence the call is only supported by archival nodes.
+    fn get_utxos_at_chain_block(&self, chain_block: Hash, script_public_keys: &ScriptPublicKeys) -> ConsensusResult<UtxoCollection> {
+        unimplemented!()
+    }
+
+    /// Returns the latest selected chain block with a DAA score not above `daa_score`. Only supported by archival nodes.
+    fn get_chain_block_at_daa_score(&self, daa_score: u64) -> ConsensusResult<Hash> {
+        unimplemented!()
+    }
+
+    /// Exports the selected chain blocks within `range` along with the blocks they merge. See [`DagExportRange`]
+    fn export_dag(&self, range: DagExportRange, max_blocks: usize) -> ConsensusResult<Vec<DagExportBlock>> {
+        unimplemented!()
+    }
+
     fn get_tips(&self) -> Vec<Hash> {
         unimplemented!()
     }
@@ -188,6 +207,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Validates the proof in isolation, regardless of the current pruning point, and returns statistics of each of its levels
+    fn inspect_pruning_proof(&self, proof: &PruningPointProof) -> PruningImportResult<Vec<PruningProofLevelStats>> {
+        unimplemented!()
+    }
+
     fn apply_pruning_proof(&self, proof: PruningPointProof, trusted_set: &[TrustedBlock]) -> PruningImportResult<()> {
         unimplemented!()
     }
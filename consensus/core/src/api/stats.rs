@@ -1,5 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 #[cfg(not(target_family = "wasm"))]
 use pyo3::{pyclass};
@@ -47,4 +48,93 @@ pub struct ConsensusStats {
 
     /// Virtual-related stats
     pub virtual_stats: VirtualStateStats,
+
+    /// Chain reorgs observed since consensus startup
+    pub reorg_histogram: ReorgHistogram,
+}
+
+/// Statistics of a single virtual change which removed blocks from the selected chain
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReorgStats {
+    /// Number of chain blocks removed from the selected chain
+    pub depth: u64,
+    /// DAA score difference between the previous sink and the common chain ancestor of the previous and new sinks
+    pub daa_score_span: u64,
+    /// Number of transactions accepted by the removed chain blocks which are not re-accepted by the added chain blocks
+    pub unaccepted_tx_count: u64,
+}
+
+/// Number of reorg depth buckets. Bucket `i` counts reorgs with a depth within `[2^i, 2^(i+1))`, except for the
+/// last bucket which counts all deeper reorgs as well
+pub const REORG_HISTOGRAM_BUCKETS: usize = 8;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReorgHistogram {
+    /// Reorg counts by depth, see [`REORG_HISTOGRAM_BUCKETS`]
+    pub depth_buckets: [u64; REORG_HISTOGRAM_BUCKETS],
+    pub max_depth: u64,
+    pub max_daa_score_span: u64,
+    pub total_unaccepted_tx_count: u64,
+}
+
+impl ReorgHistogram {
+    pub fn bucket_index(depth: u64) -> usize {
+        (depth.max(1).ilog2() as usize).min(REORG_HISTOGRAM_BUCKETS - 1)
+    }
+
+    /// The inclusive lower depth bound of the bucket
+    pub fn bucket_lower_bound(index: usize) -> u64 {
+        1 << index
+    }
+
+    pub fn reorg_count(&self) -> u64 {
+        self.depth_buckets.iter().sum()
+    }
+}
+
+/// Concurrently recorded [`ReorgHistogram`]
+#[derive(Default)]
+pub struct ReorgCounters {
+    depth_buckets: [AtomicU64; REORG_HISTOGRAM_BUCKETS],
+    max_depth: AtomicU64,
+    max_daa_score_span: AtomicU64,
+    total_unaccepted_tx_count: AtomicU64,
+}
+
+impl ReorgCounters {
+    pub fn record(&self, stats: &ReorgStats) {
+        self.depth_buckets[ReorgHistogram::bucket_index(stats.depth)].fetch_add(1, Ordering::Relaxed);
+        self.max_depth.fetch_max(stats.depth, Ordering::Relaxed);
+        self.max_daa_score_span.fetch_max(stats.daa_score_span, Ordering::Relaxed);
+        self.total_unaccepted_tx_count.fetch_add(stats.unaccepted_tx_count, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> ReorgHistogram {
+        ReorgHistogram {
+            depth_buckets: core::array::from_fn(|i| self.depth_buckets[i].load(Ordering::Relaxed)),
+            max_depth: self.max_depth.load(Ordering::Relaxed),
+            max_daa_score_span: self.max_daa_score_span.load(Ordering::Relaxed),
+            total_unaccepted_tx_count: self.total_unaccepted_tx_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorg_histogram() {
+        let counters = ReorgCounters::default();
+        for (depth, daa_score_span, unaccepted_tx_count) in [(1, 2, 0), (2, 3, 5), (3, 7, 1), (4, 9, 0), (1000, 2000, 10)] {
+            counters.record(&ReorgStats { depth, daa_score_span, unaccepted_tx_count });
+        }
+        let histogram = counters.snapshot();
+        assert_eq!(histogram.depth_buckets, [1, 2, 1, 0, 0, 0, 0, 1]);
+        assert_eq!(histogram.reorg_count(), 5);
+        assert_eq!(histogram.max_depth, 1000);
+        assert_eq!(histogram.max_daa_score_span, 2000);
+        assert_eq!(histogram.total_unaccepted_tx_count, 16);
+        assert_eq!(ReorgHistogram::bucket_lower_bound(ReorgHistogram::bucket_index(4)), 4);
+    }
 }
@@ -1,7 +1,9 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use waglayla_utils::mem_size::MemSizeEstimator;
 use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
 pub enum BlockStatus {
     /// StatusInvalid indicates that the block is invalid.
     StatusInvalid,
@@ -50,4 +52,53 @@ impl BlockStatus {
     pub fn is_invalid(self) -> bool {
         self == BlockStatus::StatusInvalid
     }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StatusInvalid => "invalid",
+            Self::StatusUTXOValid => "utxo-valid",
+            Self::StatusUTXOPendingVerification => "utxo-pending",
+            Self::StatusDisqualifiedFromChain => "disqualified",
+            Self::StatusHeaderOnly => "header-only",
+        }
+    }
+}
+
+impl Display for BlockStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BlockStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "invalid" => Ok(Self::StatusInvalid),
+            "utxo-valid" => Ok(Self::StatusUTXOValid),
+            "utxo-pending" => Ok(Self::StatusUTXOPendingVerification),
+            "disqualified" => Ok(Self::StatusDisqualifiedFromChain),
+            "header-only" => Ok(Self::StatusHeaderOnly),
+            _ => Err(format!("unknown block status: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_status_str_roundtrip() {
+        for status in [
+            BlockStatus::StatusInvalid,
+            BlockStatus::StatusUTXOValid,
+            BlockStatus::StatusUTXOPendingVerification,
+            BlockStatus::StatusDisqualifiedFromChain,
+            BlockStatus::StatusHeaderOnly,
+        ] {
+            assert_eq!(status.to_string().parse::<BlockStatus>().unwrap(), status);
+        }
+    }
 }
@@ -34,9 +34,85 @@ impl<const BPS: u64> Bps<BPS> {
 
     /// Returns the GHOSTDAG K value which was pre-computed for this BPS
     /// (see [`calculate_ghostdag_k`] and [`gen_ghostdag_table`] for the full calculation)
-    #[rustfmt::skip]
     pub const fn ghostdag_k() -> KType {
-        match BPS {
+        RuntimeBps(BPS).ghostdag_k()
+    }
+
+    /// Returns the target time per block in milliseconds
+    pub const fn target_time_per_block() -> u64 {
+        RuntimeBps(BPS).target_time_per_block()
+    }
+
+    /// Returns the max number of direct parents a block can have
+    pub const fn max_block_parents() -> u8 {
+        RuntimeBps(BPS).max_block_parents()
+    }
+
+    pub const fn mergeset_size_limit() -> u64 {
+        RuntimeBps(BPS).mergeset_size_limit()
+    }
+
+    pub const fn merge_depth_bound() -> u64 {
+        RuntimeBps(BPS).merge_depth_bound()
+    }
+
+    pub const fn finality_depth() -> u64 {
+        RuntimeBps(BPS).finality_depth()
+    }
+
+    pub const fn pruning_depth() -> u64 {
+        RuntimeBps(BPS).pruning_depth()
+    }
+
+    pub const fn pruning_proof_m() -> u64 {
+        RuntimeBps(BPS).pruning_proof_m()
+    }
+
+    /// Sample rate for sampling blocks to the median time window (in block units, hence dependent on BPS)
+    pub const fn past_median_time_sample_rate() -> u64 {
+        RuntimeBps(BPS).past_median_time_sample_rate()
+    }
+
+    /// Sample rate for sampling blocks to the DA window (in block units, hence dependent on BPS)
+    pub const fn difficulty_adjustment_sample_rate() -> u64 {
+        RuntimeBps(BPS).difficulty_adjustment_sample_rate()
+    }
+
+    pub const fn coinbase_maturity() -> u64 {
+        RuntimeBps(BPS).coinbase_maturity()
+    }
+
+    /// DAA score after which the pre-deflationary period switches to the deflationary period
+    /// (see [`RuntimeBps::deflationary_phase_daa_score`])
+    pub const fn deflationary_phase_daa_score() -> u64 {
+        RuntimeBps(BPS).deflationary_phase_daa_score()
+    }
+
+    pub const fn pre_deflationary_phase_base_subsidy() -> u64 {
+        RuntimeBps(BPS).pre_deflationary_phase_base_subsidy()
+    }
+}
+
+/// The runtime counterpart of [`Bps`], for networks whose BPS is only known at runtime (e.g., custom networks
+/// loaded from a params file). Functions panic for unsupported BPS values, see [`RuntimeBps::is_supported`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RuntimeBps(pub u64);
+
+impl RuntimeBps {
+    /// Returns whether the BPS value has a pre-computed GHOSTDAG K and divides a second with no remainder
+    pub const fn is_supported(self) -> bool {
+        self.0 >= 1 && self.0 <= 32 && 1000 % self.0 == 0
+    }
+
+    pub const fn bps(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the GHOSTDAG K value which was pre-computed for this BPS
+    /// (see [`calculate_ghostdag_k`] and [`gen_ghostdag_table`] for the full calculation)
+    #[rustfmt::skip]
+    pub const fn ghostdag_k(self) -> KType {
+        match self.0 {
             1 => 18, 2 => 31, 3 => 43, 4 => 55, 5 => 67, 6 => 79, 7 => 90, 8 => 102, 9 => 113, 10 => 124,
             11 => 135, 12 => 146, 13 => 157, 14 => 168, 15 => 179, 16 => 190, 17 => 201, 18 => 212, 19 => 223, 20 => 234,
             21 => 244, 22 => 255, 23 => 266, 24 => 277, 25 => 288, 26 => 298, 27 => 309, 28 => 320, 29 => 330, 30 => 341,
@@ -46,16 +122,16 @@ impl<const BPS: u64> Bps<BPS> {
     }
 
     /// Returns the target time per block in milliseconds
-    pub const fn target_time_per_block() -> u64 {
-        if 1000 % BPS != 0 {
+    pub const fn target_time_per_block(self) -> u64 {
+        if 1000 % self.0 != 0 {
             panic!("target_time_per_block is in milliseconds hence BPS must divide 1000 with no remainder")
         }
-        1000 / BPS
+        1000 / self.0
     }
 
     /// Returns the max number of direct parents a block can have
-    pub const fn max_block_parents() -> u8 {
-        let val = (Self::ghostdag_k() / 2) as u8;
+    pub const fn max_block_parents(self) -> u8 {
+        let val = (self.ghostdag_k() / 2) as u8;
         if val < 10 {
             10
         } else if val > 16 {
@@ -72,8 +148,8 @@ impl<const BPS: u64> Bps<BPS> {
         }
     }
 
-    pub const fn mergeset_size_limit() -> u64 {
-        let val = Self::ghostdag_k() as u64 * 2;
+    pub const fn mergeset_size_limit(self) -> u64 {
+        let val = self.ghostdag_k() as u64 * 2;
         if val < 180 {
             180
         } else if val > 512 {
@@ -85,27 +161,27 @@ impl<const BPS: u64> Bps<BPS> {
         }
     }
 
-    pub const fn merge_depth_bound() -> u64 {
-        BPS * MERGE_DEPTH_DURATION
+    pub const fn merge_depth_bound(self) -> u64 {
+        self.0 * MERGE_DEPTH_DURATION
     }
 
-    pub const fn finality_depth() -> u64 {
-        BPS * NEW_FINALITY_DURATION
+    pub const fn finality_depth(self) -> u64 {
+        self.0 * NEW_FINALITY_DURATION
     }
 
     /// Limit used to previously calculate the pruning depth.
-    const fn prev_mergeset_size_limit() -> u64 {
-        Self::ghostdag_k() as u64 * 10
+    const fn prev_mergeset_size_limit(self) -> u64 {
+        self.ghostdag_k() as u64 * 10
     }
 
-    pub const fn pruning_depth() -> u64 {
+    pub const fn pruning_depth(self) -> u64 {
         // Based on the analysis at https://github.com/waglaylanet/docs/blob/main/Reference/prunality/Prunality.pdf
         // and on the decomposition of merge depth (rule R-I therein) from finality depth (φ)
         // We add an additional merge depth unit as a safety margin for anticone finalization
-        Self::finality_depth()
-            + Self::merge_depth_bound() * 2
-            + 4 * Self::prev_mergeset_size_limit() * Self::ghostdag_k() as u64
-            + 2 * Self::ghostdag_k() as u64
+        self.finality_depth()
+            + self.merge_depth_bound() * 2
+            + 4 * self.prev_mergeset_size_limit() * self.ghostdag_k() as u64
+            + 2 * self.ghostdag_k() as u64
             + 2
 
         // TODO (HF or restart of TN11):
@@ -113,23 +189,23 @@ impl<const BPS: u64> Bps<BPS> {
         // This will give us a round easy number to track which is not sensitive to minor changes in other related params.
     }
 
-    pub const fn pruning_proof_m() -> u64 {
+    pub const fn pruning_proof_m(self) -> u64 {
         // No need to scale this constant with BPS since the important block levels (higher) remain logarithmically short
         PRUNING_PROOF_M
     }
 
     /// Sample rate for sampling blocks to the median time window (in block units, hence dependent on BPS)
-    pub const fn past_median_time_sample_rate() -> u64 {
-        BPS * PAST_MEDIAN_TIME_SAMPLE_INTERVAL
+    pub const fn past_median_time_sample_rate(self) -> u64 {
+        self.0 * PAST_MEDIAN_TIME_SAMPLE_INTERVAL
     }
 
     /// Sample rate for sampling blocks to the DA window (in block units, hence dependent on BPS)
-    pub const fn difficulty_adjustment_sample_rate() -> u64 {
-        BPS * DIFFICULTY_WINDOW_SAMPLE_INTERVAL
+    pub const fn difficulty_adjustment_sample_rate(self) -> u64 {
+        self.0 * DIFFICULTY_WINDOW_SAMPLE_INTERVAL
     }
 
-    pub const fn coinbase_maturity() -> u64 {
-        BPS * LEGACY_COINBASE_MATURITY
+    pub const fn coinbase_maturity(self) -> u64 {
+        self.0 * LEGACY_COINBASE_MATURITY
     }
 
     /// DAA score after which the pre-deflationary period switches to the deflationary period.
@@ -140,12 +216,12 @@ impl<const BPS: u64> Bps<BPS> {
     /// - Half a year in seconds = 365.25 / 2 * 24 * 60 * 60 = 15778800
     /// - The network was down for three days shortly after launch
     /// - Three days in seconds = 3 * 24 * 60 * 60 = 259200
-    pub const fn deflationary_phase_daa_score() -> u64 {
-        BPS * (7889400)
+    pub const fn deflationary_phase_daa_score(self) -> u64 {
+        self.0 * (7889400)
     }
 
-    pub const fn pre_deflationary_phase_base_subsidy() -> u64 {
-        50000000000 / BPS
+    pub const fn pre_deflationary_phase_base_subsidy(self) -> u64 {
+        50000000000 / self.0
     }
 }
 
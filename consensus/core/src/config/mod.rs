@@ -2,8 +2,9 @@ pub mod bps;
 pub mod constants;
 pub mod genesis;
 pub mod params;
+pub mod params_file;
 
-use waglayla_utils::networking::{ContextualNetAddress, NetAddress};
+use waglayla_utils::networking::{ContextualNetAddress, IpNetwork, NetAddress};
 
 // #[cfg(feature = "devnet-prealloc")]
 use crate::utxo::utxo_collection::UtxoCollection;
@@ -17,6 +18,9 @@ use {
     params::Params,
 };
 
+/// Default minimal depth of chain reorgs which are reported, see [`Config::chain_reorg_notification_depth`]
+pub const DEFAULT_CHAIN_REORG_NOTIFICATION_DEPTH: u64 = 5;
+
 /// Various consensus configurations all bundled up under a single struct. Use `Config::new` for directly building from
 /// a `Params` instance. For anything more complex it is recommended to use `ConfigBuilder`. NOTE: this struct can be
 /// implicitly de-refed into `Params`
@@ -35,6 +39,10 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// Indicates whether this node is a header-only light node, which syncs headers on top of a verified pruning
+    /// point proof and neither processes block bodies nor maintains a UTXO set
+    pub is_light: bool,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -66,8 +74,23 @@ pub struct Config {
 
     pub disable_upnp: bool,
 
+    /// Global upload budget (bytes/sec) for serving IBD blocks and pruning point UTXO chunks to syncing peers.
+    /// Block and transaction relay are never throttled. `None` means unlimited
+    pub p2p_upload_limit: Option<u64>,
+
+    /// If set, all P2P messages exchanged with each peer are recorded to capture files in this directory
+    pub p2p_capture_dir: Option<String>,
+
+    /// Peers from these networks are never banned nor disconnected due to the inbound limit, and always
+    /// receive our transaction relay
+    pub p2p_whitelist: Vec<IpNetwork>,
+
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
+
+    /// Chain reorgs removing at least this number of chain blocks are logged as warnings and emitted as `ChainReorg`
+    /// notifications
+    pub chain_reorg_notification_depth: u64,
 }
 
 impl Config {
@@ -81,6 +104,7 @@ impl Config {
             perf,
             process_genesis: true,
             is_archival: false,
+            is_light: false,
             enable_sanity_checks: false,
             utxoindex: false,
             unsafe_rpc: false,
@@ -94,7 +118,11 @@ impl Config {
             // #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
+            p2p_upload_limit: None,
+            p2p_capture_dir: None,
+            p2p_whitelist: Default::default(),
             ram_scale: 1.0,
+            chain_reorg_notification_depth: DEFAULT_CHAIN_REORG_NOTIFICATION_DEPTH,
         }
     }
 
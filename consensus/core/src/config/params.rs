@@ -9,6 +9,7 @@ use crate::{
     BlockLevel, KType,
 };
 use waglayla_addresses::Prefix;
+use waglayla_hashes::Hash;
 use waglayla_math::Uint256;
 use std::{
     cmp::min,
@@ -93,6 +94,12 @@ pub struct Params {
     pub pruning_proof_m: u64,
 
     pub hf_relaunch_daa_score: u64,
+
+    /// Scripts of the transactions accepted by this block and by the chain blocks on its selected chain are assumed valid and
+    /// are not verified during sync, as long as the resulting UTXO state matches the commitments of these blocks. All other
+    /// header, body and UTXO validations are still performed. Unlike the params above, this one is not consensus-sensitive
+    /// and can be overridden by node configuration
+    pub assume_valid: Option<Hash>,
 }
 
 fn unix_now() -> u64 {
@@ -209,11 +216,7 @@ impl Params {
     /// Based on the analysis at <https://github.com/waglaylanet/docs/blob/main/Reference/prunality/Prunality.pdf>
     /// and on the decomposition of merge depth (rule R-I therein) from finality depth (φ)
     pub fn anticone_finalization_depth(&self) -> u64 {
-        let anticone_finalization_depth = self.finality_depth
-            + self.merge_depth
-            + 4 * self.mergeset_size_limit * self.ghostdag_k as u64
-            + 2 * self.ghostdag_k as u64
-            + 2;
+        let anticone_finalization_depth = self.unbounded_anticone_finalization_depth();
 
         // In mainnet it's guaranteed that `self.pruning_depth` is greater
         // than `anticone_finalization_depth`, but for some tests we use
@@ -223,6 +226,11 @@ impl Params {
         min(self.pruning_depth, anticone_finalization_depth)
     }
 
+    /// Returns the anticone finalization depth regardless of the pruning depth, i.e., the minimal safe pruning depth
+    pub(crate) fn unbounded_anticone_finalization_depth(&self) -> u64 {
+        self.finality_depth + self.merge_depth + 4 * self.mergeset_size_limit * self.ghostdag_k as u64 + 2 * self.ghostdag_k as u64 + 2
+    }
+
     /// Returns whether the sink timestamp is recent enough and the node is considered synced or nearly synced.
     pub fn is_nearly_synced(&self, sink_timestamp: u64, sink_daa_score: u64) -> bool {
         if self.net.is_mainnet() {
@@ -282,6 +290,7 @@ impl From<NetworkId> for Params {
             NetworkType::Testnet => match value.suffix {
                 Some(10) => TESTNET_PARAMS,
                 Some(11) => TESTNET11_PARAMS,
+                // Params of custom testnets only exist in their params file and must be loaded from it
                 Some(x) => panic!("Testnet suffix {} is not supported", x),
                 None => panic!("Testnet suffix not provided"),
             },
@@ -351,6 +360,10 @@ pub const MAINNET_PARAMS: Params = Params {
     pruning_proof_m: 1000,
 
     hf_relaunch_daa_score: u64::MAX,
+
+    // Set to a deep selected chain block, taken from a fully validated node, on releases. Until then scripts are
+    // fully verified unless a block is supplied with `--assume-valid`
+    assume_valid: None,
 };
 
 pub const TESTNET_PARAMS: Params = Params {
@@ -411,6 +424,9 @@ pub const TESTNET_PARAMS: Params = Params {
     pruning_proof_m: Bps::<10>::pruning_proof_m(),
 
     hf_relaunch_daa_score: u64::MAX,
+
+    // See the mainnet params
+    assume_valid: None,
 };
 
 pub const TESTNET11_PARAMS: Params = Params {
@@ -465,6 +481,9 @@ pub const TESTNET11_PARAMS: Params = Params {
     max_block_level: 250,
 
     hf_relaunch_daa_score: u64::MAX,
+
+    // See the mainnet params
+    assume_valid: None,
 };
 
 pub const SIMNET_PARAMS: Params = Params {
@@ -519,6 +538,8 @@ pub const SIMNET_PARAMS: Params = Params {
     max_block_level: 250,
 
     hf_relaunch_daa_score: u64::MAX,
+
+    assume_valid: None,
 };
 
 pub const DEVNET_PARAMS: Params = Params {
@@ -577,4 +598,6 @@ pub const DEVNET_PARAMS: Params = Params {
     pruning_proof_m: 1000,
 
     hf_relaunch_daa_score: u64::MAX,
+
+    assume_valid: None,
 };
@@ -0,0 +1,218 @@
+//! Consensus params of custom networks, loaded from a TOML or JSON file so that private networks can
+//! be launched from the released binary rather than by patching [`params`](super::params) and
+//! [`genesis`](super::genesis)
+
+use super::{
+    bps::RuntimeBps,
+    genesis::GenesisBlock,
+    params::{Params, DIFFICULTY_SAMPLED_WINDOW_SIZE, MIN_DIFFICULTY_WINDOW_LEN, SIMNET_PARAMS},
+};
+use crate::{errors::config::NetworkParamsError, header::Header, merkle::calc_hash_merkle_root, network::NetworkId};
+use serde::Deserialize;
+use waglayla_hashes::{Hash, ZERO_HASH};
+use waglayla_muhash::EMPTY_MUHASH;
+
+/// A custom network definition. BPS-dependent params are derived from `bps` as done for the built-in networks,
+/// while the optional fields override the derived values. All other params follow simnet, which activates all
+/// consensus rules from network inception
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkParamsFile {
+    /// Network id such as `testnet-42` or `devnet-7`, determining the address prefix, the default ports and the
+    /// data directory of the network
+    pub network: NetworkId,
+    pub bps: u64,
+    #[serde(default)]
+    pub dns_seeders: Vec<String>,
+    #[serde(default)]
+    pub skip_proof_of_work: bool,
+    pub genesis: GenesisParams,
+
+    pub coinbase_maturity: Option<u64>,
+    pub difficulty_window_size: Option<usize>,
+    pub min_difficulty_window_len: Option<usize>,
+    pub merge_depth: Option<u64>,
+    pub finality_depth: Option<u64>,
+    pub pruning_depth: Option<u64>,
+    pub deflationary_phase_daa_score: Option<u64>,
+    pub pre_deflationary_phase_base_subsidy: Option<u64>,
+}
+
+/// The genesis block fields of a custom network. The hash merkle root is computed from the coinbase payload
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisParams {
+    /// Expected genesis hash, verified against the hash of the genesis header when provided
+    pub hash: Option<Hash>,
+    #[serde(default)]
+    pub version: u16,
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+    pub bits: u32,
+    #[serde(default)]
+    pub nonce: u64,
+    #[serde(default)]
+    pub daa_score: u64,
+    /// Hex encoded coinbase transaction payload
+    pub coinbase_payload: String,
+}
+
+impl TryFrom<NetworkParamsFile> for Params {
+    type Error = NetworkParamsError;
+
+    /// Builds and validates the params of a custom network. Note that the DNS seeders and the coinbase payload are
+    /// leaked in order to satisfy the static lifetimes of [`Params`], so this is expected to be called once per process
+    fn try_from(file: NetworkParamsFile) -> Result<Self, Self::Error> {
+        if file.network.is_mainnet() {
+            return Err(NetworkParamsError::Mainnet);
+        }
+        let bps = RuntimeBps(file.bps);
+        if !bps.is_supported() {
+            return Err(NetworkParamsError::UnsupportedBps(file.bps));
+        }
+
+        let mut payload = vec![0u8; file.genesis.coinbase_payload.len() / 2];
+        faster_hex::hex_decode(file.genesis.coinbase_payload.as_bytes(), &mut payload)
+            .map_err(|err| NetworkParamsError::InvalidCoinbasePayload(err.to_string()))?;
+        let mut genesis = GenesisBlock {
+            hash: ZERO_HASH,
+            version: file.genesis.version,
+            hash_merkle_root: ZERO_HASH,
+            utxo_commitment: EMPTY_MUHASH,
+            timestamp: file.genesis.timestamp,
+            bits: file.genesis.bits,
+            nonce: file.genesis.nonce,
+            daa_score: file.genesis.daa_score,
+            coinbase_payload: Box::leak(payload.into_boxed_slice()),
+        };
+        genesis.hash_merkle_root = calc_hash_merkle_root(genesis.build_genesis_transactions().iter());
+        genesis.hash = Header::from(&genesis).hash;
+        if let Some(expected) = file.genesis.hash.filter(|&expected| expected != genesis.hash) {
+            return Err(NetworkParamsError::GenesisHashMismatch(expected, genesis.hash));
+        }
+
+        let dns_seeders: Vec<&'static str> = file.dns_seeders.into_iter().map(|seeder| &*Box::leak(seeder.into_boxed_str())).collect();
+        let params = Params {
+            dns_seeders: Box::leak(dns_seeders.into_boxed_slice()),
+            net: file.network,
+            genesis,
+            ghostdag_k: bps.ghostdag_k(),
+            target_time_per_block: bps.target_time_per_block(),
+            past_median_time_sample_rate: bps.past_median_time_sample_rate(),
+            difficulty_sample_rate: bps.difficulty_adjustment_sample_rate(),
+            sampled_difficulty_window_size: file.difficulty_window_size.unwrap_or(DIFFICULTY_SAMPLED_WINDOW_SIZE as usize),
+            min_difficulty_window_len: file.min_difficulty_window_len.unwrap_or(MIN_DIFFICULTY_WINDOW_LEN),
+            max_block_parents: bps.max_block_parents(),
+            mergeset_size_limit: bps.mergeset_size_limit(),
+            merge_depth: file.merge_depth.unwrap_or(bps.merge_depth_bound()),
+            finality_depth: file.finality_depth.unwrap_or(bps.finality_depth()),
+            pruning_depth: file.pruning_depth.unwrap_or(bps.pruning_depth()),
+            pruning_proof_m: bps.pruning_proof_m(),
+            deflationary_phase_daa_score: file.deflationary_phase_daa_score.unwrap_or(bps.deflationary_phase_daa_score()),
+            pre_deflationary_phase_base_subsidy: file
+                .pre_deflationary_phase_base_subsidy
+                .unwrap_or(bps.pre_deflationary_phase_base_subsidy()),
+            coinbase_maturity: file.coinbase_maturity.unwrap_or(bps.coinbase_maturity()),
+            skip_proof_of_work: file.skip_proof_of_work,
+            // blocks of other networks are not in the DAG of a custom network
+            assume_valid: None,
+            ..SIMNET_PARAMS
+        };
+
+        if params.merge_depth > params.finality_depth {
+            return Err(NetworkParamsError::MergeDepthAboveFinalityDepth(params.merge_depth, params.finality_depth));
+        }
+        if params.pruning_depth < params.unbounded_anticone_finalization_depth() {
+            return Err(NetworkParamsError::PruningDepthBelowFinalization(
+                params.pruning_depth,
+                params.unbounded_anticone_finalization_depth(),
+            ));
+        }
+        if params.min_difficulty_window_len > params.sampled_difficulty_window_size {
+            return Err(NetworkParamsError::MinDifficultyWindowLenAboveWindowSize(
+                params.min_difficulty_window_len,
+                params.sampled_difficulty_window_size,
+            ));
+        }
+        if params.coinbase_maturity >= params.pruning_depth {
+            return Err(NetworkParamsError::CoinbaseMaturityAbovePruningDepth(params.coinbase_maturity, params.pruning_depth));
+        }
+
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::bps::Testnet11Bps;
+
+    fn params_file(extra: &str) -> NetworkParamsFile {
+        let json = format!(
+            r#"{{
+                "network": "testnet-42",
+                "bps": 10,
+                "dns_seeders": ["seeder.example.org"],
+                "genesis": {{ "timestamp": 1700000000000, "bits": 511705087, "coinbase_payload": "0000000000000000" }}
+                {extra}
+            }}"#
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_params_file() {
+        let params = Params::try_from(params_file("")).unwrap();
+        assert_eq!(params.net, NetworkId::with_suffix(crate::network::NetworkType::Testnet, 42));
+        assert_eq!(params.dns_seeders, &["seeder.example.org"]);
+        assert_eq!(params.ghostdag_k, Testnet11Bps::ghostdag_k());
+        assert_eq!(params.target_time_per_block, Testnet11Bps::target_time_per_block());
+        assert_eq!(params.pruning_depth, Testnet11Bps::pruning_depth());
+        assert_eq!(params.coinbase_maturity, Testnet11Bps::coinbase_maturity());
+        assert_eq!(params.genesis.coinbase_payload, &[0u8; 8]);
+        assert_eq!(params.genesis.hash, Header::from(&params.genesis).hash);
+        assert!(!params.skip_proof_of_work);
+        assert_eq!(params.assume_valid, None);
+
+        // Pinning the genesis hash
+        let genesis_hash = params.genesis.hash;
+        let pinned = params_file(r#", "coinbase_maturity": 20, "skip_proof_of_work": true"#);
+        let mut pinned_genesis = pinned.clone();
+        pinned_genesis.genesis.hash = Some(genesis_hash);
+        let params = Params::try_from(pinned_genesis).unwrap();
+        assert_eq!(params.coinbase_maturity, 20);
+        assert!(params.skip_proof_of_work);
+        let mut wrong_genesis = pinned;
+        wrong_genesis.genesis.hash = Some(1.into());
+        assert_eq!(Params::try_from(wrong_genesis).unwrap_err(), NetworkParamsError::GenesisHashMismatch(1.into(), genesis_hash));
+    }
+
+    #[test]
+    fn test_params_file_validation() {
+        let mut file = params_file("");
+        file.network = NetworkId::new(crate::network::NetworkType::Mainnet);
+        assert_eq!(Params::try_from(file).unwrap_err(), NetworkParamsError::Mainnet);
+
+        let mut file = params_file("");
+        file.bps = 3;
+        assert_eq!(Params::try_from(file).unwrap_err(), NetworkParamsError::UnsupportedBps(3));
+
+        let mut file = params_file("");
+        file.genesis.coinbase_payload = "0x".to_string();
+        assert!(matches!(Params::try_from(file).unwrap_err(), NetworkParamsError::InvalidCoinbasePayload(_)));
+
+        let file = params_file(r#", "merge_depth": 100, "finality_depth": 50"#);
+        assert_eq!(Params::try_from(file).unwrap_err(), NetworkParamsError::MergeDepthAboveFinalityDepth(100, 50));
+
+        let file = params_file(r#", "pruning_depth": 1000"#);
+        assert!(matches!(Params::try_from(file).unwrap_err(), NetworkParamsError::PruningDepthBelowFinalization(1000, _)));
+
+        let file = params_file(r#", "difficulty_window_size": 5"#);
+        assert_eq!(Params::try_from(file).unwrap_err(), NetworkParamsError::MinDifficultyWindowLenAboveWindowSize(10, 5));
+
+        // Unknown fields are rejected so that typos do not silently fall back to derived values
+        let json = r#"{ "network": "devnet-1", "bps": 1, "coinbase_maturty": 5,
+            "genesis": { "timestamp": 0, "bits": 0, "coinbase_payload": "" } }"#;
+        assert!(serde_json::from_str::<NetworkParamsFile>(json).is_err());
+    }
+}
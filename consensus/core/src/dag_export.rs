@@ -0,0 +1,151 @@
+//! Export of DAG ranges along with their GHOSTDAG and acceptance data, for visualization and offline analysis.
+
+use crate::{blockstatus::BlockStatus, tx::TransactionId, BlockHashSet, BlueWorkType};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use waglayla_hashes::Hash;
+
+/// A range of selected chain blocks, inclusive on both ends. An export of the range holds each of its chain blocks
+/// along with the blocks it merges, hence merged blocks might have scores outside the range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DagExportRange {
+    DaaScore { start: u64, end: u64 },
+    BlueScore { start: u64, end: u64 },
+}
+
+impl DagExportRange {
+    pub fn bounds(&self) -> (u64, u64) {
+        match *self {
+            Self::DaaScore { start, end } | Self::BlueScore { start, end } => (start, end),
+        }
+    }
+}
+
+/// The transactions of a mergeset block which were accepted by a selected chain block
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DagExportAcceptance {
+    pub block_hash: Hash,
+    pub accepted_transaction_ids: Vec<TransactionId>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DagExportBlock {
+    pub hash: Hash,
+    /// Direct (level 0) parents
+    pub parents: Vec<Hash>,
+    pub daa_score: u64,
+    pub blue_score: u64,
+    pub blue_work: BlueWorkType,
+    pub selected_parent: Hash,
+    pub mergeset_blues: Vec<Hash>,
+    pub mergeset_reds: Vec<Hash>,
+    pub status: BlockStatus,
+    /// Whether the block is on the selected chain of the exporting node
+    pub is_chain_block: bool,
+    /// The acceptance data of a chain block, i.e. the transactions it accepted from each of its mergeset blocks
+    pub acceptance: Option<Vec<DagExportAcceptance>>,
+}
+
+/// Writes the blocks as JSON Lines, one block object per line
+pub fn write_jsonl<W: Write>(writer: &mut W, blocks: &[DagExportBlock]) -> std::io::Result<()> {
+    for block in blocks {
+        serde_json::to_writer(&mut *writer, block)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Reads blocks previously written by [`write_jsonl`], skipping empty lines
+pub fn read_jsonl<R: BufRead>(reader: R) -> std::io::Result<Vec<DagExportBlock>> {
+    let mut blocks = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            blocks.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(blocks)
+}
+
+/// Writes the blocks as a Graphviz DOT digraph with edges pointing from children to parents. Chain blocks are
+/// colored blue, blocks merged as red by any exported block are colored red and invalid blocks gray. Selected
+/// parent edges are drawn bold, and parents outside the export are omitted.
+pub fn write_dot<W: Write>(writer: &mut W, blocks: &[DagExportBlock]) -> std::io::Result<()> {
+    let exported: BlockHashSet = blocks.iter().map(|block| block.hash).collect();
+    let reds: BlockHashSet = blocks.iter().flat_map(|block| block.mergeset_reds.iter().copied()).collect();
+
+    writeln!(writer, "digraph dag {{")?;
+    writeln!(writer, "  rankdir=RL;")?;
+    writeln!(writer, "  node [shape=box, style=filled, fontname=monospace];")?;
+    for block in blocks {
+        let color = if block.status.is_invalid() {
+            "gray"
+        } else if block.is_chain_block {
+            "lightblue"
+        } else if reds.contains(&block.hash) {
+            "salmon"
+        } else {
+            "white"
+        };
+        let short_hash = &block.hash.to_string()[..8];
+        writeln!(
+            writer,
+            "  \"{}\" [label=\"{}\\nDAA {} / blue {}\\n{}\", fillcolor={}];",
+            block.hash, short_hash, block.daa_score, block.blue_score, block.status, color
+        )?;
+    }
+    for block in blocks {
+        for parent in block.parents.iter().filter(|parent| exported.contains(parent)) {
+            let style = if *parent == block.selected_parent { " [style=bold]" } else { "" };
+            writeln!(writer, "  \"{}\" -> \"{}\"{};", block.hash, parent, style)?;
+        }
+    }
+    writeln!(writer, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(hash: u64, parents: &[u64], reds: &[u64], is_chain_block: bool) -> DagExportBlock {
+        DagExportBlock {
+            hash: hash.into(),
+            parents: parents.iter().map(|&parent| parent.into()).collect(),
+            daa_score: hash,
+            blue_score: hash,
+            blue_work: hash.into(),
+            selected_parent: parents.first().map(|&parent| parent.into()).unwrap_or_default(),
+            mergeset_blues: parents.iter().filter(|parent| !reds.contains(parent)).map(|&parent| parent.into()).collect(),
+            mergeset_reds: reds.iter().map(|&red| red.into()).collect(),
+            status: BlockStatus::StatusUTXOValid,
+            is_chain_block,
+            acceptance: is_chain_block
+                .then(|| vec![DagExportAcceptance { block_hash: hash.into(), accepted_transaction_ids: vec![] }]),
+        }
+    }
+
+    #[test]
+    fn test_dag_export_formats() {
+        let blocks = vec![block(1, &[], &[], true), block(2, &[1], &[], false), block(3, &[1, 2], &[2], true)];
+
+        let mut jsonl = Vec::new();
+        write_jsonl(&mut jsonl, &blocks).unwrap();
+        assert_eq!(jsonl.iter().filter(|&&b| b == b'\n').count(), blocks.len());
+        assert_eq!(read_jsonl(jsonl.as_slice()).unwrap(), blocks);
+
+        let mut dot = Vec::new();
+        write_dot(&mut dot, &blocks).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        let (h1, h2, h3) = (Hash::from(1), Hash::from(2), Hash::from(3));
+        assert!(dot.starts_with("digraph dag {"));
+        assert!(dot.contains(&format!("\"{h3}\" -> \"{h1}\" [style=bold];")));
+        assert!(dot.contains(&format!("\"{h3}\" -> \"{h2}\";")));
+        assert!(dot.contains(&format!("\"{h2}\" -> \"{h1}\" [style=bold];")));
+        assert!(dot.contains("fillcolor=salmon"));
+        assert_eq!(dot.matches("fillcolor=lightblue").count(), 2);
+    }
+}
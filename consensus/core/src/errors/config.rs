@@ -1,4 +1,5 @@
 use thiserror::Error;
+use waglayla_hashes::Hash;
 
 #[derive(Error, Debug, Clone)]
 pub enum ConfigError {
@@ -17,6 +18,21 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --light and --archival cannot be used together")]
+    MixedLightAndArchival,
+
+    #[error("Configuration: --light and --utxoindex cannot be used together")]
+    MixedLightAndUtxoIndex,
+
+    #[error("Configuration: --network-params cannot be used together with --testnet, --devnet or --simnet")]
+    MixedNetworkParamsAndNetwork,
+
+    #[error("Configuration: invalid --p2p-pin entry {0}: {1}")]
+    InvalidPinnedPeer(String, String),
+
+    #[error("Configuration: cannot load the P2P identity key from {0}: {1}")]
+    InvalidP2pIdentityKey(String, String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -27,3 +43,30 @@ pub enum ConfigError {
 }
 
 pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NetworkParamsError {
+    #[error("custom network params cannot be used for mainnet")]
+    Mainnet,
+
+    #[error("BPS {0} is not supported, expected a value between 1 and 32 which divides 1000")]
+    UnsupportedBps(u64),
+
+    #[error("genesis coinbase payload is not a valid hex string: {0}")]
+    InvalidCoinbasePayload(String),
+
+    #[error("genesis hash is {1} while the params file expects {0}")]
+    GenesisHashMismatch(Hash, Hash),
+
+    #[error("merge depth {0} is greater than finality depth {1}")]
+    MergeDepthAboveFinalityDepth(u64, u64),
+
+    #[error("pruning depth {0} is below the anticone finalization depth {1}")]
+    PruningDepthBelowFinalization(u64, u64),
+
+    #[error("min difficulty window length {0} is greater than the difficulty window size {1}")]
+    MinDifficultyWindowLenAboveWindowSize(usize, usize),
+
+    #[error("coinbase maturity {0} is not below the pruning depth {1}")]
+    CoinbaseMaturityAbovePruningDepth(u64, u64),
+}
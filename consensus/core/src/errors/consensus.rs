@@ -23,6 +23,18 @@ pub enum ConsensusError {
     #[error("pruning point is not at sufficient depth from virtual, cannot obtain its final anticone at this stage")]
     PruningPointInsufficientDepth,
 
+    #[error("historical UTXO state is only available on archival nodes")]
+    NotArchival,
+
+    #[error("block {0} is not a selected chain block")]
+    NotChainBlock(Hash),
+
+    #[error("no selected chain block with DAA score at or below {0} is retained by this node")]
+    NoChainBlockAtDaaScore(u64),
+
+    #[error("the requested DAG range spans more than {0} blocks")]
+    DagExportTooLarge(usize),
+
     #[error("sync manager error: {0}")]
     SyncManagerError(#[from] SyncManagerError),
 
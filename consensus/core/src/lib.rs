@@ -15,6 +15,7 @@ pub mod blockstatus;
 pub mod coinbase;
 pub mod config;
 pub mod constants;
+pub mod dag_export;
 pub mod daa_score_timestamp;
 pub mod errors;
 pub mod hashing;
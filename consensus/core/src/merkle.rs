@@ -1,6 +1,11 @@
-use crate::{hashing, tx::Transaction};
+use crate::{
+    block::Block,
+    hashing,
+    header::Header,
+    tx::{Transaction, TransactionId},
+};
 use waglayla_hashes::Hash;
-use waglayla_merkle::calc_merkle_root;
+use waglayla_merkle::{calc_merkle_root, create_merkle_witness, MerkleWitness};
 
 pub fn calc_hash_merkle_root_with_options<'a>(txs: impl ExactSizeIterator<Item = &'a Transaction>, include_mass_field: bool) -> Hash {
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)))
@@ -10,10 +15,93 @@ pub fn calc_hash_merkle_root<'a>(txs: impl ExactSizeIterator<Item = &'a Transact
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, false)))
 }
 
+/// Proves that a transaction is included in the body of a block by committing to the block header's `hash_merkle_root`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionInclusionProof {
+    pub block_hash: Hash,
+    pub transaction: Transaction,
+    pub witness: MerkleWitness,
+}
+
+impl TransactionInclusionProof {
+    /// Creates a proof for the transaction at `index` within the body of `block`. Returns `None` if `index` is out of bounds
+    pub fn new(block: &Block, index: usize) -> Option<Self> {
+        let witness = create_merkle_witness(block.transactions.iter().map(|tx| hashing::tx::hash(tx, false)), index)?;
+        Some(Self { block_hash: block.hash(), transaction: block.transactions[index].clone(), witness })
+    }
+
+    /// Verifies the proof against `header`, which is expected to be already validated by the caller
+    pub fn verify(&self, header: &Header) -> bool {
+        header.hash == self.block_hash && self.witness.verify(hashing::tx::hash(&self.transaction, false), header.hash_merkle_root)
+    }
+}
+
+/// Creates a witness proving `transaction_id` is committed to by the `accepted_id_merkle_root` of a selected chain
+/// block accepting `accepted_ids`. Returns `None` if the transaction is not amongst them
+pub fn create_accepted_id_merkle_witness(
+    accepted_ids: impl IntoIterator<Item = TransactionId>,
+    transaction_id: TransactionId,
+) -> Option<MerkleWitness> {
+    // Accepted ids are sorted before building the merkle root, see `UtxoProcessingContext`
+    let mut accepted_ids: Vec<_> = accepted_ids.into_iter().collect();
+    accepted_ids.sort();
+    let index = accepted_ids.binary_search(&transaction_id).ok()?;
+    create_merkle_witness(accepted_ids.into_iter(), index)
+}
+
+/// A self-contained receipt for a transaction, verifiable with no access to consensus data. It links the block
+/// containing the transaction through a chain of headers to a selected chain block, which optionally proves
+/// the transaction was accepted by committing to it in its `accepted_id_merkle_root`.
+///
+/// A valid receipt only proves the transaction is included in the ancestry of the last header. The party verifying
+/// it is expected to check that the hash returned by [`TransactionReceipt::verify`] is a chain block of a node it trusts
+#[derive(Clone, Debug)]
+pub struct TransactionReceipt {
+    pub inclusion: TransactionInclusionProof,
+    /// Headers from the block containing the transaction up to the selected chain block, each a direct parent of the next
+    pub headers: Vec<Header>,
+    /// Proves the transaction is accepted by the last header
+    pub acceptance_witness: Option<MerkleWitness>,
+}
+
+impl TransactionReceipt {
+    pub fn new(inclusion: TransactionInclusionProof, headers: Vec<Header>, acceptance_witness: Option<MerkleWitness>) -> Self {
+        Self { inclusion, headers, acceptance_witness }
+    }
+
+    /// Verifies the receipt. Header hashes are recalculated rather than trusted. Returns the hash of the last header,
+    /// or `None` if the receipt is invalid
+    pub fn verify(&self) -> Option<Hash> {
+        let (first, last) = (self.headers.first()?, self.headers.last()?);
+        if self.headers.iter().any(|header| hashing::header::hash(header) != header.hash) {
+            return None;
+        }
+        if !self.inclusion.verify(first) {
+            return None;
+        }
+        if !self.headers.windows(2).all(|pair| pair[1].direct_parents().contains(&pair[0].hash)) {
+            return None;
+        }
+        if let Some(witness) = self.acceptance_witness.as_ref() {
+            if !witness.verify(self.inclusion.transaction.id(), last.accepted_id_merkle_root) {
+                return None;
+            }
+        }
+        Some(last.hash)
+    }
+
+    /// Whether the receipt proves the transaction was accepted by the last header rather than merely included in its ancestry
+    pub fn is_accepted(&self) -> bool {
+        self.acceptance_witness.is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::merkle::calc_hash_merkle_root;
+    use crate::merkle::{calc_hash_merkle_root, create_accepted_id_merkle_witness, TransactionInclusionProof, TransactionReceipt};
     use crate::{
+        block::Block,
+        header::Header,
         subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
         tx::{scriptvec, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
     };
@@ -250,4 +338,75 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn transaction_inclusion_proof_test() {
+        let txs: Vec<_> = (0..5u8).map(|i| Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![i])).collect();
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(1), vec![]);
+        header.hash_merkle_root = calc_hash_merkle_root(txs.iter());
+        let block = Block::new(header.clone(), txs);
+
+        for index in 0..block.transactions.len() {
+            let proof = TransactionInclusionProof::new(&block, index).unwrap();
+            assert!(proof.verify(&header));
+
+            // A proof of a modified transaction does not hold
+            let mut forged = proof.clone();
+            forged.transaction.payload.push(0);
+            assert!(!forged.verify(&header));
+        }
+        assert!(TransactionInclusionProof::new(&block, block.transactions.len()).is_none());
+
+        // Nor does a proof checked against a different header
+        let proof = TransactionInclusionProof::new(&block, 0).unwrap();
+        assert!(!proof.verify(&Header::from_precomputed_hash(Hash::from_u64_word(2), vec![])));
+    }
+
+    #[test]
+    fn transaction_receipt_test() {
+        let txs: Vec<_> = (0..3u8).map(|i| Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![i])).collect();
+        let mut containing = Header::from_precomputed_hash(Hash::default(), vec![]);
+        containing.hash_merkle_root = calc_hash_merkle_root(txs.iter());
+        containing.finalize();
+        let block = Block::new(containing.clone(), txs.clone());
+
+        let accepted_ids: Vec<_> = txs.iter().map(|tx| tx.id()).chain((10..15).map(Hash::from_u64_word)).collect();
+        let mut middle = Header::from_precomputed_hash(Hash::default(), vec![containing.hash]);
+        middle.finalize();
+        let mut chain_block = Header::from_precomputed_hash(Hash::default(), vec![Hash::from_u64_word(20), middle.hash]);
+        chain_block.accepted_id_merkle_root = waglayla_merkle::calc_merkle_root({
+            let mut ids = accepted_ids.clone();
+            ids.sort();
+            ids.into_iter()
+        });
+        chain_block.finalize();
+
+        let inclusion = TransactionInclusionProof::new(&block, 1).unwrap();
+        let acceptance = create_accepted_id_merkle_witness(accepted_ids.iter().copied(), txs[1].id());
+        assert!(acceptance.is_some());
+        let headers = vec![containing.clone(), middle.clone(), chain_block.clone()];
+        let receipt = TransactionReceipt::new(inclusion.clone(), headers.clone(), acceptance.clone());
+        assert_eq!(receipt.verify(), Some(chain_block.hash));
+        assert!(receipt.is_accepted());
+
+        // A receipt without an acceptance witness still proves inclusion in the ancestry of the last header
+        assert_eq!(TransactionReceipt::new(inclusion.clone(), headers.clone(), None).verify(), Some(chain_block.hash));
+
+        // A header whose hash does not match its contents
+        let mut tampered = headers.clone();
+        tampered[2].accepted_id_merkle_root = Hash::from_u64_word(1);
+        assert_eq!(TransactionReceipt::new(inclusion.clone(), tampered, acceptance.clone()).verify(), None);
+
+        // A broken header chain
+        let unlinked = vec![containing.clone(), chain_block.clone()];
+        assert_eq!(TransactionReceipt::new(inclusion.clone(), unlinked, acceptance.clone()).verify(), None);
+
+        // An acceptance witness of another transaction
+        let other = create_accepted_id_merkle_witness(accepted_ids.iter().copied(), txs[2].id());
+        assert_eq!(TransactionReceipt::new(inclusion.clone(), headers.clone(), other).verify(), None);
+        assert!(create_accepted_id_merkle_witness(accepted_ids.iter().copied(), Hash::from_u64_word(99)).is_none());
+
+        // No headers at all
+        assert_eq!(TransactionReceipt::new(inclusion, vec![], acceptance).verify(), None);
+    }
 }
@@ -1,6 +1,7 @@
 use crate::{
     header::Header,
     trusted::{TrustedGhostdagData, TrustedHeader},
+    BlockHashSet, BlockLevel, BlueWorkType,
 };
 use waglayla_hashes::Hash;
 use std::sync::Arc;
@@ -19,3 +20,96 @@ pub struct PruningPointTrustedData {
     /// Union of GHOSTDAG data required to verify blocks in the future of the pruning point
     pub ghostdag_blocks: Vec<TrustedGhostdagData>,
 }
+
+/// Statistics of a single pruning point proof level, gathered while validating the proof in isolation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PruningProofLevelStats {
+    pub level: BlockLevel,
+    pub header_count: usize,
+    /// The pruning point if the level is at most the pruning point level, otherwise its parent at this level
+    pub selected_tip: Hash,
+    /// Blue score of the selected tip as computed by the GHOSTDAG of this level
+    pub selected_tip_blue_score: u64,
+    /// Blue work of the selected tip as computed by the GHOSTDAG of this level
+    pub selected_tip_blue_work: BlueWorkType,
+    pub lowest_daa_score: u64,
+    pub highest_daa_score: u64,
+}
+
+/// The difference between a single level of two pruning point proofs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PruningProofLevelDiff {
+    pub level: BlockLevel,
+    /// Number of leading headers which are identical in both proofs
+    pub common_prefix_len: usize,
+    /// Headers of the first proof at this level which are missing from the second, in proof order
+    pub only_in_first: Vec<Hash>,
+    /// Headers of the second proof at this level which are missing from the first, in proof order
+    pub only_in_second: Vec<Hash>,
+    /// Number of headers at this level in each proof
+    pub header_counts: (usize, usize),
+}
+
+impl PruningProofLevelDiff {
+    pub fn is_identical(&self) -> bool {
+        self.common_prefix_len == self.header_counts.0 && self.common_prefix_len == self.header_counts.1
+    }
+}
+
+/// Compares two pruning point proofs level by level. A level missing from one of the proofs is considered empty
+pub fn diff_pruning_point_proofs(first: &PruningPointProof, second: &PruningPointProof) -> Vec<PruningProofLevelDiff> {
+    let empty = Vec::new();
+    (0..first.len().max(second.len()))
+        .map(|level_idx| {
+            let first_level = first.get(level_idx).unwrap_or(&empty);
+            let second_level = second.get(level_idx).unwrap_or(&empty);
+            let first_hashes: BlockHashSet = first_level.iter().map(|header| header.hash).collect();
+            let second_hashes: BlockHashSet = second_level.iter().map(|header| header.hash).collect();
+            PruningProofLevelDiff {
+                level: level_idx as BlockLevel,
+                common_prefix_len: first_level.iter().zip(second_level.iter()).take_while(|(a, b)| a.hash == b.hash).count(),
+                only_in_first: first_level.iter().map(|header| header.hash).filter(|hash| !second_hashes.contains(hash)).collect(),
+                only_in_second: second_level.iter().map(|header| header.hash).filter(|hash| !first_hashes.contains(hash)).collect(),
+                header_counts: (first_level.len(), second_level.len()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(hashes: &[u64]) -> Vec<Arc<Header>> {
+        hashes.iter().map(|&hash| Arc::new(Header::from_precomputed_hash(hash.into(), vec![]))).collect()
+    }
+
+    #[test]
+    fn test_diff_pruning_point_proofs() {
+        let first = vec![level(&[1, 2, 3, 4]), level(&[1, 3]), level(&[1])];
+        let second = vec![level(&[1, 2, 5, 6]), level(&[1, 3])];
+        let diff = diff_pruning_point_proofs(&first, &second);
+        assert_eq!(diff.len(), 3);
+
+        assert!(!diff[0].is_identical());
+        assert_eq!(diff[0].common_prefix_len, 2);
+        assert_eq!(diff[0].only_in_first, vec![3.into(), 4.into()]);
+        assert_eq!(diff[0].only_in_second, vec![5.into(), 6.into()]);
+        assert_eq!(diff[0].header_counts, (4, 4));
+
+        assert!(diff[1].is_identical());
+        assert_eq!(diff[1].common_prefix_len, 2);
+
+        // A level missing from the second proof is considered empty
+        assert!(!diff[2].is_identical());
+        assert_eq!(diff[2].common_prefix_len, 0);
+        assert_eq!(diff[2].only_in_first, vec![1.into()]);
+        assert_eq!(diff[2].header_counts, (1, 0));
+
+        // The same headers in a different order
+        let diff = diff_pruning_point_proofs(&vec![level(&[1, 2, 3])], &vec![level(&[1, 3, 2])]);
+        assert_eq!(diff[0].common_prefix_len, 1);
+        assert!(diff[0].only_in_first.is_empty() && diff[0].only_in_second.is_empty());
+        assert!(!diff[0].is_identical());
+    }
+}
@@ -76,6 +76,14 @@ impl UtxoDiff {
         Self::new(self.remove, self.add)
     }
 
+    /// Reverts this diff on `utxos`, the subset of entries matching `filter` of the UTXO set this diff was applied to.
+    /// Results in the matching subset of the UTXO set prior to applying the diff.
+    pub fn unapply_filtered(&self, utxos: &mut UtxoCollection, filter: impl Fn(&UtxoEntry) -> bool) {
+        // Removals are reverted last since an outpoint might be both removed and added with a different DAA score
+        utxos.remove_collection(&self.add);
+        utxos.extend(self.remove.iter().filter(|(_, entry)| filter(entry)).map(|(outpoint, entry)| (*outpoint, entry.clone())));
+    }
+
     pub fn with_diff(&self, other: &impl ImmutableUtxoDiff) -> UtxoResult<UtxoDiff> {
         let mut clone = self.clone();
         clone.with_diff_in_place(other)?;
@@ -567,4 +575,36 @@ mod tests {
         drop(utxo_entry1);
         drop(utxo_entry2);
     }
+
+    #[test]
+    fn test_unapply_filtered() {
+        let outpoint = |index| TransactionOutpoint::new(TransactionId::from_u64_word(1), index);
+        let tracked = ScriptPublicKey::from_vec(0, vec![1]);
+        let other = ScriptPublicKey::from_vec(0, vec![2]);
+        let entry = |amount, script_public_key: &ScriptPublicKey, daa_score| {
+            UtxoEntry::new(amount, script_public_key.clone(), daa_score, false)
+        };
+
+        // A tracked UTXO is spent into a new tracked UTXO and an untracked one, while another untracked UTXO is spent
+        // and an outpoint is re-added with a different DAA score
+        let mut diff = UtxoDiff::default();
+        diff.remove.insert(outpoint(0), entry(100, &tracked, 1));
+        diff.remove.insert(outpoint(1), entry(50, &other, 1));
+        diff.remove.insert(outpoint(2), entry(10, &tracked, 1));
+        diff.add.insert(outpoint(2), entry(10, &tracked, 5));
+        diff.add.insert(outpoint(3), entry(60, &tracked, 5));
+        diff.add.insert(outpoint(4), entry(40, &other, 5));
+
+        let mut utxos: UtxoCollection =
+            [(outpoint(2), entry(10, &tracked, 5)), (outpoint(3), entry(60, &tracked, 5)), (outpoint(5), entry(7, &tracked, 0))]
+                .into_iter()
+                .collect();
+        diff.unapply_filtered(&mut utxos, |entry| entry.script_public_key == tracked);
+
+        let expected: UtxoCollection =
+            [(outpoint(0), entry(100, &tracked, 1)), (outpoint(2), entry(10, &tracked, 1)), (outpoint(5), entry(7, &tracked, 0))]
+                .into_iter()
+                .collect();
+        assert_eq!(utxos, expected);
+    }
 }
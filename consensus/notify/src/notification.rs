@@ -42,6 +42,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "ChainReorg notification: {} removed chain blocks, DAA score span {}", "_0.depth", "_0.daa_score_span")]
+    ChainReorg(ChainReorgNotification),
 }
 }
 
@@ -181,3 +184,30 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+/// A virtual change which removed at least the configured number of blocks from the selected chain
+#[derive(Debug, Clone)]
+pub struct ChainReorgNotification {
+    pub previous_sink: Hash,
+    pub new_sink: Hash,
+    /// The last chain block common to the previous and new selected chains
+    pub common_ancestor: Hash,
+    /// Number of removed chain blocks
+    pub depth: u64,
+    pub daa_score_span: u64,
+    /// Number of transactions accepted by the removed chain blocks which are not re-accepted by the added ones
+    pub unaccepted_transaction_count: u64,
+}
+
+impl ChainReorgNotification {
+    pub fn new(
+        previous_sink: Hash,
+        new_sink: Hash,
+        common_ancestor: Hash,
+        depth: u64,
+        daa_score_span: u64,
+        unaccepted_transaction_count: u64,
+    ) -> Self {
+        Self { previous_sink, new_sink, common_ancestor, depth, daa_score_span, unaccepted_transaction_count }
+    }
+}
@@ -0,0 +1,127 @@
+use super::storage::ConsensusStorage;
+use crate::model::stores::{
+    acceptance_data::AcceptanceDataStoreReader, ghostdag::GhostdagStoreReader, headers::HeaderStoreReader,
+    pruning::PruningStoreReader, relations::RelationsStoreReader, selected_chain::SelectedChainStoreReader,
+    statuses::StatusesStoreReader, virtual_state::VirtualStateStoreReader,
+};
+use waglayla_consensus_core::{
+    dag_export::{DagExportAcceptance, DagExportBlock, DagExportRange},
+    errors::consensus::{ConsensusError, ConsensusResult},
+};
+use waglayla_database::prelude::StoreResultExtensions;
+use waglayla_hashes::Hash;
+
+impl ConsensusStorage {
+    /// Exports the selected chain blocks within `range` along with the blocks they merge, in consensus order. If the
+    /// range reaches the sink, blocks merged by the virtual are exported as well. Only reads from the stores, hence can be
+    /// called over a data directory copied from a node. Fails if more than `max_blocks` blocks would be exported.
+    pub fn export_dag(&self, range: DagExportRange, max_blocks: usize) -> ConsensusResult<Vec<DagExportBlock>> {
+        let (start, end) = range.bounds();
+        let score_of = |hash| match range {
+            DagExportRange::DaaScore { .. } => self.headers_store.get_daa_score(hash).unwrap(),
+            DagExportRange::BlueScore { .. } => self.ghostdag_primary_store.get_blue_score(hash).unwrap(),
+        };
+
+        let mut chain = Vec::new();
+        let root = {
+            let sc_read = self.selected_chain_store.read();
+            let Some((tip_index, _)) = sc_read.get_tip().unwrap_option() else { return Ok(vec![]) };
+            let pruning_read = self.pruning_point_store.read();
+            let root_index = [pruning_read.history_root(), pruning_read.pruning_point()]
+                .into_iter()
+                .find_map(|root| root.ok().and_then(|root| sc_read.get_by_hash(root).ok()))
+                .unwrap_or_default();
+            drop(pruning_read);
+
+            // Chain scores are monotonic, so binary search for the first chain block with a score not below `start`
+            let (mut low, mut high) = (root_index, tip_index + 1);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if score_of(sc_read.get_by_index(mid).unwrap()) < start {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            }
+            for index in low..=tip_index {
+                let hash = sc_read.get_by_index(index).unwrap();
+                if score_of(hash) > end {
+                    break;
+                }
+                // Every chain block is exported, so stop collecting once the chain alone exceeds the limit
+                if chain.len() == max_blocks {
+                    return Err(ConsensusError::DagExportTooLarge(max_blocks));
+                }
+                chain.push(hash);
+            }
+            sc_read.get_by_index(root_index).unwrap()
+        };
+
+        let mut blocks = Vec::new();
+        for &chain_block in chain.iter() {
+            // The past of the root of retained history, including its mergeset, is pruned
+            if chain_block != root {
+                let ghostdag_data = self.ghostdag_primary_store.get_data(chain_block).unwrap();
+                for merged in ghostdag_data.consensus_ordered_mergeset_without_selected_parent(self.ghostdag_primary_store.as_ref()) {
+                    blocks.push(self.export_block(merged, false)?);
+                }
+            }
+            blocks.push(self.export_block(chain_block, true)?);
+            if blocks.len() > max_blocks {
+                return Err(ConsensusError::DagExportTooLarge(max_blocks));
+            }
+        }
+
+        // Blocks merged by the virtual are not yet merged by any chain block
+        let virtual_state = self.virtual_stores.read().state.get().unwrap_option();
+        if let Some(virtual_state) = virtual_state {
+            if chain.last() == Some(&virtual_state.ghostdag_data.selected_parent) {
+                for merged in virtual_state
+                    .ghostdag_data
+                    .consensus_ordered_mergeset_without_selected_parent(self.ghostdag_primary_store.as_ref())
+                {
+                    blocks.push(self.export_block(merged, false)?);
+                }
+                if blocks.len() > max_blocks {
+                    return Err(ConsensusError::DagExportTooLarge(max_blocks));
+                }
+            }
+        }
+        Ok(blocks)
+    }
+
+    fn export_block(&self, hash: Hash, is_chain_block: bool) -> ConsensusResult<DagExportBlock> {
+        let ghostdag_data = self.ghostdag_primary_store.get_data(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))?;
+        let parents = self.relations_stores.read()[0].get_parents(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))?;
+        let status = self.statuses_store.read().get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))?;
+        let acceptance = match is_chain_block {
+            true => self.acceptance_data_store.get(hash).unwrap_option().map(|acceptance_data| {
+                acceptance_data
+                    .iter()
+                    .map(|mergeset_block| DagExportAcceptance {
+                        block_hash: mergeset_block.block_hash,
+                        accepted_transaction_ids: mergeset_block
+                            .accepted_transactions
+                            .iter()
+                            .map(|entry| entry.transaction_id)
+                            .collect(),
+                    })
+                    .collect()
+            }),
+            false => None,
+        };
+        Ok(DagExportBlock {
+            hash,
+            parents: parents.to_vec(),
+            daa_score: self.headers_store.get_daa_score(hash).unwrap_option().ok_or(ConsensusError::HeaderNotFound(hash))?,
+            blue_score: ghostdag_data.blue_score,
+            blue_work: ghostdag_data.blue_work,
+            selected_parent: ghostdag_data.selected_parent,
+            mergeset_blues: ghostdag_data.mergeset_blues.to_vec(),
+            mergeset_reds: ghostdag_data.mergeset_reds.to_vec(),
+            status,
+            is_chain_block,
+            acceptance,
+        })
+    }
+}
@@ -16,11 +16,11 @@ use crossbeam_channel::{
 use itertools::Itertools;
 use tokio::sync::oneshot;
 
-use waglayla_consensus_core::{acceptance_data::AcceptanceData, api::{BlockValidationFutures, ConsensusApi, ConsensusStats, stats::BlockCount}, block::{Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId}, blockhash::BlockHashExtensions, BlockHashSet, blockstatus::BlockStatus, BlueWorkType, ChainPath, coinbase::MinerData, daa_score_timestamp::DaaScoreTimestamp, errors::{
+use waglayla_consensus_core::{acceptance_data::AcceptanceData, api::{BlockValidationFutures, ConsensusApi, ConsensusStats, stats::BlockCount}, block::{Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId}, blockhash::BlockHashExtensions, BlockHashSet, blockstatus::BlockStatus, BlueWorkType, ChainPath, coinbase::MinerData, daa_score_timestamp::DaaScoreTimestamp, dag_export::{DagExportBlock, DagExportRange}, errors::{
     coinbase::CoinbaseResult,
     consensus::{ConsensusError, ConsensusResult},
     tx::TxResult,
-}, errors::{difficulty::DifficultyError, pruning::PruningImportError}, header::Header, muhash::MuHashExtensions, network::NetworkType, pruning::{PruningPointProof, PruningPointsList, PruningPointTrustedData}, trusted::{ExternalGhostdagData, TrustedBlock}, tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry}};
+}, errors::{difficulty::DifficultyError, pruning::PruningImportError}, header::Header, muhash::MuHashExtensions, network::NetworkType, pruning::{PruningPointProof, PruningPointsList, PruningPointTrustedData, PruningProofLevelStats}, trusted::{ExternalGhostdagData, TrustedBlock}, tx::{MutableTransaction, ScriptPublicKeys, Transaction, TransactionOutpoint, UtxoEntry}, utxo::utxo_collection::UtxoCollection};
 use waglayla_consensus_notify::root::ConsensusNotificationRoot;
 use waglayla_consensusmanager::{SessionLock, SessionReadGuard};
 use waglayla_core::info;
@@ -46,7 +46,9 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
-            utxo_set::{UtxoSetStore, UtxoSetStoreReader},
+            utxo_diffs::UtxoDiffsStoreReader,
+            utxo_set::{DbUtxoSetStore, UtxoSetStore, UtxoSetStoreReader},
+            virtual_state::VirtualStateStoreReader,
         },
     },
     pipeline::{
@@ -65,6 +67,7 @@ use self::{services::ConsensusServices, storage::ConsensusStorage};
 
 pub mod cache_policy_builder;
 pub mod ctl;
+pub mod dag_export;
 pub mod factory;
 pub mod services;
 pub mod storage;
@@ -242,6 +245,7 @@ impl Consensus {
             pruning_lock.clone(),
             notification_root.clone(),
             counters.clone(),
+            config.chain_reorg_notification_depth,
         ));
 
         let pruning_processor = Arc::new(PruningProcessor::new(
@@ -436,6 +440,7 @@ impl ConsensusApi for Consensus {
             // turns out to be not fast enough then we should maintain an atomic integer holding this value
             num_tips: self.get_tips_len() as u64,
             virtual_stats: self.lkg_virtual_state.load().as_ref().into(),
+            reorg_histogram: self.virtual_processor.reorg_counters.snapshot(),
         }
     }
 
@@ -612,6 +617,74 @@ impl ConsensusApi for Consensus {
         iter.map(|item| item.unwrap()).collect()
     }
 
+    fn get_utxos_at_chain_block(&self, chain_block: Hash, script_public_keys: &ScriptPublicKeys) -> ConsensusResult<UtxoCollection> {
+        // Chain block UTXO diffs are pruned on non-archival nodes
+        if !self.config.is_archival {
+            return Err(ConsensusError::NotArchival);
+        }
+        let _guard = self.pruning_lock.blocking_read();
+        self.validate_block_exists(chain_block)?;
+        let is_tracked = |entry: &UtxoEntry| script_public_keys.contains(&entry.script_public_key);
+
+        // Open an iterator over the virtual UTXO set along with its matching virtual state. The DB iterator reads from
+        // an implicit snapshot taken on creation, so the virtual lock is released before the set is scanned. The diffs
+        // of chain blocks below the captured sink are never modified
+        let utxo_set: DbUtxoSetStore;
+        let (utxo_iter, virtual_state) = {
+            let virtual_stores = self.virtual_stores.read();
+            utxo_set = virtual_stores.utxo_set.clone();
+            (utxo_set.iterator(), virtual_stores.state.get().unwrap())
+        };
+        let mut utxos: UtxoCollection = utxo_iter
+            .map(|item| item.unwrap())
+            .filter(|(_, entry)| is_tracked(entry))
+            .map(|(outpoint, entry)| (outpoint, (*entry).clone()))
+            .collect();
+        let sink = virtual_state.ghostdag_data.selected_parent;
+        if !self.services.reachability_service.is_chain_ancestor_of(chain_block, sink) {
+            return Err(ConsensusError::NotChainBlock(chain_block));
+        }
+
+        virtual_state.utxo_diff.unapply_filtered(&mut utxos, is_tracked);
+        let mut current = sink;
+        while current != chain_block {
+            let diff = self.utxo_diffs_store.get(current).unwrap_option().ok_or(ConsensusError::MissingData(current))?;
+            diff.unapply_filtered(&mut utxos, is_tracked);
+            current = self.ghostdag_primary_store.get_selected_parent(current).unwrap();
+        }
+        Ok(utxos)
+    }
+
+    fn get_chain_block_at_daa_score(&self, daa_score: u64) -> ConsensusResult<Hash> {
+        if !self.config.is_archival {
+            return Err(ConsensusError::NotArchival);
+        }
+        let _guard = self.pruning_lock.blocking_read();
+        let source = self.get_source();
+        let sc_read = self.selected_chain_store.read();
+        let daa_score_at = |index| self.headers_store.get_daa_score(sc_read.get_by_index(index).unwrap()).unwrap();
+
+        // Binary search for the last chain block with a DAA score not above `daa_score`, which is monotonic along the chain
+        let (mut low, mut high) = (sc_read.get_by_hash(source).unwrap_option().unwrap_or_default(), sc_read.get_tip().unwrap().0);
+        if daa_score_at(low) > daa_score {
+            return Err(ConsensusError::NoChainBlockAtDaaScore(daa_score));
+        }
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if daa_score_at(mid) <= daa_score {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Ok(sc_read.get_by_index(low).unwrap())
+    }
+
+    fn export_dag(&self, range: DagExportRange, max_blocks: usize) -> ConsensusResult<Vec<DagExportBlock>> {
+        let _guard = self.pruning_lock.blocking_read();
+        self.storage.export_dag(range, max_blocks)
+    }
+
     fn get_tips(&self) -> Vec<Hash> {
         self.body_tips_store.read().get().unwrap().read().iter().copied().collect_vec()
     }
@@ -652,6 +725,10 @@ impl ConsensusApi for Consensus {
         self.services.pruning_proof_manager.validate_pruning_point_proof(proof)
     }
 
+    fn inspect_pruning_proof(&self, proof: &PruningPointProof) -> PruningImportResult<Vec<PruningProofLevelStats>> {
+        self.services.pruning_proof_manager.inspect_pruning_point_proof(proof)
+    }
+
     fn apply_pruning_proof(&self, proof: PruningPointProof, trusted_set: &[TrustedBlock]) -> PruningImportResult<()> {
         self.services.pruning_proof_manager.apply_proof(proof, trusted_set)
     }
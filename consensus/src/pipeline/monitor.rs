@@ -61,6 +61,13 @@ impl ConsensusMonitor {
                 if delta.body_counts != 0 { delta.txs_counts as f64 / delta.body_counts as f64 } else{ 0f64 },
                 if delta.body_counts != 0 { delta.mass_counts as f64 / delta.body_counts as f64 } else{ 0f64 },
             );
+            if delta.assumed_valid_txs_counts != 0 {
+                info!(
+                    "Skipped script validation of {} transactions below the assume-valid block ({:.0} transactions/s)",
+                    delta.assumed_valid_txs_counts,
+                    delta.assumed_valid_txs_counts as f64 / (now - last_log_time).as_secs_f64()
+                );
+            }
 
             last_snapshot = snapshot;
             last_log_time = now;
@@ -48,6 +48,7 @@ use crate::{
 };
 use waglayla_consensus_core::{
     acceptance_data::AcceptanceData,
+    api::stats::{ReorgCounters, ReorgStats},
     block::{BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
     blockstatus::BlockStatus::{StatusDisqualifiedFromChain, StatusUTXOValid},
     coinbase::MinerData,
@@ -55,7 +56,7 @@ use waglayla_consensus_core::{
     header::Header,
     merkle::calc_hash_merkle_root_with_options,
     pruning::PruningPointsList,
-    tx::{MutableTransaction, Transaction},
+    tx::{MutableTransaction, Transaction, TransactionId},
     utxo::{
         utxo_diff::UtxoDiff,
         utxo_view::{UtxoView, UtxoViewComposition},
@@ -64,8 +65,8 @@ use waglayla_consensus_core::{
 };
 use waglayla_consensus_notify::{
     notification::{
-        NewBlockTemplateNotification, Notification, SinkBlueScoreChangedNotification, UtxosChangedNotification,
-        VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+        ChainReorgNotification, NewBlockTemplateNotification, Notification, SinkBlueScoreChangedNotification,
+        UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
     },
     root::ConsensusNotificationRoot,
 };
@@ -88,7 +89,7 @@ use rayon::{
 use rocksdb::WriteBatch;
 use std::{
     cmp::min,
-    collections::{BinaryHeap, HashMap, VecDeque},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     ops::Deref,
     sync::{atomic::Ordering, Arc},
 };
@@ -155,10 +156,17 @@ pub struct VirtualStateProcessor {
     notification_root: Arc<ConsensusNotificationRoot>,
 
     // Counters
-    counters: Arc<ProcessingCounters>,
+    pub(super) counters: Arc<ProcessingCounters>,
+
+    // Chain reorg monitoring
+    chain_reorg_notification_depth: u64,
+    pub(crate) reorg_counters: ReorgCounters,
 
     // Storage mass hardfork DAA score
     pub(crate) storage_mass_activation_daa_score: u64,
+
+    // Block at and below which transaction scripts are assumed valid
+    pub(super) assume_valid: Option<Hash>,
 }
 
 impl VirtualStateProcessor {
@@ -175,6 +183,7 @@ impl VirtualStateProcessor {
         pruning_lock: SessionLock,
         notification_root: Arc<ConsensusNotificationRoot>,
         counters: Arc<ProcessingCounters>,
+        chain_reorg_notification_depth: u64,
     ) -> Self {
         Self {
             receiver,
@@ -219,7 +228,10 @@ impl VirtualStateProcessor {
             pruning_lock,
             notification_root,
             counters,
+            chain_reorg_notification_depth,
+            reorg_counters: Default::default(),
             storage_mass_activation_daa_score: params.storage_mass_activation_daa_score,
+            assume_valid: params.assume_valid,
         }
     }
 
@@ -323,6 +335,9 @@ impl VirtualStateProcessor {
         self.notification_root
             .notify(Notification::VirtualDaaScoreChanged(VirtualDaaScoreChangedNotification::new(new_virtual_state.daa_score)))
             .expect("expecting an open unbounded channel");
+        if !chain_path.removed.is_empty() {
+            self.report_reorg(prev_sink, new_sink, &chain_path);
+        }
         if self.notification_root.has_subscription(EventType::VirtualChainChanged) {
             // check for subscriptions before the heavy lifting
             let added_chain_blocks_acceptance_data =
@@ -337,6 +352,49 @@ impl VirtualStateProcessor {
         }
     }
 
+    /// Records the statistics of a virtual change which removed chain blocks, and reports it if it is deep enough
+    fn report_reorg(&self, prev_sink: Hash, new_sink: Hash, chain_path: &ChainPath) {
+        let common_ancestor = self.ghostdag_primary_store.get_selected_parent(*chain_path.removed.last().unwrap()).unwrap();
+        let prev_sink_daa_score = self.headers_store.get_daa_score(prev_sink).unwrap();
+        let daa_score_span = prev_sink_daa_score.saturating_sub(self.headers_store.get_daa_score(common_ancestor).unwrap());
+        let accepted_transaction_ids = |chain_block: Hash| -> Vec<TransactionId> {
+            self.acceptance_data_store
+                .get(chain_block)
+                .unwrap_option()
+                .map(|acceptance_data| {
+                    acceptance_data
+                        .iter()
+                        .flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter().map(|entry| entry.transaction_id))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let mut unaccepted: HashSet<TransactionId> = chain_path.removed.iter().copied().flat_map(accepted_transaction_ids).collect();
+        for id in chain_path.added.iter().copied().flat_map(accepted_transaction_ids) {
+            unaccepted.remove(&id);
+        }
+        let stats =
+            ReorgStats { depth: chain_path.removed.len() as u64, daa_score_span, unaccepted_tx_count: unaccepted.len() as u64 };
+        self.reorg_counters.record(&stats);
+
+        if stats.depth >= self.chain_reorg_notification_depth {
+            warn!(
+                "Chain reorg of depth {} (DAA score span {}) from sink {} to sink {} below common ancestor {}, {} transactions were un-accepted",
+                stats.depth, stats.daa_score_span, prev_sink, new_sink, common_ancestor, stats.unaccepted_tx_count
+            );
+            self.notification_root
+                .notify(Notification::ChainReorg(ChainReorgNotification::new(
+                    prev_sink,
+                    new_sink,
+                    common_ancestor,
+                    stats.depth,
+                    stats.daa_score_span,
+                    stats.unaccepted_tx_count,
+                )))
+                .expect("expecting an open unbounded channel");
+        }
+    }
+
     pub(crate) fn virtual_finality_point(&self, virtual_ghostdag_data: &GhostdagData, pruning_point: Hash) -> Hash {
         let finality_point = self.depth_manager.calc_finality_point(virtual_ghostdag_data, pruning_point);
         if self.reachability_service.is_chain_ancestor_of(pruning_point, finality_point) {
@@ -402,14 +460,16 @@ impl VirtualStateProcessor {
 
                     let header = self.headers_store.get_header(current).unwrap();
                     let mergeset_data = self.ghostdag_primary_store.get_data(current).unwrap();
-                    let pov_daa_score = header.daa_score;
 
                     let selected_parent_multiset_hash = self.utxo_multisets_store.get(selected_parent).unwrap();
                     let selected_parent_utxo_view = (&stores.utxo_set).compose(&*diff);
 
-                    let mut ctx = UtxoProcessingContext::new(mergeset_data.into(), selected_parent_multiset_hash);
-
-                    self.calculate_utxo_state(&mut ctx, &selected_parent_utxo_view, pov_daa_score);
+                    let mut ctx = self.calculate_chain_block_utxo_state(
+                        &mergeset_data,
+                        selected_parent_multiset_hash,
+                        &selected_parent_utxo_view,
+                        &header,
+                    );
                     let res = self.verify_expected_utxo_state(&mut ctx, &selected_parent_utxo_view, &header);
 
                     if let Err(rule_error) = res {
@@ -426,6 +486,14 @@ impl VirtualStateProcessor {
                         self.commit_utxo_state(current, ctx.mergeset_diff, ctx.multiset_hash, ctx.mergeset_acceptance_data);
                         // Count the number of UTXO-processed chain blocks
                         chain_block_counter += 1;
+
+                        if Some(current) == self.assume_valid {
+                            info!(
+                                "Reached the assume-valid block {}, after skipping script validation of {} transactions. All following blocks are fully validated",
+                                current,
+                                self.counters.assumed_valid_txs_counts.load(Ordering::Relaxed)
+                            );
+                        }
                     }
                 }
                 Err(err) => panic!("unexpected error {err}"),
@@ -485,7 +553,7 @@ impl VirtualStateProcessor {
         let virtual_past_median_time = self.window_manager.calc_past_median_time(&virtual_ghostdag_data)?.0;
 
         // Calc virtual UTXO state relative to selected parent
-        self.calculate_utxo_state(&mut ctx, &selected_parent_utxo_view, virtual_daa_window.daa_score);
+        self.calculate_utxo_state(&mut ctx, &selected_parent_utxo_view, virtual_daa_window.daa_score, false);
 
         // Update the accumulated diff
         accumulated_diff.with_diff_in_place(&ctx.mergeset_diff).unwrap();
@@ -1,4 +1,6 @@
+use super::utxo_validation::UtxoProcessingContext;
 use crate::{consensus::test_consensus::TestConsensus, model::services::reachability::ReachabilityService};
+use itertools::Itertools;
 use waglayla_consensus_core::{
     api::ConsensusApi,
     block::{Block, BlockTemplate, MutableBlock, TemplateBuildMode, TemplateTransactionSelector},
@@ -6,10 +8,17 @@ use waglayla_consensus_core::{
     blockstatus::BlockStatus,
     coinbase::MinerData,
     config::{params::MAINNET_PARAMS, ConfigBuilder},
-    tx::{ScriptPublicKey, ScriptVec, Transaction},
+    constants::TX_VERSION,
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{
+        ScriptPublicKey, ScriptVec, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
+    },
+    utxo::utxo_collection::UtxoCollection,
     BlockHashSet,
 };
 use waglayla_hashes::Hash;
+use waglayla_muhash::MuHash;
+use waglayla_txscript::opcodes::codes::{OpFalse, OpTrue};
 use std::{collections::VecDeque, thread::JoinHandle};
 
 struct OnetimeTxSelector {
@@ -296,6 +305,144 @@ async fn double_search_disqualified_test() {
     ctx.assert_tips_num(1);
 }
 
+#[tokio::test]
+async fn parallel_mergeset_validation_test() {
+    let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().build();
+    let mut ctx = TestContext::new(TestConsensus::new(&config));
+
+    // The selected parent UTXO view. The last outpoint can only be spent by skipping script checks
+    let op_true = ScriptPublicKey::from_vec(0, vec![OpTrue]);
+    let op_false = ScriptPublicKey::from_vec(0, vec![OpFalse]);
+    let outpoints = (1..=4).map(|i| TransactionOutpoint::new(Hash::from_u64_word(i), 0)).collect_vec();
+    let utxo_view: UtxoCollection = outpoints
+        .iter()
+        .enumerate()
+        .map(|(i, &outpoint)| (outpoint, UtxoEntry::new(1000, if i < 3 { op_true.clone() } else { op_false.clone() }, 0, false)))
+        .collect();
+
+    let a1 = spend(outpoints[0], 900);
+    let a2 = spend(outpoints[1], 900);
+    let b1 = spend(TransactionOutpoint::new(a1.id(), 0), 800); // Spends an output created by another mergeset block
+    let b2 = spend(outpoints[1], 850); // Double spends a2
+    let c1 = a2.clone(); // Duplicates a2
+    let c2 = spend(TransactionOutpoint::new(b2.id(), 0), 700); // Spends the output of a double spend
+    let c3 = spend(outpoints[3], 900); // Fails script checks unless merged by the selected parent
+    let c4 = spend(outpoints[2], 900);
+
+    // Mine an antichain of blocks with the above txs. The blocks are not UTXO valid, but their bodies are stored
+    let blocks: [(Hash, Vec<Transaction>); 3] = [
+        (1.into(), vec![a1.clone(), a2.clone()]),
+        (2.into(), vec![b1.clone(), b2.clone()]),
+        (3.into(), vec![c1, c2.clone(), c3.clone(), c4.clone()]),
+    ];
+    for (hash, txs) in blocks.iter().cloned() {
+        let block = ctx.consensus.build_block_with_parents_and_transactions(hash, vec![config.genesis.hash], txs);
+        ctx.validate_and_insert_block(block.to_immutable()).await;
+    }
+
+    // Calculate the UTXO state of a block merging all of them, both in parallel and sequentially
+    let ghostdag_data = ctx.consensus.ghostdag_manager().ghostdag(&blocks.iter().map(|(hash, _)| *hash).collect_vec());
+    let pov_daa_score = 10;
+    let mut parallel = UtxoProcessingContext::new((&ghostdag_data).into(), MuHash::new());
+    ctx.consensus.virtual_processor().calculate_utxo_state(&mut parallel, &utxo_view, pov_daa_score, false);
+    let mut sequential = UtxoProcessingContext::new((&ghostdag_data).into(), MuHash::new());
+    ctx.consensus.virtual_processor().calculate_utxo_state_sequentially(&mut sequential, &utxo_view, pov_daa_score, false);
+
+    let acceptance = |utxo_ctx: &UtxoProcessingContext| {
+        utxo_ctx
+            .mergeset_acceptance_data
+            .iter()
+            .map(|data| {
+                (data.block_hash, data.accepted_transactions.iter().map(|e| (e.transaction_id, e.index_within_block)).collect_vec())
+            })
+            .collect_vec()
+    };
+    assert_eq!(parallel.multiset_hash.finalize(), sequential.multiset_hash.finalize());
+    assert_eq!(parallel.accepted_tx_ids, sequential.accepted_tx_ids);
+    assert_eq!(parallel.mergeset_diff, sequential.mergeset_diff);
+    assert_eq!(acceptance(&parallel), acceptance(&sequential));
+
+    // Sanity check the conflict resolution against the consensus ordering of the mergeset
+    let order = |hash: u64| parallel.mergeset_acceptance_data.iter().position(|data| data.block_hash == Hash::from(hash)).unwrap();
+    let is_accepted = |id: TransactionId| parallel.accepted_tx_ids.contains(&id);
+    let first_spender = [1, 2, 3].into_iter().min_by_key(|&hash| order(hash)).unwrap();
+    assert!(is_accepted(a1.id()) && is_accepted(c4.id()));
+    assert_eq!(is_accepted(c3.id()), order(3) == 0);
+    assert_eq!(is_accepted(a2.id()), first_spender != 2);
+    assert_eq!(is_accepted(b2.id()), first_spender == 2);
+    assert_eq!(is_accepted(b1.id()), order(1) < order(2));
+    assert_eq!(is_accepted(c2.id()), first_spender == 2 && order(2) < order(3));
+    assert!(parallel.accepted_tx_ids.iter().all_unique());
+}
+
+#[tokio::test]
+async fn assume_valid_test() {
+    for assume_valid in [Some(10.into()), None] {
+        let config =
+            ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().edit_consensus_params(|p| p.assume_valid = assume_valid).build();
+        let mut ctx = TestContext::new(TestConsensus::new(&config));
+        let processor = ctx.consensus.virtual_processor().clone();
+
+        // The tx has an invalid script, which an honest node rejects
+        let op_false = ScriptPublicKey::from_vec(0, vec![OpFalse]);
+        let outpoint = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+        let utxo_view: UtxoCollection = [(outpoint, UtxoEntry::new(1000, op_false, 0, false))].into_iter().collect();
+        let invalid_tx = spend(outpoint, 900);
+
+        // The assume-valid block 10 has the selected chain 1 -> 5 -> 10 and merges block 2, which holds the invalid tx.
+        // Block 3 is not in its past at all
+        let genesis = config.genesis.hash;
+        for (hash, parents, txs) in [
+            (1, vec![genesis], vec![]),
+            (5, vec![1.into()], vec![]),
+            (2, vec![genesis], vec![invalid_tx.clone()]),
+            (10, vec![5.into(), 2.into()], vec![]),
+            (3, vec![genesis], vec![]),
+        ] {
+            let block = ctx.consensus.build_block_with_parents_and_transactions(hash.into(), parents, txs);
+            ctx.validate_and_insert_block(block.to_immutable()).await;
+        }
+
+        // Script checks are only ever skipped along the selected chain of the assume-valid block
+        for (hash, expected) in [(1, true), (5, true), (10, true), (2, false), (3, false)] {
+            assert_eq!(processor.is_assumed_valid(hash.into()), expected && assume_valid.is_some());
+        }
+
+        // Skipping script checks changes the accepted transactions, hence the UTXO state
+        let ghostdag_data = ctx.consensus.ghostdag_manager().ghostdag(&[5.into(), 2.into()]);
+        assert_eq!(ghostdag_data.selected_parent, 5.into());
+        let mut skipped = UtxoProcessingContext::new((&ghostdag_data).into(), MuHash::new());
+        processor.calculate_utxo_state(&mut skipped, &utxo_view, 10, true);
+        let mut full = UtxoProcessingContext::new((&ghostdag_data).into(), MuHash::new());
+        processor.calculate_utxo_state(&mut full, &utxo_view, 10, false);
+        assert!(skipped.accepted_tx_ids.contains(&invalid_tx.id()));
+        assert!(!full.accepted_tx_ids.contains(&invalid_tx.id()));
+
+        // Given a header committing to the honest state, the node ends up with the honest state, whether it first tried
+        // skipping script checks or not
+        let mut header = (*ctx.consensus.get_header(10.into()).unwrap()).clone();
+        header.daa_score = 10;
+        header.utxo_commitment = full.multiset_hash.finalize();
+        header.accepted_id_merkle_root = waglayla_merkle::calc_merkle_root(full.accepted_tx_ids.iter().copied());
+        let utxo_ctx = processor.calculate_chain_block_utxo_state(&ghostdag_data, MuHash::new(), &utxo_view, &header);
+        assert_eq!(utxo_ctx.accepted_tx_ids, full.accepted_tx_ids);
+        assert_eq!(utxo_ctx.mergeset_diff, full.mergeset_diff);
+        assert_eq!(ctx.consensus.processing_counters().snapshot().assumed_valid_txs_counts > 0, assume_valid.is_some());
+    }
+}
+
+fn spend(outpoint: TransactionOutpoint, value: u64) -> Transaction {
+    Transaction::new(
+        TX_VERSION,
+        vec![TransactionInput::new(outpoint, vec![], 0, 0)],
+        vec![TransactionOutput::new(value, ScriptPublicKey::from_vec(0, vec![OpTrue]))],
+        0,
+        SUBNETWORK_ID_NATIVE,
+        0,
+        vec![],
+    )
+}
+
 fn new_miner_data() -> MinerData {
     let secp = secp256k1::Secp256k1::new();
     let mut rng = rand::thread_rng();
@@ -4,7 +4,10 @@ use crate::{
         BlockProcessResult,
         RuleError::{BadAcceptedIDMerkleRoot, BadCoinbaseTransaction, BadUTXOCommitment, InvalidTransactionsInUtxoContext},
     },
-    model::stores::{block_transactions::BlockTransactionsStoreReader, daa::DaaStoreReader, ghostdag::GhostdagData},
+    model::{
+        services::reachability::ReachabilityService,
+        stores::{block_transactions::BlockTransactionsStoreReader, daa::DaaStoreReader, ghostdag::GhostdagData},
+    },
     processes::{
         mass::Kip9Version,
         transaction_validator::{
@@ -26,13 +29,18 @@ use waglayla_consensus_core::{
     },
     BlockHashMap, BlockHashSet, HashMapCustomHasher,
 };
-use waglayla_core::{info, trace};
+use waglayla_core::{debug, info, trace};
 use waglayla_hashes::Hash;
 use waglayla_muhash::MuHash;
 use waglayla_utils::refs::Refs;
 
 use rayon::prelude::*;
-use std::{iter::once, ops::Deref};
+use std::{
+    collections::HashSet,
+    iter::once,
+    ops::Deref,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// A context for processing the UTXO state of a block with respect to its selected parent.
 /// Note this can also be the virtual block.
@@ -64,75 +72,79 @@ impl<'a> UtxoProcessingContext<'a> {
 }
 
 impl VirtualStateProcessor {
-    /// Calculates UTXO state and transaction acceptance data relative to the selected parent state
+    /// Calculates UTXO state and transaction acceptance data relative to the selected parent state.
+    ///
+    /// Following `processes/Parallel Processing.md`, the transactions of all mergeset blocks are validated in parallel.
+    /// Transactions which do not spend outputs of other mergeset transactions are validated upfront, and a deterministic
+    /// pass over the mergeset in consensus order then rejects those double spending outputs already spent by earlier accepted
+    /// transactions. Transactions which do spend outputs of mergeset transactions are validated during this pass, against the
+    /// exact UTXO view accumulated up to their block. The result is identical to processing the mergeset block by block.
+    ///
+    /// If `assumed_valid` is set, the scripts of merged transactions are not verified. The caller is then responsible for
+    /// verifying the result against commitments which are known to be valid, see [`Self::calculate_chain_block_utxo_state`].
     pub(super) fn calculate_utxo_state<V: UtxoView + Sync>(
         &self,
         ctx: &mut UtxoProcessingContext,
         selected_parent_utxo_view: &V,
         pov_daa_score: u64,
+        assumed_valid: bool,
     ) {
-        let selected_parent_transactions = self.block_transactions_store.get(ctx.selected_parent()).unwrap();
-        let validated_coinbase = ValidatedTransaction::new_coinbase(&selected_parent_transactions[0]);
+        let mergeset = self.collect_mergeset_transactions(ctx);
+        Self::accept_selected_parent_coinbase(ctx, &mergeset[0].1[0], pov_daa_score);
 
-        ctx.mergeset_diff.add_transaction(&validated_coinbase, pov_daa_score).unwrap();
-        ctx.multiset_hash.add_transaction(&validated_coinbase, pov_daa_score);
-        let validated_coinbase_id = validated_coinbase.id();
-        ctx.accepted_tx_ids.push(validated_coinbase_id);
-
-        for (i, (merged_block, txs)) in once((ctx.selected_parent(), selected_parent_transactions))
-            .chain(
-                ctx.ghostdag_data
-                    .consensus_ordered_mergeset_without_selected_parent(self.ghostdag_primary_store.deref())
-                    .map(|b| (b, self.block_transactions_store.get(b).unwrap())),
-            )
-            .enumerate()
-        {
-            // Create a composed UTXO view from the selected parent UTXO view + the mergeset UTXO diff
-            let composed_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+        let mergeset_tx_ids: HashSet<TransactionId> =
+            mergeset.iter().flat_map(|(_, txs)| txs.iter().skip(1).map(|tx| tx.id())).collect();
+        let is_dependent =
+            |tx: &Transaction| tx.inputs.iter().any(|input| mergeset_tx_ids.contains(&input.previous_outpoint.transaction_id));
+        let is_independent = |tx: &Transaction| !is_dependent(tx);
 
-            // The first block in the mergeset is always the selected parent
-            let is_selected_parent = i == 0;
-
-            // No need to fully validate selected parent transactions since selected parent txs were already validated
-            // as part of selected parent UTXO state verification with the exact same UTXO context.
-            let validation_flags = if is_selected_parent { TxValidationFlags::SkipScriptChecks } else { TxValidationFlags::Full };
-            let validated_transactions = self.validate_transactions_in_parallel(&txs, &composed_view, pov_daa_score, validation_flags);
+        // At this point the mergeset diff only holds the selected parent coinbase. All outputs added to it later on belong
+        // to mergeset transactions, hence independent transactions observe the same UTXO entries they would observe when
+        // processed sequentially, unless some of their outpoints were spent in the meantime
+        let independent_validations: Vec<_> = {
+            let composed_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+            self.thread_pool.install(|| {
+                mergeset
+                    .par_iter()
+                    .enumerate()
+                    .map(|(i, (_, txs))| {
+                        self.validate_transactions_in_parallel_filtered(
+                            txs,
+                            &composed_view,
+                            pov_daa_score,
+                            Self::mergeset_block_validation_flags(i == 0, assumed_valid),
+                            is_independent,
+                        )
+                    })
+                    .collect()
+            })
+        };
 
-            let mut block_fee = 0u64;
-            for (validated_tx, _) in validated_transactions.iter() {
-                ctx.mergeset_diff.add_transaction(validated_tx, pov_daa_score).unwrap();
-                ctx.multiset_hash.add_transaction(validated_tx, pov_daa_score);
-                ctx.accepted_tx_ids.push(validated_tx.id());
-                block_fee += validated_tx.calculated_fee;
+        // Resolve conflicts in consensus order, so that the earliest transaction spending an outpoint is the one accepted
+        let mut spent_outpoints = HashSet::new();
+        for (i, ((merged_block, txs), independent_validated)) in mergeset.iter().zip(independent_validations).enumerate() {
+            let validation_flags = Self::mergeset_block_validation_flags(i == 0, assumed_valid);
+            if i != 0 && assumed_valid {
+                self.counters.assumed_valid_txs_counts.fetch_add(txs.len() as u64 - 1, Ordering::Relaxed);
             }
-
-            if is_selected_parent {
-                // For the selected parent, we prepend the coinbase tx
-                ctx.mergeset_acceptance_data.push(MergesetBlockAcceptanceData {
-                    block_hash: merged_block,
-                    accepted_transactions: once(AcceptedTxEntry { transaction_id: validated_coinbase_id, index_within_block: 0 })
-                        .chain(
-                            validated_transactions
-                                .into_iter()
-                                .map(|(tx, tx_idx)| AcceptedTxEntry { transaction_id: tx.id(), index_within_block: tx_idx }),
-                        )
-                        .collect(),
-                });
-            } else {
-                ctx.mergeset_acceptance_data.push(MergesetBlockAcceptanceData {
-                    block_hash: merged_block,
-                    accepted_transactions: validated_transactions
-                        .into_iter()
-                        .map(|(tx, tx_idx)| AcceptedTxEntry { transaction_id: tx.id(), index_within_block: tx_idx })
-                        .collect(),
-                });
+            let mut validated_transactions: Vec<_> = independent_validated
+                .into_iter()
+                .filter(|(tx, _)| tx.tx.inputs.iter().all(|input| !spent_outpoints.contains(&input.previous_outpoint)))
+                .collect();
+            if txs.iter().skip(1).any(is_dependent) {
+                let composed_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+                validated_transactions.extend(self.validate_transactions_in_parallel_filtered(
+                    txs,
+                    &composed_view,
+                    pov_daa_score,
+                    validation_flags,
+                    is_dependent,
+                ));
+                validated_transactions.sort_unstable_by_key(|&(_, tx_idx)| tx_idx);
             }
-
-            let coinbase_data = self.coinbase_manager.deserialize_coinbase_payload(&txs[0].payload).unwrap();
-            ctx.mergeset_rewards.insert(
-                merged_block,
-                BlockRewardData::new(coinbase_data.subsidy, block_fee, coinbase_data.miner_data.script_public_key),
-            );
+            spent_outpoints
+                .extend(validated_transactions.iter().flat_map(|(tx, _)| tx.tx.inputs.iter().map(|input| input.previous_outpoint)));
+            self.accept_mergeset_block_transactions(ctx, *merged_block, txs, validated_transactions, pov_daa_score);
         }
 
         // Make sure accepted tx ids are sorted before building the merkle root
@@ -140,20 +152,126 @@ impl VirtualStateProcessor {
         ctx.accepted_tx_ids.sort();
     }
 
-    /// Verify that the current block fully respects its own UTXO view. We define a block as
-    /// UTXO valid if all the following conditions hold:
-    ///     1. The block header includes the expected `utxo_commitment`.
-    ///     2. The block header includes the expected `accepted_id_merkle_root`.
-    ///     3. The block coinbase transaction rewards the mergeset blocks correctly.
-    ///     4. All non-coinbase block transactions are valid against its own UTXO view.
-    pub(super) fn verify_expected_utxo_state<V: UtxoView + Sync>(
+    /// The reference implementation of [`Self::calculate_utxo_state`], which validates the mergeset block by block
+    #[cfg(test)]
+    pub(super) fn calculate_utxo_state_sequentially<V: UtxoView + Sync>(
+        &self,
+        ctx: &mut UtxoProcessingContext,
+        selected_parent_utxo_view: &V,
+        pov_daa_score: u64,
+        assumed_valid: bool,
+    ) {
+        let mergeset = self.collect_mergeset_transactions(ctx);
+        Self::accept_selected_parent_coinbase(ctx, &mergeset[0].1[0], pov_daa_score);
+
+        for (i, (merged_block, txs)) in mergeset.iter().enumerate() {
+            // Create a composed UTXO view from the selected parent UTXO view + the mergeset UTXO diff
+            let composed_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+            let validation_flags = Self::mergeset_block_validation_flags(i == 0, assumed_valid);
+            let validated_transactions = self.validate_transactions_in_parallel(txs, &composed_view, pov_daa_score, validation_flags);
+            self.accept_mergeset_block_transactions(ctx, *merged_block, txs, validated_transactions, pov_daa_score);
+        }
+
+        ctx.accepted_tx_ids.sort();
+    }
+
+    /// Returns the transactions of all mergeset blocks in consensus order. The first block is always the selected parent
+    fn collect_mergeset_transactions(&self, ctx: &UtxoProcessingContext) -> Vec<(Hash, Arc<Vec<Transaction>>)> {
+        once(ctx.selected_parent())
+            .chain(ctx.ghostdag_data.consensus_ordered_mergeset_without_selected_parent(self.ghostdag_primary_store.deref()))
+            .map(|b| (b, self.block_transactions_store.get(b).unwrap()))
+            .collect()
+    }
+
+    fn mergeset_block_validation_flags(is_selected_parent: bool, assumed_valid: bool) -> TxValidationFlags {
+        // No need to fully validate selected parent transactions since selected parent txs were already validated
+        // as part of selected parent UTXO state verification with the exact same UTXO context.
+        if is_selected_parent || assumed_valid {
+            TxValidationFlags::SkipScriptChecks
+        } else {
+            TxValidationFlags::Full
+        }
+    }
+
+    fn accept_selected_parent_coinbase(ctx: &mut UtxoProcessingContext, coinbase: &Transaction, pov_daa_score: u64) {
+        let validated_coinbase = ValidatedTransaction::new_coinbase(coinbase);
+        ctx.mergeset_diff.add_transaction(&validated_coinbase, pov_daa_score).unwrap();
+        ctx.multiset_hash.add_transaction(&validated_coinbase, pov_daa_score);
+        ctx.accepted_tx_ids.push(validated_coinbase.id());
+    }
+
+    /// Applies the validated transactions of a mergeset block to the UTXO state and records their acceptance
+    fn accept_mergeset_block_transactions(
         &self,
         ctx: &mut UtxoProcessingContext,
+        merged_block: Hash,
+        txs: &[Transaction],
+        validated_transactions: Vec<(ValidatedTransaction, u32)>,
+        pov_daa_score: u64,
+    ) {
+        let mut block_fee = 0u64;
+        for (validated_tx, _) in validated_transactions.iter() {
+            ctx.mergeset_diff.add_transaction(validated_tx, pov_daa_score).unwrap();
+            ctx.multiset_hash.add_transaction(validated_tx, pov_daa_score);
+            ctx.accepted_tx_ids.push(validated_tx.id());
+            block_fee += validated_tx.calculated_fee;
+        }
+
+        if merged_block == ctx.selected_parent() {
+            // For the selected parent, we prepend the coinbase tx
+            ctx.mergeset_acceptance_data.push(MergesetBlockAcceptanceData {
+                block_hash: merged_block,
+                accepted_transactions: once(AcceptedTxEntry { transaction_id: txs[0].id(), index_within_block: 0 })
+                    .chain(
+                        validated_transactions
+                            .into_iter()
+                            .map(|(tx, tx_idx)| AcceptedTxEntry { transaction_id: tx.id(), index_within_block: tx_idx }),
+                    )
+                    .collect(),
+            });
+        } else {
+            ctx.mergeset_acceptance_data.push(MergesetBlockAcceptanceData {
+                block_hash: merged_block,
+                accepted_transactions: validated_transactions
+                    .into_iter()
+                    .map(|(tx, tx_idx)| AcceptedTxEntry { transaction_id: tx.id(), index_within_block: tx_idx })
+                    .collect(),
+            });
+        }
+
+        let coinbase_data = self.coinbase_manager.deserialize_coinbase_payload(&txs[0].payload).unwrap();
+        ctx.mergeset_rewards
+            .insert(merged_block, BlockRewardData::new(coinbase_data.subsidy, block_fee, coinbase_data.miner_data.script_public_key));
+    }
+
+    /// Calculates the UTXO state of the chain block `header`. For chain blocks on the selected chain of the assume-valid block,
+    /// merged transaction scripts are not verified, as long as the resulting state matches the commitments of the header, which
+    /// the assume-valid block transitively commits to. Otherwise the scripts might have made a difference to the accepted
+    /// transactions, so the state is recalculated with full validation and the header is judged by that state alone
+    pub(super) fn calculate_chain_block_utxo_state<'a, V: UtxoView + Sync>(
+        &self,
+        ghostdag_data: &'a GhostdagData,
+        selected_parent_multiset_hash: MuHash,
         selected_parent_utxo_view: &V,
         header: &Header,
-    ) -> BlockProcessResult<()> {
+    ) -> UtxoProcessingContext<'a> {
+        if self.is_assumed_valid(header.hash) {
+            let mut ctx = UtxoProcessingContext::new(ghostdag_data.into(), selected_parent_multiset_hash.clone());
+            self.calculate_utxo_state(&mut ctx, selected_parent_utxo_view, header.daa_score, true);
+            match Self::verify_utxo_commitments(&ctx, header) {
+                Ok(()) => return ctx,
+                Err(err) => debug!("Falling back to full script validation of the mergeset of block {}: {}", header.hash, err),
+            }
+        }
+        let mut ctx = UtxoProcessingContext::new(ghostdag_data.into(), selected_parent_multiset_hash);
+        self.calculate_utxo_state(&mut ctx, selected_parent_utxo_view, header.daa_score, false);
+        ctx
+    }
+
+    /// Verify that the header commits to the UTXO state and the accepted transactions of `ctx`
+    fn verify_utxo_commitments(ctx: &UtxoProcessingContext, header: &Header) -> BlockProcessResult<()> {
         // Verify header UTXO commitment
-        let expected_commitment = ctx.multiset_hash.finalize();
+        let expected_commitment = ctx.multiset_hash.clone().finalize();
         if expected_commitment != header.utxo_commitment {
             return Err(BadUTXOCommitment(header.hash, header.utxo_commitment, expected_commitment));
         }
@@ -164,6 +282,22 @@ impl VirtualStateProcessor {
         if expected_accepted_id_merkle_root != header.accepted_id_merkle_root {
             return Err(BadAcceptedIDMerkleRoot(header.hash, header.accepted_id_merkle_root, expected_accepted_id_merkle_root));
         }
+        Ok(())
+    }
+
+    /// Verify that the current block fully respects its own UTXO view. We define a block as
+    /// UTXO valid if all the following conditions hold:
+    ///     1. The block header includes the expected `utxo_commitment`.
+    ///     2. The block header includes the expected `accepted_id_merkle_root`.
+    ///     3. The block coinbase transaction rewards the mergeset blocks correctly.
+    ///     4. All non-coinbase block transactions are valid against its own UTXO view.
+    pub(super) fn verify_expected_utxo_state<V: UtxoView + Sync>(
+        &self,
+        ctx: &mut UtxoProcessingContext,
+        selected_parent_utxo_view: &V,
+        header: &Header,
+    ) -> BlockProcessResult<()> {
+        Self::verify_utxo_commitments(ctx, header)?;
 
         let txs = self.block_transactions_store.get(header.hash).unwrap();
 
@@ -176,10 +310,17 @@ impl VirtualStateProcessor {
             &self.daa_excluded_store.get_mergeset_non_daa(header.hash).unwrap(),
         )?;
 
-        // Verify all transactions are valid in context
+        // Verify all transactions are valid in context. The transactions of a chain block strictly below the assume-valid
+        // block are all accepted by its chain child, whose accepted id merkle root the assume-valid block commits to
         let current_utxo_view = selected_parent_utxo_view.compose(&ctx.mergeset_diff);
+        let validation_flags = if self.assume_valid != Some(header.hash) && self.is_assumed_valid(header.hash) {
+            self.counters.assumed_valid_txs_counts.fetch_add(txs.len() as u64 - 1, Ordering::Relaxed);
+            TxValidationFlags::SkipScriptChecks
+        } else {
+            TxValidationFlags::Full
+        };
         let validated_transactions =
-            self.validate_transactions_in_parallel(&txs, &current_utxo_view, header.daa_score, TxValidationFlags::Full);
+            self.validate_transactions_in_parallel(&txs, &current_utxo_view, header.daa_score, validation_flags);
         if validated_transactions.len() < txs.len() - 1 {
             // Some non-coinbase transactions are invalid
             return Err(InvalidTransactionsInUtxoContext(txs.len() - 1 - validated_transactions.len(), txs.len() - 1));
@@ -188,6 +329,15 @@ impl VirtualStateProcessor {
         Ok(())
     }
 
+    /// Returns whether script checks may be skipped while processing `chain_block`, i.e. whether it is the configured assume-valid
+    /// block or on its selected chain. Blocks are fully validated as long as the header of the assume-valid block is unknown
+    pub(super) fn is_assumed_valid(&self, chain_block: Hash) -> bool {
+        self.assume_valid.is_some_and(|assume_valid| {
+            self.reachability_service.is_dag_ancestor_of_result(chain_block, assume_valid).unwrap_or_default()
+                && self.reachability_service.is_chain_ancestor_of(chain_block, assume_valid)
+        })
+    }
+
     fn verify_coinbase_transaction(
         &self,
         coinbase: &Transaction,
@@ -218,6 +368,18 @@ impl VirtualStateProcessor {
         utxo_view: &V,
         pov_daa_score: u64,
         flags: TxValidationFlags,
+    ) -> Vec<(ValidatedTransaction<'a>, u32)> {
+        self.validate_transactions_in_parallel_filtered(txs, utxo_view, pov_daa_score, flags, |_| true)
+    }
+
+    /// Same as [`Self::validate_transactions_in_parallel`], but only validates the transactions matching `filter`
+    fn validate_transactions_in_parallel_filtered<'a, V: UtxoView + Sync>(
+        &self,
+        txs: &'a [Transaction],
+        utxo_view: &V,
+        pov_daa_score: u64,
+        flags: TxValidationFlags,
+        filter: impl Fn(&Transaction) -> bool + Sync,
     ) -> Vec<(ValidatedTransaction<'a>, u32)> {
         self.thread_pool.install(|| {
             txs
@@ -225,6 +387,7 @@ impl VirtualStateProcessor {
                             // that all txs within each block are independent
                 .enumerate()
                 .skip(1) // Skip the coinbase tx.
+                .filter(|(_, tx)| filter(tx))
                 .filter_map(|(i, tx)| self.validate_transaction_in_utxo_context(tx, &utxo_view, pov_daa_score, flags).ok().map(|vtx| (vtx, i as u32)))
                 .collect()
         })
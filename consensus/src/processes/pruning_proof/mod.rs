@@ -21,12 +21,13 @@ use waglayla_consensus_core::{
         pruning::{PruningImportError, PruningImportResult},
     },
     header::Header,
-    pruning::{PruningPointProof, PruningPointTrustedData},
+    pruning::{PruningPointProof, PruningPointTrustedData, PruningProofLevelStats},
     trusted::{TrustedBlock, TrustedGhostdagData, TrustedHeader},
     BlockHashMap, BlockHashSet, BlockLevel, HashMapCustomHasher, KType,
 };
 use waglayla_core::{debug, info, trace};
 use waglayla_database::prelude::{CachePolicy, ConnBuilder, StoreResultEmptyTuple, StoreResultExtensions};
+use waglayla_database::utils::DbLifetime;
 use waglayla_hashes::Hash;
 use waglayla_pow::calc_block_level;
 use waglayla_utils::{binary_heap::BinaryHeapExtensions, vec::VecExtensions};
@@ -76,6 +77,16 @@ enum PruningProofManagerInternalError {
     NoCommonAncestor(String),
 }
 
+/// The GHOSTDAG data of each pruning point proof level as computed over a temporary DB
+struct ProofLevelsData {
+    ghostdag_stores: Vec<Arc<DbGhostdagStore>>,
+    selected_tip_by_level: Vec<Hash>,
+    pp_header: Arc<Header>,
+    pp_level: BlockLevel,
+    // Declared last so that the stores release the temporary DB before it is destroyed
+    _db_lifetime: DbLifetime,
+}
+
 struct CachedPruningPointData<T: ?Sized> {
     pruning_point: Hash,
     data: Arc<T>,
@@ -386,7 +397,9 @@ impl PruningProofManager {
         }
     }
 
-    pub fn validate_pruning_point_proof(&self, proof: &PruningPointProof) -> PruningImportResult<()> {
+    /// Validates the proof levels in isolation, i.e., without relying on the local DAG, and returns the GHOSTDAG data
+    /// computed for each level over a temporary DB
+    fn validate_proof_levels(&self, proof: &PruningPointProof) -> PruningImportResult<ProofLevelsData> {
         if proof.len() != self.max_block_level as usize + 1 {
             return Err(PruningImportError::ProofNotEnoughLevels(self.max_block_level as usize + 1));
         }
@@ -532,14 +545,62 @@ impl PruningProofManager {
             selected_tip_by_level[level_idx] = selected_tip;
         }
 
+        Ok(ProofLevelsData {
+            ghostdag_stores,
+            selected_tip_by_level: selected_tip_by_level.into_iter().map(Option::unwrap).collect(),
+            pp_header: proof_pp_header.clone(),
+            pp_level: proof_pp_level,
+            _db_lifetime: db_lifetime,
+        })
+    }
+
+    /// Validates the proof in isolation and returns statistics of each of its levels. Unlike [`Self::validate_pruning_point_proof`],
+    /// the proof is not compared to the local pruning point, and the selected tips of all levels are checked
+    pub fn inspect_pruning_point_proof(&self, proof: &PruningPointProof) -> PruningImportResult<Vec<PruningProofLevelStats>> {
+        let proof_levels = self.validate_proof_levels(proof)?;
+        let proof_pp = proof_levels.pp_header.hash;
+        let mut stats = Vec::with_capacity(proof.len());
+        for (level_idx, &selected_tip) in proof_levels.selected_tip_by_level.iter().enumerate() {
+            let level = level_idx as BlockLevel;
+            if level <= proof_levels.pp_level {
+                if selected_tip != proof_pp {
+                    return Err(PruningImportError::PruningProofSelectedTipIsNotThePruningPoint(selected_tip, level));
+                }
+            } else if !self.parents_manager.parents_at_level(&proof_levels.pp_header, level).contains(&selected_tip) {
+                return Err(PruningImportError::PruningProofSelectedTipNotParentOfPruningPoint(selected_tip, level));
+            }
+
+            let selected_tip_gd = proof_levels.ghostdag_stores[level_idx].get_compact_data(selected_tip).unwrap();
+            let (lowest_daa_score, highest_daa_score) =
+                proof[level_idx].iter().map(|header| header.daa_score).minmax().into_option().expect("levels are never empty");
+            stats.push(PruningProofLevelStats {
+                level,
+                header_count: proof[level_idx].len(),
+                selected_tip,
+                selected_tip_blue_score: selected_tip_gd.blue_score,
+                selected_tip_blue_work: selected_tip_gd.blue_work,
+                lowest_daa_score,
+                highest_daa_score,
+            });
+        }
+
+        Ok(stats)
+    }
+
+    pub fn validate_pruning_point_proof(&self, proof: &PruningPointProof) -> PruningImportResult<()> {
+        let proof_levels = self.validate_proof_levels(proof)?;
+        let ghostdag_stores = &proof_levels.ghostdag_stores;
+        let proof_pp_header = &proof_levels.pp_header;
+        let proof_pp = proof_pp_header.hash;
+        let proof_pp_level = proof_levels.pp_level;
+
         let pruning_read = self.pruning_point_store.read();
         let relations_read = self.relations_stores.read();
         let current_pp = pruning_read.get().unwrap().pruning_point;
         let current_pp_header = self.headers_store.get_header(current_pp).unwrap();
 
-        for (level_idx, selected_tip) in selected_tip_by_level.into_iter().enumerate() {
+        for (level_idx, &selected_tip) in proof_levels.selected_tip_by_level.iter().enumerate() {
             let level = level_idx as BlockLevel;
-            let selected_tip = selected_tip.unwrap();
             if level <= proof_pp_level {
                 if selected_tip != proof_pp {
                     return Err(PruningImportError::PruningProofSelectedTipIsNotThePruningPoint(selected_tip, level));
@@ -613,7 +674,7 @@ impl PruningProofManager {
 
         drop(pruning_read);
         drop(relations_read);
-        drop(db_lifetime);
+        drop(proof_levels);
 
         Err(PruningImportError::PruningProofNotEnoughHeaders)
     }
@@ -22,8 +22,103 @@ pub fn calc_merkle_root(hashes: impl ExactSizeIterator<Item = Hash>) -> Hash {
     merkles.last().unwrap().unwrap()
 }
 
+/// The sibling hashes on the path from a leaf to the root of a tree built by [`calc_merkle_root`], proving the leaf is included in it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleWitness {
+    /// The position of the leaf amongst the tree leaves
+    pub index: u32,
+    /// Sibling hashes ordered from the leaf level up
+    pub siblings: Vec<Hash>,
+}
+
+impl MerkleWitness {
+    pub fn new(index: u32, siblings: Vec<Hash>) -> Self {
+        Self { index, siblings }
+    }
+
+    /// Calculates the root of the tree implied by this witness with `leaf` at position `self.index`
+    pub fn calc_root(&self, leaf: Hash) -> Hash {
+        let mut position = self.index;
+        self.siblings.iter().fold(leaf, |node, &sibling| {
+            let parent = if position & 1 == 0 { merkle_hash(node, sibling) } else { merkle_hash(sibling, node) };
+            position >>= 1;
+            parent
+        })
+    }
+
+    /// Returns whether this witness proves that `leaf` is included in the tree whose root is `root`
+    pub fn verify(&self, leaf: Hash, root: Hash) -> bool {
+        // The index must be reachable with the given number of levels
+        let index_in_range = self.siblings.len() >= u32::BITS as usize || self.index >> self.siblings.len() == 0;
+        index_in_range && self.calc_root(leaf) == root
+    }
+}
+
+/// Creates a witness for the leaf at `index` of the tree built by [`calc_merkle_root`] over `hashes`.
+/// Returns `None` if `index` is out of bounds
+pub fn create_merkle_witness(hashes: impl ExactSizeIterator<Item = Hash>, index: usize) -> Option<MerkleWitness> {
+    if index >= hashes.len() {
+        return None;
+    }
+    let mut level: Vec<Hash> = hashes.collect();
+    let mut position = index;
+    let mut siblings = Vec::with_capacity(level.len().next_power_of_two().trailing_zeros() as usize);
+    while level.len() > 1 {
+        // A missing right sibling is hashed as zero, see `calc_merkle_root`
+        siblings.push(level.get(position ^ 1).copied().unwrap_or(ZERO_HASH));
+        level = level.chunks(2).map(|pair| merkle_hash(pair[0], pair.get(1).copied().unwrap_or(ZERO_HASH))).collect();
+        position >>= 1;
+    }
+    Some(MerkleWitness::new(index as u32, siblings))
+}
+
 fn merkle_hash(left: Hash, right: Hash) -> Hash {
     let mut hasher = MerkleBranchHash::new();
     hasher.update(left).update(right);
     hasher.finalize()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u64) -> Vec<Hash> {
+        (0..count).map(|i| Hash::from_u64_word(i + 1)).collect()
+    }
+
+    #[test]
+    fn test_merkle_witness() {
+        for count in 1..=17 {
+            let hashes = leaves(count);
+            let root = calc_merkle_root(hashes.iter().copied());
+            for (index, &leaf) in hashes.iter().enumerate() {
+                let witness = create_merkle_witness(hashes.iter().copied(), index).unwrap();
+                assert_eq!(witness.calc_root(leaf), root, "count {count}, index {index}");
+                assert!(witness.verify(leaf, root));
+                assert!(!witness.verify(Hash::from_u64_word(u64::MAX), root));
+            }
+            assert!(create_merkle_witness(hashes.iter().copied(), hashes.len()).is_none());
+        }
+    }
+
+    #[test]
+    fn test_merkle_witness_tampering() {
+        let hashes = leaves(6);
+        let root = calc_merkle_root(hashes.iter().copied());
+        let witness = create_merkle_witness(hashes.iter().copied(), 4).unwrap();
+
+        // A witness for a different position does not hold
+        let mut moved = witness.clone();
+        moved.index = 5;
+        assert!(!moved.verify(hashes[4], root));
+
+        // Neither does an index which is out of the tree's range, even if it agrees with the path bits
+        let mut overflowed = witness.clone();
+        overflowed.index += 1 << overflowed.siblings.len();
+        assert!(!overflowed.verify(hashes[4], root));
+
+        let mut corrupted = witness;
+        corrupted.siblings[2] = ZERO_HASH;
+        assert!(!corrupted.verify(hashes[4], root));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{caches::Cache, SigCacheKey};
+
+/// A signature verification which was deferred by the script engine, see [`DeferredSignatures`]
+#[derive(Clone)]
+pub struct DeferredSignature {
+    input_index: usize,
+    key: SigCacheKey,
+}
+
+impl DeferredSignature {
+    /// The index of the transaction input whose script requested this verification
+    pub fn input_index(&self) -> usize {
+        self.input_index
+    }
+
+    /// Verifies the signature, consulting and updating `sig_cache`
+    pub fn verify(&self, sig_cache: &Cache<SigCacheKey, bool>) -> bool {
+        if let Some(valid) = sig_cache.get(&self.key) {
+            return valid;
+        }
+        let valid = self.key.verify();
+        sig_cache.insert(self.key.clone(), valid);
+        valid
+    }
+}
+
+/// Collects `OpCheckSig`/`OpCheckMultiSig` verifications deferred by a [`TxScriptEngine`](crate::TxScriptEngine), so
+/// that the signatures of a whole block can be gathered and verified as a single workload once all scripts were executed.
+///
+/// While deferring, the engine optimistically assumes every deferred signature is valid. Hence, a script which executed
+/// successfully is only valid if all of its deferred signatures are. Verification results are stored in the signature
+/// cache, so re-executing the script of an input with an invalid signature does not verify its signatures again.
+///
+/// Consensus validation does not defer signatures: libsecp256k1 does not expose Schnorr batch verification, so every
+/// deferred signature would still be verified on its own. This collection is the extension point for a batch-capable
+/// Schnorr backend, which is required before deferral can pay off during block validation.
+#[derive(Clone, Default)]
+pub struct DeferredSignatures {
+    signatures: Vec<DeferredSignature>,
+}
+
+impl DeferredSignatures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.signatures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &DeferredSignature> {
+        self.signatures.iter()
+    }
+
+    pub fn as_slice(&self) -> &[DeferredSignature] {
+        &self.signatures
+    }
+
+    pub(crate) fn push(&mut self, input_index: usize, key: SigCacheKey) {
+        self.signatures.push(DeferredSignature { input_index, key });
+    }
+
+    /// Drops the signatures deferred after there were `len` signatures, e.g. those of a script which failed
+    pub fn truncate(&mut self, len: usize) {
+        self.signatures.truncate(len);
+    }
+
+    /// Verifies all deferred signatures one by one and returns the sorted indices of the inputs with invalid signatures
+    pub fn invalid_inputs(&self, sig_cache: &Cache<SigCacheKey, bool>) -> Vec<usize> {
+        let mut inputs: Vec<usize> = self.signatures.iter().filter(|sig| !sig.verify(sig_cache)).map(|sig| sig.input_index).collect();
+        inputs.dedup();
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{opcodes::codes::OpData65, pay_to_address_script, script_builder::ScriptBuilder, TxScriptEngine};
+    use std::str::FromStr;
+    use waglayla_addresses::{Address, Prefix, Version};
+    use waglayla_consensus_core::{
+        hashing::{
+            sighash::{calc_schnorr_signature_hash, SigHashReusedValues},
+            sighash_type::SIG_HASH_ALL,
+        },
+        sign::sign,
+        subnets::SubnetworkId,
+        tx::*,
+    };
+    use waglayla_txscript_errors::TxScriptError;
+
+    fn keypair(seed: u8) -> secp256k1::Keypair {
+        secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &[seed; 32]).unwrap()
+    }
+
+    fn unsigned_tx(script_public_key: ScriptPublicKey, inputs: usize) -> SignableTransaction {
+        let prev_tx_id = TransactionId::from_str("63020db736215f8b1105a9281f7bcbb6473d965ecc45bb2fb5da59bd35e6ff84").unwrap();
+        let tx = Transaction::new(
+            0,
+            (0..inputs as u32)
+                .map(|index| TransactionInput {
+                    previous_outpoint: TransactionOutpoint { transaction_id: prev_tx_id, index },
+                    signature_script: vec![],
+                    sequence: 0,
+                    sig_op_count: 1,
+                })
+                .collect(),
+            vec![],
+            0,
+            SubnetworkId::from_bytes([0; 20]),
+            0,
+            vec![],
+        );
+        let entries = (0..inputs)
+            .map(|_| UtxoEntry { amount: 1000, script_public_key: script_public_key.clone(), block_daa_score: 0, is_coinbase: false })
+            .collect();
+        SignableTransaction::with_entries(tx, entries)
+    }
+
+    fn signed_p2pk_tx(inputs: usize) -> SignableTransaction {
+        let kp = keypair(1);
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &kp.x_only_public_key().0.serialize());
+        sign(unsigned_tx(pay_to_address_script(&address), inputs), kp)
+    }
+
+    /// Executes all input scripts, deferring signature verifications into `deferred` if provided
+    fn execute(
+        tx: &SignableTransaction,
+        sig_cache: &Cache<SigCacheKey, bool>,
+        mut deferred: Option<&mut DeferredSignatures>,
+    ) -> Result<(), TxScriptError> {
+        let tx = tx.as_verifiable();
+        let mut reused_values = SigHashReusedValues::new();
+        for (i, (input, entry)) in tx.populated_inputs().enumerate() {
+            let mut engine = TxScriptEngine::from_transaction_input(&tx, input, i, entry, &mut reused_values, sig_cache)?;
+            if let Some(deferred) = deferred.as_deref_mut() {
+                engine = engine.with_deferred_signatures(deferred);
+            }
+            engine.execute()?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_deferred_valid_signatures() {
+        let tx = signed_p2pk_tx(3);
+        let sig_cache = Cache::new(10_000);
+        let mut deferred = DeferredSignatures::new();
+        execute(&tx, &sig_cache, Some(&mut deferred)).unwrap();
+        assert_eq!(deferred.iter().map(|sig| sig.input_index()).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(deferred.invalid_inputs(&sig_cache).is_empty());
+
+        // Verified signatures are cached, so they are not deferred again
+        let mut deferred = DeferredSignatures::new();
+        execute(&tx, &sig_cache, Some(&mut deferred)).unwrap();
+        assert!(deferred.is_empty());
+    }
+
+    #[test]
+    fn test_deferred_invalid_signature() {
+        let mut tx = signed_p2pk_tx(3);
+        // Corrupt the signature of the second input
+        tx.tx.inputs[1].signature_script[10] ^= 1;
+
+        let sig_cache = Cache::new(10_000);
+        let mut deferred = DeferredSignatures::new();
+        // The engine optimistically assumes deferred signatures are valid
+        execute(&tx, &sig_cache, Some(&mut deferred)).unwrap();
+        assert_eq!(deferred.len(), 3);
+        assert_eq!(deferred.invalid_inputs(&sig_cache), vec![1]);
+
+        // Re-executing the scripts relies on the cached verification result of the invalid signature
+        assert_eq!(execute(&tx, &sig_cache, None), Err(TxScriptError::EvalFalse));
+    }
+
+    #[test]
+    fn test_multisig_deferral() {
+        let kps = [keypair(1), keypair(2)];
+        let pub_keys = kps.iter().map(|kp| kp.x_only_public_key().0.serialize());
+        let redeem_script = crate::multisig_redeem_script(pub_keys, 1).unwrap();
+        let mut tx = unsigned_tx(crate::pay_to_script_hash_script(&redeem_script), 1);
+
+        let mut reused_values = SigHashReusedValues::new();
+        let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, SIG_HASH_ALL, &mut reused_values);
+        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
+        let sig = *kps[1].sign_schnorr(msg).as_ref();
+        tx.tx.inputs[0].signature_script = std::iter::once(OpData65)
+            .chain(sig)
+            .chain([SIG_HASH_ALL.to_u8()])
+            .chain(ScriptBuilder::new().add_data(&redeem_script).unwrap().drain())
+            .collect();
+
+        // A 1-of-2 multisig signed by the second key requires skipping the first key, so it cannot be deferred
+        let sig_cache = Cache::new(10_000);
+        let mut deferred = DeferredSignatures::new();
+        execute(&tx, &sig_cache, Some(&mut deferred)).unwrap();
+        assert!(deferred.is_empty());
+    }
+}
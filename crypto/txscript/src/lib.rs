@@ -15,10 +15,12 @@ pub use standard::*;
 
 use crate::caches::Cache;
 use crate::data_stack::{DataStack, Stack};
+use crate::deferred::DeferredSignatures;
 use crate::opcodes::{deserialize_next_opcode, OpCodeImplementation};
 
 pub mod caches;
 mod data_stack;
+pub mod deferred;
 pub mod opcodes;
 pub mod script_builder;
 pub mod script_class;
@@ -62,6 +64,16 @@ pub struct SigCacheKey {
     message: secp256k1::Message,
 }
 
+impl SigCacheKey {
+    fn verify(&self) -> bool {
+        match (&self.signature, &self.pub_key) {
+            (Signature::Secp256k1(sig), PublicKey::Schnorr(pk)) => sig.verify(&self.message, pk).is_ok(),
+            (Signature::Ecdsa(sig), PublicKey::Ecdsa(pk)) => sig.verify(&self.message, pk).is_ok(),
+            _ => false,
+        }
+    }
+}
+
 enum ScriptSource<'a, T: VerifiableTransaction> {
     TxInput { tx: &'a T, input: &'a TransactionInput, id: usize, utxo_entry: &'a UtxoEntry, is_p2sh: bool },
     StandAloneScripts(Vec<&'a [u8]>),
@@ -77,6 +89,8 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction> {
     // TODO:: make it compatible with threading
     reused_values: &'a mut SigHashReusedValues,
     sig_cache: &'a Cache<SigCacheKey, bool>,
+    // If set, signature verifications which are not found in the cache are deferred into this collection
+    deferred_signatures: Option<&'a mut DeferredSignatures>,
 
     cond_stack: Vec<OpCond>, // Following if stacks, and whether it is running
 
@@ -151,6 +165,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             script_source: ScriptSource::StandAloneScripts(vec![]),
             reused_values,
             sig_cache,
+            deferred_signatures: None,
             cond_stack: vec![],
             num_ops: 0,
         }
@@ -175,6 +190,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 script_source: ScriptSource::TxInput { tx, input, id: input_idx, utxo_entry, is_p2sh },
                 reused_values,
                 sig_cache,
+                deferred_signatures: None,
                 cond_stack: Default::default(),
                 num_ops: 0,
             }),
@@ -189,11 +205,19 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             script_source: ScriptSource::StandAloneScripts(vec![script]),
             reused_values,
             sig_cache,
+            deferred_signatures: None,
             cond_stack: Default::default(),
             num_ops: 0,
         }
     }
 
+    /// Defers signature verifications into `deferred` instead of verifying them during execution.
+    /// See [`DeferredSignatures`] for the implications on the execution result.
+    pub fn with_deferred_signatures(mut self, deferred: &'a mut DeferredSignatures) -> Self {
+        self.deferred_signatures = Some(deferred);
+        self
+    }
+
     #[inline]
     pub fn is_executing(&self) -> bool {
         return self.cond_stack.is_empty() || *self.cond_stack.last().expect("Checked not empty") == OpCond::True;
@@ -378,6 +402,10 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             false => return Err(TxScriptError::InvalidStackOperation(num_sigs, self.dstack.len())),
         };
 
+        // Signatures are matched against public keys in order, where a failed match skips the public key.
+        // Assuming validity is thus only sound when there are no keys to skip.
+        let deferrable = num_sigs == num_keys_usize;
+
         let mut failed = false;
         let mut pub_key_iter = pub_keys.iter();
         'outer: for (sig_idx, signature) in signatures.iter().enumerate() {
@@ -404,9 +432,9 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 let pub_key = pub_key_iter.next().unwrap();
 
                 let check_signature_result = if ecdsa {
-                    self.check_ecdsa_signature(hash_type, pub_key.as_slice(), signature)
+                    self.check_ecdsa_signature_with_deferral(hash_type, pub_key.as_slice(), signature, deferrable)
                 } else {
-                    self.check_schnorr_signature(hash_type, pub_key.as_slice(), signature)
+                    self.check_schnorr_signature_with_deferral(hash_type, pub_key.as_slice(), signature, deferrable)
                 };
 
                 match check_signature_result {
@@ -433,6 +461,37 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
 
     #[inline]
     fn check_schnorr_signature(&mut self, hash_type: SigHashType, key: &[u8], sig: &[u8]) -> Result<bool, TxScriptError> {
+        self.check_schnorr_signature_with_deferral(hash_type, key, sig, true)
+    }
+
+    fn check_ecdsa_signature(&mut self, hash_type: SigHashType, key: &[u8], sig: &[u8]) -> Result<bool, TxScriptError> {
+        self.check_ecdsa_signature_with_deferral(hash_type, key, sig, true)
+    }
+
+    /// Returns the cached validity of the signature, or verifies it. If deferred signatures are collected and `deferrable`
+    /// is set, the verification is deferred instead and the signature is assumed valid.
+    fn verify_signature(&mut self, sig_cache_key: SigCacheKey, deferrable: bool) -> bool {
+        if let Some(valid) = self.sig_cache.get(&sig_cache_key) {
+            return valid;
+        }
+        if let (true, Some(deferred), ScriptSource::TxInput { id, .. }) =
+            (deferrable, self.deferred_signatures.as_deref_mut(), &self.script_source)
+        {
+            deferred.push(*id, sig_cache_key);
+            return true;
+        }
+        let valid = sig_cache_key.verify();
+        self.sig_cache.insert(sig_cache_key, valid);
+        valid
+    }
+
+    fn check_schnorr_signature_with_deferral(
+        &mut self,
+        hash_type: SigHashType,
+        key: &[u8],
+        sig: &[u8],
+        deferrable: bool,
+    ) -> Result<bool, TxScriptError> {
         match self.script_source {
             ScriptSource::TxInput { tx, id, .. } => {
                 if sig.len() != 64 {
@@ -445,29 +504,19 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
                 let sig_cache_key =
                     SigCacheKey { signature: Signature::Secp256k1(sig), pub_key: PublicKey::Schnorr(pk), message: msg };
-
-                match self.sig_cache.get(&sig_cache_key) {
-                    Some(valid) => Ok(valid),
-                    None => {
-                        // TODO: Find a way to parallelize this part.
-                        match sig.verify(&msg, &pk) {
-                            Ok(()) => {
-                                self.sig_cache.insert(sig_cache_key, true);
-                                Ok(true)
-                            }
-                            Err(_) => {
-                                self.sig_cache.insert(sig_cache_key, false);
-                                Ok(false)
-                            }
-                        }
-                    }
-                }
+                Ok(self.verify_signature(sig_cache_key, deferrable))
             }
             _ => Err(TxScriptError::NotATransactionInput),
         }
     }
 
-    fn check_ecdsa_signature(&mut self, hash_type: SigHashType, key: &[u8], sig: &[u8]) -> Result<bool, TxScriptError> {
+    fn check_ecdsa_signature_with_deferral(
+        &mut self,
+        hash_type: SigHashType,
+        key: &[u8],
+        sig: &[u8],
+        deferrable: bool,
+    ) -> Result<bool, TxScriptError> {
         match self.script_source {
             ScriptSource::TxInput { tx, id, .. } => {
                 if sig.len() != 64 {
@@ -479,23 +528,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 let sig_hash = calc_ecdsa_signature_hash(tx, id, hash_type, self.reused_values);
                 let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
                 let sig_cache_key = SigCacheKey { signature: Signature::Ecdsa(sig), pub_key: PublicKey::Ecdsa(pk), message: msg };
-
-                match self.sig_cache.get(&sig_cache_key) {
-                    Some(valid) => Ok(valid),
-                    None => {
-                        // TODO: Find a way to parallelize this part.
-                        match sig.verify(&msg, &pk) {
-                            Ok(()) => {
-                                self.sig_cache.insert(sig_cache_key, true);
-                                Ok(true)
-                            }
-                            Err(_) => {
-                                self.sig_cache.insert(sig_cache_key, false);
-                                Ok(false)
-                            }
-                        }
-                    }
-                }
+                Ok(self.verify_signature(sig_cache_key, deferrable))
             }
             _ => Err(TxScriptError::NotATransactionInput),
         }
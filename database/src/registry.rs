@@ -48,6 +48,7 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    AddressRecords = 130,
 
     // ---- Indexes ----
     UtxoIndex = 192,
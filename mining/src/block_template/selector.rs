@@ -217,14 +217,13 @@ impl TransactionsSelector {
     /// included in the block.
     fn calc_tx_value(&self, transaction: &CandidateTransaction) -> f64 {
         let mass_limit = self.policy.max_block_mass as f64;
-        let mass = transaction.calculated_mass as f64;
-        let fee = transaction.calculated_fee as f64;
+        let fee_rate = transaction.priority_fee_rate();
         if transaction.tx.subnetwork_id.is_builtin_or_native() {
-            fee / mass / mass_limit
+            fee_rate / mass_limit
         } else {
             // TODO: Replace with real gas once implemented
             let gas_limit = u64::MAX as f64;
-            fee / mass / mass_limit + transaction.tx.gas as f64 / gas_limit
+            fee_rate / mass_limit + transaction.tx.gas as f64 / gas_limit
         }
     }
 }
@@ -307,6 +306,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_package_priority() {
+        let mut transaction = create_transaction(SOMPI_PER_WAGLAYLA);
+        let fee_rate = transaction.calculated_fee as f64 / transaction.calculated_mass as f64;
+        assert_eq!(transaction.priority_fee_rate(), fee_rate);
+
+        // A descendant paying a lower fee rate does not lower the priority of its ancestor
+        transaction.package_mass = 2 * transaction.calculated_mass;
+        assert_eq!(transaction.priority_fee_rate(), fee_rate);
+
+        // A descendant paying a higher fee rate raises it
+        transaction.package_fee = 10 * transaction.calculated_fee;
+        assert!((transaction.priority_fee_rate() - 5.0 * fee_rate).abs() < 1e-9);
+
+        let policy = Policy::new(100_000);
+        let selector = TransactionsSelector::new(policy, vec![]);
+        let unbumped = create_transaction(SOMPI_PER_WAGLAYLA);
+        assert!(selector.calc_tx_value(&transaction) > selector.calc_tx_value(&unbumped));
+    }
+
     fn create_transaction(value: u64) -> CandidateTransaction {
         let previous_outpoint = TransactionOutpoint::new(TransactionId::default(), 0);
         let (script_public_key, redeem_script) = op_true_script();
@@ -318,6 +337,6 @@ mod tests {
         let calculated_mass = transaction_estimated_serialized_size(&tx);
         let calculated_fee = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
 
-        CandidateTransaction { tx, calculated_fee, calculated_mass }
+        CandidateTransaction { tx, calculated_fee, calculated_mass, package_fee: calculated_fee, package_mass: calculated_mass }
     }
 }
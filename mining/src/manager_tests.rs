@@ -767,6 +767,40 @@ mod tests {
         // TODO: extend the test according to the golang scenario
     }
 
+    // test_package_candidate_transactions verifies that the block template candidates are prioritized by the fee rate
+    // of the package made of the ready transaction and its descendants (child pays for parent).
+    #[test]
+    fn test_package_candidate_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        const CHILD_FEE: u64 = 100 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE;
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_WAGLAYLA]);
+        let parent_tx = create_transaction(&funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let child_tx = create_transaction(&parent_tx, CHILD_FEE);
+        consensus.add_transaction(funding_tx, 1);
+
+        for tx in [&parent_tx, &child_tx] {
+            let result =
+                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), Priority::Low, Orphan::Allowed);
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        // Only the parent is ready, the child can only be included in a later block
+        let transactions = mining_manager.block_candidate_transactions();
+        assert_eq!(1, transactions.len(), "the mempool should only provide the parent transaction as candidate");
+        let candidate = &transactions[0];
+        assert_eq!(candidate.tx.id(), parent_tx.id());
+        assert_eq!(candidate.calculated_fee, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        assert_eq!(candidate.package_fee, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE + CHILD_FEE);
+        assert_eq!(candidate.package_mass, candidate.calculated_mass + transaction_estimated_serialized_size(&child_tx));
+        assert!(
+            candidate.priority_fee_rate() > candidate.calculated_fee as f64 / candidate.calculated_mass as f64,
+            "the child fees should raise the priority of the parent"
+        );
+    }
+
     fn sweep_compare_modified_template_to_built(
         consensus: &dyn ConsensusApi,
         address_prefix: Prefix,
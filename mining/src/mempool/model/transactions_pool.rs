@@ -163,12 +163,23 @@ impl TransactionsPool {
 
     /// all_ready_transactions returns all fully populated mempool transactions having no parents in the mempool.
     /// These transactions are ready for being inserted in a block template.
+    ///
+    /// The package of each returned transaction includes its descendants in the mempool, so that
+    /// a child paying for its parent raises the priority of the parent in the block template.
     pub(crate) fn all_ready_transactions(&self) -> Vec<CandidateTransaction> {
         // The returned transactions are leaving the mempool so they are cloned
         self.ready_transactions
             .iter()
             .take(self.config.maximum_ready_transaction_count as usize)
-            .map(|id| CandidateTransaction::from_mutable(&self.all_transactions.get(id).unwrap().mtx))
+            .map(|id| {
+                let candidate = CandidateTransaction::from_mutable(&self.all_transactions.get(id).unwrap().mtx);
+                if self.transaction_is_unchained(id) {
+                    candidate
+                } else {
+                    let descendants = self.get_redeemer_ids_in_pool(id);
+                    candidate.with_descendants(descendants.iter().map(|id| &self.all_transactions.get(id).unwrap().mtx))
+                }
+            })
             .collect()
     }
 
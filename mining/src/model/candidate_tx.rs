@@ -11,12 +11,38 @@ pub(crate) struct CandidateTransaction {
     pub calculated_fee: u64,
     /// Populated mass
     pub calculated_mass: u64,
+    /// Populated fee of the transaction and of its descendants in the mempool
+    pub package_fee: u64,
+    /// Populated mass of the transaction and of its descendants in the mempool
+    pub package_mass: u64,
 }
 
 impl CandidateTransaction {
     pub(crate) fn from_mutable(tx: &MutableTransaction) -> Self {
         let mass = tx.tx.mass();
         assert_ne!(mass, 0, "mass field is expected to be set when inserting to the mempool");
-        Self { tx: tx.tx.clone(), calculated_fee: tx.calculated_fee.expect("fee is expected to be populated"), calculated_mass: mass }
+        let fee = tx.calculated_fee.expect("fee is expected to be populated");
+        Self { tx: tx.tx.clone(), calculated_fee: fee, calculated_mass: mass, package_fee: fee, package_mass: mass }
+    }
+
+    /// Adds the fees and masses of the descendants of the transaction to its package
+    pub(crate) fn with_descendants<'a>(mut self, descendants: impl Iterator<Item = &'a MutableTransaction>) -> Self {
+        for descendant in descendants {
+            self.package_fee += descendant.calculated_fee.expect("fee is expected to be populated");
+            self.package_mass += descendant.tx.mass();
+        }
+        self
+    }
+
+    /// Fee rate by which the transaction is prioritized: the highest of its own fee rate and of the fee
+    /// rate of its package, so that a descendant paying a higher fee raises the priority of its ancestor
+    /// (child pays for parent).
+    ///
+    /// Since the transactions of a block are validated independently of each other, a descendant can only
+    /// be included in a later block than its ancestor, once it becomes ready itself.
+    pub(crate) fn priority_fee_rate(&self) -> f64 {
+        let fee_rate = self.calculated_fee as f64 / self.calculated_mass as f64;
+        let package_fee_rate = self.package_fee as f64 / self.package_mass as f64;
+        fee_rate.max(package_fee_rate)
     }
 }
@@ -1,9 +1,13 @@
 use crate::flowcontext::{
+    light_relay::LightRelayPool,
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
     transactions::TransactionsSpread,
 };
-use crate::{v5, v6};
+use crate::{
+    v5,
+    v6::{self, inclusion_proofs::InclusionProofRequest},
+};
 use async_trait::async_trait;
 use futures::future::join_all;
 use waglayla_addressmanager::AddressManager;
@@ -12,6 +16,7 @@ use waglayla_consensus_core::api::{BlockValidationFuture, BlockValidationFutures
 use waglayla_consensus_core::block::Block;
 use waglayla_consensus_core::config::Config;
 use waglayla_consensus_core::errors::block::RuleError;
+use waglayla_consensus_core::merkle::TransactionInclusionProof;
 use waglayla_consensus_core::tx::{Transaction, TransactionId};
 use waglayla_consensus_notify::{
     notification::{Notification, PruningPointUtxoSetOverrideNotification},
@@ -32,13 +37,15 @@ use waglayla_p2p_lib::{
     common::ProtocolError,
     convert::model::version::Version,
     make_message,
-    pb::{waglaylad_message::Payload, InvRelayBlockMessage},
-    ConnectionInitializer, Hub, WaglayladHandshake, PeerKey, PeerProperties, Router,
+    pb::{waglaylad_message::Payload, InvRelayBlockMessage, InvTransactionsMessage},
+    ConnectionInitializer, Hub, HubTraffic, PeerKey, PeerProperties, Router, TransportConfig, WaglayladHandshake,
 };
+use waglayla_utils::channel::Channel;
 use waglayla_utils::iter::IterExtensions;
 use waglayla_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Instant;
 use std::{collections::hash_map::Entry, fmt::Display};
 use std::{
@@ -52,13 +59,20 @@ use std::{
 };
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
-    RwLock as AsyncRwLock,
+    oneshot, RwLock as AsyncRwLock,
 };
+use tokio::time::timeout;
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
-/// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 8;
+/// The P2P protocol version. Version 10 adds transaction inclusion proofs (see `v6::inclusion_proofs`)
+const PROTOCOL_VERSION: u32 = v6::inclusion_proofs::INCLUSION_PROOFS_PROTOCOL_VERSION;
+
+/// The protocol version adding the encrypted transport (see `waglayla_p2p_lib::TransportConfig`)
+const ENCRYPTED_PROTOCOL_VERSION: u32 = 9;
+
+/// The last protocol version preceding the encrypted transport, still served with the same flows
+const PLAINTEXT_PROTOCOL_VERSION: u32 = 8;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -69,6 +83,9 @@ const MAX_ORPHANS_UPPER_BOUND: usize = 1024;
 /// The min time to wait before allowing another parallel request
 const REQUEST_SCOPE_WAIT_TIME: Duration = Duration::from_secs(1);
 
+/// The max time to wait for a peer to provide a verified transaction inclusion proof
+const INCLUSION_PROOF_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Represents a block event to be logged
 #[derive(Debug, PartialEq)]
 pub enum BlockLogEvent {
@@ -213,6 +230,7 @@ pub struct FlowContextInner {
     pub consensus_manager: Arc<ConsensusManager>,
     pub config: Arc<Config>,
     hub: Hub,
+    transport_config: Arc<TransportConfig>,
     orphans_pool: AsyncRwLock<OrphanBlocksPool>,
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
@@ -225,6 +243,12 @@ pub struct FlowContextInner {
     pub(crate) tick_service: Arc<TickService>,
     notification_root: Arc<ConsensusNotificationRoot>,
 
+    // Inclusion proof requests of a light node, taken by the first available peer
+    inclusion_proof_requests: Channel<InclusionProofRequest>,
+
+    // Transactions submitted to a light node, kept for serving the peers they were announced to
+    light_relay_pool: Mutex<LightRelayPool>,
+
     // Special sampling logger used only for high-bps networks where logs must be throttled
     block_event_logger: Option<BlockEventLogger>,
 
@@ -303,8 +327,10 @@ impl FlowContext {
         mining_manager: MiningManagerProxy,
         tick_service: Arc<TickService>,
         notification_root: Arc<ConsensusNotificationRoot>,
+        transport_config: Arc<TransportConfig>,
     ) -> Self {
-        let hub = Hub::new();
+        let hub = Hub::with_traffic(HubTraffic::new(config.p2p_upload_limit, config.p2p_capture_dir.as_ref().map(PathBuf::from)))
+            .with_whitelist(config.p2p_whitelist.clone());
 
         let orphan_resolution_range = BASELINE_ORPHAN_RESOLUTION_RANGE + (config.bps() as f64).log2().ceil() as u32;
 
@@ -322,11 +348,14 @@ impl FlowContext {
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
                 hub,
+                transport_config,
                 address_manager,
                 connection_manager: Default::default(),
                 mining_manager,
                 tick_service,
                 notification_root,
+                inclusion_proof_requests: Channel::default(),
+                light_relay_pool: Default::default(),
                 block_event_logger: if config.bps() > 1 { Some(BlockEventLogger::new(config.bps() as usize)) } else { None },
                 orphan_resolution_range,
                 max_orphans,
@@ -595,6 +624,30 @@ impl FlowContext {
         }
     }
 
+    pub fn inclusion_proof_requests(&self) -> &Channel<InclusionProofRequest> {
+        &self.inclusion_proof_requests
+    }
+
+    /// Requests a verified proof from peers that the transaction is included in the body of a block whose header is
+    /// known to this node. If `block_hash` is not specified, peers search the recently accepted transactions.
+    ///
+    /// Proofs are only requested by light nodes, which cannot build them since they hold no block bodies.
+    pub async fn request_transaction_inclusion_proof(
+        &self,
+        transaction_id: TransactionId,
+        block_hash: Option<Hash>,
+    ) -> Result<TransactionInclusionProof, ProtocolError> {
+        let (response, receiver) = oneshot::channel();
+        if self.inclusion_proof_requests.send(InclusionProofRequest { transaction_id, block_hash, response }).await.is_err() {
+            return Err(ProtocolError::ConnectionClosed);
+        }
+        match timeout(INCLUSION_PROOF_REQUEST_TIMEOUT, receiver).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(ProtocolError::ConnectionClosed),
+            Err(_) => Err(ProtocolError::Timeout(INCLUSION_PROOF_REQUEST_TIMEOUT)),
+        }
+    }
+
     /// Notifies that the UTXO set was reset due to pruning point change via IBD.
     pub fn on_pruning_point_utxoset_override(&self) {
         // Notifications from the flow context might be ignored if the inner channel is already closing
@@ -618,6 +671,9 @@ impl FlowContext {
         transaction: Transaction,
         orphan: Orphan,
     ) -> Result<(), ProtocolError> {
+        if self.config.is_light {
+            return self.relay_light_transaction(transaction).await;
+        }
         let accepted_transactions =
             self.mining_manager().clone().validate_and_insert_transaction(consensus, transaction, Priority::High, orphan).await?;
         self.broadcast_transactions(
@@ -628,6 +684,28 @@ impl FlowContext {
         Ok(())
     }
 
+    /// Announces a transaction submitted to a light node to all transaction relaying peers. Lacking a UTXO set, a light node
+    /// only performs context-free checks and leaves the full validation to its peers. The announcement is not delayed by
+    /// the broadcast interval, since a light node has no block processing which would later flush the queue.
+    async fn relay_light_transaction(&self, transaction: Transaction) -> Result<(), ProtocolError> {
+        if transaction.is_coinbase() {
+            return Err(ProtocolError::Other("coinbase transactions cannot be submitted"));
+        }
+        if transaction.inputs.is_empty() {
+            return Err(ProtocolError::Other("transaction has no inputs"));
+        }
+        let transaction_id = transaction.id();
+        self.light_relay_pool.lock().insert(Arc::new(transaction));
+        let msg = make_message!(Payload::InvTransactions, InvTransactionsMessage { ids: vec![transaction_id.into()] });
+        self.hub.broadcast_transactions(msg).await;
+        Ok(())
+    }
+
+    /// Returns a transaction submitted to this light node, if it is still being relayed
+    pub fn get_light_relay_transaction(&self, transaction_id: &TransactionId) -> Option<Arc<Transaction>> {
+        self.light_relay_pool.lock().get(transaction_id)
+    }
+
     /// Returns true if the time has come for running the task cleaning mempool transactions.
     async fn should_run_mempool_scanning_task(&self) -> bool {
         self.transactions_spread.write().await.should_run_mempool_scanning_task()
@@ -660,7 +738,7 @@ impl FlowContext {
 impl ConnectionInitializer for FlowContext {
     async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
         // Build the handshake object and subscribe to handshake messages
-        let mut handshake = WaglayladHandshake::new(&router);
+        let mut handshake = WaglayladHandshake::new(&router).with_transport_config(self.transport_config.clone());
 
         // We start the router receive loop only after we registered to handshake routes
         router.start();
@@ -674,7 +752,8 @@ impl ConnectionInitializer for FlowContext {
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
         // TODO: get number of live services
-        // TODO: disable_relay_tx from config/cmd
+        // Block-relay-only connections, as well as light nodes which have no mempool, ask the peer not to relay transactions to us
+        self_version_message.disable_relay_tx = router.is_block_relay_only() || self.config.is_light;
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message.into()).await?;
@@ -700,7 +779,8 @@ impl ConnectionInitializer for FlowContext {
 
         // Register all flows according to version
         let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone(), PROTOCOL_VERSION), PROTOCOL_VERSION),
+            v @ (ENCRYPTED_PROTOCOL_VERSION | PLAINTEXT_PROTOCOL_VERSION) => (v6::register(self.clone(), router.clone(), v), v),
             5 => (v5::register(self.clone(), router.clone()), 5),
             v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
         };
@@ -731,6 +811,12 @@ impl ConnectionInitializer for FlowContext {
 
             if router.is_outbound() {
                 address_manager.add_address(router.net_address().into());
+                address_manager.set_peer_info(
+                    router.net_address().into(),
+                    peer_version.protocol_version,
+                    peer_version.services,
+                    &peer_version.user_agent,
+                );
             }
 
             if let Some(peer_ip_address) = peer_version.address {
@@ -0,0 +1,108 @@
+use indexmap::IndexMap;
+use waglayla_consensus_core::tx::{Transaction, TransactionId};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The maximum number of transactions kept by a light node for serving its peers
+const MAX_LIGHT_RELAY_TRANSACTIONS: usize = 1_000;
+
+/// The duration during which a relayed transaction is served to peers requesting it
+const LIGHT_RELAY_EXPIRATION: Duration = Duration::from_secs(120);
+
+/// Transactions submitted via RPC to a light node. Having no UTXO set, a light node cannot validate transactions in
+/// a mempool, so it announces them to its peers and keeps them only for serving the requests which follow. The peers
+/// fully validate the transactions before adding them to their mempool and relaying them further.
+#[derive(Default)]
+pub struct LightRelayPool {
+    transactions: IndexMap<TransactionId, (Arc<Transaction>, Instant)>,
+}
+
+impl LightRelayPool {
+    pub fn insert(&mut self, transaction: Arc<Transaction>) {
+        self.insert_at(transaction, Instant::now())
+    }
+
+    fn insert_at(&mut self, transaction: Arc<Transaction>, now: Instant) {
+        self.expire(now);
+        if self.transactions.contains_key(&transaction.id()) {
+            return;
+        }
+        if self.transactions.len() == MAX_LIGHT_RELAY_TRANSACTIONS {
+            self.transactions.shift_remove_index(0);
+        }
+        self.transactions.insert(transaction.id(), (transaction, now));
+    }
+
+    pub fn get(&self, transaction_id: &TransactionId) -> Option<Arc<Transaction>> {
+        self.get_at(transaction_id, Instant::now())
+    }
+
+    fn get_at(&self, transaction_id: &TransactionId, now: Instant) -> Option<Arc<Transaction>> {
+        self.transactions
+            .get(transaction_id)
+            .filter(|(_, added)| now.duration_since(*added) < LIGHT_RELAY_EXPIRATION)
+            .map(|(transaction, _)| transaction.clone())
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    fn expire(&mut self, now: Instant) {
+        // Transactions are kept in insertion order, so the expired ones are at the front
+        while let Some((_, (_, added))) = self.transactions.first() {
+            if now.duration_since(*added) < LIGHT_RELAY_EXPIRATION {
+                break;
+            }
+            self.transactions.shift_remove_index(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use waglayla_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+
+    fn transaction(lock_time: u64) -> Arc<Transaction> {
+        Arc::new(Transaction::new(0, vec![], vec![], lock_time, SUBNETWORK_ID_NATIVE, 0, vec![]))
+    }
+
+    #[test]
+    fn test_light_relay_pool_expiration() {
+        let mut pool = LightRelayPool::default();
+        let start = Instant::now();
+        let (first, second) = (transaction(1), transaction(2));
+        pool.insert_at(first.clone(), start);
+        pool.insert_at(second.clone(), start + LIGHT_RELAY_EXPIRATION / 2);
+        assert_eq!(pool.get_at(&first.id(), start).unwrap().id(), first.id());
+
+        // The first transaction is no longer served once expired, and is removed by the next insertion
+        let later = start + LIGHT_RELAY_EXPIRATION;
+        assert!(pool.get_at(&first.id(), later).is_none());
+        assert!(pool.get_at(&second.id(), later).is_some());
+        pool.insert_at(transaction(3), later);
+        assert_eq!(pool.len(), 2);
+        assert!(!pool.transactions.contains_key(&first.id()));
+    }
+
+    #[test]
+    fn test_light_relay_pool_capacity() {
+        let mut pool = LightRelayPool::default();
+        let now = Instant::now();
+        for lock_time in 0..MAX_LIGHT_RELAY_TRANSACTIONS as u64 + 1 {
+            pool.insert_at(transaction(lock_time), now);
+        }
+        // Inserting an existing transaction is a no-op
+        pool.insert_at(transaction(1), now);
+        assert_eq!(pool.len(), MAX_LIGHT_RELAY_TRANSACTIONS);
+        assert!(pool.get_at(&transaction(0).id(), now).is_none());
+        assert!(pool.get_at(&transaction(1).id(), now).is_some());
+    }
+}
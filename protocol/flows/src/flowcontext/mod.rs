@@ -1,3 +1,4 @@
+pub mod light_relay;
 pub mod orphans;
 pub(crate) mod process_queue;
 pub mod transactions;
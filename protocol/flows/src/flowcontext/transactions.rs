@@ -98,9 +98,9 @@ impl TransactionsSpread {
     async fn broadcast(&self, msg: WaglayladMessage, should_throttle: bool) {
         if should_throttle {
             // TODO: Figure out a better number
-            self.hub.broadcast_to_some_peers(msg, 8).await
+            self.hub.broadcast_transactions_to_some_peers(msg, 8).await
         } else {
-            self.hub.broadcast(msg).await
+            self.hub.broadcast_transactions(msg).await
         }
     }
 }
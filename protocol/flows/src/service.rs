@@ -20,6 +20,7 @@ pub struct P2pService {
     add_peers: Vec<NetAddress>,
     listen: NetAddress,
     outbound_target: usize,
+    block_relay_only_target: usize,
     inbound_limit: usize,
     dns_seeders: &'static [&'static str],
     default_port: u16,
@@ -34,6 +35,7 @@ impl P2pService {
         add_peers: Vec<NetAddress>,
         listen: NetAddress,
         outbound_target: usize,
+        block_relay_only_target: usize,
         inbound_limit: usize,
         dns_seeders: &'static [&'static str],
         default_port: u16,
@@ -46,6 +48,7 @@ impl P2pService {
             shutdown: SingleTrigger::default(),
             listen,
             outbound_target,
+            block_relay_only_target,
             inbound_limit,
             dns_seeders,
             default_port,
@@ -71,6 +74,7 @@ impl AsyncService for P2pService {
         let connection_manager = ConnectionManager::new(
             p2p_adaptor.clone(),
             self.outbound_target,
+            self.block_relay_only_target,
             self.inbound_limit,
             self.dns_seeders,
             self.default_port,
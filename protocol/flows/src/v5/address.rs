@@ -53,9 +53,10 @@ impl ReceiveAddressesFlow {
         if address_list.len() > MAX_ADDRESSES_RECEIVE {
             return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
         }
+        let source = self.router.net_address().into();
         let mut amgr_lock = self.ctx.address_manager.lock();
         for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+            amgr_lock.add_address_from(NetAddress::new(ip, port), Some(source))
         }
 
         Ok(())
@@ -87,7 +88,12 @@ impl SendAddressesFlow {
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
+            // Block-relay-only connections do not take part in address gossip, so we reply with an empty list
+            let addresses = if self.router.is_block_relay_only() {
+                vec![]
+            } else {
+                self.ctx.address_manager.lock().iterate_addresses().collect_vec()
+            };
             let address_list = addresses
                 .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
                 .map(|addr| (addr.ip, addr.port).into())
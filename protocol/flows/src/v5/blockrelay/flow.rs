@@ -72,7 +72,11 @@ impl Flow for HandleRelayInvsFlow {
     }
 
     async fn start(&mut self) -> Result<(), ProtocolError> {
-        self.start_impl().await
+        if self.ctx.config.is_light {
+            self.start_light_impl().await
+        } else {
+            self.start_impl().await
+        }
     }
 }
 
@@ -215,6 +219,46 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Light nodes only validate the headers of relay blocks. Since they hold no block bodies they neither relay blocks
+    /// to other peers nor keep orphans, so a header with missing parents is handed over to IBD for syncing its past headers
+    async fn start_light_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let inv = self.invs_route.dequeue().await?;
+            let session = self.ctx.consensus().unguarded_session();
+
+            match session.async_get_block_status(inv.hash).await {
+                None => {} // Continue processing this missing inv
+                Some(BlockStatus::StatusInvalid) => {
+                    return Err(ProtocolError::OtherOwned(format!("sent inv of an invalid block {}", inv.hash)));
+                }
+                Some(_) => continue, // Header is already known
+            }
+
+            if self.ctx.is_ibd_running() {
+                debug!("Got relay block {} while in IBD, continuing...", inv.hash);
+                continue;
+            }
+
+            let Some((block, request_scope)) = self.request_block(inv.hash, self.msg_route.id()).await? else {
+                debug!("Relay block {} was already requested from another peer, continuing...", inv.hash);
+                continue;
+            };
+            request_scope.report_obtained();
+
+            match session.validate_and_insert_block(Block::from_header_arc(block.header.clone())).block_task.await {
+                Ok(_) => self.ctx.log_block_event(BlockLogEvent::Relay(inv.hash)),
+                Err(RuleError::MissingParents(_)) => {
+                    // As in `process_orphan`, a pending IBD job is replaced if this block has higher blue work
+                    match self.ibd_sender.try_send(block, |b, c| if b.header.blue_work > c.header.blue_work { b } else { c }) {
+                        Ok(_) | Err(TrySendError::Full(_)) => {}
+                        Err(TrySendError::Closed(_)) => return Err(ProtocolError::ConnectionClosed),
+                    }
+                }
+                Err(rule_error) => return Err(rule_error.into()),
+            }
+        }
+    }
+
     fn enqueue_orphan_roots(&mut self, _orphan: Hash, roots: Vec<Hash>, known_within_range: bool) {
         self.invs_route.enqueue_indirect_invs(roots, known_within_range)
     }
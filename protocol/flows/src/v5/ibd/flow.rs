@@ -122,7 +122,9 @@ impl IbdFlow {
                             "Header download stage of IBD with headers proof completed successfully from {}. Committed staging consensus.",
                             self.router
                         );
-                        self.ctx.on_pruning_point_utxoset_override();
+                        if !self.ctx.config.is_light {
+                            self.ctx.on_pruning_point_utxoset_override();
+                        }
                         // This will reobtain the freshly committed staging consensus
                         session = self.ctx.consensus().session().await;
                     }
@@ -135,6 +137,12 @@ impl IbdFlow {
             }
         }
 
+        // Light nodes are done once headers are synced since they neither download block bodies
+        // nor keep orphans (which are full blocks)
+        if self.ctx.config.is_light {
+            return Ok(());
+        }
+
         // Sync missing bodies in the past of syncer sink (virtual selected parent)
         self.sync_missing_block_bodies(&session, negotiation_output.syncer_virtual_selected_parent).await?;
 
@@ -222,7 +230,10 @@ impl IbdFlow {
         self.sync_headers(&staging_session, syncer_virtual_selected_parent, pruning_point, relay_block).await?;
         staging_session.async_validate_pruning_points().await?;
         self.validate_staging_timestamps(&self.ctx.consensus().session().await, &staging_session).await?;
-        self.sync_pruning_point_utxoset(&staging_session, pruning_point).await?;
+        // Light nodes do not maintain a UTXO set
+        if !self.ctx.config.is_light {
+            self.sync_pruning_point_utxoset(&staging_session, pruning_point).await?;
+        }
         Ok(())
     }
 
@@ -37,7 +37,7 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
     // IBD flow <-> invs flow communication uses a job channel in order to always
     // maintain at most a single pending job which can be updated
     let (ibd_sender, relay_receiver) = channel::job();
-    let flows: Vec<Box<dyn Flow>> = vec![
+    let mut flows: Vec<Box<dyn Flow>> = vec![
         Box::new(IbdFlow::new(
             ctx.clone(),
             router.clone(),
@@ -131,19 +131,27 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![WaglayladMessagePayloadType::RequestTransactions]),
         )),
-        Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![WaglayladMessagePayloadType::Addresses]))),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
             router.clone(),
             router.subscribe(vec![WaglayladMessagePayloadType::RequestAddresses]),
         )),
         Box::new(RequestBlockLocatorFlow::new(
-            ctx,
+            ctx.clone(),
             router.clone(),
             router.subscribe(vec![WaglayladMessagePayloadType::RequestBlockLocator]),
         )),
     ];
 
+    // Block-relay-only connections do not request addresses from the peer
+    if !router.is_block_relay_only() {
+        flows.push(Box::new(ReceiveAddressesFlow::new(
+            ctx,
+            router.clone(),
+            router.subscribe(vec![WaglayladMessagePayloadType::Addresses]),
+        )));
+    }
+
     // The reject message is handled as a special case by the router
     // WaglayladMessagePayloadType::Reject,
 
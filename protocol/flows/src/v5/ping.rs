@@ -96,7 +96,12 @@ impl SendPingsFlow {
             } else {
                 debug!("Successful ping with peer {} (nonce: {})", self.peer, pong.nonce);
             }
-            router.set_last_ping_duration(ping_time.elapsed().as_millis() as u64);
+            let ping_duration = ping_time.elapsed().as_millis() as u64;
+            router.set_last_ping_duration(ping_duration);
+            if router.is_outbound() {
+                // Only outbound peers are known by their listening address
+                self.ctx.address_manager.lock().set_latency(router.net_address().into(), ping_duration);
+            }
         }
     }
 }
@@ -37,7 +37,7 @@ impl HandleIbdBlockRequests {
 
             for hash in hashes {
                 let block = session.async_get_block(hash).await?;
-                self.router.enqueue(make_response!(Payload::IbdBlock, (&block).into(), request_id)).await?;
+                self.router.enqueue_throttled(make_response!(Payload::IbdBlock, (&block).into(), request_id)).await?;
             }
         }
     }
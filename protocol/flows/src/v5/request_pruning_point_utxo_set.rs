@@ -59,9 +59,9 @@ impl RequestPruningPointUtxoSetFlow {
                 }?;
             debug!("Retrieved {} UTXOs for pruning point {}", pruning_point_utxos.len(), expected_pp);
 
-            // Send the chunk
+            // Send the chunk (subject to the global upload budget if configured)
             self.router
-                .enqueue(make_message!(
+                .enqueue_throttled(make_message!(
                     Payload::PruningPointUtxoSetChunk,
                     PruningPointUtxoSetChunkMessage {
                         outpoint_and_utxo_entry_pairs: pruning_point_utxos
@@ -113,6 +113,12 @@ impl RelayTransactionsFlow {
                 return Err(ProtocolError::Other("Number of invs in tx inv message is over the limit"));
             }
 
+            // Block-relay-only connections do not take part in transaction gossip. Peers which do not honor the
+            // `disable_relay_tx` flag of our version message may still send invs, so we silently ignore them
+            if self.router.is_block_relay_only() {
+                continue;
+            }
+
             let session = self.ctx.consensus().unguarded_session();
 
             // Transaction relay is disabled if the node is out of sync and thus not mining
@@ -284,11 +290,23 @@ impl RequestTransactionsFlow {
             let msg = dequeue!(self.incoming_route, Payload::RequestTransactions)?;
             let tx_ids: Vec<_> = msg.try_into()?;
             for transaction_id in tx_ids {
-                if let Some(mutable_tx) =
-                    self.ctx.mining_manager().clone().get_transaction(transaction_id, TransactionQuery::TransactionsOnly).await
-                {
-                    // trace!("Send transaction {} to {}", mutable_tx.id(), self.router.identity());
-                    self.router.enqueue(make_message!(Payload::Transaction, (&*mutable_tx.tx).into())).await?;
+                // Never expose our mempool through a block-relay-only connection
+                let transaction = if self.router.is_block_relay_only() {
+                    None
+                } else if self.ctx.config.is_light {
+                    // Light nodes have no mempool and only serve the transactions submitted to them
+                    self.ctx.get_light_relay_transaction(&transaction_id)
+                } else {
+                    self.ctx
+                        .mining_manager()
+                        .clone()
+                        .get_transaction(transaction_id, TransactionQuery::TransactionsOnly)
+                        .await
+                        .map(|mutable_tx| mutable_tx.tx)
+                };
+                if let Some(transaction) = transaction {
+                    // trace!("Send transaction {} to {}", transaction.id(), self.router.identity());
+                    self.router.enqueue(make_message!(Payload::Transaction, (&*transaction).into())).await?;
                 } else {
                     // trace!("Send transaction id {} not found to {}", transaction_id, self.router.identity());
                     self.router
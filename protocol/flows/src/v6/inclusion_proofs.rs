@@ -0,0 +1,252 @@
+//!
+//! Protocol version 10 allows peers to request proofs that a transaction is included in a block body. Light nodes,
+//! which only hold headers, use them for proving payments of their own.
+//!
+
+use waglayla_consensus_core::{merkle::TransactionInclusionProof, tx::TransactionId};
+use waglayla_consensusmanager::ConsensusProxy;
+use waglayla_hashes::Hash;
+use waglayla_p2p_lib::{
+    common::{ProtocolError, DEFAULT_TIMEOUT},
+    dequeue_with_request_id, make_request, make_response,
+    pb::{
+        waglaylad_message::Payload, RequestTransactionInclusionProofMessage, TransactionInclusionProofNotFoundMessage,
+    },
+    IncomingRoute, Router,
+};
+use log::debug;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::oneshot, time::timeout};
+
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+
+/// The protocol version adding inclusion proof messages
+pub const INCLUSION_PROOFS_PROTOCOL_VERSION: u32 = 10;
+
+/// The number of selected chain blocks, starting from the sink, whose accepted transactions are searched by the
+/// RPC service when the requester does not specify the block containing the transaction
+pub const INCLUSION_PROOF_SEARCH_DEPTH: usize = 1000;
+
+/// Same as [`INCLUSION_PROOF_SEARCH_DEPTH`] for requests of peers, which cost them nothing and are thus kept cheap
+pub const PEER_INCLUSION_PROOF_SEARCH_DEPTH: usize = 50;
+
+/// The number of inclusion proof requests of a peer which may fail to find the transaction within
+/// [`INCLUSION_PROOF_MISS_WINDOW`] before the peer is considered misbehaving
+pub const MAX_INCLUSION_PROOF_MISSES: usize = 16;
+
+pub const INCLUSION_PROOF_MISS_WINDOW: Duration = Duration::from_secs(600);
+
+pub type InclusionProofResult = Result<TransactionInclusionProof, ProtocolError>;
+
+/// A pending inclusion proof request of a light node, handled by the first available peer
+pub struct InclusionProofRequest {
+    pub transaction_id: TransactionId,
+    pub block_hash: Option<Hash>,
+    pub response: oneshot::Sender<InclusionProofResult>,
+}
+
+/// Serves inclusion proofs of transactions found in the block bodies held by this node
+pub struct HandleTransactionInclusionProofRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+    /// Requests of the current miss window which did not find the transaction
+    misses: usize,
+    miss_window_start: Instant,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleTransactionInclusionProofRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleTransactionInclusionProofRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route, misses: 0, miss_window_start: Instant::now() }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let (msg, request_id) = dequeue_with_request_id!(self.incoming_route, Payload::RequestTransactionInclusionProof)?;
+            let (transaction_id, block_hash): (TransactionId, Option<Hash>) = msg.try_into()?;
+            debug!("Got inclusion proof request for transaction {}", transaction_id);
+
+            let session = self.ctx.consensus().unguarded_session();
+            let response = match Self::create_proof(&session, transaction_id, block_hash).await {
+                Some(proof) => make_response!(Payload::TransactionInclusionProof, (&proof).into(), request_id),
+                None => {
+                    self.register_miss(transaction_id)?;
+                    make_response!(
+                        Payload::TransactionInclusionProofNotFound,
+                        TransactionInclusionProofNotFoundMessage { transaction_id: Some(transaction_id.into()) },
+                        request_id
+                    )
+                }
+            };
+            self.router.enqueue(response).await?;
+        }
+    }
+
+    /// Counts a request which did not find the transaction. Too many of them are treated as misbehaviour, since each
+    /// might have cost a search of the acceptance data
+    fn register_miss(&mut self, transaction_id: TransactionId) -> Result<(), ProtocolError> {
+        let now = Instant::now();
+        if now.duration_since(self.miss_window_start) > INCLUSION_PROOF_MISS_WINDOW {
+            self.misses = 0;
+            self.miss_window_start = now;
+        }
+        self.misses += 1;
+        if self.misses > MAX_INCLUSION_PROOF_MISSES {
+            return Err(ProtocolError::MisbehavingPeer(format!(
+                "requested {} inclusion proofs of unknown transactions (last: {})",
+                self.misses, transaction_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn create_proof(
+        consensus: &ConsensusProxy,
+        transaction_id: TransactionId,
+        block_hash: Option<Hash>,
+    ) -> Option<TransactionInclusionProof> {
+        let (block_hash, index) = match block_hash {
+            Some(block_hash) => (block_hash, None),
+            None => {
+                let (block_hash, index) = Self::find_accepted_transaction(consensus, transaction_id).await?;
+                (block_hash, Some(index))
+            }
+        };
+        // Fails for header-only blocks, hence light nodes never serve proofs
+        let block = consensus.async_get_block(block_hash).await.ok()?;
+        let index = match index {
+            Some(index) => index,
+            None => block.transactions.iter().position(|tx| tx.id() == transaction_id)?,
+        };
+        TransactionInclusionProof::new(&block, index).filter(|proof| proof.transaction.id() == transaction_id)
+    }
+
+    /// Searches the acceptance data of recent selected chain blocks for the transaction. Returns the hash of the
+    /// merged block containing it along with its index within the block body
+    async fn find_accepted_transaction(consensus: &ConsensusProxy, transaction_id: TransactionId) -> Option<(Hash, usize)> {
+        let mut chain_block = consensus.async_get_sink().await;
+        for _ in 0..PEER_INCLUSION_PROOF_SEARCH_DEPTH {
+            let acceptance_data = consensus.async_get_block_acceptance_data(chain_block).await.ok()?;
+            for mergeset_block in acceptance_data.iter() {
+                if let Some(entry) = mergeset_block.accepted_transactions.iter().find(|entry| entry.transaction_id == transaction_id) {
+                    return Some((mergeset_block.block_hash, entry.index_within_block as usize));
+                }
+            }
+            chain_block = consensus.async_get_ghostdag_data(chain_block).await.ok()?.selected_parent;
+        }
+        None
+    }
+}
+
+/// Takes the inclusion proof requests of this light node whenever the peer is available, requests the proof
+/// from the peer and verifies it against the locally validated block header
+pub struct RequestTransactionInclusionProofsFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for RequestTransactionInclusionProofsFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl RequestTransactionInclusionProofsFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        let requests = self.ctx.inclusion_proof_requests().receiver();
+        while let Ok(request) = requests.recv().await {
+            // The requester might have given up meanwhile
+            if request.response.is_closed() {
+                continue;
+            }
+            match self.request_proof(request.transaction_id, request.block_hash).await {
+                Ok(result) => {
+                    let _ = request.response.send(result);
+                }
+                Err(err) => {
+                    // Hand the request over to another peer before disconnecting from this one
+                    let _ = self.ctx.inclusion_proof_requests().try_send(request);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Requests a proof from the peer. Errors which are not caused by the peer, such as the transaction not being found,
+    /// are returned as the inner result
+    async fn request_proof(
+        &mut self,
+        transaction_id: TransactionId,
+        block_hash: Option<Hash>,
+    ) -> Result<InclusionProofResult, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestTransactionInclusionProof,
+                RequestTransactionInclusionProofMessage {
+                    transaction_id: Some(transaction_id.into()),
+                    block_hash: block_hash.map(|h| h.into())
+                },
+                self.incoming_route.id()
+            ))
+            .await?;
+
+        let proof: TransactionInclusionProof = match timeout(DEFAULT_TIMEOUT, self.incoming_route.recv()).await {
+            Ok(Some(msg)) => match msg.payload {
+                Some(Payload::TransactionInclusionProof(payload)) => payload.try_into()?,
+                Some(Payload::TransactionInclusionProofNotFound(_)) => {
+                    return Ok(Err(ProtocolError::OtherOwned(format!(
+                        "transaction {} was not found by peer {}",
+                        transaction_id, self.router
+                    ))))
+                }
+                _ => {
+                    return Err(ProtocolError::UnexpectedMessage(
+                        stringify!(Payload::TransactionInclusionProof | Payload::TransactionInclusionProofNotFound),
+                        msg.payload.as_ref().map(|v| v.into()),
+                    ))
+                }
+            },
+            Ok(None) => return Err(ProtocolError::ConnectionClosed),
+            Err(_) => return Err(ProtocolError::Timeout(DEFAULT_TIMEOUT)),
+        };
+
+        if proof.transaction.id() != transaction_id || block_hash.is_some_and(|h| h != proof.block_hash) {
+            return Err(ProtocolError::OtherOwned(format!("sent an inclusion proof not matching transaction {}", transaction_id)));
+        }
+        let Ok(header) = self.ctx.consensus().unguarded_session().async_get_header(proof.block_hash).await else {
+            return Ok(Err(ProtocolError::OtherOwned(format!(
+                "the header of block {} containing transaction {} is not known yet",
+                proof.block_hash, transaction_id
+            ))));
+        };
+        if !proof.verify(&header) {
+            return Err(ProtocolError::OtherOwned(format!("sent an invalid inclusion proof for transaction {}", transaction_id)));
+        }
+        Ok(Ok(proof))
+    }
+}
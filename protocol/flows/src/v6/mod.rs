@@ -18,11 +18,15 @@ use waglayla_p2p_lib::{WaglayladMessagePayloadType, Router, SharedIncomingRoute}
 use waglayla_utils::channel;
 use std::sync::Arc;
 
-use crate::v6::request_pruning_point_and_anticone::PruningPointAndItsAnticoneRequestsFlow;
+use crate::v6::{
+    inclusion_proofs::{HandleTransactionInclusionProofRequests, RequestTransactionInclusionProofsFlow, INCLUSION_PROOFS_PROTOCOL_VERSION},
+    request_pruning_point_and_anticone::PruningPointAndItsAnticoneRequestsFlow,
+};
 
+pub mod inclusion_proofs;
 pub(crate) mod request_pruning_point_and_anticone;
 
-pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+pub fn register(ctx: FlowContext, router: Arc<Router>, protocol_version: u32) -> Vec<Box<dyn Flow>> {
     // IBD flow <-> invs flow communication uses a job channel in order to always
     // maintain at most a single pending job which can be updated
     let (ibd_sender, relay_receiver) = channel::job();
@@ -112,7 +116,6 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![WaglayladMessagePayloadType::RequestTransactions]),
         )),
-        Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![WaglayladMessagePayloadType::Addresses]))),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
             router.clone(),
@@ -125,6 +128,35 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
         )),
     ];
 
+    if protocol_version >= INCLUSION_PROOFS_PROTOCOL_VERSION {
+        flows.push(Box::new(HandleTransactionInclusionProofRequests::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![WaglayladMessagePayloadType::RequestTransactionInclusionProof]),
+        )));
+
+        // Light nodes obtain proofs for the transactions of their users from peers
+        if ctx.config.is_light {
+            flows.push(Box::new(RequestTransactionInclusionProofsFlow::new(
+                ctx.clone(),
+                router.clone(),
+                router.subscribe(vec![
+                    WaglayladMessagePayloadType::TransactionInclusionProof,
+                    WaglayladMessagePayloadType::TransactionInclusionProofNotFound,
+                ]),
+            )));
+        }
+    }
+
+    // Block-relay-only connections do not request addresses from the peer
+    if !router.is_block_relay_only() {
+        flows.push(Box::new(ReceiveAddressesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![WaglayladMessagePayloadType::Addresses]),
+        )));
+    }
+
     let invs_route = router.subscribe_with_capacity(vec![WaglayladMessagePayloadType::InvRelayBlock], ctx.block_invs_channel_size());
     let shared_invs_route = SharedIncomingRoute::new(invs_route);
 
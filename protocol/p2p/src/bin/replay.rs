@@ -0,0 +1,93 @@
+use waglayla_core::{error, info};
+use waglayla_p2p_lib::{
+    capture::{Capture, Direction},
+    replay::{ReplayOptions, Replayer},
+    WaglayladMessagePayloadType,
+};
+use std::{path::PathBuf, process::exit, time::Duration};
+
+const USAGE: &str = "\
+Usage: waglayla_p2p_replay <CAPTURE_FILE> [TARGET_ADDRESS] [OPTIONS]
+
+Replays a P2P capture (recorded with `waglaylad --p2p-capture=<DIR>`) against the node listening on
+TARGET_ADDRESS (ip:port), acting as the captured remote peer. If no target is given, the capture is printed.
+
+Options:
+  --strict               Abort on the first message diverging from the capture
+  --realtime             Preserve the original delays between messages
+  --step-timeout=<SECS>  Max time to wait for each expected message from the node (default: 30)";
+
+#[tokio::main]
+async fn main() {
+    waglayla_core::log::init_logger(None, "info");
+
+    let mut capture_path = None;
+    let mut target = None;
+    let mut options = ReplayOptions::default();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--strict" => options.strict = true,
+            "--realtime" => options.realtime = true,
+            "-h" | "--help" => exit_with_usage(0),
+            _ if arg.starts_with("--step-timeout=") => match arg["--step-timeout=".len()..].parse() {
+                Ok(secs) => options.step_timeout = Duration::from_secs(secs),
+                Err(_) => exit_with_usage(1),
+            },
+            _ if arg.starts_with("--") => exit_with_usage(1),
+            _ if capture_path.is_none() => capture_path = Some(PathBuf::from(arg)),
+            _ if target.is_none() => target = Some(arg),
+            _ => exit_with_usage(1),
+        }
+    }
+    let Some(capture_path) = capture_path else { exit_with_usage(1) };
+
+    let capture = match Capture::load(&capture_path) {
+        Ok(capture) => capture,
+        Err(err) => {
+            error!("Failed loading capture {}: {}", capture_path.display(), err);
+            exit(1);
+        }
+    };
+    info!(
+        "Loaded capture of {} peer {} with {} records ({} inbound)",
+        if capture.header.is_outbound { "outbound" } else { "inbound" },
+        capture.header.peer_address,
+        capture.records.len(),
+        capture.inbound().count()
+    );
+
+    let Some(target) = target else {
+        for (i, record) in capture.records.iter().enumerate() {
+            let direction = match record.direction {
+                Direction::Inbound => "<-",
+                Direction::Outbound => "->",
+            };
+            let msg_type = record.message.payload.as_ref().map(WaglayladMessagePayloadType::from);
+            println!(
+                "{:>8} {:>12.6}s {} {:?} (request id: {}, response id: {})",
+                i,
+                record.elapsed_micros as f64 / 1_000_000.0,
+                direction,
+                msg_type,
+                record.message.request_id,
+                record.message.response_id
+            );
+        }
+        return;
+    };
+
+    match Replayer::new(capture, options).run(&target).await {
+        Ok(summary) => {
+            info!("Replay completed: {:?}", summary);
+        }
+        Err(err) => {
+            error!("Replay failed: {}", err);
+            exit(1);
+        }
+    }
+}
+
+fn exit_with_usage(code: i32) -> ! {
+    eprintln!("{USAGE}");
+    exit(code)
+}
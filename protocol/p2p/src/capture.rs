@@ -0,0 +1,364 @@
+//! Recording of P2P traffic into compact per-peer capture files.
+//!
+//! A capture file starts with a header identifying the peer, followed by a sequence of records, each holding
+//! the direction of the message, the time elapsed since the capture started and the protobuf-encoded message:
+//!
+//! ```text
+//! header:  magic (6 bytes) | version (u8) | is_outbound (u8) | started unix millis (u64) | address len (u16) | address (utf8)
+//! record:  direction (u8) | elapsed micros (u64) | message len (u32) | message (protobuf)
+//! ```
+//!
+//! All integers are little-endian.
+//!
+//! The files of all peers are written by a single [`CaptureWriter`] thread, so that recording never performs
+//! blocking I/O on the async runtime.
+
+use crate::pb::WaglayladMessage;
+use waglayla_core::{time::unix_now, warn};
+use prost::Message;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Instant;
+
+const CAPTURE_MAGIC: &[u8; 6] = b"WGLCAP";
+const CAPTURE_VERSION: u8 = 1;
+
+/// File extension used for capture files
+pub const CAPTURE_FILE_EXTENSION: &str = "wcap";
+
+/// The direction of a captured message, relative to the capturing node
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent by the remote peer to the capturing node
+    Inbound = 0,
+    /// Sent by the capturing node to the remote peer
+    Outbound = 1,
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Direction::Inbound),
+            1 => Ok(Direction::Outbound),
+            v => Err(io::Error::new(io::ErrorKind::InvalidData, format!("invalid capture record direction {v}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CaptureHeader {
+    /// Whether the connection was initiated by the capturing node
+    pub is_outbound: bool,
+    /// Unix time (in milliseconds) of the capture start
+    pub started: u64,
+    /// Address of the remote peer
+    pub peer_address: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CaptureRecord {
+    pub direction: Direction,
+    /// Time elapsed since capture start, in microseconds
+    pub elapsed_micros: u64,
+    pub message: WaglayladMessage,
+}
+
+enum WriterCommand {
+    Open { id: u64, path: PathBuf, header: CaptureHeader },
+    Record { id: u64, direction: Direction, elapsed_micros: u64, encoded: Vec<u8> },
+    Flush { id: u64 },
+    Close { id: u64 },
+    Barrier(mpsc::Sender<()>),
+}
+
+/// Writes the capture files of all peers of a hub on a dedicated thread
+#[derive(Debug, Clone)]
+pub struct CaptureWriter {
+    dir: PathBuf,
+    sender: mpsc::Sender<WriterCommand>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl CaptureWriter {
+    /// Creates a writer recording capture files into `dir` (created on first use if needed).
+    /// The writer thread exits once the writer and all its peer captures are dropped.
+    pub fn new(dir: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("p2p-capture".to_string())
+            .spawn(move || run_writer(receiver))
+            .expect("failed spawning the P2P capture writer thread");
+        Self { dir, sender, next_id: Default::default() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Starts a new capture file for the peer at `peer_address`
+    pub fn open(&self, peer_address: SocketAddr, is_outbound: bool) -> PeerCapture {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let started = unix_now();
+        let file_name = format!(
+            "{}-{}-{}.{}",
+            started,
+            peer_address.to_string().replace([':', '[', ']'], "_"),
+            if is_outbound { "out" } else { "in" },
+            CAPTURE_FILE_EXTENSION
+        );
+        let path = self.dir.join(file_name);
+        let header = CaptureHeader { is_outbound, started, peer_address: peer_address.to_string() };
+        let _ = self.sender.send(WriterCommand::Open { id, path: path.clone(), header });
+        PeerCapture { id, path, started: Instant::now(), sender: self.sender.clone() }
+    }
+
+    /// Blocks until all records submitted so far are written to their files.
+    ///
+    /// Note: this call blocks the current thread and should not be used from async code
+    pub fn sync(&self) {
+        let (sender, receiver) = mpsc::channel();
+        if self.sender.send(WriterCommand::Barrier(sender)).is_ok() {
+            let _ = receiver.recv();
+        }
+    }
+}
+
+fn run_writer(receiver: mpsc::Receiver<WriterCommand>) {
+    let mut files: HashMap<u64, (PathBuf, BufWriter<File>)> = HashMap::new();
+    while let Ok(command) = receiver.recv() {
+        match command {
+            WriterCommand::Open { id, path, header } => {
+                let res = (|| -> io::Result<BufWriter<File>> {
+                    if let Some(dir) = path.parent() {
+                        fs::create_dir_all(dir)?;
+                    }
+                    let mut writer = BufWriter::new(File::create(&path)?);
+                    write_header(&mut writer, &header)?;
+                    Ok(writer)
+                })();
+                match res {
+                    Ok(writer) => {
+                        files.insert(id, (path, writer));
+                    }
+                    Err(err) => fail(&path, err),
+                }
+            }
+            WriterCommand::Record { id, direction, elapsed_micros, encoded } => {
+                if let Some((path, writer)) = files.get_mut(&id) {
+                    if let Err(err) = write_record(writer, direction, elapsed_micros, &encoded) {
+                        fail(path, err);
+                        files.remove(&id);
+                    }
+                }
+            }
+            WriterCommand::Flush { id } => {
+                if let Some((path, writer)) = files.get_mut(&id) {
+                    if let Err(err) = writer.flush() {
+                        fail(path, err);
+                        files.remove(&id);
+                    }
+                }
+            }
+            WriterCommand::Close { id } => {
+                if let Some((path, mut writer)) = files.remove(&id) {
+                    if let Err(err) = writer.flush() {
+                        fail(&path, err);
+                    }
+                }
+            }
+            WriterCommand::Barrier(done) => {
+                files.retain(|_, (path, writer)| match writer.flush() {
+                    Ok(()) => true,
+                    Err(err) => {
+                        fail(path, err);
+                        false
+                    }
+                });
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+fn fail(path: &Path, err: io::Error) {
+    warn!("P2P capture, failed writing to {}, capture of this peer is disabled: {}", path.display(), err);
+}
+
+/// Records all messages exchanged with a single peer. Write errors are logged once by the writer thread,
+/// after which the capture of the peer is disabled. The file is closed when the capture is dropped.
+#[derive(Debug)]
+pub struct PeerCapture {
+    id: u64,
+    path: PathBuf,
+    started: Instant,
+    sender: mpsc::Sender<WriterCommand>,
+}
+
+impl PeerCapture {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a message to the capture
+    pub fn record(&self, direction: Direction, message: &WaglayladMessage) {
+        self.record_encoded(direction, message.encode_to_vec());
+    }
+
+    /// Appends an already protobuf-encoded message to the capture
+    pub fn record_encoded(&self, direction: Direction, encoded: Vec<u8>) {
+        let elapsed_micros = self.started.elapsed().as_micros() as u64;
+        let _ = self.sender.send(WriterCommand::Record { id: self.id, direction, elapsed_micros, encoded });
+    }
+
+    /// Flushes buffered records to the file
+    pub fn flush(&self) {
+        let _ = self.sender.send(WriterCommand::Flush { id: self.id });
+    }
+}
+
+impl Drop for PeerCapture {
+    fn drop(&mut self) {
+        let _ = self.sender.send(WriterCommand::Close { id: self.id });
+    }
+}
+
+fn write_header(writer: &mut impl Write, header: &CaptureHeader) -> io::Result<()> {
+    let address = header.peer_address.as_bytes();
+    writer.write_all(CAPTURE_MAGIC)?;
+    writer.write_all(&[CAPTURE_VERSION, header.is_outbound as u8])?;
+    writer.write_all(&header.started.to_le_bytes())?;
+    writer.write_all(&(address.len() as u16).to_le_bytes())?;
+    writer.write_all(address)
+}
+
+fn write_record(writer: &mut impl Write, direction: Direction, elapsed_micros: u64, encoded: &[u8]) -> io::Result<()> {
+    writer.write_all(&[direction as u8])?;
+    writer.write_all(&elapsed_micros.to_le_bytes())?;
+    writer.write_all(&(encoded.len() as u32).to_le_bytes())?;
+    writer.write_all(encoded)
+}
+
+/// A fully loaded capture file
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub header: CaptureHeader,
+    pub records: Vec<CaptureRecord>,
+}
+
+impl Capture {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let header = read_header(reader)?;
+        let mut records = Vec::new();
+        while let Some(record) = read_record(reader)? {
+            records.push(record);
+        }
+        Ok(Self { header, records })
+    }
+
+    /// Iterates over the messages sent by the remote peer
+    pub fn inbound(&self) -> impl Iterator<Item = &CaptureRecord> {
+        self.records.iter().filter(|r| r.direction == Direction::Inbound)
+    }
+
+    /// Iterates over the messages sent by the capturing node
+    pub fn outbound(&self) -> impl Iterator<Item = &CaptureRecord> {
+        self.records.iter().filter(|r| r.direction == Direction::Outbound)
+    }
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_header(reader: &mut impl Read) -> io::Result<CaptureHeader> {
+    let magic: [u8; 6] = read_array(reader)?;
+    if &magic != CAPTURE_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a P2P capture file"));
+    }
+    let [version, is_outbound] = read_array(reader)?;
+    if version != CAPTURE_VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unsupported capture version {version}")));
+    }
+    let started = u64::from_le_bytes(read_array(reader)?);
+    let address_len = u16::from_le_bytes(read_array(reader)?) as usize;
+    let mut address = vec![0u8; address_len];
+    reader.read_exact(&mut address)?;
+    let peer_address = String::from_utf8(address).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(CaptureHeader { is_outbound: is_outbound != 0, started, peer_address })
+}
+
+/// Reads the next record, returning `None` on a clean end of file. A truncated last record (e.g. of a node
+/// which was killed while capturing) is treated as the end of the capture.
+fn read_record(reader: &mut impl Read) -> io::Result<Option<CaptureRecord>> {
+    let mut direction = [0u8; 1];
+    if reader.read(&mut direction)? == 0 {
+        return Ok(None);
+    }
+    let res = (|| -> io::Result<(u64, Vec<u8>)> {
+        let elapsed_micros = u64::from_le_bytes(read_array(reader)?);
+        let len = u32::from_le_bytes(read_array(reader)?) as usize;
+        let mut encoded = vec![0u8; len];
+        reader.read_exact(&mut encoded)?;
+        Ok((elapsed_micros, encoded))
+    })();
+    let (elapsed_micros, encoded) = match res {
+        Ok(res) => res,
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let message = WaglayladMessage::decode(encoded.as_slice()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(Some(CaptureRecord { direction: direction[0].try_into()?, elapsed_micros, message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{waglaylad_message::Payload, PingMessage, PongMessage};
+    use crate::{make_message, WaglayladMessagePayloadType};
+
+    #[test]
+    fn test_capture_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("waglayla-p2p-capture-test-{}", std::process::id()));
+        let peer_address: SocketAddr = "[::1]:16111".parse().unwrap();
+        let writer = CaptureWriter::new(dir.clone());
+        let capture = writer.open(peer_address, true);
+        capture.record(Direction::Outbound, &make_message!(Payload::Ping, PingMessage { nonce: 7 }));
+        capture.record(Direction::Inbound, &make_message!(Payload::Pong, PongMessage { nonce: 7 }, 3, 0));
+        writer.sync();
+
+        let loaded = Capture::load(capture.path()).unwrap();
+        assert!(loaded.header.is_outbound);
+        assert_eq!(loaded.header.peer_address, peer_address.to_string());
+        assert_eq!(loaded.records.len(), 2);
+        assert_eq!(loaded.outbound().count(), 1);
+        let pong = loaded.inbound().next().unwrap();
+        assert_eq!(pong.message.response_id, 3);
+        assert_eq!(pong.message.payload.as_ref().map(WaglayladMessagePayloadType::from), Some(WaglayladMessagePayloadType::Pong));
+        assert!(loaded.records[0].elapsed_micros <= pong.elapsed_micros);
+
+        // A truncated trailing record is ignored
+        let mut bytes = fs::read(capture.path()).unwrap();
+        bytes.truncate(bytes.len() - 2);
+        let truncated = Capture::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(truncated.records.len(), 1);
+
+        drop(capture);
+        writer.sync();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
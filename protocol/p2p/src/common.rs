@@ -1,4 +1,4 @@
-use crate::{convert::error::ConversionError, core::peer::PeerKey, WaglayladMessagePayloadType};
+use crate::{convert::error::ConversionError, core::peer::PeerKey, core::transport::TransportError, WaglayladMessagePayloadType};
 use waglayla_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
 use waglayla_mining_errors::manager::MiningManagerError;
 use std::time::Duration;
@@ -40,6 +40,9 @@ pub enum ProtocolError {
     #[error("{0}")]
     IdentityError(#[from] uuid::Error),
 
+    #[error("{0}")]
+    TransportError(#[from] TransportError),
+
     #[error("{0}")]
     Other(&'static str),
 
@@ -9,10 +9,12 @@ use super::{
 use crate::pb as protowire;
 use waglayla_consensus_core::{
     header::Header,
+    merkle::TransactionInclusionProof,
     pruning::{PruningPointProof, PruningPointsList},
     tx::{TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use waglayla_hashes::Hash;
+use waglayla_merkle::MerkleWitness;
 use waglayla_utils::networking::{IpAddress, PeerId};
 
 use std::sync::Arc;
@@ -37,6 +39,17 @@ impl From<Version> for protowire::VersionMessage {
     }
 }
 
+impl From<&TransactionInclusionProof> for protowire::TransactionInclusionProofMessage {
+    fn from(item: &TransactionInclusionProof) -> Self {
+        Self {
+            block_hash: Some(item.block_hash.into()),
+            transaction: Some((&item.transaction).into()),
+            index: item.witness.index,
+            witness: item.witness.siblings.iter().map(|h| h.into()).collect(),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -220,3 +233,32 @@ impl TryFrom<protowire::RequestAntipastMessage> for (Hash, Hash) {
         Ok((msg.block_hash.try_into_ex()?, msg.context_hash.try_into_ex()?))
     }
 }
+
+impl TryFrom<protowire::RequestTransactionInclusionProofMessage> for (TransactionId, Option<Hash>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::RequestTransactionInclusionProofMessage) -> Result<Self, Self::Error> {
+        let block_hash = match msg.block_hash {
+            Some(block_hash) => Some(block_hash.try_into()?),
+            None => None,
+        };
+        Ok((msg.transaction_id.try_into_ex()?, block_hash))
+    }
+}
+
+impl TryFrom<protowire::TransactionInclusionProofMessage> for TransactionInclusionProof {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::TransactionInclusionProofMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_hash: msg.block_hash.try_into_ex()?,
+            transaction: msg.transaction.try_into_ex()?,
+            witness: MerkleWitness::new(msg.index, msg.witness.into_iter().map(|h| h.try_into()).collect::<Result<_, _>>()?),
+        })
+    }
+}
+
+impl TryFrom<protowire::TransactionInclusionProofNotFoundMessage> for TransactionId {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::TransactionInclusionProofNotFoundMessage) -> Result<Self, Self::Error> {
+        msg.transaction_id.try_into_ex()
+    }
+}
@@ -1,7 +1,7 @@
 use crate::common::ProtocolError;
 use crate::core::hub::Hub;
 use crate::ConnectionError;
-use crate::{core::connection_handler::ConnectionHandler, Router};
+use crate::{core::connection_handler::ConnectionHandler, PeerRelayMode, Router};
 use waglayla_utils::networking::NetAddress;
 use waglayla_utils_tower::counters::TowerConnectionCounters;
 use std::ops::Deref;
@@ -48,7 +48,7 @@ impl Adaptor {
     /// Creates a P2P adaptor with only client-side support. Typical Waglayla nodes should use `Adaptor::bidirectional`
     pub fn client_only(hub: Hub, initializer: Arc<dyn ConnectionInitializer>, counters: Arc<TowerConnectionCounters>) -> Arc<Self> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, hub.traffic.clone());
         let adaptor = Arc::new(Adaptor::new(None, connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
         adaptor
@@ -62,7 +62,7 @@ impl Adaptor {
         counters: Arc<TowerConnectionCounters>,
     ) -> Result<Arc<Self>, ConnectionError> {
         let (hub_sender, hub_receiver) = mpsc_channel(Self::hub_channel_size());
-        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters);
+        let connection_handler = ConnectionHandler::new(hub_sender, initializer.clone(), counters, hub.traffic.clone());
         let server_termination = connection_handler.serve(serve_address)?;
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, hub));
         adaptor.hub.clone().start_event_loop(hub_receiver, initializer);
@@ -71,7 +71,16 @@ impl Adaptor {
 
     /// Connect to a new peer (no retries)
     pub async fn connect_peer(&self, peer_address: String) -> Result<PeerKey, ConnectionError> {
-        self.connection_handler.connect_with_retry(peer_address, 1, Default::default()).await.map(|r| r.key())
+        self.connect_peer_with_relay_mode(peer_address, PeerRelayMode::Full).await
+    }
+
+    /// Connect to a new peer with the given relay mode (no retries)
+    pub async fn connect_peer_with_relay_mode(
+        &self,
+        peer_address: String,
+        relay_mode: PeerRelayMode,
+    ) -> Result<PeerKey, ConnectionError> {
+        self.connection_handler.connect_with_retry(peer_address, 1, Default::default(), relay_mode).await.map(|r| r.key())
     }
 
     /// Connect to a new peer (with params controlling retry behavior)
@@ -81,7 +90,10 @@ impl Adaptor {
         retry_attempts: u8,
         retry_interval: Duration,
     ) -> Result<PeerKey, ConnectionError> {
-        self.connection_handler.connect_with_retry(peer_address, retry_attempts, retry_interval).await.map(|r| r.key())
+        self.connection_handler
+            .connect_with_retry(peer_address, retry_attempts, retry_interval, PeerRelayMode::Full)
+            .await
+            .map(|r| r.key())
     }
 
     /// Terminates all peers and cleans up any additional async resources
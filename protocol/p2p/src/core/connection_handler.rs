@@ -1,9 +1,10 @@
 use crate::common::ProtocolError;
 use crate::core::hub::HubEvent;
+use crate::core::traffic::HubTraffic;
 use crate::pb::{
     p2p_client::P2pClient as ProtoP2pClient, p2p_server::P2p as ProtoP2p, p2p_server::P2pServer as ProtoP2pServer, WaglayladMessage,
 };
-use crate::{ConnectionInitializer, Router};
+use crate::{ConnectionInitializer, PeerRelayMode, Router};
 use futures::FutureExt;
 use waglayla_core::{debug, info};
 use waglayla_utils::networking::NetAddress;
@@ -52,6 +53,8 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    /// Shared with the hub and passed on to every new router
+    hub_traffic: Arc<HubTraffic>,
 }
 
 impl ConnectionHandler {
@@ -59,8 +62,9 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        hub_traffic: Arc<HubTraffic>,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self { hub_sender, initializer, counters, hub_traffic }
     }
 
     /// Launches a P2P server listener loop
@@ -95,7 +99,7 @@ impl ConnectionHandler {
     }
 
     /// Connect to a new peer
-    pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
+    pub(crate) async fn connect(&self, peer_address: String, relay_mode: PeerRelayMode) -> Result<Arc<Router>, ConnectionError> {
         let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
             return Err(ConnectionError::NoAddress);
         };
@@ -123,7 +127,16 @@ impl ConnectionHandler {
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_network_channel_size());
         let incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
 
-        let router = Router::new(socket_address, true, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(
+            socket_address,
+            true,
+            relay_mode,
+            self.hub_sender.clone(),
+            incoming_stream,
+            outgoing_route,
+            self.hub_traffic.clone(),
+        )
+        .await;
 
         // For outbound peers, we perform the initialization as part of the connect logic
         match self.initializer.initialize_connection(router.clone()).await {
@@ -150,11 +163,12 @@ impl ConnectionHandler {
         address: String,
         retry_attempts: u8,
         retry_interval: Duration,
+        relay_mode: PeerRelayMode,
     ) -> Result<Arc<Router>, ConnectionError> {
         let mut counter = 0;
         loop {
             counter += 1;
-            match self.connect(address.clone()).await {
+            match self.connect(address.clone(), relay_mode).await {
                 Ok(router) => {
                     debug!("P2P, Client connected, peer: {:?}", address);
                     return Ok(router);
@@ -215,7 +229,16 @@ impl ProtoP2p for ConnectionHandler {
         let incoming_stream = request.into_inner();
 
         // Build the router object
-        let router = Router::new(remote_address, false, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(
+            remote_address,
+            false,
+            PeerRelayMode::Full,
+            self.hub_sender.clone(),
+            incoming_stream,
+            outgoing_route,
+            self.hub_traffic.clone(),
+        )
+        .await;
 
         // Notify the central Hub about the new peer
         self.hub_sender.send(HubEvent::NewPeer(router)).await.expect("hub receiver should never drop before senders");
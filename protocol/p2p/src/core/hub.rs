@@ -1,13 +1,16 @@
 use crate::{common::ProtocolError, pb::WaglayladMessage, ConnectionInitializer, Peer, Router};
 use waglayla_core::{debug, info, warn};
+use waglayla_utils::networking::IpNetwork;
 use parking_lot::RwLock;
 use std::{
     collections::{hash_map::Entry::Occupied, HashMap},
+    net::IpAddr,
     sync::Arc,
 };
 use tokio::sync::mpsc::Receiver as MpscReceiver;
 
 use super::peer::PeerKey;
+use super::traffic::HubTraffic;
 use rand::prelude::IteratorRandom;
 
 #[derive(Debug)]
@@ -23,11 +26,38 @@ pub struct Hub {
     ///
     /// Note: the map key holds the node id and IP to prevent node impersonating.
     pub(crate) peers: Arc<RwLock<HashMap<PeerKey, Arc<Router>>>>,
+
+    /// Traffic counters aggregated over all peers, and the optional upload budget shared by them
+    pub(crate) traffic: Arc<HubTraffic>,
+
+    /// Networks whose peers are exempt from bans and inbound limits and always receive transaction relay
+    whitelist: Arc<Vec<IpNetwork>>,
 }
 
 impl Hub {
     pub fn new() -> Self {
-        Self { peers: Arc::new(RwLock::new(HashMap::new())) }
+        Self::with_traffic(Default::default())
+    }
+
+    /// Creates a hub whose peers share the given traffic settings (see [`HubTraffic`])
+    pub fn with_traffic(traffic: HubTraffic) -> Self {
+        Self { peers: Arc::new(RwLock::new(HashMap::new())), traffic: Arc::new(traffic), whitelist: Default::default() }
+    }
+
+    /// Sets the whitelisted networks of this hub. Expected to be called on hub construction
+    pub fn with_whitelist(mut self, whitelist: Vec<IpNetwork>) -> Self {
+        self.whitelist = Arc::new(whitelist);
+        self
+    }
+
+    /// Returns whether `ip` belongs to a whitelisted network
+    pub fn is_whitelisted(&self, ip: &IpAddr) -> bool {
+        self.whitelist.iter().any(|net| net.contains(ip))
+    }
+
+    /// Returns the traffic state shared by all peers of this hub
+    pub fn traffic(&self) -> &HubTraffic {
+        &self.traffic
     }
 
     /// Starts a loop for receiving central hub events from all peer routers. This mechanism is used for
@@ -85,10 +115,11 @@ impl Hub {
         }
     }
 
-    /// Selects a random subset of peers, trying to select at least half for outbound when possible
-    fn select_some_peers(&self, num_peers: usize) -> impl Iterator<Item = Arc<Router>> {
+    /// Selects a random subset of the peers matching `filter`, trying to select at least half for outbound when possible
+    fn select_some_peers(&self, num_peers: usize, filter: impl Fn(&Router) -> bool) -> impl Iterator<Item = Arc<Router>> {
         let peers = self.peers.read();
-        let total_outbound = peers.values().filter(|peer| peer.is_outbound()).count();
+        let peers = peers.values().filter(|peer| filter(peer)).collect::<Vec<_>>();
+        let total_outbound = peers.iter().filter(|peer| peer.is_outbound()).count();
         let total_inbound = peers.len() - total_outbound;
 
         let mut outbound_count = ((num_peers + 1) / 2).min(total_outbound);
@@ -104,12 +135,12 @@ impl Hub {
         let thread_rng = &mut rand::thread_rng();
 
         peers
-            .values()
+            .iter()
             .filter(|peer| peer.is_outbound())
-            .cloned()
+            .map(|&peer| peer.clone())
             .choose_multiple(thread_rng, outbound_count) // Randomly select about half from outbound
             .into_iter() // Then select the rest from inbound
-            .chain(peers.values().filter(|peer| !peer.is_outbound()).cloned().choose_multiple(thread_rng, inbound_count))
+            .chain(peers.iter().filter(|peer| !peer.is_outbound()).map(|&peer| peer.clone()).choose_multiple(thread_rng, inbound_count))
     }
 
     /// Send a message to a specific peer
@@ -135,13 +166,41 @@ impl Hub {
     pub async fn broadcast_to_some_peers(&self, msg: WaglayladMessage, num_peers: usize) {
         assert!(num_peers > 0);
 
-        let peers = self.select_some_peers(num_peers);
+        let peers = self.select_some_peers(num_peers, |_| true);
+
+        for router in peers {
+            let _ = router.enqueue(msg.clone()).await;
+        }
+    }
 
+    /// Broadcast a transaction relay message to all peers participating in transaction relay
+    pub async fn broadcast_transactions(&self, msg: WaglayladMessage) {
+        let peers = self.peers.read().values().filter(|router| router.relays_transactions()).cloned().collect::<Vec<_>>();
         for router in peers {
             let _ = router.enqueue(msg.clone()).await;
         }
     }
 
+    /// Broadcast a transaction relay message to some number of peers participating in transaction relay.
+    /// Whitelisted peers always receive the message and are not counted in `num_peers`
+    pub async fn broadcast_transactions_to_some_peers(&self, msg: WaglayladMessage, num_peers: usize) {
+        assert!(num_peers > 0);
+
+        let whitelisted = self
+            .peers
+            .read()
+            .values()
+            .filter(|router| router.relays_transactions() && self.is_whitelisted(&router.net_address().ip()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let peers = self
+            .select_some_peers(num_peers, |router| router.relays_transactions() && !self.is_whitelisted(&router.net_address().ip()));
+
+        for router in whitelisted.into_iter().chain(peers) {
+            let _ = router.enqueue(msg.clone()).await;
+        }
+    }
+
     /// Broadcast a vector of messages to all peers
     pub async fn broadcast_many(&self, msgs: Vec<WaglayladMessage>) {
         if msgs.is_empty() {
@@ -198,3 +257,17 @@ impl Default for Hub {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitelist() {
+        let hub = Hub::new().with_whitelist(vec!["192.168.1.0/24".parse().unwrap(), "::1".parse().unwrap()]);
+        assert!(hub.is_whitelisted(&"192.168.1.200".parse().unwrap()));
+        assert!(hub.is_whitelisted(&"::1".parse().unwrap()));
+        assert!(!hub.is_whitelisted(&"192.168.2.1".parse().unwrap()));
+        assert!(!Hub::new().is_whitelisted(&"192.168.1.200".parse().unwrap()));
+    }
+}
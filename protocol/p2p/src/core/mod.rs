@@ -4,3 +4,5 @@ pub mod hub;
 pub mod payload_type;
 pub mod peer;
 pub mod router;
+pub mod traffic;
+pub mod transport;
@@ -46,6 +46,11 @@ pub enum WaglayladMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    KeyExchange,
+    Encrypted,
+    RequestTransactionInclusionProof,
+    TransactionInclusionProof,
+    TransactionInclusionProofNotFound,
 }
 
 impl From<&WaglayladMessagePayload> for WaglayladMessagePayloadType {
@@ -100,6 +105,15 @@ impl From<&WaglayladMessagePayload> for WaglayladMessagePayloadType {
             WaglayladMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 WaglayladMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            WaglayladMessagePayload::KeyExchange(_) => WaglayladMessagePayloadType::KeyExchange,
+            WaglayladMessagePayload::Encrypted(_) => WaglayladMessagePayloadType::Encrypted,
+            WaglayladMessagePayload::RequestTransactionInclusionProof(_) => {
+                WaglayladMessagePayloadType::RequestTransactionInclusionProof
+            }
+            WaglayladMessagePayload::TransactionInclusionProof(_) => WaglayladMessagePayloadType::TransactionInclusionProof,
+            WaglayladMessagePayload::TransactionInclusionProofNotFound(_) => {
+                WaglayladMessagePayloadType::TransactionInclusionProofNotFound
+            }
         }
     }
 }
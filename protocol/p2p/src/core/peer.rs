@@ -2,6 +2,8 @@ use waglayla_consensus_core::subnets::SubnetworkId;
 use waglayla_utils::networking::{IpAddress, PeerId};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
 
+use super::traffic::TrafficSnapshot;
+
 #[derive(Debug, Clone, Default)]
 pub struct PeerProperties {
     pub user_agent: String,
@@ -13,14 +15,28 @@ pub struct PeerProperties {
     pub time_offset: i64,
 }
 
+/// The gossip role of a connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PeerRelayMode {
+    /// The connection takes part in block, transaction and address gossip
+    #[default]
+    Full,
+
+    /// An outbound connection used for block relay only. Transaction and address gossip are not exchanged
+    /// over it, which makes it harder to infer through gossip-based topology probing
+    BlockRelayOnly,
+}
+
 #[derive(Debug)]
 pub struct Peer {
     identity: PeerId,
     net_address: SocketAddr,
     is_outbound: bool,
+    relay_mode: PeerRelayMode,
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    traffic: TrafficSnapshot,
 }
 
 impl Peer {
@@ -28,11 +44,13 @@ impl Peer {
         identity: PeerId,
         net_address: SocketAddr,
         is_outbound: bool,
+        relay_mode: PeerRelayMode,
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        traffic: TrafficSnapshot,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self { identity, net_address, is_outbound, relay_mode, connection_started, properties, last_ping_duration, traffic }
     }
 
     /// Internal identity of this peer
@@ -54,6 +72,15 @@ impl Peer {
         self.is_outbound
     }
 
+    pub fn relay_mode(&self) -> PeerRelayMode {
+        self.relay_mode
+    }
+
+    /// Indicates whether this is a block-relay-only connection
+    pub fn is_block_relay_only(&self) -> bool {
+        self.relay_mode == PeerRelayMode::BlockRelayOnly
+    }
+
     pub fn time_connected(&self) -> u64 {
         Instant::now().duration_since(self.connection_started).as_millis() as u64
     }
@@ -65,6 +92,11 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    /// Traffic exchanged with this peer at the time this object was created
+    pub fn traffic(&self) -> &TrafficSnapshot {
+        &self.traffic
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
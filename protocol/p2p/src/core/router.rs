@@ -1,8 +1,10 @@
+use crate::capture::{Direction, PeerCapture};
 use crate::core::hub::HubEvent;
-use crate::pb::RejectMessage;
 use crate::pb::{waglaylad_message::Payload as WaglayladMessagePayload, WaglayladMessage};
+use crate::pb::{EncryptedMessage, RejectMessage};
 use crate::{common::ProtocolError, WaglayladMessagePayloadType};
 use crate::{make_message, Peer};
+use prost::Message;
 use waglayla_core::{debug, error, info, trace, warn};
 use waglayla_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
@@ -19,7 +21,9 @@ use tokio::sync::mpsc::{channel as mpsc_channel, Receiver as MpscReceiver, Sende
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tonic::Streaming;
 
-use super::peer::{PeerKey, PeerProperties};
+use super::peer::{PeerKey, PeerProperties, PeerRelayMode};
+use super::traffic::{HubTraffic, TrafficCounters, TrafficSnapshot};
+use super::transport::{EphemeralKey, TransportError, TransportState};
 
 pub struct IncomingRoute {
     rx: MpscReceiver<WaglayladMessage>,
@@ -123,6 +127,9 @@ pub struct Router {
     /// Indicates whether this connection is an outbound connection
     is_outbound: bool,
 
+    /// The gossip role of this connection
+    relay_mode: PeerRelayMode,
+
     /// Time of creation of this object and the connection it holds
     connection_started: Instant,
 
@@ -139,6 +146,18 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Traffic counters of this peer, by message type
+    traffic: TrafficCounters,
+
+    /// Traffic state shared with all other routers of the hub
+    hub_traffic: Arc<HubTraffic>,
+
+    /// Records all messages exchanged with this peer, if P2P capture is enabled
+    capture: Option<PeerCapture>,
+
+    /// Encryption state of the connection (see [`super::transport`])
+    transport: Mutex<TransportState>,
 }
 
 impl Display for Router {
@@ -159,9 +178,11 @@ impl From<&Router> for Peer {
             router.identity(),
             router.net_address,
             router.is_outbound,
+            router.relay_mode,
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.traffic(),
         )
     }
 }
@@ -176,23 +197,35 @@ impl Router {
     pub(crate) async fn new(
         net_address: SocketAddr,
         is_outbound: bool,
+        relay_mode: PeerRelayMode,
         hub_sender: MpscSender<HubEvent>,
         mut incoming_stream: Streaming<WaglayladMessage>,
         outgoing_route: MpscSender<WaglayladMessage>,
+        hub_traffic: Arc<HubTraffic>,
     ) -> Arc<Self> {
         let (start_sender, start_receiver) = oneshot_channel();
         let (shutdown_sender, mut shutdown_receiver) = oneshot_channel();
+        let capture = hub_traffic.capture.as_ref().map(|writer| {
+            let capture = writer.open(net_address, is_outbound);
+            debug!("P2P, capturing traffic of peer {} to {}", net_address, capture.path().display());
+            capture
+        });
 
         let router = Arc::new(Router {
             identity: Default::default(),
             net_address,
             is_outbound,
+            relay_mode,
             connection_started: Instant::now(),
             routing_map_by_type: RwLock::new(HashMap::new()),
             routing_map_by_id: RwLock::new(HashMap::new()),
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            traffic: Default::default(),
+            hub_traffic,
+            capture,
+            transport: Default::default(),
         });
 
         let router_clone = router.clone();
@@ -269,6 +302,21 @@ impl Router {
         self.is_outbound
     }
 
+    pub fn relay_mode(&self) -> PeerRelayMode {
+        self.relay_mode
+    }
+
+    /// Indicates whether this is a block-relay-only connection
+    pub fn is_block_relay_only(&self) -> bool {
+        self.relay_mode == PeerRelayMode::BlockRelayOnly
+    }
+
+    /// Indicates whether transaction invs may be relayed to this peer, i.e., this is not a block-relay-only
+    /// connection and the peer did not ask to disable transaction relay during the handshake
+    pub fn relays_transactions(&self) -> bool {
+        !self.is_block_relay_only() && !self.properties().disable_relay_tx
+    }
+
     pub fn connection_started(&self) -> Instant {
         self.connection_started
     }
@@ -294,6 +342,27 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Returns a snapshot of the traffic exchanged with this peer
+    pub fn traffic(&self) -> TrafficSnapshot {
+        self.traffic.snapshot()
+    }
+
+    /// Sets our ephemeral key for the key exchange. Must be called before the receive loop is started.
+    pub(crate) fn set_transport_key(&self, key: EphemeralKey) {
+        self.transport.lock().set_local_key(key);
+    }
+
+    /// Encrypts all subsequent messages sent to the peer. Returns false if no key exchange took place.
+    pub(crate) fn enable_encrypted_send(&self) -> bool {
+        self.transport.lock().enable_send()
+    }
+
+    /// Indicates whether the traffic with this peer is encrypted in both directions
+    pub fn is_encrypted(&self) -> bool {
+        let transport = self.transport.lock();
+        transport.is_send_encrypted() && transport.is_receive_encrypted()
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -367,7 +436,15 @@ impl Router {
             debug!("P2P, Route to flow got empty payload, peer: {}", self);
             return Err(ProtocolError::Other("received waglaylad p2p message with empty payload"));
         }
+        let msg = self.open_transport(msg)?;
         let msg_type: WaglayladMessagePayloadType = msg.payload.as_ref().expect("payload was just verified").into();
+        let msg_len = msg.encoded_len() as u64;
+        self.traffic.record_received(msg_type, msg_len);
+        self.hub_traffic.counters.record_received(msg_type, msg_len);
+        if let Some(capture) = self.capture.as_ref() {
+            capture.record(Direction::Inbound, &msg);
+        }
+
         // Handle the special case of a reject message ending the connection
         if msg_type == WaglayladMessagePayloadType::Reject {
             let Some(WaglayladMessagePayload::Reject(reject)) = msg.payload else { unreachable!() };
@@ -399,11 +476,73 @@ impl Router {
         }
     }
 
+    /// Decrypts an encrypted message, or processes the peer's key exchange so that subsequent messages can be decrypted
+    fn open_transport(&self, msg: WaglayladMessage) -> Result<WaglayladMessage, ProtocolError> {
+        let mut transport = self.transport.lock();
+        match msg.payload {
+            Some(WaglayladMessagePayload::Encrypted(encrypted)) => {
+                let plaintext = transport.open(&encrypted.ciphertext)?;
+                let msg = WaglayladMessage::decode(plaintext.as_slice())
+                    .map_err(|_| ProtocolError::Other("failed decoding a decrypted p2p message"))?;
+                match msg.payload {
+                    None => Err(ProtocolError::Other("received waglaylad p2p message with empty payload")),
+                    Some(WaglayladMessagePayload::Encrypted(_)) => {
+                        Err(ProtocolError::Other("received a nested encrypted p2p message"))
+                    }
+                    Some(WaglayladMessagePayload::KeyExchange(_)) => Err(TransportError::UnexpectedKeyExchange.into()),
+                    Some(_) => Ok(msg),
+                }
+            }
+            Some(WaglayladMessagePayload::KeyExchange(ref key_exchange)) => {
+                // The peer encrypts everything it sends after its key exchange, so the session keys must be
+                // derived right away rather than by the handshake flow
+                transport.on_remote_key(&key_exchange.public_key, self.is_outbound)?;
+                Ok(msg)
+            }
+            _ if transport.is_receive_encrypted() => Err(TransportError::UnexpectedPlaintext.into()),
+            _ => Ok(msg),
+        }
+    }
+
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: WaglayladMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Waglayla P2P message should always have a value");
+        let msg_len = msg.encoded_len() as u64;
+        self.enqueue_with_len(msg, msg_len)
+    }
+
+    /// Enqueues a bulk message (such as IBD blocks or pruning point UTXO chunks served to a syncing peer).
+    /// If a global upload budget is configured, waits until the message fits in the budget before enqueuing it.
+    ///
+    /// Note: latency-sensitive traffic such as block relay should always use [`Router::enqueue`]
+    pub async fn enqueue_throttled(&self, msg: WaglayladMessage) -> Result<(), ProtocolError> {
+        assert!(msg.payload.is_some(), "Waglayla P2P message should always have a value");
+        let msg_len = msg.encoded_len() as u64;
+        if let Some(budget) = self.hub_traffic.upload_budget.as_ref() {
+            budget.consume(msg_len).await;
+        }
+        self.enqueue_with_len(msg, msg_len)
+    }
+
+    fn enqueue_with_len(&self, msg: WaglayladMessage, msg_len: u64) -> Result<(), ProtocolError> {
+        let msg_type: WaglayladMessagePayloadType = msg.payload.as_ref().expect("payload was verified by the caller").into();
+        // Encoded before sealing and recorded only once the message was actually enqueued
+        let captured = self.capture.as_ref().map(|_| msg.encode_to_vec());
+        // The transport lock is held while sending so that messages are put on the wire in nonce order
+        let mut transport = self.transport.lock();
+        let msg = match transport.seal_message(&msg) {
+            Some(ciphertext) => make_message!(WaglayladMessagePayload::Encrypted, EncryptedMessage { ciphertext }),
+            None => msg,
+        };
         match self.outgoing_route.try_send(msg) {
-            Ok(_) => Ok(()),
+            Ok(_) => {
+                self.traffic.record_sent(msg_type, msg_len);
+                self.hub_traffic.counters.record_sent(msg_type, msg_len);
+                if let (Some(capture), Some(encoded)) = (self.capture.as_ref(), captured) {
+                    capture.record_encoded(Direction::Outbound, encoded);
+                }
+                Ok(())
+            }
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
             Err(TrySendError::Full(_)) => Err(ProtocolError::OutgoingRouteCapacityReached(self.to_string())),
         }
@@ -444,6 +583,10 @@ impl Router {
         self.routing_map_by_type.write().clear();
         self.routing_map_by_id.write().clear();
 
+        if let Some(capture) = self.capture.as_ref() {
+            capture.flush();
+        }
+
         // Send a close notification to the central Hub
         self.hub_sender.send(HubEvent::PeerClosing(self.clone())).await.expect("hub receiver should never drop before senders");
 
@@ -0,0 +1,223 @@
+use crate::capture::CaptureWriter;
+use crate::WaglayladMessagePayloadType;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Message and byte counts for a single traffic direction (or a single message type within it)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TrafficEntry {
+    pub messages: u64,
+    pub bytes: u64,
+}
+
+impl TrafficEntry {
+    fn add(&mut self, bytes: u64) {
+        self.messages += 1;
+        self.bytes += bytes;
+    }
+}
+
+#[derive(Debug, Default)]
+struct DirectionCounters {
+    messages: AtomicU64,
+    bytes: AtomicU64,
+    by_type: Mutex<HashMap<WaglayladMessagePayloadType, TrafficEntry>>,
+}
+
+impl DirectionCounters {
+    fn record(&self, msg_type: WaglayladMessagePayloadType, bytes: u64) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.by_type.lock().entry(msg_type).or_default().add(bytes);
+    }
+
+    fn total(&self) -> TrafficEntry {
+        TrafficEntry { messages: self.messages.load(Ordering::Relaxed), bytes: self.bytes.load(Ordering::Relaxed) }
+    }
+}
+
+/// Per message type traffic counters, measured in encoded (pre-compression) protobuf bytes
+#[derive(Debug, Default)]
+pub struct TrafficCounters {
+    sent: DirectionCounters,
+    received: DirectionCounters,
+}
+
+impl TrafficCounters {
+    pub fn record_sent(&self, msg_type: WaglayladMessagePayloadType, bytes: u64) {
+        self.sent.record(msg_type, bytes);
+    }
+
+    pub fn record_received(&self, msg_type: WaglayladMessagePayloadType, bytes: u64) {
+        self.received.record(msg_type, bytes);
+    }
+
+    pub fn snapshot(&self) -> TrafficSnapshot {
+        // Totals are read before the type maps so that the per-type breakdown is never behind the totals
+        let sent = self.sent.total();
+        let received = self.received.total();
+        let mut by_type: HashMap<WaglayladMessagePayloadType, MessageTypeTraffic> = HashMap::new();
+        for (msg_type, entry) in self.sent.by_type.lock().iter() {
+            by_type.entry(*msg_type).or_insert_with(|| MessageTypeTraffic::new(*msg_type)).sent = *entry;
+        }
+        for (msg_type, entry) in self.received.by_type.lock().iter() {
+            by_type.entry(*msg_type).or_insert_with(|| MessageTypeTraffic::new(*msg_type)).received = *entry;
+        }
+        let mut by_type = by_type.into_values().collect::<Vec<_>>();
+        by_type.sort_by_key(|t| t.message_type as u8);
+        TrafficSnapshot { sent, received, by_type }
+    }
+}
+
+/// Sent and received traffic of a single message type
+#[derive(Debug, Clone, Copy)]
+pub struct MessageTypeTraffic {
+    pub message_type: WaglayladMessagePayloadType,
+    pub sent: TrafficEntry,
+    pub received: TrafficEntry,
+}
+
+impl MessageTypeTraffic {
+    fn new(message_type: WaglayladMessagePayloadType) -> Self {
+        Self { message_type, sent: Default::default(), received: Default::default() }
+    }
+}
+
+/// A point-in-time copy of [`TrafficCounters`]
+#[derive(Debug, Clone, Default)]
+pub struct TrafficSnapshot {
+    pub sent: TrafficEntry,
+    pub received: TrafficEntry,
+    /// Breakdown by message type, ordered by message type
+    pub by_type: Vec<MessageTypeTraffic>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Available bytes, may become negative when a message larger than the remaining budget is sent
+    available: f64,
+    last_refill: Instant,
+}
+
+/// A global token bucket limiting the upload rate of bulk (IBD-serving) traffic.
+///
+/// The bucket holds at most one second worth of budget. Callers are served in FIFO order
+/// since the async mutex is held while waiting for the budget to refill.
+#[derive(Debug)]
+pub struct UploadBudget {
+    bytes_per_sec: u64,
+    state: tokio::sync::Mutex<BucketState>,
+    throttled_bytes: AtomicU64,
+    throttled_millis: AtomicU64,
+}
+
+impl UploadBudget {
+    pub fn new(bytes_per_sec: u64) -> Self {
+        assert!(bytes_per_sec > 0, "upload budget must be positive");
+        Self {
+            bytes_per_sec,
+            state: tokio::sync::Mutex::new(BucketState { available: bytes_per_sec as f64, last_refill: Instant::now() }),
+            throttled_bytes: Default::default(),
+            throttled_millis: Default::default(),
+        }
+    }
+
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Total bytes which had to wait for budget before being sent
+    pub fn throttled_bytes(&self) -> u64 {
+        self.throttled_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Total time (in milliseconds) spent waiting for budget
+    pub fn throttled_millis(&self) -> u64 {
+        self.throttled_millis.load(Ordering::Relaxed)
+    }
+
+    /// Consumes `bytes` from the budget, waiting for the bucket to refill if it is exhausted
+    pub async fn consume(&self, bytes: u64) {
+        let mut state = self.state.lock().await;
+        let now = Instant::now();
+        let rate = self.bytes_per_sec as f64;
+        state.available = (state.available + now.duration_since(state.last_refill).as_secs_f64() * rate).min(rate);
+        state.last_refill = now;
+        state.available -= bytes as f64;
+        if state.available < 0.0 {
+            let wait = Duration::from_secs_f64(-state.available / rate);
+            self.throttled_bytes.fetch_add(bytes, Ordering::Relaxed);
+            self.throttled_millis.fetch_add(wait.as_millis() as u64, Ordering::Relaxed);
+            // Keep holding the lock so that waiting callers are served in order
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Traffic state shared by all routers of a hub
+#[derive(Debug, Default)]
+pub struct HubTraffic {
+    /// Aggregated counters of all peers connected since startup
+    pub counters: TrafficCounters,
+
+    /// Optional global upload budget applied to bulk traffic
+    pub upload_budget: Option<UploadBudget>,
+
+    /// If set, all messages exchanged with each peer are recorded to a capture file
+    pub capture: Option<CaptureWriter>,
+}
+
+impl HubTraffic {
+    pub fn new(upload_limit: Option<u64>, capture_dir: Option<PathBuf>) -> Self {
+        Self {
+            counters: Default::default(),
+            upload_budget: upload_limit.filter(|&limit| limit > 0).map(UploadBudget::new),
+            capture: capture_dir.map(CaptureWriter::new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traffic_counters_snapshot() {
+        let counters = TrafficCounters::default();
+        counters.record_sent(WaglayladMessagePayloadType::IbdBlock, 100);
+        counters.record_sent(WaglayladMessagePayloadType::IbdBlock, 50);
+        counters.record_received(WaglayladMessagePayloadType::RequestIbdBlocks, 10);
+        counters.record_received(WaglayladMessagePayloadType::Addresses, 7);
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.sent, TrafficEntry { messages: 2, bytes: 150 });
+        assert_eq!(snapshot.received, TrafficEntry { messages: 2, bytes: 17 });
+        assert_eq!(
+            snapshot.by_type.iter().map(|t| t.message_type).collect::<Vec<_>>(),
+            vec![
+                WaglayladMessagePayloadType::Addresses,
+                WaglayladMessagePayloadType::IbdBlock,
+                WaglayladMessagePayloadType::RequestIbdBlocks
+            ]
+        );
+        let ibd_block = snapshot.by_type[1];
+        assert_eq!(ibd_block.sent, TrafficEntry { messages: 2, bytes: 150 });
+        assert_eq!(ibd_block.received, TrafficEntry::default());
+    }
+
+    #[tokio::test]
+    async fn test_upload_budget() {
+        let budget = UploadBudget::new(1000);
+        let start = Instant::now();
+        // The initial burst is served immediately
+        budget.consume(1000).await;
+        assert_eq!(budget.throttled_bytes(), 0);
+        // The bucket is now empty, so this call must wait for ~200ms of budget
+        budget.consume(200).await;
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert_eq!(budget.throttled_bytes(), 200);
+    }
+}
@@ -0,0 +1,381 @@
+//! Encrypted P2P transport.
+//!
+//! Peers which both support [`ENCRYPTED_TRANSPORT_PROTOCOL_VERSION`] exchange ephemeral secp256k1 public keys right
+//! after the version exchange (see [`crate::WaglayladHandshake`]). Each side derives the ECDH shared secret, from which
+//! two directional ChaCha20-Poly1305 keys are derived. From that point on, every message is encoded, encrypted and sent
+//! wrapped in an `EncryptedMessage` payload, with a per-direction message counter used as the nonce. Older peers skip
+//! the key exchange and keep using plaintext.
+//!
+//! A node may additionally hold a static [`NodeIdentity`], with which it signs its ephemeral key. Peers which pin that
+//! identity (see [`TransportConfig`]) refuse the connection unless the key exchange is signed by the pinned key, thus
+//! authenticating the encrypted channel between trusted peer pairs.
+
+use blake2b_simd::Params;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use secp256k1::{ecdh::SharedSecret, schnorr, Keypair, Message, PublicKey, SecretKey, XOnlyPublicKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    fs, io,
+    net::IpAddr,
+    path::Path,
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// The first protocol version supporting the encrypted transport
+pub const ENCRYPTED_TRANSPORT_PROTOCOL_VERSION: u32 = 9;
+
+/// Domain separation for the key derivation (blake2b personalization is limited to 16 bytes)
+const KDF_PERSONALIZATION: &[u8; 16] = b"WaglaylaP2PKeys_";
+
+/// Domain separation for identity signatures over ephemeral keys
+const SIGNATURE_DOMAIN: &[u8] = b"waglayla-p2p-key-exchange";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TransportError {
+    #[error("invalid public key in key exchange")]
+    InvalidPublicKey,
+
+    #[error("invalid identity key")]
+    InvalidIdentityKey,
+
+    #[error("key exchange is not signed by the pinned identity key {0}")]
+    PinnedKeyMismatch(String),
+
+    #[error("peer does not support the encrypted transport, which is required for pinned peers")]
+    EncryptionRequired,
+
+    #[error("unexpected key exchange")]
+    UnexpectedKeyExchange,
+
+    #[error("received a plaintext message over an encrypted transport")]
+    UnexpectedPlaintext,
+
+    #[error("received an encrypted message before the key exchange")]
+    UnexpectedCiphertext,
+
+    #[error("message decryption failed")]
+    DecryptionFailed,
+}
+
+/// An ephemeral key pair, generated for a single connection
+#[derive(Clone)]
+pub struct EphemeralKey {
+    secret: SecretKey,
+    public: PublicKey,
+}
+
+impl EphemeralKey {
+    pub fn generate() -> Self {
+        let secret = SecretKey::new(&mut rand::thread_rng());
+        Self { secret, public: PublicKey::from_secret_key(SECP256K1, &secret) }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public
+    }
+}
+
+/// The static identity of a node, used for signing ephemeral keys so that peers can pin it
+#[derive(Clone)]
+pub struct NodeIdentity {
+    keypair: Keypair,
+}
+
+impl NodeIdentity {
+    pub fn generate() -> Self {
+        Self { keypair: Keypair::new(SECP256K1, &mut rand::thread_rng()) }
+    }
+
+    pub fn from_secret_bytes(bytes: &[u8]) -> Result<Self, TransportError> {
+        let secret = SecretKey::from_slice(bytes).map_err(|_| TransportError::InvalidIdentityKey)?;
+        Ok(Self { keypair: Keypair::from_secret_key(SECP256K1, &secret) })
+    }
+
+    /// Loads the hex-encoded secret key stored at `path`, or generates a new identity and stores it there
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        if path.exists() {
+            let hex = fs::read_to_string(path)?;
+            let mut bytes = [0u8; 32];
+            faster_hex::hex_decode(hex.trim().as_bytes(), &mut bytes)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "identity key file should hold a 32 bytes hex key"))?;
+            Self::from_secret_bytes(&bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        } else {
+            let identity = Self::generate();
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, faster_hex::hex_string(&identity.keypair.secret_bytes()))?;
+            Ok(identity)
+        }
+    }
+
+    /// The public identity key, as pinned by peers
+    pub fn public_key(&self) -> XOnlyPublicKey {
+        self.keypair.x_only_public_key().0
+    }
+
+    /// Signs an ephemeral public key with this identity
+    pub fn sign(&self, ephemeral: &PublicKey) -> schnorr::Signature {
+        SECP256K1.sign_schnorr(&signature_message(ephemeral), &self.keypair)
+    }
+}
+
+fn signature_message(ephemeral: &PublicKey) -> Message {
+    let hash = Params::new().hash_length(32).to_state().update(SIGNATURE_DOMAIN).update(&ephemeral.serialize()).finalize();
+    Message::from_digest_slice(hash.as_bytes()).expect("hash is 32 bytes")
+}
+
+/// Verifies that `signature` is a signature of `identity` over the ephemeral key `ephemeral`
+pub fn verify_identity_signature(identity: &XOnlyPublicKey, ephemeral: &PublicKey, signature: &[u8]) -> Result<(), TransportError> {
+    let signature = schnorr::Signature::from_slice(signature).map_err(|_| TransportError::PinnedKeyMismatch(identity.to_string()))?;
+    SECP256K1
+        .verify_schnorr(&signature, &signature_message(ephemeral), identity)
+        .map_err(|_| TransportError::PinnedKeyMismatch(identity.to_string()))
+}
+
+/// A peer IP pinned to an identity key, in `<PUBKEY>@<IP>` format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinnedPeer {
+    pub key: XOnlyPublicKey,
+    pub ip: IpAddr,
+}
+
+impl FromStr for PinnedPeer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, ip) = s.split_once('@').ok_or_else(|| format!("expected <PUBKEY>@<IP>, got {s}"))?;
+        let key = XOnlyPublicKey::from_str(key).map_err(|err| format!("invalid pinned key {key}: {err}"))?;
+        let ip = IpAddr::from_str(ip).map_err(|err| format!("invalid pinned peer IP {ip}: {err}"))?;
+        Ok(Self { key, ip })
+    }
+}
+
+impl Display for PinnedPeer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.key, self.ip)
+    }
+}
+
+/// Encrypted transport settings shared by all connections
+#[derive(Clone, Default)]
+pub struct TransportConfig {
+    /// If set, our ephemeral keys are signed with this identity
+    pub identity: Option<NodeIdentity>,
+
+    /// Peers which must authenticate with their identity key
+    pub pinned: HashMap<IpAddr, XOnlyPublicKey>,
+}
+
+impl TransportConfig {
+    pub fn new(identity: Option<NodeIdentity>, pinned: impl IntoIterator<Item = PinnedPeer>) -> Self {
+        Self { identity, pinned: pinned.into_iter().map(|p| (p.ip, p.key)).collect() }
+    }
+
+    /// Returns the identity key pinned for `ip`, if any
+    pub fn pinned_key(&self, ip: &IpAddr) -> Option<&XOnlyPublicKey> {
+        self.pinned.get(ip)
+    }
+}
+
+/// Encrypts or decrypts the messages of a single direction
+pub struct DirectionalCipher {
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl DirectionalCipher {
+    fn new(key: &[u8; 32]) -> Self {
+        Self { cipher: ChaCha20Poly1305::new(Key::from_slice(key)), counter: 0 }
+    }
+
+    fn next_nonce(&mut self) -> Nonce {
+        let mut nonce = [0u8; 12];
+        nonce[..8].copy_from_slice(&self.counter.to_le_bytes());
+        // A connection would need to exchange 2^64 messages for the counter to wrap around
+        self.counter += 1;
+        *Nonce::from_slice(&nonce)
+    }
+
+    pub fn encrypt(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = self.next_nonce();
+        self.cipher.encrypt(&nonce, plaintext).expect("encryption of in-memory buffers is infallible")
+    }
+
+    pub fn decrypt(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, TransportError> {
+        let nonce = self.next_nonce();
+        self.cipher.decrypt(&nonce, ciphertext).map_err(|_| TransportError::DecryptionFailed)
+    }
+}
+
+/// Derives the (send, receive) ciphers of a connection. The initiator (outbound) side sends with the first
+/// derived key and receives with the second, and vice versa for the responder.
+fn derive_ciphers(local: &EphemeralKey, remote: &PublicKey, is_outbound: bool) -> (DirectionalCipher, DirectionalCipher) {
+    let shared = SharedSecret::new(remote, &local.secret).secret_bytes();
+    let (initiator, responder) = if is_outbound { (local.public, *remote) } else { (*remote, local.public) };
+    let derive = |label: &[u8]| -> [u8; 32] {
+        let hash = Params::new()
+            .hash_length(32)
+            .key(&shared)
+            .personal(KDF_PERSONALIZATION)
+            .to_state()
+            .update(label)
+            .update(&initiator.serialize())
+            .update(&responder.serialize())
+            .finalize();
+        hash.as_bytes().try_into().expect("hash is 32 bytes")
+    };
+    let initiator_cipher = DirectionalCipher::new(&derive(b"initiator"));
+    let responder_cipher = DirectionalCipher::new(&derive(b"responder"));
+    if is_outbound {
+        (initiator_cipher, responder_cipher)
+    } else {
+        (responder_cipher, initiator_cipher)
+    }
+}
+
+/// The encryption state of a single connection.
+///
+/// Our ephemeral key is set before the handshake starts. When the peer's key exchange arrives the receive direction
+/// is encrypted immediately (the peer encrypts everything it sends after its key exchange message), while the send
+/// direction is only switched on by the handshake, once our own key exchange message was sent and the peer was
+/// authenticated.
+#[derive(Default)]
+pub struct TransportState {
+    local_key: Option<EphemeralKey>,
+    receive: Option<DirectionalCipher>,
+    pending_send: Option<DirectionalCipher>,
+    send: Option<DirectionalCipher>,
+}
+
+impl Debug for TransportState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransportState")
+            .field("send_encrypted", &self.is_send_encrypted())
+            .field("receive_encrypted", &self.is_receive_encrypted())
+            .finish()
+    }
+}
+
+impl TransportState {
+    pub fn set_local_key(&mut self, key: EphemeralKey) {
+        self.local_key = Some(key);
+    }
+
+    /// Derives the session keys from the peer's ephemeral key and enables decryption of subsequent messages
+    pub fn on_remote_key(&mut self, remote: &[u8], is_outbound: bool) -> Result<(), TransportError> {
+        let Some(local) = self.local_key.take() else {
+            return Err(TransportError::UnexpectedKeyExchange);
+        };
+        let remote = PublicKey::from_slice(remote).map_err(|_| TransportError::InvalidPublicKey)?;
+        let (send, receive) = derive_ciphers(&local, &remote, is_outbound);
+        self.receive = Some(receive);
+        self.pending_send = Some(send);
+        Ok(())
+    }
+
+    /// Enables encryption of subsequent messages sent to the peer. Returns false if no key exchange took place.
+    pub fn enable_send(&mut self) -> bool {
+        match self.pending_send.take() {
+            Some(send) => {
+                self.send = Some(send);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_receive_encrypted(&self) -> bool {
+        self.receive.is_some()
+    }
+
+    pub fn is_send_encrypted(&self) -> bool {
+        self.send.is_some()
+    }
+
+    /// Encrypts `plaintext` if the send direction is encrypted
+    pub fn seal(&mut self, plaintext: &[u8]) -> Option<Vec<u8>> {
+        self.send.as_mut().map(|cipher| cipher.encrypt(plaintext))
+    }
+
+    /// Encodes and encrypts `msg` if the send direction is encrypted
+    pub fn seal_message(&mut self, msg: &impl prost::Message) -> Option<Vec<u8>> {
+        self.send.as_mut().map(|cipher| cipher.encrypt(&msg.encode_to_vec()))
+    }
+
+    pub fn open(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, TransportError> {
+        self.receive.as_mut().ok_or(TransportError::UnexpectedCiphertext)?.decrypt(ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_pair() -> (TransportState, TransportState) {
+        let (outbound_key, inbound_key) = (EphemeralKey::generate(), EphemeralKey::generate());
+        let (mut outbound, mut inbound) = (TransportState::default(), TransportState::default());
+        outbound.set_local_key(outbound_key.clone());
+        inbound.set_local_key(inbound_key.clone());
+        outbound.on_remote_key(&inbound_key.public_key().serialize(), true).unwrap();
+        inbound.on_remote_key(&outbound_key.public_key().serialize(), false).unwrap();
+        assert!(outbound.enable_send() && inbound.enable_send());
+        (outbound, inbound)
+    }
+
+    #[test]
+    fn test_transport_roundtrip() {
+        let (mut outbound, mut inbound) = session_pair();
+        for i in 0..3u8 {
+            let sealed = outbound.seal(&[i; 40]).unwrap();
+            assert_ne!(&sealed[..40], &[i; 40]);
+            assert_eq!(inbound.open(&sealed).unwrap(), vec![i; 40]);
+            let sealed = inbound.seal(b"pong").unwrap();
+            assert_eq!(outbound.open(&sealed).unwrap(), b"pong");
+        }
+
+        // Reordered or tampered messages fail to decrypt
+        let _first = outbound.seal(b"first").unwrap();
+        let second = outbound.seal(b"second").unwrap();
+        assert_eq!(inbound.open(&second), Err(TransportError::DecryptionFailed));
+
+        let (mut outbound, mut inbound) = session_pair();
+        let mut tampered = outbound.seal(b"first").unwrap();
+        tampered[0] ^= 1;
+        assert_eq!(inbound.open(&tampered), Err(TransportError::DecryptionFailed));
+    }
+
+    #[test]
+    fn test_plaintext_before_key_exchange() {
+        let mut state = TransportState::default();
+        assert!(state.seal(b"plain").is_none());
+        assert_eq!(state.open(b"cipher"), Err(TransportError::UnexpectedCiphertext));
+        assert_eq!(
+            state.on_remote_key(&EphemeralKey::generate().public_key().serialize(), true),
+            Err(TransportError::UnexpectedKeyExchange)
+        );
+        assert!(!state.enable_send());
+    }
+
+    #[test]
+    fn test_identity_signature() {
+        let identity = NodeIdentity::generate();
+        let ephemeral = EphemeralKey::generate().public_key();
+        let signature = identity.sign(&ephemeral);
+        assert!(verify_identity_signature(&identity.public_key(), &ephemeral, signature.as_ref()).is_ok());
+        // A signature over another ephemeral key or by another identity is rejected
+        let other = EphemeralKey::generate().public_key();
+        assert!(verify_identity_signature(&identity.public_key(), &other, signature.as_ref()).is_err());
+        assert!(verify_identity_signature(&NodeIdentity::generate().public_key(), &ephemeral, signature.as_ref()).is_err());
+
+        let pinned = PinnedPeer::from_str(&format!("{}@10.0.0.1", identity.public_key())).unwrap();
+        assert_eq!(pinned.key, identity.public_key());
+        assert_eq!(PinnedPeer::from_str(&pinned.to_string()).unwrap(), pinned);
+        assert!(PinnedPeer::from_str("10.0.0.1").is_err());
+    }
+}
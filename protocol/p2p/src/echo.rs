@@ -64,6 +64,9 @@ impl EchoFlow {
             WaglayladMessagePayloadType::IbdChainBlockLocator,
             WaglayladMessagePayloadType::RequestAntipast,
             WaglayladMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            WaglayladMessagePayloadType::RequestTransactionInclusionProof,
+            WaglayladMessagePayloadType::TransactionInclusionProof,
+            WaglayladMessagePayloadType::TransactionInclusionProofNotFound,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
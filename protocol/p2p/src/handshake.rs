@@ -1,8 +1,13 @@
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::pb::{waglaylad_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
+use crate::core::transport::{
+    verify_identity_signature, EphemeralKey, TransportConfig, TransportError, ENCRYPTED_TRANSPORT_PROTOCOL_VERSION,
+};
+use crate::pb::{waglaylad_message::Payload, KeyExchangeMessage, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, WaglayladMessagePayloadType, Router};
+use secp256k1::PublicKey;
 use waglayla_core::debug;
 
 /// Implements the Waglayla peer-to-peer handshake protocol
@@ -11,19 +16,35 @@ pub struct WaglayladHandshake<'a> {
     version_receiver: IncomingRoute,
     verack_receiver: IncomingRoute,
     ready_receiver: IncomingRoute,
+    key_exchange_receiver: IncomingRoute,
+    ephemeral_key: PublicKey,
+    transport_config: Arc<TransportConfig>,
 }
 
 impl<'a> WaglayladHandshake<'a> {
     /// Builds the handshake object and subscribes to handshake messages
     pub fn new(router: &'a Router) -> Self {
+        // The ephemeral key is installed before the receive loop starts, since the peer's key exchange
+        // might arrive before our handshake flow reaches the key exchange step
+        let ephemeral_key = EphemeralKey::generate();
+        router.set_transport_key(ephemeral_key.clone());
         Self {
             router,
             version_receiver: router.subscribe(vec![WaglayladMessagePayloadType::Version]),
             verack_receiver: router.subscribe(vec![WaglayladMessagePayloadType::Verack]),
             ready_receiver: router.subscribe(vec![WaglayladMessagePayloadType::Ready]),
+            key_exchange_receiver: router.subscribe(vec![WaglayladMessagePayloadType::KeyExchange]),
+            ephemeral_key: ephemeral_key.public_key(),
+            transport_config: Default::default(),
         }
     }
 
+    /// Sets the identity key and pinned peer keys used for authenticating the key exchange
+    pub fn with_transport_config(mut self, transport_config: Arc<TransportConfig>) -> Self {
+        self.transport_config = transport_config;
+        self
+    }
+
     async fn receive_version_flow(router: &Router, version_receiver: &mut IncomingRoute) -> Result<VersionMessage, ProtocolError> {
         debug!("starting receive version flow");
 
@@ -67,14 +88,166 @@ impl<'a> WaglayladHandshake<'a> {
         Ok(())
     }
 
-    /// Performs the handshake with the peer, essentially exchanging version messages
+    /// Exchanges ephemeral keys with the peer if both sides support the encrypted transport, after which all
+    /// messages are encrypted. If the peer IP is pinned, the key exchange must be signed by the pinned identity key.
+    async fn key_exchange_flow(&mut self, self_protocol_version: u32, peer_protocol_version: u32) -> Result<(), ProtocolError> {
+        let pinned_key = self.transport_config.pinned_key(&self.router.net_address().ip()).copied();
+        if self_protocol_version.min(peer_protocol_version) < ENCRYPTED_TRANSPORT_PROTOCOL_VERSION {
+            return match pinned_key {
+                Some(_) => Err(TransportError::EncryptionRequired.into()),
+                None => Ok(()),
+            };
+        }
+
+        debug!("starting key exchange flow");
+        let signature = self
+            .transport_config
+            .identity
+            .as_ref()
+            .map(|identity| identity.sign(&self.ephemeral_key).as_ref().to_vec())
+            .unwrap_or_default();
+        let key_exchange = KeyExchangeMessage { public_key: self.ephemeral_key.serialize().to_vec(), signature };
+        self.router.enqueue(make_message!(Payload::KeyExchange, key_exchange)).await?;
+
+        // The session keys are derived by the router as soon as the peer's key exchange arrives
+        let peer_key_exchange = dequeue_with_timeout!(self.key_exchange_receiver, Payload::KeyExchange, Duration::from_secs(4))?;
+        if let Some(pinned_key) = pinned_key {
+            let peer_ephemeral_key =
+                PublicKey::from_slice(&peer_key_exchange.public_key).map_err(|_| TransportError::InvalidPublicKey)?;
+            verify_identity_signature(&pinned_key, &peer_ephemeral_key, &peer_key_exchange.signature)?;
+            debug!("authenticated the pinned identity key {} of peer {}", pinned_key, self.router);
+        }
+
+        if !self.router.enable_encrypted_send() {
+            return Err(TransportError::UnexpectedKeyExchange.into());
+        }
+        Ok(())
+    }
+
+    /// Performs the handshake with the peer, essentially exchanging version messages, followed by
+    /// a key exchange if both sides support the encrypted transport
     pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+        let self_protocol_version = self_version_message.protocol_version;
         // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
         let (send_res, recv_res) = tokio::join!(
             Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message),
             Self::receive_version_flow(self.router, &mut self.version_receiver)
         );
         send_res?;
-        recv_res
+        let peer_version_message = recv_res?;
+        self.key_exchange_flow(self_protocol_version, peer_version_message.protocol_version).await?;
+        Ok(peer_version_message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Mutex};
+
+    use super::*;
+    use crate::core::adaptor::ConnectionInitializer;
+    use crate::core::transport::{NodeIdentity, PinnedPeer};
+    use crate::{Adaptor, ConnectionError, Hub};
+    use tonic::async_trait;
+    use uuid::Uuid;
+    use waglayla_core::time::unix_now;
+    use waglayla_utils::networking::NetAddress;
+
+    /// Performs the handshake with the given transport config and records whether the resulting traffic is encrypted
+    struct TransportInitializer {
+        transport_config: Arc<TransportConfig>,
+        encrypted: Mutex<Vec<bool>>,
+    }
+
+    impl TransportInitializer {
+        fn new(transport_config: TransportConfig) -> Arc<Self> {
+            Arc::new(Self { transport_config: Arc::new(transport_config), encrypted: Default::default() })
+        }
+    }
+
+    #[async_trait]
+    impl ConnectionInitializer for TransportInitializer {
+        async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+            let mut handshake = WaglayladHandshake::new(&router).with_transport_config(self.transport_config.clone());
+            router.start();
+            let version_message = VersionMessage {
+                protocol_version: ENCRYPTED_TRANSPORT_PROTOCOL_VERSION,
+                services: 0,
+                timestamp: unix_now() as i64,
+                address: None,
+                id: Vec::from(Uuid::new_v4().as_ref()),
+                user_agent: String::new(),
+                disable_relay_tx: false,
+                subnetwork_id: None,
+                network: "waglayla-mainnet".to_string(),
+            };
+            handshake.handshake(version_message).await?;
+            handshake.exchange_ready_messages().await?;
+            self.encrypted.lock().unwrap().push(router.is_encrypted());
+            Ok(())
+        }
+    }
+
+    fn pinned(identity: &NodeIdentity) -> PinnedPeer {
+        PinnedPeer::from_str(&format!("{}@::1", identity.public_key())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pinned_identity_handshake() {
+        waglayla_core::log::try_init_logger("debug");
+
+        let server_identity = NodeIdentity::generate();
+        let client_pin = pinned(&server_identity);
+
+        let server = TransportInitializer::new(TransportConfig::new(Some(server_identity), []));
+        let address = NetAddress::from_str("[::1]:50055").unwrap();
+        let server_adaptor = Adaptor::bidirectional(address, Hub::new(), server.clone(), Default::default()).unwrap();
+
+        let client = TransportInitializer::new(TransportConfig::new(None, [client_pin]));
+        let client_adaptor = Adaptor::client_only(Hub::new(), client.clone(), Default::default());
+
+        // The server signs the key exchange with the pinned identity, so the handshake succeeds
+        let peer_key = client_adaptor
+            .connect_peer_with_retries(String::from("[::1]:50055"), 16, Duration::from_secs(1))
+            .await
+            .expect("handshake with the pinned identity failed");
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        assert_eq!(*client.encrypted.lock().unwrap(), vec![true]);
+        assert_eq!(*server.encrypted.lock().unwrap(), vec![true]);
+        assert_eq!(server_adaptor.active_peers().len(), 1);
+
+        client_adaptor.terminate(peer_key).await;
+        client_adaptor.close().await;
+        server_adaptor.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_pinned_identity_mismatch() {
+        waglayla_core::log::try_init_logger("debug");
+
+        let pinned_identity = NodeIdentity::generate();
+        let client_pin = pinned(&pinned_identity);
+
+        // The server runs with a different identity than the one pinned by the client
+        let server = TransportInitializer::new(TransportConfig::new(Some(NodeIdentity::generate()), []));
+        let address = NetAddress::from_str("[::1]:50056").unwrap();
+        let server_adaptor = Adaptor::bidirectional(address, Hub::new(), server.clone(), Default::default()).unwrap();
+
+        let client = TransportInitializer::new(TransportConfig::new(None, [client_pin]));
+        let client_adaptor = Adaptor::client_only(Hub::new(), client.clone(), Default::default());
+
+        let result = client_adaptor.connect_peer_with_retries(String::from("[::1]:50056"), 16, Duration::from_secs(1)).await;
+        match result {
+            Err(ConnectionError::ProtocolError(ProtocolError::TransportError(TransportError::PinnedKeyMismatch(key)))) => {
+                assert_eq!(key, pinned_identity.public_key().to_string())
+            }
+            other => panic!("expected a pinned key mismatch, got {other:?}"),
+        }
+        assert!(client.encrypted.lock().unwrap().is_empty());
+        assert!(client_adaptor.active_peers().is_empty());
+
+        client_adaptor.close().await;
+        server_adaptor.close().await;
     }
 }
@@ -3,9 +3,11 @@ pub mod pb {
     tonic::include_proto!("protowire");
 }
 
+pub mod capture;
 pub mod common;
 pub mod convert;
 pub mod echo;
+pub mod replay;
 
 mod core;
 mod handshake;
@@ -14,6 +16,8 @@ pub use crate::core::adaptor::{Adaptor, ConnectionInitializer};
 pub use crate::core::connection_handler::ConnectionError;
 pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::WaglayladMessagePayloadType;
-pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
+pub use crate::core::peer::{Peer, PeerKey, PeerProperties, PeerRelayMode};
 pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
+pub use crate::core::traffic::{HubTraffic, MessageTypeTraffic, TrafficCounters, TrafficEntry, TrafficSnapshot, UploadBudget};
+pub use crate::core::transport::{NodeIdentity, PinnedPeer, TransportConfig, TransportError, ENCRYPTED_TRANSPORT_PROTOCOL_VERSION};
 pub use handshake::WaglayladHandshake;
@@ -0,0 +1,216 @@
+//! Deterministic replay of a P2P capture (see [`crate::capture`]) against a live node.
+//!
+//! The replayer connects to the target node as a fake peer and plays the role of the *remote* peer of the capture:
+//! it sends the captured inbound messages in their original order, and before sending each one it waits until the
+//! node has sent as many messages as the capturing node had sent at that point. This reproduces the original
+//! request/response interleaving regardless of timing, so that flows (e.g. IBD) can be exercised offline.
+//!
+//! Since some fields are inherently session specific, the replayer rewrites them on the fly:
+//! - response ids of replayed messages are mapped to the request ids actually used by the node
+//! - pong nonces are replaced with the nonce of the last ping received from the node
+//! - the version message timestamp is set to the current time
+//!
+//! Captures hold messages in their decrypted form. The replayer does not implement the encrypted transport, so it
+//! advertises a pre-encryption protocol version and skips the captured key exchange messages in both directions.
+
+use crate::capture::{Capture, Direction};
+use crate::pb::{p2p_client::P2pClient as ProtoP2pClient, waglaylad_message::Payload, WaglayladMessage};
+use crate::core::transport::ENCRYPTED_TRANSPORT_PROTOCOL_VERSION;
+use crate::WaglayladMessagePayloadType;
+use waglayla_core::{debug, info, time::unix_now, warn};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::mpsc::channel as mpsc_channel;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Streaming;
+
+/// Maximum decoded gRPC message size accepted from the target node
+const REPLAY_MAX_MESSAGE_SIZE: usize = 1024 * 1024 * 1024; // 1GB
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("{0}")]
+    TonicError(#[from] tonic::transport::Error),
+
+    #[error("{0}")]
+    TonicStatus(#[from] tonic::Status),
+
+    #[error("connection to the target node was closed after {0} received messages")]
+    ConnectionClosed(usize),
+
+    #[error("timed out after {0:?} waiting for message #{1} from the target node")]
+    Timeout(Duration, usize),
+
+    #[error("message #{index} from the target node diverges from the capture: expected {expected:?}, got {actual:?}")]
+    Divergence { index: usize, expected: Option<WaglayladMessagePayloadType>, actual: Option<WaglayladMessagePayloadType> },
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// Maximum time to wait for each expected message from the target node
+    pub step_timeout: Duration,
+
+    /// Fail on the first message from the target node whose type differs from the captured one.
+    /// When false, divergences are only counted and logged.
+    pub strict: bool,
+
+    /// Preserve the original inter-message delays of the capture (in addition to the message dependencies)
+    pub realtime: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self { step_timeout: Duration::from_secs(30), strict: false, realtime: false }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ReplaySummary {
+    /// Number of captured messages sent to the target node
+    pub sent: usize,
+    /// Number of messages received from the target node
+    pub received: usize,
+    /// Number of received messages whose type differs from the captured one
+    pub divergences: usize,
+}
+
+/// Replays captured traffic against a target node
+pub struct Replayer {
+    capture: Capture,
+    options: ReplayOptions,
+}
+
+impl Replayer {
+    pub fn new(capture: Capture, options: ReplayOptions) -> Self {
+        Self { capture, options }
+    }
+
+    /// Connects to the node at `target_address` (`ip:port`) and replays the capture
+    pub async fn run(&self, target_address: &str) -> Result<ReplaySummary, ReplayError> {
+        let channel = tonic::transport::Endpoint::new(format!("http://{}", target_address))?.connect().await?;
+        let mut client = ProtoP2pClient::new(channel)
+            .send_compressed(tonic::codec::CompressionEncoding::Gzip)
+            .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+            .max_decoding_message_size(REPLAY_MAX_MESSAGE_SIZE);
+
+        let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_channel_size());
+        let incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
+        let mut session = ReplaySession::new(&self.capture, &self.options, incoming_stream);
+        let started = Instant::now();
+
+        for record in self.capture.records.iter().filter(|r| !is_key_exchange(&r.message)) {
+            match record.direction {
+                Direction::Outbound => session.expected_outbound += 1,
+                Direction::Inbound => {
+                    session.wait_for_expected().await?;
+                    if self.options.realtime {
+                        let due = Duration::from_micros(record.elapsed_micros);
+                        tokio::time::sleep(due.saturating_sub(started.elapsed())).await;
+                    }
+                    let msg = session.rewrite(record.message.clone());
+                    if outgoing_route.send(msg).await.is_err() {
+                        return Err(ReplayError::ConnectionClosed(session.summary.received));
+                    }
+                    session.summary.sent += 1;
+                }
+            }
+        }
+        // Wait for the node to send the remaining captured messages
+        session.wait_for_expected().await?;
+
+        info!(
+            "P2P replay finished: sent {}, received {}, divergences {}",
+            session.summary.sent, session.summary.received, session.summary.divergences
+        );
+        Ok(session.summary)
+    }
+
+    fn outgoing_channel_size() -> usize {
+        1 << 10
+    }
+}
+
+struct ReplaySession<'a> {
+    options: &'a ReplayOptions,
+    /// Captured messages sent by the capturing node, in order
+    captured_outbound: Vec<&'a WaglayladMessage>,
+    incoming_stream: Streaming<WaglayladMessage>,
+    /// Number of messages the node is expected to have sent so far
+    expected_outbound: usize,
+    /// Maps request ids of the capturing node to request ids used by the target node
+    request_ids: HashMap<u32, u32>,
+    last_ping_nonce: Option<u64>,
+    summary: ReplaySummary,
+}
+
+impl<'a> ReplaySession<'a> {
+    fn new(capture: &'a Capture, options: &'a ReplayOptions, incoming_stream: Streaming<WaglayladMessage>) -> Self {
+        Self {
+            options,
+            captured_outbound: capture.outbound().map(|r| &r.message).filter(|msg| !is_key_exchange(msg)).collect(),
+            incoming_stream,
+            expected_outbound: 0,
+            request_ids: HashMap::new(),
+            last_ping_nonce: None,
+            summary: Default::default(),
+        }
+    }
+
+    async fn wait_for_expected(&mut self) -> Result<(), ReplayError> {
+        while self.summary.received < self.expected_outbound {
+            let index = self.summary.received;
+            let msg = match tokio::time::timeout(self.options.step_timeout, self.incoming_stream.message()).await {
+                Ok(res) => res?.ok_or(ReplayError::ConnectionClosed(index))?,
+                Err(_) => return Err(ReplayError::Timeout(self.options.step_timeout, index)),
+            };
+            self.on_received(index, msg)?;
+        }
+        Ok(())
+    }
+
+    fn on_received(&mut self, index: usize, msg: WaglayladMessage) -> Result<(), ReplayError> {
+        self.summary.received += 1;
+        let captured = self.captured_outbound[index];
+        let expected = captured.payload.as_ref().map(WaglayladMessagePayloadType::from);
+        let actual = msg.payload.as_ref().map(WaglayladMessagePayloadType::from);
+        debug!("P2P replay, received message #{}: {:?}", index, actual);
+        if expected != actual {
+            if self.options.strict {
+                return Err(ReplayError::Divergence { index, expected, actual });
+            }
+            warn!("P2P replay, message #{} diverges from the capture: expected {:?}, got {:?}", index, expected, actual);
+            self.summary.divergences += 1;
+        }
+        if captured.request_id != 0 && msg.request_id != 0 {
+            self.request_ids.insert(captured.request_id, msg.request_id);
+        }
+        if let Some(Payload::Ping(ping)) = msg.payload.as_ref() {
+            self.last_ping_nonce = Some(ping.nonce);
+        }
+        Ok(())
+    }
+
+    fn rewrite(&self, mut msg: WaglayladMessage) -> WaglayladMessage {
+        if let Some(&request_id) = self.request_ids.get(&msg.response_id) {
+            msg.response_id = request_id;
+        }
+        match msg.payload.as_mut() {
+            Some(Payload::Pong(pong)) => {
+                if let Some(nonce) = self.last_ping_nonce {
+                    pong.nonce = nonce;
+                }
+            }
+            Some(Payload::Version(version)) => {
+                version.timestamp = unix_now() as i64;
+                version.protocol_version = version.protocol_version.min(ENCRYPTED_TRANSPORT_PROTOCOL_VERSION - 1);
+            }
+            _ => {}
+        }
+        msg
+    }
+}
+
+fn is_key_exchange(msg: &WaglayladMessage) -> bool {
+    matches!(msg.payload, Some(Payload::KeyExchange(_)))
+}
@@ -44,6 +44,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "ChainReorg notification: {} removed chain blocks, DAA score span {}", "_0.depth", "_0.daa_score_span")]
+    ChainReorg(ChainReorgNotification),
 }
 }
 
@@ -60,6 +63,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::ChainReorg(v) => to_value(&v),
         }
     }
 }
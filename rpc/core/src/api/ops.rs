@@ -86,6 +86,20 @@ pub enum RpcApiOps {
     GetCoinSupply,
     /// Get DAA Score timestamp estimate
     GetDaaScoreTimestampEstimate,
+    /// Export the address manager's known address records
+    ExportAddressBook,
+    /// Import address records into the address manager
+    ImportAddressBook,
+    /// Get a receipt proving a transaction is included in a block and accepted by the selected chain
+    GetTransactionInclusionProof,
+    /// Get the UTXOs of a list of addresses as they were at a past selected chain block (archival nodes only)
+    GetUtxoSetAtBlock,
+    /// Get the balances of a list of addresses as they were at a past DAA score (archival nodes only)
+    GetBalanceAtDaaScore,
+    /// Export a range of the DAG along with GHOSTDAG, status and acceptance data
+    GetDagExport,
+    /// Get the pruning point proof of the node, as sent to syncing peers
+    GetPruningPointProof,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -97,6 +111,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
     NotifySinkBlueScoreChanged,
+    NotifyChainReorg,
 
     // ~
     Subscribe,
@@ -114,6 +129,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+    ChainReorgNotification,
 }
 
 impl RpcApiOps {
@@ -129,6 +145,7 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyChainReorg
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -155,6 +172,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::ChainReorg => RpcApiOps::ChainReorgNotification,
         }
     }
 }
@@ -304,6 +304,66 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse>;
 
+    /// Exports all the address records known to the node's address manager.
+    async fn export_address_book(&self) -> RpcResult<Vec<RpcAddressRecord>> {
+        Ok(self.export_address_book_call(ExportAddressBookRequest {}).await?.records)
+    }
+    async fn export_address_book_call(&self, request: ExportAddressBookRequest) -> RpcResult<ExportAddressBookResponse>;
+
+    /// Imports address records into the node's address manager and returns the number of addresses added.
+    /// Requires the node to run with `--unsaferpc`.
+    async fn import_address_book(&self, records: Vec<RpcAddressRecord>) -> RpcResult<u64> {
+        Ok(self.import_address_book_call(ImportAddressBookRequest { records }).await?.imported)
+    }
+    async fn import_address_book_call(&self, request: ImportAddressBookRequest) -> RpcResult<ImportAddressBookResponse>;
+
+    /// Requests a receipt proving the transaction is included in a block, along with the header chain from that block
+    /// up to a selected chain block. If `block_hash` is not provided, recent selected chain blocks are searched for the
+    /// transaction.
+    async fn get_transaction_inclusion_proof(
+        &self,
+        transaction_id: RpcTransactionId,
+        block_hash: Option<RpcHash>,
+    ) -> RpcResult<GetTransactionInclusionProofResponse> {
+        self.get_transaction_inclusion_proof_call(GetTransactionInclusionProofRequest::new(transaction_id, block_hash)).await
+    }
+    async fn get_transaction_inclusion_proof_call(
+        &self,
+        request: GetTransactionInclusionProofRequest,
+    ) -> RpcResult<GetTransactionInclusionProofResponse>;
+
+    /// Requests the UTXOs of the given addresses as they were at a past selected chain block.
+    /// Only available on archival nodes.
+    async fn get_utxo_set_at_block(
+        &self,
+        addresses: Vec<RpcAddress>,
+        block_hash: RpcHash,
+    ) -> RpcResult<Vec<RpcUtxosByAddressesEntry>> {
+        Ok(self.get_utxo_set_at_block_call(GetUtxoSetAtBlockRequest::new(addresses, block_hash)).await?.entries)
+    }
+    async fn get_utxo_set_at_block_call(&self, request: GetUtxoSetAtBlockRequest) -> RpcResult<GetUtxoSetAtBlockResponse>;
+
+    /// Requests the balances of the given addresses as they were at the last selected chain block
+    /// with a DAA score lower or equal to `daa_score`. Only available on archival nodes.
+    async fn get_balance_at_daa_score(&self, addresses: Vec<RpcAddress>, daa_score: u64) -> RpcResult<GetBalanceAtDaaScoreResponse> {
+        self.get_balance_at_daa_score_call(GetBalanceAtDaaScoreRequest::new(addresses, daa_score)).await
+    }
+    async fn get_balance_at_daa_score_call(&self, request: GetBalanceAtDaaScoreRequest) -> RpcResult<GetBalanceAtDaaScoreResponse>;
+
+    /// Exports the selected chain blocks with a DAA score (or blue score) within `[start, end]`, along with the
+    /// blocks they merge, their GHOSTDAG data, statuses and acceptance data.
+    async fn get_dag_export(&self, start: u64, end: u64, by_blue_score: bool) -> RpcResult<Vec<RpcDagExportBlock>> {
+        Ok(self.get_dag_export_call(GetDagExportRequest::new(start, end, by_blue_score)).await?.blocks)
+    }
+    async fn get_dag_export_call(&self, request: GetDagExportRequest) -> RpcResult<GetDagExportResponse>;
+
+    /// Requests the proof of the current pruning point, as sent to syncing peers. The proof is empty while the
+    /// pruning point is genesis.
+    async fn get_pruning_point_proof(&self) -> RpcResult<Vec<Vec<RpcHeader>>> {
+        Ok(self.get_pruning_point_proof_call(GetPruningPointProofRequest {}).await?.levels)
+    }
+    async fn get_pruning_point_proof_call(&self, request: GetPruningPointProofRequest) -> RpcResult<GetPruningPointProofResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -0,0 +1,50 @@
+use crate::{GetTransactionInclusionProofResponse, RpcError, RpcMerkleWitness, RpcResult};
+use waglayla_consensus_core::{
+    merkle::{TransactionInclusionProof, TransactionReceipt},
+    tx::Transaction,
+};
+use waglayla_merkle::MerkleWitness;
+
+// ----------------------------------------------------------------------------
+// consensus_core to rpc_core
+// ----------------------------------------------------------------------------
+
+impl From<&MerkleWitness> for RpcMerkleWitness {
+    fn from(item: &MerkleWitness) -> Self {
+        Self { index: item.index, siblings: item.siblings.clone() }
+    }
+}
+
+impl From<&TransactionReceipt> for GetTransactionInclusionProofResponse {
+    fn from(item: &TransactionReceipt) -> Self {
+        Self {
+            transaction: (&item.inclusion.transaction).into(),
+            block_hash: item.inclusion.block_hash,
+            merkle_witness: (&item.inclusion.witness).into(),
+            headers: item.headers.clone(),
+            acceptance_witness: item.acceptance_witness.as_ref().map(RpcMerkleWitness::from),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// rpc_core to consensus_core
+// ----------------------------------------------------------------------------
+
+impl From<&RpcMerkleWitness> for MerkleWitness {
+    fn from(item: &RpcMerkleWitness) -> Self {
+        Self::new(item.index, item.siblings.clone())
+    }
+}
+
+impl TryFrom<&GetTransactionInclusionProofResponse> for TransactionReceipt {
+    type Error = RpcError;
+    fn try_from(item: &GetTransactionInclusionProofResponse) -> RpcResult<Self> {
+        let inclusion = TransactionInclusionProof {
+            block_hash: item.block_hash,
+            transaction: Transaction::try_from(&item.transaction)?,
+            witness: (&item.merkle_witness).into(),
+        };
+        Ok(Self::new(inclusion, item.headers.clone(), item.acceptance_witness.as_ref().map(MerkleWitness::from)))
+    }
+}
@@ -1,4 +1,5 @@
 pub mod block;
+pub mod merkle;
 pub mod notification;
 pub mod scope;
 pub mod tx;
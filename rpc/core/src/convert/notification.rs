@@ -1,7 +1,8 @@
 use crate::{
-    convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
-    NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
-    SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+    convert::utxo::utxo_set_into_rpc, BlockAddedNotification, ChainReorgNotification, FinalityConflictNotification,
+    FinalityConflictResolvedNotification, NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification,
+    RpcAcceptedTransactionIds, SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification,
+    VirtualDaaScoreChangedNotification,
 };
 use waglayla_consensus_notify::notification as consensus_notify;
 use waglayla_index_core::notification as index_notify;
@@ -29,6 +30,7 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::ChainReorg(msg) => Notification::ChainReorg(msg.into()),
         }
     }
 }
@@ -110,6 +112,19 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<&consensus_notify::ChainReorgNotification> for ChainReorgNotification {
+    fn from(item: &consensus_notify::ChainReorgNotification) -> Self {
+        Self {
+            previous_sink: item.previous_sink,
+            new_sink: item.new_sink,
+            common_ancestor: item.common_ancestor,
+            depth: item.depth,
+            daa_score_span: item.daa_score_span,
+            unaccepted_transaction_count: item.unaccepted_transaction_count,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
@@ -64,6 +64,9 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --archival argument.")]
+    NotArchival,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -76,9 +79,15 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method unavailable on a light node, which keeps neither block bodies nor a UTXO set.")]
+    UnavailableInLightMode,
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
+    #[error("Cannot ban IP {0} because it is whitelisted.")]
+    IpIsWhitelisted(IpAddress),
+
     #[error("IP {0} is not registered as banned.")]
     IpIsNotBanned(IpAddress),
 
@@ -0,0 +1,2 @@
+pub type RpcDagExportBlock = waglayla_consensus_core::dag_export::DagExportBlock;
+pub type RpcDagExportAcceptance = waglayla_consensus_core::dag_export::DagExportAcceptance;
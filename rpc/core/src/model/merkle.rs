@@ -0,0 +1,30 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+use pyo3::pyclass;
+
+use crate::prelude::RpcHash;
+
+/// The sibling hashes on the path from a leaf to the merkle root, ordered from the leaf level up
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcMerkleWitness {
+    /// The position of the leaf amongst the tree leaves
+    #[pyo3(get)]
+    pub index: u32,
+    #[pyo3(get)]
+    pub siblings: Vec<RpcHash>,
+}
+
+/// The sibling hashes on the path from a leaf to the merkle root, ordered from the leaf level up
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcMerkleWitness {
+    /// The position of the leaf amongst the tree leaves
+    pub index: u32,
+    pub siblings: Vec<RpcHash>,
+}
@@ -1,6 +1,6 @@
 use crate::model::*;
 use borsh::{BorshDeserialize, BorshSerialize};
-use waglayla_consensus_core::api::stats::BlockCount;
+use waglayla_consensus_core::{api::stats::BlockCount, dag_export::DagExportRange};
 use waglayla_core::debug;
 use waglayla_notify::subscription::{context::SubscriptionContext, single::UtxosChangedSubscription, Command};
 use serde::{Deserialize, Serialize};
@@ -961,6 +961,16 @@ pub struct BandwidthMetrics {
     pub grpc_bytes_tx: u64,
     #[pyo3(get)]
     pub grpc_bytes_rx: u64,
+
+    /// P2P traffic of all peers since startup, by message type
+    #[pyo3(get)]
+    pub p2p_traffic: Vec<RpcP2pMessageTraffic>,
+    /// Configured P2P upload budget for bulk (IBD-serving) traffic in bytes/sec, 0 if unlimited
+    #[pyo3(get)]
+    pub p2p_upload_limit: u64,
+    /// Total bulk P2P bytes which had to wait for upload budget
+    #[pyo3(get)]
+    pub p2p_throttled_bytes: u64,
 }
 
 #[cfg(target_family = "wasm")]
@@ -975,6 +985,10 @@ pub struct BandwidthMetrics {
     pub p2p_bytes_rx: u64,
     pub grpc_bytes_tx: u64,
     pub grpc_bytes_rx: u64,
+
+    pub p2p_traffic: Vec<RpcP2pMessageTraffic>,
+    pub p2p_upload_limit: u64,
+    pub p2p_throttled_bytes: u64,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -1163,6 +1177,193 @@ impl GetDaaScoreTimestampEstimateResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAddressBookRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAddressBookResponse {
+    pub records: Vec<RpcAddressRecord>,
+}
+
+impl ExportAddressBookResponse {
+    pub fn new(records: Vec<RpcAddressRecord>) -> Self {
+        Self { records }
+    }
+}
+
+/// Imports address records, typically exported from another node, in order to seed the address manager without DNS.
+/// Addresses which are already known, banned or local are skipped.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAddressBookRequest {
+    pub records: Vec<RpcAddressRecord>,
+}
+
+impl ImportAddressBookRequest {
+    pub fn new(records: Vec<RpcAddressRecord>) -> Self {
+        Self { records }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAddressBookResponse {
+    /// Number of records actually added to the address manager
+    pub imported: u64,
+}
+
+impl ImportAddressBookResponse {
+    pub fn new(imported: u64) -> Self {
+        Self { imported }
+    }
+}
+
+/// Requests a receipt proving a transaction is included in a block and, when possible, accepted by a selected chain block.
+/// If `block_hash` is not specified, the acceptance data of recent selected chain blocks is searched for the transaction.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionInclusionProofRequest {
+    pub transaction_id: RpcTransactionId,
+    /// The hash of the block containing the transaction
+    pub block_hash: Option<RpcHash>,
+}
+
+impl GetTransactionInclusionProofRequest {
+    pub fn new(transaction_id: RpcTransactionId, block_hash: Option<RpcHash>) -> Self {
+        Self { transaction_id, block_hash }
+    }
+}
+
+/// A transaction receipt, verifiable with no access to the node by converting it into a
+/// [`TransactionReceipt`](waglayla_consensus_core::merkle::TransactionReceipt)
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionInclusionProofResponse {
+    pub transaction: RpcTransaction,
+    /// The hash of the block containing the transaction
+    pub block_hash: RpcHash,
+    /// Proves the transaction is committed to by the `hash_merkle_root` of the containing block
+    pub merkle_witness: RpcMerkleWitness,
+    /// Headers from the containing block up to a selected chain block, each a direct parent of the next
+    pub headers: Vec<RpcHeader>,
+    /// Proves the transaction is committed to by the `accepted_id_merkle_root` of the last header. Missing if the
+    /// accepting chain block is unknown, e.g. when served by a light node
+    pub acceptance_witness: Option<RpcMerkleWitness>,
+}
+
+/// Requests the UTXOs of the given addresses as they were in the UTXO set of a past selected chain block,
+/// that is, after the block accepted its mergeset. Only available on archival nodes.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUtxoSetAtBlockRequest {
+    pub addresses: Vec<RpcAddress>,
+    /// The hash of a selected chain block
+    pub block_hash: RpcHash,
+}
+
+impl GetUtxoSetAtBlockRequest {
+    pub fn new(addresses: Vec<RpcAddress>, block_hash: RpcHash) -> Self {
+        Self { addresses, block_hash }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUtxoSetAtBlockResponse {
+    pub entries: Vec<RpcUtxosByAddressesEntry>,
+}
+
+impl GetUtxoSetAtBlockResponse {
+    pub fn new(entries: Vec<RpcUtxosByAddressesEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+/// Requests the balances of the given addresses as they were at the last selected chain block
+/// with a DAA score lower or equal to `daa_score`.
+/// Only available on archival nodes.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalanceAtDaaScoreRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub daa_score: u64,
+}
+
+impl GetBalanceAtDaaScoreRequest {
+    pub fn new(addresses: Vec<RpcAddress>, daa_score: u64) -> Self {
+        Self { addresses, daa_score }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalanceAtDaaScoreResponse {
+    /// The hash of the selected chain block the balances were computed at
+    pub block_hash: RpcHash,
+    pub entries: Vec<RpcBalancesByAddressesEntry>,
+}
+
+impl GetBalanceAtDaaScoreResponse {
+    pub fn new(block_hash: RpcHash, entries: Vec<RpcBalancesByAddressesEntry>) -> Self {
+        Self { block_hash, entries }
+    }
+}
+
+/// Requests the selected chain blocks with a DAA score (or blue score if `by_blue_score` is set) within
+/// `[start, end]`, along with the blocks they merge. Merged blocks might have scores outside the range.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDagExportRequest {
+    pub start: u64,
+    pub end: u64,
+    pub by_blue_score: bool,
+}
+
+impl GetDagExportRequest {
+    pub fn new(start: u64, end: u64, by_blue_score: bool) -> Self {
+        Self { start, end, by_blue_score }
+    }
+
+    pub fn range(&self) -> DagExportRange {
+        match self.by_blue_score {
+            true => DagExportRange::BlueScore { start: self.start, end: self.end },
+            false => DagExportRange::DaaScore { start: self.start, end: self.end },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDagExportResponse {
+    /// Blocks in consensus order, each merged block preceding the chain block merging it
+    pub blocks: Vec<RpcDagExportBlock>,
+}
+
+impl GetDagExportResponse {
+    pub fn new(blocks: Vec<RpcDagExportBlock>) -> Self {
+        Self { blocks }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPruningPointProofRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPruningPointProofResponse {
+    /// Proof headers by block level, starting at level 0. Empty while the pruning point is genesis
+    pub levels: Vec<Vec<RpcHeader>>,
+}
+
+impl GetPruningPointProofResponse {
+    pub fn new(levels: Vec<Vec<RpcHeader>>) -> Self {
+        Self { levels }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -1471,6 +1672,47 @@ pub struct NotifyNewBlockTemplateResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ChainReorgNotification
+
+/// NotifyChainReorgRequest registers this connection for chainReorg notifications.
+///
+/// See: ChainReorgNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyChainReorgRequest {
+    pub command: Command,
+}
+
+impl NotifyChainReorgRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyChainReorgResponse {}
+
+/// ChainReorgNotification is sent whenever a virtual change removes at least the
+/// number of chain blocks configured on the node (see `--reorg-notify-depth`).
+///
+/// See: NotifyChainReorgRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainReorgNotification {
+    pub previous_sink: RpcHash,
+    pub new_sink: RpcHash,
+    /// The last chain block common to the previous and new selected chains
+    pub common_ancestor: RpcHash,
+    /// Number of removed chain blocks
+    pub depth: u64,
+    /// DAA score difference between the previous sink and the common ancestor
+    pub daa_score_span: u64,
+    /// Number of transactions accepted by the removed chain blocks which are not re-accepted by the added ones
+    pub unaccepted_transaction_count: u64,
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -1,10 +1,12 @@
 pub mod address;
 pub mod block;
 pub mod blue_work;
+pub mod dag_export;
 pub mod hash;
 pub mod header;
 pub mod hex_cnv;
 pub mod mempool;
+pub mod merkle;
 pub mod message;
 pub mod network;
 pub mod peer;
@@ -15,10 +17,12 @@ pub mod tx;
 pub use address::*;
 pub use block::*;
 pub use blue_work::*;
+pub use dag_export::*;
 pub use hash::*;
 pub use header::*;
 pub use hex_cnv::*;
 pub use mempool::*;
+pub use merkle::*;
 pub use message::*;
 pub use network::*;
 pub use peer::*;
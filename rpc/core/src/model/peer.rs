@@ -34,6 +34,18 @@ pub struct RpcPeerInfo {
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     #[pyo3(get)]
     pub is_ibd_peer: bool,
+
+    #[pyo3(get)]
+    pub messages_sent: u64,
+    #[pyo3(get)]
+    pub bytes_sent: u64,
+    #[pyo3(get)]
+    pub messages_received: u64,
+    #[pyo3(get)]
+    pub bytes_received: u64,
+    /// Traffic breakdown by P2P message type
+    #[pyo3(get)]
+    pub traffic: Vec<RpcP2pMessageTraffic>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -50,4 +62,90 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+    /// Traffic breakdown by P2P message type
+    pub traffic: Vec<RpcP2pMessageTraffic>,
+}
+
+/// P2P traffic of a single message type. Byte counts are of encoded, pre-compression messages.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcP2pMessageTraffic {
+    #[pyo3(get)]
+    pub message_type: String,
+    #[pyo3(get)]
+    pub messages_sent: u64,
+    #[pyo3(get)]
+    pub bytes_sent: u64,
+    #[pyo3(get)]
+    pub messages_received: u64,
+    #[pyo3(get)]
+    pub bytes_received: u64,
+}
+
+/// P2P traffic of a single message type. Byte counts are of encoded, pre-compression messages.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcP2pMessageTraffic {
+    pub message_type: String,
+    pub messages_sent: u64,
+    pub bytes_sent: u64,
+    pub messages_received: u64,
+    pub bytes_received: u64,
+}
+
+/// A known peer address along with its connection quality history. Timestamps are unix milliseconds, where 0 stands for never.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcAddressRecord {
+    #[pyo3(get)]
+    pub address: RpcPeerAddress,
+    #[pyo3(get)]
+    pub connection_failed_count: u64,
+    #[pyo3(get)]
+    pub last_seen: u64,
+    #[pyo3(get)]
+    pub last_attempt: u64,
+    #[pyo3(get)]
+    pub last_success: u64,
+    /// Last measured ping round-trip time, in milliseconds
+    #[pyo3(get)]
+    pub latency: Option<u64>,
+    #[pyo3(get)]
+    pub protocol_version: u32,
+    #[pyo3(get)]
+    pub services: u64,
+    #[pyo3(get)]
+    pub user_agent: String,
+    /// The peer which advertised this address, if any
+    #[pyo3(get)]
+    pub source: Option<RpcPeerAddress>,
+}
+
+/// A known peer address along with its connection quality history. Timestamps are unix milliseconds, where 0 stands for never.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcAddressRecord {
+    pub address: RpcPeerAddress,
+    pub connection_failed_count: u64,
+    pub last_seen: u64,
+    pub last_attempt: u64,
+    pub last_success: u64,
+    /// Last measured ping round-trip time, in milliseconds
+    pub latency: Option<u64>,
+    pub protocol_version: u32,
+    pub services: u64,
+    pub user_agent: String,
+    /// The peer which advertised this address, if any
+    pub source: Option<RpcPeerAddress>,
 }
@@ -40,6 +40,28 @@ const TS_ACCEPTED_TRANSACTION_IDS: &'static str = r#"
     }
 "#;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ADDRESS_RECORD: &'static str = r#"
+    /**
+     * A known peer address along with its connection quality history.
+     * Timestamps are unix milliseconds, where 0 stands for never.
+     * 
+     * @category Node RPC
+     */
+    export interface IAddressRecord {
+        address : { ip : string, port : number };
+        connectionFailedCount : bigint;
+        lastSeen : bigint;
+        lastAttempt : bigint;
+        lastSuccess : bigint;
+        latency? : bigint;
+        protocolVersion : number;
+        services : bigint;
+        userAgent : string;
+        source? : { ip : string, port : number };
+    }
+"#;
+
 // ---
 
 declare! {
@@ -862,6 +884,297 @@ try_from! ( args: GetDaaScoreTimestampEstimateResponse, IGetDaaScoreTimestampEst
 
 // ---
 
+declare! {
+    IExportAddressBookRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IExportAddressBookRequest { }
+    "#,
+}
+
+try_from! ( args: IExportAddressBookRequest, ExportAddressBookRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IExportAddressBookResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IExportAddressBookResponse {
+        records : IAddressRecord[];
+    }
+    "#,
+}
+
+try_from! ( args: ExportAddressBookResponse, IExportAddressBookResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IImportAddressBookRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IImportAddressBookRequest {
+        records : IAddressRecord[];
+    }
+    "#,
+}
+
+try_from! ( args: IImportAddressBookRequest, ImportAddressBookRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IImportAddressBookResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IImportAddressBookResponse {
+        imported : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: ImportAddressBookResponse, IImportAddressBookResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionInclusionProofRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetTransactionInclusionProofRequest {
+        transactionId : HexString;
+        /**
+         * The hash of the block containing the transaction. If not specified,
+         * recent selected chain blocks are searched for the transaction.
+         */
+        blockHash? : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionInclusionProofRequest, GetTransactionInclusionProofRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionInclusionProofResponse,
+    r#"
+    /**
+     * A transaction receipt which can be verified using {@link verifyTransactionReceipt}.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionInclusionProofResponse extends ITransactionReceipt { }
+    "#,
+}
+
+try_from! ( args: GetTransactionInclusionProofResponse, IGetTransactionInclusionProofResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetUtxoSetAtBlockRequest,
+    r#"
+    /**
+     * Only available on archival nodes.
+     *
+     * @category Node RPC
+     */
+    export interface IGetUtxoSetAtBlockRequest {
+        addresses : Address[] | string[];
+        /**
+         * The hash of a selected chain block
+         */
+        blockHash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetUtxoSetAtBlockRequest, GetUtxoSetAtBlockRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetUtxoSetAtBlockResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetUtxoSetAtBlockResponse {
+        entries : IUtxoEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: GetUtxoSetAtBlockResponse, IGetUtxoSetAtBlockResponse, {
+    let GetUtxoSetAtBlockResponse { entries } = args;
+    let entries = entries.into_iter().map(UtxoEntryReference::from).collect::<Vec<UtxoEntryReference>>();
+    let entries = js_sys::Array::from_iter(entries.into_iter().map(JsValue::from));
+    let response = IGetUtxoSetAtBlockResponse::default();
+    response.set("entries", entries.as_ref())?;
+    Ok(response)
+});
+
+// ---
+
+declare! {
+    IGetBalanceAtDaaScoreRequest,
+    r#"
+    /**
+     * Only available on archival nodes.
+     *
+     * @category Node RPC
+     */
+    export interface IGetBalanceAtDaaScoreRequest {
+        addresses : Address[] | string[];
+        daaScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IGetBalanceAtDaaScoreRequest, GetBalanceAtDaaScoreRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetBalanceAtDaaScoreResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetBalanceAtDaaScoreResponse {
+        /**
+         * The hash of the selected chain block the balances were computed at
+         */
+        blockHash : HexString;
+        entries : IBalancesByAddressesEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: GetBalanceAtDaaScoreResponse, IGetBalanceAtDaaScoreResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetDagExportRequest,
+    r#"
+    /**
+     * Selected chain blocks with a DAA score (or blue score if `byBlueScore` is set)
+     * within `[start, end]` are exported along with the blocks they merge.
+     *
+     * @category Node RPC
+     */
+    export interface IGetDagExportRequest {
+        start : bigint;
+        end : bigint;
+        byBlueScore : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetDagExportRequest, GetDagExportRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetDagExportResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IDagExportAcceptance {
+        blockHash : HexString;
+        acceptedTransactionIds : HexString[];
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IDagExportBlock {
+        hash : HexString;
+        parents : HexString[];
+        daaScore : bigint;
+        blueScore : bigint;
+        blueWork : HexString;
+        selectedParent : HexString;
+        mergesetBlues : HexString[];
+        mergesetReds : HexString[];
+        status : string;
+        isChainBlock : boolean;
+        acceptance? : IDagExportAcceptance[];
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IGetDagExportResponse {
+        /**
+         * Blocks in consensus order, each merged block preceding the chain block merging it
+         */
+        blocks : IDagExportBlock[];
+    }
+    "#,
+}
+
+try_from! ( args: GetDagExportResponse, IGetDagExportResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetPruningPointProofRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetPruningPointProofRequest { }
+    "#,
+}
+
+try_from! ( args: IGetPruningPointProofRequest, GetPruningPointProofRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetPruningPointProofResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetPruningPointProofResponse {
+        /**
+         * Proof headers by block level, starting at level 0. Empty while the pruning point is genesis
+         */
+        levels : IHeader[][];
+    }
+    "#,
+}
+
+try_from! ( args: GetPruningPointProofResponse, IGetPruningPointProofResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetCurrentNetworkRequest,
     r#"
@@ -271,6 +271,13 @@ impl RpcApi for GrpcClient {
     route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
     route!(get_coin_supply_call, GetCoinSupply);
     route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    route!(export_address_book_call, ExportAddressBook);
+    route!(import_address_book_call, ImportAddressBook);
+    route!(get_transaction_inclusion_proof_call, GetTransactionInclusionProof);
+    route!(get_utxo_set_at_block_call, GetUtxoSetAtBlock);
+    route!(get_balance_at_daa_score_call, GetBalanceAtDaaScore);
+    route!(get_dag_export_call, GetDagExport);
+    route!(get_pruning_point_proof_call, GetPruningPointProof);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
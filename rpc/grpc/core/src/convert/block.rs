@@ -1,6 +1,6 @@
 use crate::protowire;
 use crate::{from, try_from};
-use waglayla_rpc_core::{RpcError, RpcHash};
+use waglayla_rpc_core::{FromRpcHex, RpcError, RpcHash, ToRpcHex};
 use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
@@ -30,6 +30,33 @@ from!(item: &waglayla_rpc_core::RpcBlockVerboseData, protowire::RpcBlockVerboseD
     }
 });
 
+from!(item: &waglayla_rpc_core::RpcMerkleWitness, protowire::RpcMerkleWitness, {
+    Self { index: item.index, siblings: item.siblings.iter().map(|x| x.to_string()).collect() }
+});
+
+from!(item: &waglayla_rpc_core::RpcDagExportAcceptance, protowire::RpcDagExportAcceptance, {
+    Self {
+        block_hash: item.block_hash.to_string(),
+        accepted_transaction_ids: item.accepted_transaction_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
+from!(item: &waglayla_rpc_core::RpcDagExportBlock, protowire::RpcDagExportBlock, {
+    Self {
+        hash: item.hash.to_string(),
+        parents: item.parents.iter().map(|x| x.to_string()).collect(),
+        daa_score: item.daa_score,
+        blue_score: item.blue_score,
+        blue_work: item.blue_work.to_rpc_hex(),
+        selected_parent: item.selected_parent.to_string(),
+        mergeset_blues: item.mergeset_blues.iter().map(|x| x.to_string()).collect(),
+        mergeset_reds: item.mergeset_reds.iter().map(|x| x.to_string()).collect(),
+        status: item.status.to_string(),
+        is_chain_block: item.is_chain_block,
+        acceptance: item.acceptance.iter().flatten().map(|x| x.into()).collect(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -76,3 +103,41 @@ try_from!(item: &protowire::RpcBlockVerboseData, waglayla_rpc_core::RpcBlockVerb
         is_chain_block: item.is_chain_block,
     }
 });
+
+try_from!(item: &protowire::RpcMerkleWitness, waglayla_rpc_core::RpcMerkleWitness, {
+    Self {
+        index: item.index,
+        siblings: item.siblings.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, faster_hex::Error>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcDagExportAcceptance, waglayla_rpc_core::RpcDagExportAcceptance, {
+    Self {
+        block_hash: RpcHash::from_str(&item.block_hash)?,
+        accepted_transaction_ids: item
+            .accepted_transaction_ids
+            .iter()
+            .map(|x| RpcHash::from_str(x))
+            .collect::<Result<Vec<_>, faster_hex::Error>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcDagExportBlock, waglayla_rpc_core::RpcDagExportBlock, {
+    let hashes = |hashes: &[String]| hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, faster_hex::Error>>();
+    Self {
+        hash: RpcHash::from_str(&item.hash)?,
+        parents: hashes(&item.parents)?,
+        daa_score: item.daa_score,
+        blue_score: item.blue_score,
+        blue_work: waglayla_rpc_core::RpcBlueWorkType::from_rpc_hex(&item.blue_work)?,
+        selected_parent: RpcHash::from_str(&item.selected_parent)?,
+        mergeset_blues: hashes(&item.mergeset_blues)?,
+        mergeset_reds: hashes(&item.mergeset_reds)?,
+        status: item.status.parse().map_err(RpcError::General)?,
+        is_chain_block: item.is_chain_block,
+        acceptance: match item.acceptance.is_empty() {
+            true => None,
+            false => Some(item.acceptance.iter().map(waglayla_rpc_core::RpcDagExportAcceptance::try_from).collect::<Result<Vec<_>, _>>()?),
+        },
+    }
+});
@@ -394,6 +394,68 @@ from!(item: RpcResult<&waglayla_rpc_core::GetDaaScoreTimestampEstimateResponse>,
     Self { timestamps: item.timestamps.clone(), error: None }
 });
 
+from!(&waglayla_rpc_core::ExportAddressBookRequest, protowire::ExportAddressBookRequestMessage);
+from!(item: RpcResult<&waglayla_rpc_core::ExportAddressBookResponse>, protowire::ExportAddressBookResponseMessage, {
+    Self { records: item.records.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &waglayla_rpc_core::ImportAddressBookRequest, protowire::ImportAddressBookRequestMessage, {
+    Self { records: item.records.iter().map(|x| x.into()).collect() }
+});
+from!(item: RpcResult<&waglayla_rpc_core::ImportAddressBookResponse>, protowire::ImportAddressBookResponseMessage, {
+    Self { imported: item.imported, error: None }
+});
+
+from!(item: &waglayla_rpc_core::GetTransactionInclusionProofRequest, protowire::GetTransactionInclusionProofRequestMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        block_hash: item.block_hash.map(|x| x.to_string()).unwrap_or_default(),
+    }
+});
+from!(item: RpcResult<&waglayla_rpc_core::GetTransactionInclusionProofResponse>, protowire::GetTransactionInclusionProofResponseMessage, {
+    Self {
+        transaction: Some((&item.transaction).into()),
+        block_hash: item.block_hash.to_string(),
+        merkle_witness: Some((&item.merkle_witness).into()),
+        headers: item.headers.iter().map(|x| x.into()).collect(),
+        acceptance_witness: item.acceptance_witness.as_ref().map(|x| x.into()),
+        error: None,
+    }
+});
+
+from!(item: &waglayla_rpc_core::GetUtxoSetAtBlockRequest, protowire::GetUtxoSetAtBlockRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), block_hash: item.block_hash.to_string() }
+});
+from!(item: RpcResult<&waglayla_rpc_core::GetUtxoSetAtBlockResponse>, protowire::GetUtxoSetAtBlockResponseMessage, {
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &waglayla_rpc_core::GetBalanceAtDaaScoreRequest, protowire::GetBalanceAtDaaScoreRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), daa_score: item.daa_score }
+});
+from!(item: RpcResult<&waglayla_rpc_core::GetBalanceAtDaaScoreResponse>, protowire::GetBalanceAtDaaScoreResponseMessage, {
+    Self { block_hash: item.block_hash.to_string(), entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &waglayla_rpc_core::GetDagExportRequest, protowire::GetDagExportRequestMessage, {
+    Self { start: item.start, end: item.end, by_blue_score: item.by_blue_score }
+});
+from!(item: RpcResult<&waglayla_rpc_core::GetDagExportResponse>, protowire::GetDagExportResponseMessage, {
+    Self { blocks: item.blocks.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&waglayla_rpc_core::GetPruningPointProofRequest, protowire::GetPruningPointProofRequestMessage);
+from!(item: RpcResult<&waglayla_rpc_core::GetPruningPointProofResponse>, protowire::GetPruningPointProofResponseMessage, {
+    Self {
+        levels: item
+            .levels
+            .iter()
+            .map(|level| protowire::RpcPruningPointProofLevel { headers: level.iter().map(|x| x.into()).collect() })
+            .collect(),
+        error: None,
+    }
+});
+
 from!(&waglayla_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&waglayla_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -478,6 +540,11 @@ from!(item: &waglayla_rpc_core::NotifySinkBlueScoreChangedRequest, protowire::No
 });
 from!(RpcResult<&waglayla_rpc_core::NotifySinkBlueScoreChangedResponse>, protowire::NotifySinkBlueScoreChangedResponseMessage);
 
+from!(item: &waglayla_rpc_core::NotifyChainReorgRequest, protowire::NotifyChainReorgRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&waglayla_rpc_core::NotifyChainReorgResponse>, protowire::NotifyChainReorgResponseMessage);
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -791,6 +858,87 @@ try_from!(item: &protowire::GetDaaScoreTimestampEstimateResponseMessage, RpcResu
     Self { timestamps: item.timestamps.clone() }
 });
 
+try_from!(&protowire::ExportAddressBookRequestMessage, waglayla_rpc_core::ExportAddressBookRequest);
+try_from!(item: &protowire::ExportAddressBookResponseMessage, RpcResult<waglayla_rpc_core::ExportAddressBookResponse>, {
+    Self { records: item.records.iter().map(waglayla_rpc_core::RpcAddressRecord::try_from).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::ImportAddressBookRequestMessage, waglayla_rpc_core::ImportAddressBookRequest, {
+    Self { records: item.records.iter().map(waglayla_rpc_core::RpcAddressRecord::try_from).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::ImportAddressBookResponseMessage, RpcResult<waglayla_rpc_core::ImportAddressBookResponse>, {
+    Self { imported: item.imported }
+});
+
+try_from!(item: &protowire::GetTransactionInclusionProofRequestMessage, waglayla_rpc_core::GetTransactionInclusionProofRequest, {
+    Self {
+        transaction_id: waglayla_rpc_core::RpcTransactionId::from_str(&item.transaction_id)?,
+        block_hash: if item.block_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.block_hash)?) },
+    }
+});
+try_from!(item: &protowire::GetTransactionInclusionProofResponseMessage, RpcResult<waglayla_rpc_core::GetTransactionInclusionProofResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("GetTransactionInclusionProofResponseMessage".to_string(), "transaction".to_string())
+            })?
+            .try_into()?,
+        block_hash: RpcHash::from_str(&item.block_hash)?,
+        merkle_witness: item
+            .merkle_witness
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("GetTransactionInclusionProofResponseMessage".to_string(), "merkleWitness".to_string())
+            })?
+            .try_into()?,
+        headers: item.headers.iter().map(waglayla_rpc_core::RpcHeader::try_from).collect::<Result<Vec<_>, _>>()?,
+        acceptance_witness: item.acceptance_witness.as_ref().map(waglayla_rpc_core::RpcMerkleWitness::try_from).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::GetUtxoSetAtBlockRequestMessage, waglayla_rpc_core::GetUtxoSetAtBlockRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        block_hash: RpcHash::from_str(&item.block_hash)?,
+    }
+});
+try_from!(item: &protowire::GetUtxoSetAtBlockResponseMessage, RpcResult<waglayla_rpc_core::GetUtxoSetAtBlockResponse>, {
+    Self { entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetBalanceAtDaaScoreRequestMessage, waglayla_rpc_core::GetBalanceAtDaaScoreRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        daa_score: item.daa_score,
+    }
+});
+try_from!(item: &protowire::GetBalanceAtDaaScoreResponseMessage, RpcResult<waglayla_rpc_core::GetBalanceAtDaaScoreResponse>, {
+    Self {
+        block_hash: RpcHash::from_str(&item.block_hash)?,
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetDagExportRequestMessage, waglayla_rpc_core::GetDagExportRequest, {
+    Self { start: item.start, end: item.end, by_blue_score: item.by_blue_score }
+});
+try_from!(item: &protowire::GetDagExportResponseMessage, RpcResult<waglayla_rpc_core::GetDagExportResponse>, {
+    Self { blocks: item.blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetPruningPointProofRequestMessage, waglayla_rpc_core::GetPruningPointProofRequest);
+try_from!(item: &protowire::GetPruningPointProofResponseMessage, RpcResult<waglayla_rpc_core::GetPruningPointProofResponse>, {
+    Self {
+        levels: item
+            .levels
+            .iter()
+            .map(|level| level.headers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>())
+            .collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, waglayla_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<waglayla_rpc_core::PingResponse>);
 
@@ -880,6 +1028,11 @@ try_from!(item: &protowire::NotifySinkBlueScoreChangedRequestMessage, waglayla_r
 });
 try_from!(&protowire::NotifySinkBlueScoreChangedResponseMessage, RpcResult<waglayla_rpc_core::NotifySinkBlueScoreChangedResponse>);
 
+try_from!(item: &protowire::NotifyChainReorgRequestMessage, waglayla_rpc_core::NotifyChainReorgRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyChainReorgResponseMessage, RpcResult<waglayla_rpc_core::NotifyChainReorgResponse>);
+
 // ----------------------------------------------------------------------------
 // Unit tests
 // ----------------------------------------------------------------------------
@@ -42,6 +42,9 @@ from!(item: &waglayla_rpc_core::BandwidthMetrics, protowire::BandwidthMetrics, {
         grpc_p2p_bytes_rx: item.p2p_bytes_rx,
         grpc_user_bytes_tx: item.grpc_bytes_tx,
         grpc_user_bytes_rx: item.grpc_bytes_rx,
+        p2p_traffic: item.p2p_traffic.iter().map(|x| x.into()).collect(),
+        p2p_upload_limit: item.p2p_upload_limit,
+        p2p_throttled_bytes: item.p2p_throttled_bytes,
     }
 });
 
@@ -106,6 +109,9 @@ try_from!(item: &protowire::BandwidthMetrics, waglayla_rpc_core::BandwidthMetric
         p2p_bytes_rx: item.grpc_p2p_bytes_rx,
         grpc_bytes_tx: item.grpc_user_bytes_tx,
         grpc_bytes_rx: item.grpc_user_bytes_rx,
+        p2p_traffic: item.p2p_traffic.iter().map(waglayla_rpc_core::RpcP2pMessageTraffic::try_from).collect::<Result<Vec<_>, _>>()?,
+        p2p_upload_limit: item.p2p_upload_limit,
+        p2p_throttled_bytes: item.p2p_throttled_bytes,
     }
 });
 
@@ -1,5 +1,6 @@
 use crate::protowire::{
-    waglaylad_response::Payload, BlockAddedNotificationMessage, WaglayladResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
+    waglaylad_response::Payload, BlockAddedNotificationMessage, ChainReorgNotificationMessage, WaglayladResponse,
+    NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
     FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
@@ -34,6 +35,7 @@ from!(item: &waglayla_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::ChainReorg(ref notification) => Payload::ChainReorgNotification(notification.into()),
     }
 });
 
@@ -68,6 +70,17 @@ from!(item: &waglayla_rpc_core::SinkBlueScoreChangedNotification, SinkBlueScoreC
     Self { sink_blue_score: item.sink_blue_score }
 });
 
+from!(item: &waglayla_rpc_core::ChainReorgNotification, ChainReorgNotificationMessage, {
+    Self {
+        previous_sink: item.previous_sink.to_string(),
+        new_sink: item.new_sink.to_string(),
+        common_ancestor: item.common_ancestor.to_string(),
+        depth: item.depth,
+        daa_score_span: item.daa_score_span,
+        unaccepted_transaction_count: item.unaccepted_transaction_count,
+    }
+});
+
 from!(item: &waglayla_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScoreChangedNotificationMessage, {
     Self { virtual_daa_score: item.virtual_daa_score }
 });
@@ -117,6 +130,7 @@ try_from!(item: &Payload, waglayla_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::ChainReorgNotification(ref notification) => Notification::ChainReorg(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -165,6 +179,17 @@ try_from!(item: &SinkBlueScoreChangedNotificationMessage, waglayla_rpc_core::Sin
     Self { sink_blue_score: item.sink_blue_score }
 });
 
+try_from!(item: &ChainReorgNotificationMessage, waglayla_rpc_core::ChainReorgNotification, {
+    Self {
+        previous_sink: RpcHash::from_str(&item.previous_sink)?,
+        new_sink: RpcHash::from_str(&item.new_sink)?,
+        common_ancestor: RpcHash::from_str(&item.common_ancestor)?,
+        depth: item.depth,
+        daa_score_span: item.daa_score_span,
+        unaccepted_transaction_count: item.unaccepted_transaction_count,
+    }
+});
+
 try_from!(item: &VirtualDaaScoreChangedNotificationMessage, waglayla_rpc_core::VirtualDaaScoreChangedNotification, {
     Self { virtual_daa_score: item.virtual_daa_score }
 });
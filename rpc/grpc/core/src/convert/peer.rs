@@ -19,6 +19,36 @@ from!(item: &waglayla_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMess
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+        traffic: item.traffic.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &waglayla_rpc_core::RpcP2pMessageTraffic, protowire::P2pMessageTrafficMessage, {
+    Self {
+        message_type: item.message_type.clone(),
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+    }
+});
+
+from!(item: &waglayla_rpc_core::RpcAddressRecord, protowire::AddressRecordMessage, {
+    Self {
+        address: item.address.to_string(),
+        connection_failed_count: item.connection_failed_count,
+        last_seen: item.last_seen,
+        last_attempt: item.last_attempt,
+        last_success: item.last_success,
+        latency: item.latency.unwrap_or_default(),
+        protocol_version: item.protocol_version,
+        services: item.services,
+        user_agent: item.user_agent.clone(),
+        source: item.source.map(|x| x.to_string()).unwrap_or_default(),
     }
 });
 
@@ -40,6 +70,36 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, waglayla_rpc_core::RpcP
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+        traffic: item.traffic.iter().map(waglayla_rpc_core::RpcP2pMessageTraffic::try_from).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::P2pMessageTrafficMessage, waglayla_rpc_core::RpcP2pMessageTraffic, {
+    Self {
+        message_type: item.message_type.clone(),
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+    }
+});
+
+try_from!(item: &protowire::AddressRecordMessage, waglayla_rpc_core::RpcAddressRecord, {
+    Self {
+        address: RpcPeerAddress::from_str(&item.address)?,
+        connection_failed_count: item.connection_failed_count,
+        last_seen: item.last_seen,
+        last_attempt: item.last_attempt,
+        last_success: item.last_success,
+        latency: (item.latency > 0).then_some(item.latency),
+        protocol_version: item.protocol_version,
+        services: item.services,
+        user_agent: item.user_agent.clone(),
+        source: if item.source.is_empty() { None } else { Some(RpcPeerAddress::from_str(&item.source)?) },
     }
 });
 
@@ -57,6 +57,13 @@ pub mod waglaylad_request_convert {
     impl_into_waglaylad_request!(GetServerInfo);
     impl_into_waglaylad_request!(GetSyncStatus);
     impl_into_waglaylad_request!(GetDaaScoreTimestampEstimate);
+    impl_into_waglaylad_request!(ExportAddressBook);
+    impl_into_waglaylad_request!(ImportAddressBook);
+    impl_into_waglaylad_request!(GetTransactionInclusionProof);
+    impl_into_waglaylad_request!(GetUtxoSetAtBlock);
+    impl_into_waglaylad_request!(GetBalanceAtDaaScore);
+    impl_into_waglaylad_request!(GetDagExport);
+    impl_into_waglaylad_request!(GetPruningPointProof);
 
     impl_into_waglaylad_request!(NotifyBlockAdded);
     impl_into_waglaylad_request!(NotifyNewBlockTemplate);
@@ -66,6 +73,7 @@ pub mod waglaylad_request_convert {
     impl_into_waglaylad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_waglaylad_request!(NotifyVirtualChainChanged);
     impl_into_waglaylad_request!(NotifySinkBlueScoreChanged);
+    impl_into_waglaylad_request!(NotifyChainReorg);
 
     macro_rules! impl_into_waglaylad_request {
         ($name:tt) => {
@@ -188,6 +196,13 @@ pub mod waglaylad_response_convert {
     impl_into_waglaylad_response!(GetServerInfo);
     impl_into_waglaylad_response!(GetSyncStatus);
     impl_into_waglaylad_response!(GetDaaScoreTimestampEstimate);
+    impl_into_waglaylad_response!(ExportAddressBook);
+    impl_into_waglaylad_response!(ImportAddressBook);
+    impl_into_waglaylad_response!(GetTransactionInclusionProof);
+    impl_into_waglaylad_response!(GetUtxoSetAtBlock);
+    impl_into_waglaylad_response!(GetBalanceAtDaaScore);
+    impl_into_waglaylad_response!(GetDagExport);
+    impl_into_waglaylad_response!(GetPruningPointProof);
 
     impl_into_waglaylad_notify_response!(NotifyBlockAdded);
     impl_into_waglaylad_notify_response!(NotifyNewBlockTemplate);
@@ -197,6 +212,7 @@ pub mod waglaylad_response_convert {
     impl_into_waglaylad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_waglaylad_notify_response!(NotifyVirtualChainChanged);
     impl_into_waglaylad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_waglaylad_notify_response!(NotifyChainReorg);
 
     impl_into_waglaylad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_waglaylad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
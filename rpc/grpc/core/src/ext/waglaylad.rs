@@ -2,9 +2,9 @@ use waglayla_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
     waglaylad_request, waglaylad_response, WaglayladRequest, WaglayladResponse, NotifyBlockAddedRequestMessage,
-    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifyChainReorgRequestMessage, NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl WaglayladRequest {
@@ -64,6 +64,9 @@ impl waglaylad_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::ChainReorg(_) => {
+                waglaylad_request::Payload::NotifyChainReorgRequest(NotifyChainReorgRequestMessage { command: command.into() })
+            }
         }
     }
 
@@ -79,6 +82,7 @@ impl waglaylad_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyChainReorgRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +112,7 @@ impl waglaylad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::ChainReorgNotification(_) => true,
             _ => false,
         }
     }
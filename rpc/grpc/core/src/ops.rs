@@ -81,6 +81,13 @@ pub enum WaglayladPayloadOps {
     GetServerInfo,
     GetSyncStatus,
     GetDaaScoreTimestampEstimate,
+    ExportAddressBook,
+    ImportAddressBook,
+    GetTransactionInclusionProof,
+    GetUtxoSetAtBlock,
+    GetBalanceAtDaaScore,
+    GetDagExport,
+    GetPruningPointProof,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -91,6 +98,7 @@ pub enum WaglayladPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyChainReorg,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -75,6 +75,13 @@ impl Factory {
                 GetServerInfo,
                 GetSyncStatus,
                 GetDaaScoreTimestampEstimate,
+                ExportAddressBook,
+                ImportAddressBook,
+                GetTransactionInclusionProof,
+                GetUtxoSetAtBlock,
+                GetBalanceAtDaaScore,
+                GetDagExport,
+                GetPruningPointProof,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -83,6 +90,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyChainReorg,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
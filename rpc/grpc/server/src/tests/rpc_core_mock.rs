@@ -228,6 +228,37 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn export_address_book_call(&self, _request: ExportAddressBookRequest) -> RpcResult<ExportAddressBookResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn import_address_book_call(&self, _request: ImportAddressBookRequest) -> RpcResult<ImportAddressBookResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_inclusion_proof_call(
+        &self,
+        _request: GetTransactionInclusionProofRequest,
+    ) -> RpcResult<GetTransactionInclusionProofResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_utxo_set_at_block_call(&self, _request: GetUtxoSetAtBlockRequest) -> RpcResult<GetUtxoSetAtBlockResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_balance_at_daa_score_call(&self, _request: GetBalanceAtDaaScoreRequest) -> RpcResult<GetBalanceAtDaaScoreResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_dag_export_call(&self, _request: GetDagExportRequest) -> RpcResult<GetDagExportResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_pruning_point_proof_call(&self, _request: GetPruningPointProofRequest) -> RpcResult<GetPruningPointProofResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
+use waglayla_addressmanager::AddressRecord;
 use waglayla_p2p_flows::flow_context::FlowContext;
-use waglayla_p2p_lib::{Peer, PeerKey};
-use waglayla_rpc_core::RpcPeerInfo;
+use waglayla_p2p_lib::{MessageTypeTraffic, Peer, PeerKey, TrafficSnapshot};
+use waglayla_rpc_core::{RpcAddressRecord, RpcP2pMessageTraffic, RpcPeerInfo};
 
 pub struct ProtocolConverter {
     flow_context: Arc<FlowContext>,
@@ -15,6 +16,7 @@ impl ProtocolConverter {
 
     fn get_peer_info(&self, peer: &Peer, ibd_peer_key: &Option<PeerKey>) -> RpcPeerInfo {
         let properties = peer.properties();
+        let traffic = peer.traffic();
         RpcPeerInfo {
             id: peer.identity(),
             address: peer.net_address().into(),
@@ -25,6 +27,11 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            messages_sent: traffic.sent.messages,
+            bytes_sent: traffic.sent.bytes,
+            messages_received: traffic.received.messages,
+            bytes_received: traffic.received.bytes,
+            traffic: self.get_message_traffic(traffic),
         }
     }
 
@@ -32,4 +39,48 @@ impl ProtocolConverter {
         let ibd_peer_key = self.flow_context.ibd_peer_key();
         peers.iter().map(|x| self.get_peer_info(x, &ibd_peer_key)).collect()
     }
+
+    pub fn get_message_traffic(&self, traffic: &TrafficSnapshot) -> Vec<RpcP2pMessageTraffic> {
+        traffic.by_type.iter().map(|x| self.get_message_type_traffic(x)).collect()
+    }
+
+    fn get_message_type_traffic(&self, traffic: &MessageTypeTraffic) -> RpcP2pMessageTraffic {
+        RpcP2pMessageTraffic {
+            message_type: format!("{:?}", traffic.message_type),
+            messages_sent: traffic.sent.messages,
+            bytes_sent: traffic.sent.bytes,
+            messages_received: traffic.received.messages,
+            bytes_received: traffic.received.bytes,
+        }
+    }
+
+    pub fn get_address_record(&self, record: AddressRecord) -> RpcAddressRecord {
+        RpcAddressRecord {
+            address: record.address,
+            connection_failed_count: record.connection_failed_count,
+            last_seen: record.last_seen,
+            last_attempt: record.last_attempt,
+            last_success: record.last_success,
+            latency: record.latency,
+            protocol_version: record.protocol_version,
+            services: record.services,
+            user_agent: record.user_agent,
+            source: record.source,
+        }
+    }
+
+    pub fn get_address_book_record(&self, record: RpcAddressRecord) -> AddressRecord {
+        AddressRecord {
+            address: record.address,
+            connection_failed_count: record.connection_failed_count,
+            last_seen: record.last_seen,
+            last_attempt: record.last_attempt,
+            last_success: record.last_success,
+            latency: record.latency,
+            protocol_version: record.protocol_version,
+            services: record.services,
+            user_agent: record.user_agent,
+            source: record.source,
+        }
+    }
 }
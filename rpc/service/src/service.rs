@@ -7,18 +7,22 @@ use async_trait::async_trait;
 use waglayla_consensus_core::api::counters::ProcessingCounters;
 use waglayla_consensus_core::errors::block::RuleError;
 use waglayla_consensus_core::{
+    acceptance_data::AcceptanceData,
     block::Block,
     coinbase::MinerData,
     config::Config,
     constants::MAX_SOMPI,
+    header::Header,
+    merkle::{create_accepted_id_merkle_witness, TransactionInclusionProof, TransactionReceipt},
     network::NetworkType,
-    tx::{Transaction, COINBASE_TRANSACTION_INDEX},
+    tx::{ScriptPublicKeys, Transaction, TransactionId, COINBASE_TRANSACTION_INDEX},
+    BlockHashMap, BlockHashSet,
 };
 use waglayla_consensus_notify::{
     notifier::ConsensusNotifier,
     {connection::ConsensusChannelConnection, notification::Notification as ConsensusNotification},
 };
-use waglayla_consensusmanager::ConsensusManager;
+use waglayla_consensusmanager::{ConsensusManager, ConsensusProxy};
 use waglayla_core::time::unix_now;
 use waglayla_core::{
     core::Core,
@@ -48,7 +52,8 @@ use waglayla_notify::{
     scope::Scope,
     subscriber::{Subscriber, SubscriptionManager},
 };
-use waglayla_p2p_flows::flow_context::FlowContext;
+use waglayla_hashes::Hash;
+use waglayla_p2p_flows::{flow_context::FlowContext, v6::inclusion_proofs::INCLUSION_PROOF_SEARCH_DEPTH};
 use waglayla_p2p_lib::common::ProtocolError;
 use waglayla_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use waglayla_rpc_core::{
@@ -61,11 +66,11 @@ use waglayla_rpc_core::{
     Notification, RpcError, RpcResult,
 };
 use waglayla_txscript::{extract_script_pub_key_address, pay_to_address_script};
-use waglayla_utils::{channel::Channel, triggers::SingleTrigger};
+use waglayla_utils::{channel::Channel, option::OptionExtensions, triggers::SingleTrigger};
 use waglayla_utils_tower::counters::TowerConnectionCounters;
 use waglayla_utxoindex::api::UtxoIndexProxy;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     iter::once,
     sync::{atomic::Ordering, Arc},
     vec,
@@ -113,6 +118,9 @@ pub struct RpcCoreService {
 
 const RPC_CORE: &str = "rpc-core";
 
+/// Max number of blocks returned by a single DAG export. Larger ranges can be exported offline from a copy of the data directory
+const MAX_DAG_EXPORT_BLOCKS: usize = 10_000;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -271,11 +279,86 @@ impl RpcCoreService {
             (false, false) => Ok(TransactionQuery::TransactionsOnly),
         }
     }
+
+    /// Searches the acceptance data of recent selected chain blocks for the transaction, optionally restricted to the
+    /// mergeset block `block_hash`. Returns the accepting chain block, the merged block containing the transaction
+    /// and the acceptance data of the chain block.
+    async fn find_accepting_chain_block(
+        &self,
+        session: &ConsensusProxy,
+        transaction_id: TransactionId,
+        block_hash: Option<Hash>,
+    ) -> RpcResult<Option<(Hash, Hash, Arc<AcceptanceData>)>> {
+        // A chain block has a higher blue score than the blocks it merges, which ends the search early if the block is known
+        let min_blue_score = match block_hash {
+            Some(block_hash) => Some(session.async_get_ghostdag_data(block_hash).await?.blue_score),
+            None => None,
+        };
+        let mut chain_block = session.async_get_sink().await;
+        for _ in 0..INCLUSION_PROOF_SEARCH_DEPTH {
+            // Ghostdag and acceptance data are missing below the pruning point and for the origin
+            let (Ok(ghostdag_data), Ok(acceptance_data)) =
+                (session.async_get_ghostdag_data(chain_block).await, session.async_get_block_acceptance_data(chain_block).await)
+            else {
+                break;
+            };
+            if min_blue_score.is_some_and(|blue_score| ghostdag_data.blue_score <= blue_score) {
+                break;
+            }
+            let merged_block = acceptance_data
+                .iter()
+                .filter(|mergeset_block| block_hash.is_none_or_ex(|&block_hash| block_hash == mergeset_block.block_hash))
+                .find(|mergeset_block| mergeset_block.accepted_transactions.iter().any(|entry| entry.transaction_id == transaction_id))
+                .map(|mergeset_block| mergeset_block.block_hash);
+            if let Some(merged_block) = merged_block {
+                return Ok(Some((chain_block, merged_block, acceptance_data)));
+            }
+            chain_block = ghostdag_data.selected_parent;
+        }
+        Ok(None)
+    }
+
+    /// Returns the headers on a path of direct parents from `block` up to `chain_block`, which merges it. All blocks on
+    /// such a path are merged by `chain_block` as well, so the search is bounded by its `mergeset`.
+    async fn get_merging_header_chain(
+        &self,
+        session: &ConsensusProxy,
+        block: Hash,
+        chain_block: Hash,
+        mergeset: &BlockHashSet,
+    ) -> RpcResult<Vec<Header>> {
+        let mut successors: BlockHashMap<Hash> = BlockHashMap::new();
+        let mut headers: BlockHashMap<Arc<Header>> = BlockHashMap::new();
+        let mut queue = VecDeque::from([chain_block]);
+        while let Some(current) = queue.pop_front() {
+            let header = session.async_get_header(current).await?;
+            headers.insert(current, header.clone());
+            if current == block {
+                let mut path = vec![(*header).clone()];
+                let mut current = block;
+                while current != chain_block {
+                    current = successors[&current];
+                    path.push((*headers[&current]).clone());
+                }
+                return Ok(path);
+            }
+            for &parent in header.direct_parents() {
+                if mergeset.contains(&parent) && !successors.contains_key(&parent) {
+                    successors.insert(parent, current);
+                    queue.push_back(parent);
+                }
+            }
+        }
+        Err(RpcError::General(format!("block {block} is not merged by chain block {chain_block}")))
+    }
 }
 
 #[async_trait]
 impl RpcApi for RpcCoreService {
     async fn submit_block_call(&self, request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
         let session = self.consensus_manager.consensus().unguarded_session();
 
         // TODO: consider adding an error field to SubmitBlockReport to document both the report and error fields
@@ -336,6 +419,10 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     async fn get_block_template_call(&self, request: GetBlockTemplateRequest) -> RpcResult<GetBlockTemplateResponse> {
         trace!("incoming GetBlockTemplate request");
 
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+
         if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
             return Err(RpcError::General("Mining on mainnet is not supported for initial Rust versions".to_owned()));
         }
@@ -486,6 +573,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse> {
+        // Light nodes relay the transaction to their peers without validating it against a mempool
         let allow_orphan = self.config.unsafe_rpc && request.allow_orphan;
         if !self.config.unsafe_rpc && request.allow_orphan {
             warn!("SubmitTransaction RPC command called with AllowOrphan enabled while node in safe RPC mode -- switching to ForbidOrphan.");
@@ -723,6 +811,130 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetPeerAddressesResponse::new(address_manager.get_all_addresses(), address_manager.get_all_banned_addresses()))
     }
 
+    async fn export_address_book_call(&self, _: ExportAddressBookRequest) -> RpcResult<ExportAddressBookResponse> {
+        let records = self.flow_context.address_manager.lock().get_all_address_records();
+        Ok(ExportAddressBookResponse::new(records.into_iter().map(|x| self.protocol_converter.get_address_record(x)).collect()))
+    }
+
+    async fn import_address_book_call(&self, request: ImportAddressBookRequest) -> RpcResult<ImportAddressBookResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("ImportAddressBook RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let records = request.records.into_iter().map(|x| self.protocol_converter.get_address_book_record(x));
+        let imported = self.flow_context.address_manager.lock().import_address_records(records);
+        Ok(ImportAddressBookResponse::new(imported as u64))
+    }
+
+    async fn get_transaction_inclusion_proof_call(
+        &self,
+        request: GetTransactionInclusionProofRequest,
+    ) -> RpcResult<GetTransactionInclusionProofResponse> {
+        let transaction_id = request.transaction_id;
+        if self.config.is_light {
+            // Light nodes hold no block bodies, hence the proof is requested from peers and verified against the local
+            // header. Acceptance data is unavailable as well, so the receipt only proves inclusion in the block.
+            let proof = self
+                .flow_context
+                .request_transaction_inclusion_proof(transaction_id, request.block_hash)
+                .await
+                .map_err(|err| RpcError::General(err.to_string()))?;
+            let header = self.consensus_manager.consensus().unguarded_session().async_get_header(proof.block_hash).await?;
+            return Ok((&TransactionReceipt::new(proof, vec![(*header).clone()], None)).into());
+        }
+
+        let session = self.consensus_manager.consensus().session().await;
+        let accepting = self.find_accepting_chain_block(&session, transaction_id, request.block_hash).await?;
+        let Some(block_hash) = request.block_hash.or(accepting.as_ref().map(|(_, merged_block, _)| *merged_block)) else {
+            return Err(RpcError::TransactionNotFound(transaction_id));
+        };
+        let block = session.async_get_block(block_hash).await?;
+        let inclusion = block
+            .transactions
+            .iter()
+            .position(|tx| tx.id() == transaction_id)
+            .and_then(|index| TransactionInclusionProof::new(&block, index))
+            .ok_or(RpcError::TransactionNotFound(transaction_id))?;
+
+        let receipt = match accepting {
+            Some((chain_block, _, acceptance_data)) => {
+                let mergeset: BlockHashSet = acceptance_data.iter().map(|mergeset_block| mergeset_block.block_hash).collect();
+                let headers = self.get_merging_header_chain(&session, block_hash, chain_block, &mergeset).await?;
+                let accepted_ids = acceptance_data
+                    .iter()
+                    .flat_map(|mergeset_block| mergeset_block.accepted_transactions.iter().map(|entry| entry.transaction_id));
+                TransactionReceipt::new(inclusion, headers, create_accepted_id_merkle_witness(accepted_ids, transaction_id))
+            }
+            // The transaction is not accepted by a recent chain block, e.g. if the block is red or not merged yet
+            None => TransactionReceipt::new(inclusion, vec![(*block.header).clone()], None),
+        };
+        Ok((&receipt).into())
+    }
+
+    async fn get_utxo_set_at_block_call(&self, request: GetUtxoSetAtBlockRequest) -> RpcResult<GetUtxoSetAtBlockResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        if !self.config.is_archival {
+            return Err(RpcError::NotArchival);
+        }
+        let script_public_keys: ScriptPublicKeys = request.addresses.iter().map(pay_to_address_script).collect();
+        let session = self.consensus_manager.consensus().session().await;
+        let utxos = session.async_get_utxos_at_chain_block(request.block_hash, script_public_keys).await?;
+        let entries = utxos
+            .into_iter()
+            .map(|(outpoint, utxo_entry)| RpcUtxosByAddressesEntry {
+                address: extract_script_pub_key_address(&utxo_entry.script_public_key, self.config.prefix()).ok(),
+                outpoint,
+                utxo_entry,
+            })
+            .collect();
+        Ok(GetUtxoSetAtBlockResponse::new(entries))
+    }
+
+    async fn get_balance_at_daa_score_call(&self, request: GetBalanceAtDaaScoreRequest) -> RpcResult<GetBalanceAtDaaScoreResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        if !self.config.is_archival {
+            return Err(RpcError::NotArchival);
+        }
+        let script_public_keys: ScriptPublicKeys = request.addresses.iter().map(pay_to_address_script).collect();
+        let session = self.consensus_manager.consensus().session().await;
+        let block_hash = session.async_get_chain_block_at_daa_score(request.daa_score).await?;
+        let utxos = session.async_get_utxos_at_chain_block(block_hash, script_public_keys).await?;
+        let mut balances: HashMap<_, u64> = HashMap::new();
+        for utxo_entry in utxos.values() {
+            *balances.entry(&utxo_entry.script_public_key).or_default() += utxo_entry.amount;
+        }
+        let entries = request
+            .addresses
+            .iter()
+            .map(|address| {
+                let script_public_key = pay_to_address_script(address);
+                let balance = Some(balances.get(&script_public_key).copied().unwrap_or_default());
+                RpcBalancesByAddressesEntry { address: address.to_owned(), balance }
+            })
+            .collect();
+        Ok(GetBalanceAtDaaScoreResponse::new(block_hash, entries))
+    }
+
+    async fn get_dag_export_call(&self, request: GetDagExportRequest) -> RpcResult<GetDagExportResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        let session = self.consensus_manager.consensus().session().await;
+        let blocks = session.async_export_dag(request.range(), MAX_DAG_EXPORT_BLOCKS).await?;
+        Ok(GetDagExportResponse::new(blocks))
+    }
+
+    async fn get_pruning_point_proof_call(&self, _: GetPruningPointProofRequest) -> RpcResult<GetPruningPointProofResponse> {
+        let proof = self.consensus_manager.consensus().session().await.async_get_pruning_point_proof().await;
+        Ok(GetPruningPointProofResponse::new(
+            proof.iter().map(|level| level.iter().map(|header| (**header).clone()).collect()).collect(),
+        ))
+    }
+
     async fn ban_call(&self, request: BanRequest) -> RpcResult<BanResponse> {
         if !self.config.unsafe_rpc {
             warn!("Ban RPC command called while node in safe RPC mode -- ignoring.");
@@ -733,6 +945,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             if connection_manager.ip_has_permanent_connection(ip).await {
                 return Err(RpcError::IpHasPermanentConnection(request.ip));
             }
+            if connection_manager.is_whitelisted(ip) {
+                return Err(RpcError::IpIsWhitelisted(request.ip));
+            }
             connection_manager.ban(ip).await;
         } else {
             return Err(RpcError::NoConnectionManager);
@@ -828,15 +1043,22 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             active_peers: self.flow_context.hub().active_peers_len() as u32,
         });
 
-        let bandwidth_metrics = req.bandwidth_metrics.then_some(BandwidthMetrics {
-            borsh_bytes_tx: self.wrpc_borsh_counters.tx_bytes.load(Ordering::Relaxed) as u64,
-            borsh_bytes_rx: self.wrpc_borsh_counters.rx_bytes.load(Ordering::Relaxed) as u64,
-            json_bytes_tx: self.wrpc_json_counters.tx_bytes.load(Ordering::Relaxed) as u64,
-            json_bytes_rx: self.wrpc_json_counters.rx_bytes.load(Ordering::Relaxed) as u64,
-            p2p_bytes_tx: self.p2p_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
-            p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
-            grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
-            grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+        let bandwidth_metrics = req.bandwidth_metrics.then(|| {
+            let p2p_traffic = self.flow_context.hub().traffic();
+            let upload_budget = p2p_traffic.upload_budget.as_ref();
+            BandwidthMetrics {
+                borsh_bytes_tx: self.wrpc_borsh_counters.tx_bytes.load(Ordering::Relaxed) as u64,
+                borsh_bytes_rx: self.wrpc_borsh_counters.rx_bytes.load(Ordering::Relaxed) as u64,
+                json_bytes_tx: self.wrpc_json_counters.tx_bytes.load(Ordering::Relaxed) as u64,
+                json_bytes_rx: self.wrpc_json_counters.rx_bytes.load(Ordering::Relaxed) as u64,
+                p2p_bytes_tx: self.p2p_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
+                p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+                grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
+                grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+                p2p_traffic: self.protocol_converter.get_message_traffic(&p2p_traffic.counters.snapshot()),
+                p2p_upload_limit: upload_budget.map_or(0, |budget| budget.bytes_per_sec()),
+                p2p_throttled_bytes: upload_budget.map_or(0, |budget| budget.throttled_bytes()),
+            }
         });
 
         let consensus_metrics = if req.consensus_metrics {
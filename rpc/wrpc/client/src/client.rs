@@ -67,6 +67,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::ChainReorgNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -587,7 +588,9 @@ impl RpcApi for WaglaylaRpcClient {
             AddPeer,
             Ban,
             EstimateNetworkHashesPerSecond,
+            ExportAddressBook,
             GetBalanceByAddress,
+            GetBalanceAtDaaScore,
             GetBalancesByAddresses,
             GetBlock,
             GetBlockCount,
@@ -599,6 +602,8 @@ impl RpcApi for WaglaylaRpcClient {
             GetDaaScoreTimestampEstimate,
             GetServerInfo,
             GetCurrentNetwork,
+            GetDagExport,
+            GetPruningPointProof,
             GetHeaders,
             GetInfo,
             GetMempoolEntries,
@@ -610,8 +615,11 @@ impl RpcApi for WaglaylaRpcClient {
             GetSyncStatus,
             GetSubnetwork,
             GetUtxosByAddresses,
+            GetUtxoSetAtBlock,
             GetSinkBlueScore,
+            GetTransactionInclusionProof,
             GetVirtualChainFromBlock,
+            ImportAddressBook,
             Ping,
             ResolveFinalityConflict,
             Shutdown,
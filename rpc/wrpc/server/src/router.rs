@@ -35,7 +35,9 @@ impl Router {
                 AddPeer,
                 Ban,
                 EstimateNetworkHashesPerSecond,
+                ExportAddressBook,
                 GetBalanceByAddress,
+                GetBalanceAtDaaScore,
                 GetBalancesByAddresses,
                 GetBlock,
                 GetBlockCount,
@@ -47,6 +49,8 @@ impl Router {
                 GetDaaScoreTimestampEstimate,
                 GetServerInfo,
                 GetCurrentNetwork,
+                GetDagExport,
+                GetPruningPointProof,
                 GetHeaders,
                 GetInfo,
                 GetInfo,
@@ -59,8 +63,11 @@ impl Router {
                 GetSubnetwork,
                 GetSyncStatus,
                 GetUtxosByAddresses,
+                GetUtxoSetAtBlock,
                 GetSinkBlueScore,
+                GetTransactionInclusionProof,
                 GetVirtualChainFromBlock,
+                ImportAddressBook,
                 Ping,
                 ResolveFinalityConflict,
                 Shutdown,
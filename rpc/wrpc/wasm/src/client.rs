@@ -912,6 +912,10 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Waglayla BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a chain reorg notification event.
+    /// Chain reorg notification event is produced when a virtual change removes
+    /// at least the node's configured number of blocks from the selected chain.
+    ChainReorg,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -924,6 +928,9 @@ build_wrpc_wasm_bindgen_interface!(
         // functions with optional arguments
         // they are specified as Option<IXxxRequest>
         // which map as `request? : IXxxRequest` in typescript
+        /// Exports the address records known to the Waglayla node's address manager.
+        /// Returned information: Peer addresses with their connection quality history.
+        ExportAddressBook,
         /// Retrieves the current number of blocks in the Waglayla BlockDAG.
         /// This is not a block count, not a "block height" and can not be
         /// used for transaction validation.
@@ -966,6 +973,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// leading up to that block.
         /// Returned information: Blue score of the sink block.
         GetSinkBlueScore,
+        /// Retrieves the proof of the current pruning point, as sent to
+        /// syncing peers, for inspecting proof rejections between nodes.
+        /// Returned information: Proof headers by block level.
+        GetPruningPointProof,
         /// Tests the connection and responsiveness of a Waglayla node.
         /// Returned information: None.
         Ping,
@@ -991,12 +1002,21 @@ build_wrpc_wasm_bindgen_interface!(
         /// Estimates the network's current hash rate in hashes per second.
         /// Returned information: Estimated network hashes per second.
         EstimateNetworkHashesPerSecond,
+        /// Imports address records into the Waglayla node's address manager,
+        /// e.g. for seeding a fresh node without DNS. Requires `--unsaferpc`.
+        /// Returned information: Number of imported addresses.
+        ImportAddressBook,
         /// Retrieves the balance of a specific address in the Waglayla BlockDAG.
         /// Returned information: Balance of the address.
         GetBalanceByAddress,
         /// Retrieves balances for multiple addresses in the Waglayla BlockDAG.
         /// Returned information: Balances of the addresses.
         GetBalancesByAddresses,
+        /// Retrieves balances for multiple addresses as they were at a past
+        /// DAA score. Only available on archival nodes.
+        /// Returned information: Balances of the addresses and the selected
+        /// chain block they were computed at.
+        GetBalanceAtDaaScore,
         /// Retrieves a specific block from the Waglayla BlockDAG.
         /// Returned information: Block information.
         GetBlock,
@@ -1010,6 +1030,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// score timestamp estimate.
         /// Returned information: DAA score timestamp estimate.
         GetDaaScoreTimestampEstimate,
+        /// Exports a range of the DAG along with GHOSTDAG data, statuses
+        /// and acceptance data, for visualization and offline analysis.
+        /// Returned information: List of blocks in consensus order.
+        GetDagExport,
         /// Retrieves the current network configuration.
         /// Returned information: Current network configuration.
         GetCurrentNetwork,
@@ -1028,10 +1052,19 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves information about a subnetwork in the Waglayla BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves a receipt proving a transaction is included in a block and
+        /// accepted by the selected chain, verifiable using `verifyTransactionReceipt()`.
+        /// Returned information: Transaction receipt.
+        GetTransactionInclusionProof,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
         GetUtxosByAddresses,
+        /// Retrieves unspent transaction outputs (UTXOs) associated with
+        /// specific addresses as they were at a past selected chain block.
+        /// Only available on archival nodes.
+        /// Returned information: List of UTXOs.
+        GetUtxoSetAtBlock,
         /// Retrieves the virtual chain corresponding to a specified block hash.
         /// Returned information: Virtual chain information.
         GetVirtualChainFromBlock,
@@ -21,6 +21,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    ChainReorg = "chain-reorg",
 }
 
 /**
@@ -36,7 +37,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IChainReorg;
 
 /**
  * RPC notification event data map.
@@ -55,6 +57,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "chain-reorg" : IChainReorg,
 }
 
 /**
@@ -80,6 +83,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeChainReorg},
  * 
  * @category Node RPC
  */
@@ -237,3 +241,18 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IChainReorg,
+    r#"
+    /**
+     * Chain reorg notification event is produced when a virtual change removes
+     * at least the node's configured number of blocks from the selected chain.
+     * 
+     * @category Node RPC
+     */
+    export interface IChainReorg {
+        [key: string]: any;
+    }
+    "#,
+}
@@ -25,14 +25,18 @@ use waglayla_consensus_core::api::{BlockValidationFutures, ConsensusApi};
 use waglayla_consensus_core::block::Block;
 use waglayla_consensus_core::blockhash::new_unique;
 use waglayla_consensus_core::blockstatus::BlockStatus;
+use waglayla_consensus_core::dag_export::DagExportRange;
 use waglayla_consensus_core::constants::{BLOCK_VERSION, STORAGE_MASS_PARAMETER};
 use waglayla_consensus_core::errors::block::{BlockProcessResult, RuleError};
+use waglayla_consensus_core::errors::consensus::ConsensusError;
 use waglayla_consensus_core::header::Header;
 use waglayla_consensus_core::network::{NetworkId, NetworkType::Mainnet};
 use waglayla_consensus_core::subnets::SubnetworkId;
 use waglayla_consensus_core::trusted::{ExternalGhostdagData, TrustedBlock};
-use waglayla_consensus_core::tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry};
+use waglayla_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeys, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry};
+use waglayla_consensus_core::utxo::utxo_collection::UtxoCollection;
 use waglayla_consensus_core::{blockhash, hashing, BlockHashMap, BlueWorkType};
+use waglayla_consensus_notify::notification::Notification;
 use waglayla_consensus_notify::root::ConsensusNotificationRoot;
 use waglayla_consensus_notify::service::NotifyService;
 use waglayla_consensusmanager::ConsensusManager;
@@ -53,7 +57,8 @@ use waglayla_database::prelude::{CachePolicy, ConnBuilder};
 use waglayla_index_processor::service::IndexService;
 use waglayla_math::Uint256;
 use waglayla_muhash::{MuHash, Blake2Hash as Blake2Hash};
-use waglayla_notify::subscription::context::SubscriptionContext;
+use waglayla_notify::scope::{ChainReorgScope, Scope};
+use waglayla_notify::subscription::{context::SubscriptionContext, Command};
 use waglayla_txscript::caches::TxScriptCacheCounters;
 use waglayla_utxoindex::api::{UtxoIndexApi, UtxoIndexProxy};
 use waglayla_utxoindex::UtxoIndex;
@@ -1703,6 +1708,163 @@ async fn selected_chain_test() {
     consensus.shutdown(wait_handles);
 }
 
+#[tokio::test]
+async fn utxos_at_chain_block_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .set_archival()
+        .edit_consensus_params(|p| {
+            p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+        })
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // The test consensus pays all rewards to an empty script public key
+    let script_public_keys = ScriptPublicKeys::from_iter([ScriptPublicKey::from_vec(0, vec![])]);
+
+    // Track the expected UTXO set of every chain block independently. On a linear chain each block accepts exactly the
+    // transactions of its selected parent, whose outputs are credited with the DAA score of the accepting block
+    let mut tracked = UtxoCollection::new();
+    let mut snapshots = vec![];
+    let mut parent = config.genesis.hash;
+    for i in 1..10u64 {
+        let hash = Hash::from(i);
+        consensus.add_utxo_valid_block_with_parents(hash, vec![parent], vec![]).await.unwrap();
+        let daa_score = consensus.get_header(hash).unwrap().daa_score;
+        for tx in consensus.block_transactions_store.get(parent).unwrap().iter() {
+            for input in tx.inputs.iter() {
+                tracked.remove(&input.previous_outpoint);
+            }
+            let tracked_outputs = tx.outputs.iter().enumerate().filter(|(_, output)| script_public_keys.contains(&output.script_public_key));
+            for (index, output) in tracked_outputs {
+                let entry = UtxoEntry::new(output.value, output.script_public_key.clone(), daa_score, tx.is_coinbase());
+                tracked.insert(TransactionOutpoint::new(tx.id(), index as u32), entry);
+            }
+        }
+        snapshots.push((hash, daa_score, tracked.clone()));
+        parent = hash;
+    }
+    // A side block which is merged by the virtual but never becomes a chain block
+    consensus.add_utxo_valid_block_with_parents(10.into(), vec![5.into()], vec![]).await.unwrap();
+
+    // Walking the UTXO diffs backwards from the virtual UTXO set must yield the independently tracked states
+    for (hash, daa_score, utxos) in snapshots.iter() {
+        assert_eq!(&consensus.get_utxos_at_chain_block(*hash, &script_public_keys).unwrap(), utxos);
+        assert_eq!(consensus.get_chain_block_at_daa_score(*daa_score).unwrap(), *hash);
+    }
+    assert!(!snapshots.last().unwrap().2.is_empty());
+    assert!(consensus.get_utxos_at_chain_block(9.into(), &ScriptPublicKeys::new()).unwrap().is_empty());
+
+    assert_match!(consensus.get_utxos_at_chain_block(10.into(), &script_public_keys), Err(ConsensusError::NotChainBlock(_)));
+    assert_match!(consensus.get_utxos_at_chain_block(100.into(), &script_public_keys), Err(ConsensusError::HeaderNotFound(_)));
+    assert_eq!(consensus.get_chain_block_at_daa_score(u64::MAX).unwrap(), consensus.get_sink());
+
+    consensus.shutdown(wait_handles);
+
+    // Non-archival nodes do not retain the UTXO diffs of past chain blocks
+    let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+    assert_match!(consensus.get_utxos_at_chain_block(config.genesis.hash, &script_public_keys), Err(ConsensusError::NotArchival));
+    assert_match!(consensus.get_chain_block_at_daa_score(0), Err(ConsensusError::NotArchival));
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn dag_export_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+        })
+        .build();
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // A chain 1..6 where the side block 7 is merged by 4, and the side block 8 is only merged by the virtual
+    consensus.add_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(2.into(), vec![1.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(3.into(), vec![2.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(7.into(), vec![1.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(4.into(), vec![3.into(), 7.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(5.into(), vec![4.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(6.into(), vec![5.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(8.into(), vec![4.into()], vec![]).await.unwrap();
+
+    let blocks = consensus.export_dag(DagExportRange::DaaScore { start: 0, end: u64::MAX }, usize::MAX).unwrap();
+    let expected: Vec<Hash> = [config.genesis.hash].into_iter().chain([1, 2, 3, 7, 4, 5, 6, 8].map(Hash::from)).collect();
+    assert_eq!(blocks.iter().map(|block| block.hash).collect_vec(), expected);
+    for block in blocks.iter() {
+        let is_side_block = block.hash == 7.into() || block.hash == 8.into();
+        assert_eq!(block.is_chain_block, !is_side_block);
+        assert_eq!(block.acceptance.is_none(), is_side_block);
+        assert_eq!(block.status, BlockStatus::StatusUTXOValid);
+    }
+    let block_4 = &blocks[5];
+    assert_eq!(block_4.parents, vec![3.into(), 7.into()]);
+    assert_eq!(block_4.selected_parent, 3.into());
+    assert!(block_4.mergeset_blues.contains(&7.into()));
+    assert!(block_4.acceptance.as_ref().unwrap().iter().any(|acceptance| acceptance.block_hash == 7.into()));
+
+    // A range holds its chain blocks along with the blocks they merge
+    let daa_score = consensus.get_header(4.into()).unwrap().daa_score;
+    let blocks = consensus.export_dag(DagExportRange::DaaScore { start: daa_score, end: daa_score }, usize::MAX).unwrap();
+    assert_eq!(blocks.iter().map(|block| block.hash).collect_vec(), vec![7.into(), 4.into()]);
+    let blue_score = consensus.get_ghostdag_data(5.into()).unwrap().blue_score;
+    let blocks = consensus.export_dag(DagExportRange::BlueScore { start: blue_score, end: blue_score }, usize::MAX).unwrap();
+    assert_eq!(blocks.iter().map(|block| block.hash).collect_vec(), vec![5.into()]);
+    assert!(consensus.export_dag(DagExportRange::BlueScore { start: u64::MAX, end: u64::MAX }, usize::MAX).unwrap().is_empty());
+
+    assert_match!(
+        consensus.export_dag(DagExportRange::DaaScore { start: 0, end: u64::MAX }, 4),
+        Err(ConsensusError::DagExportTooLarge(4))
+    );
+
+    consensus.shutdown(wait_handles);
+}
+
+#[tokio::test]
+async fn chain_reorg_test() {
+    init_allocator_with_default_settings();
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+        })
+        .apply_args(|config| config.chain_reorg_notification_depth = 3)
+        .build();
+    let (notification_send, notification_recv) = unbounded();
+    let consensus = TestConsensus::with_notifier(&config, notification_send, SubscriptionContext::new());
+    consensus.notification_root().execute_subscribe_command(Scope::ChainReorg(ChainReorgScope {}), Command::Start).unwrap();
+    let wait_handles = consensus.init();
+
+    // A chain 1..3 reorged by the longer chain 4..7, both on top of genesis
+    consensus.add_utxo_valid_block_with_parents(1.into(), vec![config.genesis.hash], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(2.into(), vec![1.into()], vec![]).await.unwrap();
+    consensus.add_utxo_valid_block_with_parents(3.into(), vec![2.into()], vec![]).await.unwrap();
+    for (hash, parent) in [(4, config.genesis.hash), (5, 4.into()), (6, 5.into()), (7, 6.into())] {
+        consensus.add_utxo_valid_block_with_parents(hash.into(), vec![parent], vec![]).await.unwrap();
+    }
+    assert_eq!(consensus.get_sink(), 7.into());
+
+    let Notification::ChainReorg(reorg) = notification_recv.try_recv().unwrap() else { panic!("expected a ChainReorg notification") };
+    assert_eq!(reorg.previous_sink, 3.into());
+    assert_eq!(reorg.common_ancestor, config.genesis.hash);
+    assert_eq!(reorg.depth, 3);
+    assert_eq!(reorg.daa_score_span, 3);
+    assert!(notification_recv.try_recv().is_err());
+
+    let histogram = consensus.get_stats().reorg_histogram;
+    assert_eq!(histogram.reorg_count(), 1);
+    assert_eq!(histogram.depth_buckets[1], 1);
+    assert_eq!(histogram.max_depth, 3);
+
+    consensus.shutdown(wait_handles);
+}
+
 fn assert_selected_chain_store_matches_virtual_chain(consensus: &TestConsensus) {
     let pruning_point = consensus.pruning_point();
     let iter1 = selected_chain_store_iterator(consensus, pruning_point);
@@ -322,6 +322,107 @@ async fn daemon_utxos_propagation_test() {
     }
 }
 
+/// A light node syncs the headers of a full node and relays the transactions submitted to it, which the full node
+/// validates and adds to its mempool.
+///
+/// `cargo test --release --package waglayla-testing-integration --lib -- daemon_integration_tests::daemon_light_node_relay_test`
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn daemon_light_node_relay_test() {
+    init_allocator_with_default_settings();
+    waglayla_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        utxoindex: true,
+        ..Default::default()
+    };
+    let light_args = Args { light: true, utxoindex: false, ..args.clone() };
+    let total_fd_limit = 10;
+    let coinbase_maturity = SIMNET_PARAMS.coinbase_maturity;
+
+    let mut waglaylad1 = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client1 = waglaylad1.start().await;
+
+    let (miner_sk, miner_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let miner_address =
+        Address::new(waglaylad1.network.into(), waglayla_addresses::Version::PubKey, &miner_pk.x_only_public_key().0.serialize());
+    let miner_schnorr_key = secp256k1::Keypair::from_secret_key(secp256k1::SECP256K1, &miner_sk);
+    let blank_address = Address::new(waglaylad1.network.into(), waglayla_addresses::Version::PubKey, &[0; 32]);
+
+    // Mine blocks to the full node until some of the miner rewards reach maturity
+    for _ in 0..coinbase_maturity {
+        mine_block(miner_address.clone(), &rpc_client1, &[]).await;
+    }
+    for _ in 0..10 {
+        mine_block(blank_address.clone(), &rpc_client1, &[]).await;
+    }
+    let utxos = fetch_spendable_utxos(&rpc_client1, miner_address.clone(), coinbase_maturity).await;
+    assert!(!utxos.is_empty());
+
+    // Connect the light node to the full node
+    let mut waglaylad2 = Daemon::new_random_with_args(light_args, total_fd_limit);
+    let rpc_client2 = waglaylad2.start().await;
+    rpc_client2.add_peer(format!("127.0.0.1:{}", waglaylad1.p2p_port).try_into().unwrap(), true).await.unwrap();
+    let check_client = rpc_client2.clone();
+    wait_for(
+        50,
+        20,
+        move || {
+            async fn peer_connected(client: GrpcClient) -> bool {
+                client.get_connected_peer_info().await.unwrap().peer_info.len() == 1
+            }
+            Box::pin(peer_connected(check_client.clone()))
+        },
+        "the nodes did not connect to each other",
+    )
+    .await;
+
+    // A block relayed on top of the chain unknown to the light node triggers the header sync
+    mine_block(blank_address.clone(), &rpc_client1, &[]).await;
+    let header_count = rpc_client1.get_block_dag_info().await.unwrap().header_count;
+    let check_client = rpc_client2.clone();
+    wait_for(
+        100,
+        100,
+        move || {
+            async fn headers_synced(client: GrpcClient, header_count: u64) -> bool {
+                client.get_block_dag_info().await.unwrap().header_count == header_count
+            }
+            Box::pin(headers_synced(check_client.clone(), header_count))
+        },
+        "the light node did not sync the headers of the full node",
+    )
+    .await;
+
+    // Submit a transaction to the light node and expect the full node to receive it
+    let transaction = generate_tx(miner_schnorr_key, &utxos[0..1], utxos[0].1.amount / 2, 1, &blank_address);
+    let transaction_id = rpc_client2.submit_transaction((&transaction).into(), false).await.unwrap();
+    assert_eq!(transaction_id, transaction.id());
+    let check_client = rpc_client1.clone();
+    wait_for(
+        50,
+        40,
+        move || {
+            async fn transaction_in_mempool(client: GrpcClient, transaction_id: RpcTransactionId) -> bool {
+                client.get_mempool_entry(transaction_id, false, false).await.is_ok()
+            }
+            Box::pin(transaction_in_mempool(check_client.clone(), transaction_id))
+        },
+        "the transaction submitted to the light node was not relayed to the full node",
+    )
+    .await;
+
+    rpc_client2.disconnect().await.unwrap();
+    drop(rpc_client2);
+    waglaylad2.shutdown();
+    rpc_client1.disconnect().await.unwrap();
+    drop(rpc_client1);
+    waglaylad1.shutdown();
+}
+
 // The following test runtime parameters are required for a graceful shutdown of the gRPC server
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_cleaning_test() {
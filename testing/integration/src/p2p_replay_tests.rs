@@ -0,0 +1,129 @@
+use crate::common::{
+    daemon::Daemon,
+    utils::{mine_block, wait_for},
+};
+use waglayla_addresses::{Address, Version};
+use waglayla_alloc::init_allocator_with_default_settings;
+use waglayla_grpc_client::GrpcClient;
+use waglayla_hashes::Hash;
+use waglayla_p2p_lib::{
+    capture::{Capture, CAPTURE_FILE_EXTENSION},
+    replay::{ReplayOptions, Replayer},
+    WaglayladMessagePayloadType,
+};
+use waglayla_rpc_core::api::rpc::RpcApi;
+use waglaylad_lib::args::Args;
+use std::{path::Path, time::Duration};
+
+const BLOCK_COUNT: usize = 30;
+
+async fn wait_for_sink(client: &GrpcClient, sink: Hash, panic_message: &'static str) {
+    let client = client.clone();
+    wait_for(
+        100,
+        100,
+        move || {
+            async fn has_sink(client: GrpcClient, sink: Hash) -> bool {
+                client.get_block_dag_info().await.unwrap().sink == sink
+            }
+            Box::pin(has_sink(client.clone(), sink))
+        },
+        panic_message,
+    )
+    .await;
+}
+
+/// Loads the capture of the outbound connection recorded in `dir`, once the capture writer has written the IBD blocks
+async fn load_outbound_capture(dir: &Path) -> Capture {
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let path = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.to_string_lossy().ends_with(&format!("-out.{}", CAPTURE_FILE_EXTENSION)));
+        let Some(capture) = path.and_then(|path| Capture::load(&path).ok()) else { continue };
+        if capture
+            .inbound()
+            .any(|r| r.message.payload.as_ref().map(WaglayladMessagePayloadType::from) == Some(WaglayladMessagePayloadType::IbdBlock))
+        {
+            return capture;
+        }
+    }
+    panic!("the IBD capture was not written");
+}
+
+/// Offline IBD regression test driven by a capture file.
+///
+/// A node syncing from a live peer through IBD records the session with `--p2p-capture`. The peer is then shut down
+/// and the capture alone is replayed against a fresh node, which is expected to sync to the same DAG.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn p2p_capture_ibd_replay_test() {
+    init_allocator_with_default_settings();
+    waglayla_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+    let capture_dir = tempfile::tempdir().unwrap();
+
+    // Node #1 mines a chain while isolated
+    let mut waglaylad1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
+    let rpc_client1 = waglaylad1.start().await;
+    let pay_address = Address::new(waglaylad1.network.into(), Version::PubKey, &[0; 32]);
+    for _ in 0..BLOCK_COUNT {
+        mine_block(pay_address.clone(), &rpc_client1, &[]).await;
+    }
+
+    // Node #2 connects to node #1 while capturing its traffic
+    let capture_args = Args { p2p_capture: Some(capture_dir.path().to_str().unwrap().to_owned()), ..args.clone() };
+    let mut waglaylad2 = Daemon::new_random_with_args(capture_args, total_fd_limit);
+    let rpc_client2 = waglaylad2.start().await;
+    rpc_client2.add_peer(format!("127.0.0.1:{}", waglaylad1.p2p_port).try_into().unwrap(), true).await.unwrap();
+    let check_client = rpc_client2.clone();
+    wait_for(
+        50,
+        20,
+        move || {
+            async fn peer_connected(client: GrpcClient) -> bool {
+                client.get_connected_peer_info().await.unwrap().peer_info.len() == 1
+            }
+            Box::pin(peer_connected(check_client.clone()))
+        },
+        "the nodes did not connect to each other",
+    )
+    .await;
+
+    // A block relayed on top of the chain unknown to node #2 triggers IBD
+    mine_block(pay_address, &rpc_client1, &[]).await;
+    let expected = rpc_client1.get_block_dag_info().await.unwrap();
+    wait_for_sink(&rpc_client2, expected.sink, "node #2 did not sync from node #1").await;
+
+    // From now on only the capture is available
+    rpc_client2.disconnect().await.unwrap();
+    drop(rpc_client2);
+    waglaylad2.shutdown();
+    rpc_client1.disconnect().await.unwrap();
+    drop(rpc_client1);
+    waglaylad1.shutdown();
+
+    let capture = load_outbound_capture(capture_dir.path()).await;
+
+    // Replay the capture against a fresh node, the replayer acting as node #1
+    let mut waglaylad3 = Daemon::new_random_with_args(args, total_fd_limit);
+    let rpc_client3 = waglaylad3.start().await;
+    let summary = Replayer::new(capture, ReplayOptions::default()).run(&format!("127.0.0.1:{}", waglaylad3.p2p_port)).await.unwrap();
+    assert!(summary.sent > 0 && summary.received > 0);
+
+    wait_for_sink(&rpc_client3, expected.sink, "node #3 did not sync from the replayed capture").await;
+    let dag_info = rpc_client3.get_block_dag_info().await.unwrap();
+    assert_eq!(dag_info.block_count, expected.block_count);
+
+    rpc_client3.disconnect().await.unwrap();
+    drop(rpc_client3);
+    waglaylad3.shutdown();
+}
@@ -4,14 +4,14 @@ use crate::common::{client_notify::ChannelNotify, daemon::Daemon};
 use futures_util::future::try_join_all;
 use waglayla_addresses::{Address, Prefix, Version};
 use waglayla_consensus::params::SIMNET_GENESIS;
-use waglayla_consensus_core::{constants::MAX_SOMPI, subnets::SubnetworkId, tx::Transaction};
+use waglayla_consensus_core::{constants::MAX_SOMPI, merkle::TransactionReceipt, subnets::SubnetworkId, tx::Transaction};
 use waglayla_core::info;
 use waglayla_grpc_core::ops::WaglayladPayloadOps;
 use waglayla_hashes::Hash;
 use waglayla_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
+        BlockAddedScope, ChainReorgScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
         SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
@@ -546,6 +546,109 @@ async fn sanity_test() {
                 })
             }
 
+            WaglayladPayloadOps::ExportAddressBook => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let _ = rpc_client.export_address_book_call(ExportAddressBookRequest {}).await.unwrap();
+                })
+            }
+
+            WaglayladPayloadOps::ImportAddressBook => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let address = RpcPeerAddress::from_str("9.8.7.6:16111").unwrap();
+                    let record = RpcAddressRecord {
+                        address,
+                        connection_failed_count: 1,
+                        last_seen: 1_700_000_000_000,
+                        last_attempt: 0,
+                        last_success: 1_700_000_000_000,
+                        latency: Some(120),
+                        protocol_version: 7,
+                        services: 0,
+                        user_agent: "/waglaylad:0.14.1/".to_string(),
+                        source: None,
+                    };
+                    let loopback =
+                        RpcAddressRecord { address: RpcPeerAddress::from_str("127.0.0.1:16111").unwrap(), ..record.clone() };
+
+                    let response = rpc_client
+                        .import_address_book_call(ImportAddressBookRequest::new(vec![record.clone(), loopback]))
+                        .await
+                        .unwrap();
+                    assert_eq!(response.imported, 1);
+
+                    // Importing an already known address is a no-op
+                    let response = rpc_client.import_address_book_call(ImportAddressBookRequest::new(vec![record])).await.unwrap();
+                    assert_eq!(response.imported, 0);
+
+                    let response = rpc_client.export_address_book_call(ExportAddressBookRequest {}).await.unwrap();
+                    let exported = response.records.iter().find(|x| x.address == address).unwrap();
+                    assert_eq!(exported.latency, Some(120));
+                    assert_eq!(exported.last_success, 1_700_000_000_000);
+                })
+            }
+
+            WaglayladPayloadOps::GetTransactionInclusionProof => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let result = rpc_client.get_transaction_inclusion_proof(0.into(), None).await;
+                    assert!(result.is_err());
+
+                    let block = rpc_client.get_block(SIMNET_GENESIS.hash, true).await.unwrap();
+                    let coinbase_id = Transaction::try_from(&block.transactions[0]).unwrap().id();
+                    let response = rpc_client.get_transaction_inclusion_proof(coinbase_id, Some(SIMNET_GENESIS.hash)).await.unwrap();
+                    assert_eq!(response.block_hash, SIMNET_GENESIS.hash);
+
+                    // The receipt is verifiable without the node
+                    let mut receipt = TransactionReceipt::try_from(&response).unwrap();
+                    assert_eq!(receipt.verify(), Some(SIMNET_GENESIS.hash));
+                    receipt.inclusion.transaction.payload.push(0);
+                    assert_eq!(receipt.verify(), None);
+                })
+            }
+
+            WaglayladPayloadOps::GetUtxoSetAtBlock => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The test node is not archival
+                    let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])];
+                    let result = rpc_client.get_utxo_set_at_block(addresses, SIMNET_GENESIS.hash).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            WaglayladPayloadOps::GetBalanceAtDaaScore => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The test node is not archival
+                    let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])];
+                    let result = rpc_client.get_balance_at_daa_score(addresses, 0).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            WaglayladPayloadOps::GetDagExport => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let blocks = rpc_client.get_dag_export(0, SIMNET_GENESIS.daa_score, false).await.unwrap();
+                    assert_eq!(blocks.first().map(|block| block.hash), Some(SIMNET_GENESIS.hash));
+                    assert!(blocks[0].is_chain_block);
+
+                    let blocks = rpc_client.get_dag_export(u64::MAX, u64::MAX, true).await.unwrap();
+                    assert!(blocks.is_empty());
+                })
+            }
+
+            WaglayladPayloadOps::GetPruningPointProof => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The pruning point of a fresh node is genesis, which has an empty proof
+                    let proof = rpc_client.get_pruning_point_proof().await.unwrap();
+                    assert!(proof.is_empty());
+                })
+            }
+
             WaglayladPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -607,6 +710,13 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            WaglayladPayloadOps::NotifyChainReorg => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, ChainReorgScope {}.into()).await.unwrap();
+                })
+            }
             WaglayladPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
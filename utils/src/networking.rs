@@ -347,6 +347,40 @@ impl Display for ContextualNetAddress {
         }
     }
 }
+
+/// An IP network in CIDR notation (e.g. `192.168.1.0/24`). A plain IP address is parsed as a single host network.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct IpNetwork(pub IpNet);
+
+impl IpNetwork {
+    /// Returns whether `ip` belongs to this network
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.contains(ip)
+    }
+}
+
+impl From<IpAddr> for IpNetwork {
+    fn from(ip: IpAddr) -> Self {
+        Self(ip.into())
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match IpNet::from_str(s) {
+            Ok(net) => Ok(Self(net)),
+            Err(_) => Ok(IpAddr::from_str(s)?.into()),
+        }
+    }
+}
+
+impl Display for IpNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, Default)]
 #[repr(transparent)]
 pub struct PeerId(pub Uuid);
@@ -462,6 +496,17 @@ mod tests {
         assert!(addr_v6.is_ok());
     }
 
+    #[test]
+    fn test_ip_network_from_str() {
+        let net = IpNetwork::from_str("192.168.1.0/24").unwrap();
+        assert!(net.contains(&IpAddr::from_str("192.168.1.77").unwrap()));
+        assert!(!net.contains(&IpAddr::from_str("192.168.2.1").unwrap()));
+        let host = IpNetwork::from_str("::1").unwrap();
+        assert!(host.contains(&IpAddr::from_str("::1").unwrap()));
+        assert_eq!(host.to_string(), "::1/128");
+        assert!(IpNetwork::from_str("192.168.1.0/33").is_err());
+    }
+
     #[test]
     fn test_prefix_bucket() {
         let prefix_bytes: [u8; 2] = [42u8, 43u8];
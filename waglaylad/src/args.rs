@@ -1,15 +1,17 @@
 use clap::{arg, Arg, ArgAction, Command};
 use waglayla_consensus_core::{
-    config::Config,
+    config::{params::Params, params_file::NetworkParamsFile, Config, DEFAULT_CHAIN_REORG_NOTIFICATION_DEPTH},
     network::{NetworkId, NetworkType},
 };
 use waglayla_core::waglaylad_env::version;
+use waglayla_hashes::Hash;
 use waglayla_notify::address::tracker::Tracker;
-use waglayla_utils::networking::ContextualNetAddress;
+use waglayla_p2p_lib::PinnedPeer;
+use waglayla_utils::networking::{ContextualNetAddress, IpNetwork};
 use waglayla_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
-use std::{ffi::OsString, fs};
+use std::{ffi::OsString, fmt::Display, fs, str::FromStr};
 use toml::from_str;
 
 #[cfg(feature = "devnet-prealloc")]
@@ -60,6 +62,10 @@ pub struct Args {
     pub outbound_target: usize,
     #[serde(rename = "maxinpeers")]
     pub inbound_limit: usize,
+    #[serde(rename = "blockrelayoutpeers")]
+    pub block_relay_only_target: usize,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub whitelist: Vec<IpNetwork>,
     #[serde(rename = "rpcmaxclients")]
     pub rpc_max_clients: usize,
     pub max_tracked_addresses: usize,
@@ -70,7 +76,13 @@ pub struct Args {
     pub testnet_suffix: u32,
     pub devnet: bool,
     pub simnet: bool,
+    /// Path of a TOML or JSON file defining the consensus params of a custom network
+    pub network_params: Option<String>,
+    /// The params loaded from [`Args::network_params`]
+    #[serde(skip)]
+    pub custom_params: Option<Params>,
     pub archival: bool,
+    pub light: bool,
     pub sanity: bool,
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
@@ -87,11 +99,46 @@ pub struct Args {
     pub prealloc_amount: u64,
 
     pub disable_upnp: bool,
+    pub p2p_upload_limit: Option<u64>,
+    pub p2p_capture: Option<String>,
+    pub p2p_identity_key: Option<String>,
+    pub p2p_pin: Vec<String>,
     #[serde(rename = "nodnsseed")]
     pub disable_dns_seeding: bool,
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    pub reorg_notify_depth: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub assume_valid: Option<AssumeValid>,
+}
+
+/// The `--assume-valid` argument, overriding the assume-valid block of the network params
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssumeValid {
+    /// Validate all transaction scripts
+    None,
+    Block(Hash),
+}
+
+impl FromStr for AssumeValid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            _ => s.parse().map(Self::Block).map_err(|_| format!("expected a block hash or `none`, got `{s}`")),
+        }
+    }
+}
+
+impl Display for AssumeValid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Block(hash) => write!(f, "{hash}"),
+        }
+    }
 }
 
 impl Default for Args {
@@ -107,6 +154,8 @@ impl Default for Args {
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
+            block_relay_only_target: 0,
+            whitelist: vec![],
             rpc_max_clients: 128,
             max_tracked_addresses: 0,
             enable_unsynced_mining: false,
@@ -115,7 +164,10 @@ impl Default for Args {
             testnet_suffix: 10,
             devnet: false,
             simnet: false,
+            network_params: None,
+            custom_params: None,
             archival: false,
+            light: false,
             sanity: false,
             logdir: None,
             rpclisten: None,
@@ -139,9 +191,15 @@ impl Default for Args {
             prealloc_amount: 1_000_000,
 
             disable_upnp: false,
+            p2p_upload_limit: None,
+            p2p_capture: None,
+            p2p_identity_key: None,
+            p2p_pin: vec![],
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            reorg_notify_depth: DEFAULT_CHAIN_REORG_NOTIFICATION_DEPTH,
+            assume_valid: None,
         }
     }
 }
@@ -154,13 +212,23 @@ impl Args {
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
         config.is_archival = self.archival;
+        config.is_light = self.light;
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config.user_agent_comments.clone_from(&self.user_agent_comments);
         config.block_template_cache_lifetime = self.block_template_cache_lifetime;
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
+        config.p2p_upload_limit = self.p2p_upload_limit;
+        config.p2p_capture_dir.clone_from(&self.p2p_capture);
+        config.p2p_whitelist.clone_from(&self.whitelist);
         config.ram_scale = self.ram_scale;
+        config.chain_reorg_notification_depth = self.reorg_notify_depth;
+        match self.assume_valid {
+            Some(AssumeValid::None) => config.params.assume_valid = None,
+            Some(AssumeValid::Block(hash)) => config.params.assume_valid = Some(hash),
+            None => {}
+        }
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -183,6 +251,9 @@ impl Args {
     }
 
     pub fn network(&self) -> NetworkId {
+        if let Some(params) = self.custom_params.as_ref() {
+            return params.net;
+        }
         match (self.testnet, self.devnet, self.simnet) {
             (false, false, false) => NetworkId::new(NetworkType::Mainnet),
             (true, false, false) => NetworkId::with_suffix(NetworkType::Testnet, self.testnet_suffix),
@@ -295,6 +366,23 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Max number of inbound peers (default: 128)."),
         )
+        .arg(
+            Arg::new("blockrelayoutpeers")
+                .long("blockrelayoutpeers")
+                .value_name("blockrelayoutpeers")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of additional block-relay-only outbound peers, which do not take part in transaction and address gossip (default: 0)."),
+        )
+        .arg(
+            Arg::new("whitelist")
+                .long("whitelist")
+                .value_name("IP[/BITS]")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(IpNetwork))
+                .help("Add an IP network or IP whose peers are never banned nor disconnected due to the inbound limit, and always receive relayed transactions (eg. 192.168.1.0/24 or ::1)."),
+        )
         .arg(
             Arg::new("rpcmaxclients")
                 .long("rpcmaxclients")
@@ -333,7 +421,16 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         )
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
+        .arg(
+            Arg::new("network-params")
+                .long("network-params")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Run a custom network whose genesis and consensus params are defined in a TOML or JSON file (a .json extension selects JSON). Cannot be combined with --testnet, --devnet or --simnet."),
+        )
         .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(arg!(--light "Run as a header-only light node: syncs headers on top of a verified pruning point proof, without block bodies nor a UTXO set"))
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
         .arg(arg!(--yes "Answer yes to all interactive console questions"))
         .arg(
@@ -361,6 +458,37 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Interval in seconds for performance metrics collection."),
         )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
+        .arg(
+            Arg::new("p2p-upload-limit")
+                .long("p2p-upload-limit")
+                .value_name("BYTES_PER_SEC")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Limit the upload rate (bytes/sec) used for serving IBD blocks and pruning point UTXO chunks to syncing peers. Block and transaction relay are not throttled."),
+        )
+        .arg(
+            Arg::new("p2p-capture")
+                .long("p2p-capture")
+                .value_name("DIR")
+                .require_equals(true)
+                .help("Record all P2P messages exchanged with each peer to capture files in DIR (for debugging, see waglayla_p2p_replay)."),
+        )
+        .arg(
+            Arg::new("p2p-identity-key")
+                .long("p2p-identity-key")
+                .value_name("FILE")
+                .require_equals(true)
+                .help("Sign the encrypted P2P key exchange with the identity key stored in FILE (generated if missing), so that peers can pin it."),
+        )
+        .arg(
+            Arg::new("p2p-pin")
+                .long("p2p-pin")
+                .value_name("PUBKEY@IP")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(|s: &str| s.parse::<PinnedPeer>().map(|_| s.to_owned()))
+                .help("Require the peer at IP to use the encrypted P2P transport and to authenticate with the identity key PUBKEY."),
+        )
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
         .arg(
@@ -371,6 +499,22 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("reorg-notify-depth")
+                .long("reorg-notify-depth")
+                .value_name("CHAIN_BLOCKS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help(format!("Log a warning and emit a ChainReorg notification for chain reorgs removing at least this number of chain blocks (default: {DEFAULT_CHAIN_REORG_NOTIFICATION_DEPTH}).")),
+        )
+        .arg(
+            Arg::new("assume-valid")
+                .long("assume-valid")
+                .value_name("BLOCK_HASH")
+                .require_equals(true)
+                .value_parser(|s: &str| s.parse::<AssumeValid>())
+                .help("Skip transaction script validation along the selected chain of this block during sync, as long as the UTXO commitments match, while still performing all other validations. Overrides the network default, use `none` for validating all scripts."),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -411,7 +555,7 @@ impl Args {
             })?;
         }
 
-        let args = Args {
+        let mut args = Args {
             appdir: m.get_one::<String>("appdir").cloned().or(defaults.appdir),
             logdir: m.get_one::<String>("logdir").cloned().or(defaults.logdir),
             no_log_files: arg_match_unwrap_or::<bool>(&m, "nologfiles", defaults.no_log_files),
@@ -427,6 +571,8 @@ impl Args {
             listen: m.get_one::<ContextualNetAddress>("listen").cloned().or(defaults.listen),
             outbound_target: arg_match_unwrap_or::<usize>(&m, "outpeers", defaults.outbound_target),
             inbound_limit: arg_match_unwrap_or::<usize>(&m, "maxinpeers", defaults.inbound_limit),
+            block_relay_only_target: arg_match_unwrap_or::<usize>(&m, "blockrelayoutpeers", defaults.block_relay_only_target),
+            whitelist: arg_match_many_unwrap_or::<IpNetwork>(&m, "whitelist", defaults.whitelist),
             rpc_max_clients: arg_match_unwrap_or::<usize>(&m, "rpcmaxclients", defaults.rpc_max_clients),
             max_tracked_addresses: arg_match_unwrap_or::<usize>(&m, "max-tracked-addresses", defaults.max_tracked_addresses),
             reset_db: arg_match_unwrap_or::<bool>(&m, "reset-db", defaults.reset_db),
@@ -437,7 +583,10 @@ impl Args {
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
             simnet: arg_match_unwrap_or::<bool>(&m, "simnet", defaults.simnet),
+            network_params: m.get_one::<String>("network-params").cloned().or(defaults.network_params),
+            custom_params: None,
             archival: arg_match_unwrap_or::<bool>(&m, "archival", defaults.archival),
+            light: arg_match_unwrap_or::<bool>(&m, "light", defaults.light),
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
@@ -447,9 +596,15 @@ impl Args {
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
+            p2p_upload_limit: m.get_one::<u64>("p2p-upload-limit").cloned().or(defaults.p2p_upload_limit),
+            p2p_capture: m.get_one::<String>("p2p-capture").cloned().or(defaults.p2p_capture),
+            p2p_identity_key: m.get_one::<String>("p2p-identity-key").cloned().or(defaults.p2p_identity_key),
+            p2p_pin: arg_match_many_unwrap_or::<String>(&m, "p2p-pin", defaults.p2p_pin),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            reorg_notify_depth: arg_match_unwrap_or::<u64>(&m, "reorg-notify-depth", defaults.reorg_notify_depth),
+            assume_valid: m.get_one::<AssumeValid>("assume-valid").cloned().or(defaults.assume_valid),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -463,10 +618,31 @@ impl Args {
             println!("\nNOTE: The flag --enable-mainnet-mining is deprecated and defaults to true also w/o explicit setting\n")
         }
 
+        if let Some(path) = args.network_params.as_ref() {
+            args.custom_params = Some(load_network_params(path)?);
+        }
+
         Ok(args)
     }
 }
 
+/// Loads and validates the params of a custom network from a TOML file, or from a JSON file if the extension is `.json`
+fn load_network_params(path: &str) -> Result<Params, clap::Error> {
+    let invalid = |reason: String| {
+        clap::Error::raw(
+            clap::error::ErrorKind::ValueValidation,
+            format!("failed loading network params from {path}, reason: {reason}\n"),
+        )
+    };
+    let content = fs::read_to_string(path)?;
+    let file: NetworkParamsFile = if path.ends_with(".json") {
+        serde_json::from_str(&content).map_err(|err| invalid(err.to_string()))?
+    } else {
+        from_str(&content).map_err(|err| invalid(err.message().to_string()))?
+    };
+    Params::try_from(file).map_err(|err| invalid(err.to_string()))
+}
+
 use clap::parser::ValueSource::DefaultValue;
 use std::marker::{Send, Sync};
 fn arg_match_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatches, arg_id: &str, default: T) -> T {
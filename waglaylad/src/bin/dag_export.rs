@@ -0,0 +1,115 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    process::exit,
+    str::FromStr,
+    sync::Arc,
+};
+use waglayla_consensus::consensus::{factory::MultiConsensusManagementStore, storage::ConsensusStorage};
+use waglayla_consensus_core::{
+    config::ConfigBuilder,
+    dag_export::{write_dot, write_jsonl, DagExportRange},
+    network::NetworkId,
+};
+use waglayla_database::prelude::ConnBuilder;
+
+const USAGE: &str = "\
+Usage: waglayla_dag_export <DATA_DIR> <daa|blue> <START> <END> [OPTIONS]
+
+Exports the selected chain blocks with a DAA score (or blue score) within [START, END], along with the blocks
+they merge, from a node data directory (e.g. ~/.waglayla/waglayla-mainnet/datadir). The directory must not
+be in use by a running node, so copy it first, or use `rpc get-dag-export` of waglayla-cli against a live node.
+
+Options:
+  --format=<jsonl|dot>    Output format (default: jsonl)
+  --output=<FILE>         Output file (default: stdout)
+  --network=<NETWORK_ID>  Network of the data directory (default: mainnet)";
+
+const CONSENSUS_DB: &str = "consensus";
+const META_DB: &str = "meta";
+
+enum Format {
+    Jsonl,
+    Dot,
+}
+
+fn main() {
+    let mut positional = Vec::new();
+    let mut format = Format::Jsonl;
+    let mut output = None;
+    let mut network = NetworkId::from_str("mainnet").unwrap();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--help" => exit_with_usage(0),
+            "--format=jsonl" => format = Format::Jsonl,
+            "--format=dot" => format = Format::Dot,
+            _ if arg.starts_with("--output=") => output = Some(PathBuf::from(&arg["--output=".len()..])),
+            _ if arg.starts_with("--network=") => match NetworkId::from_str(&arg["--network=".len()..]) {
+                Ok(network_id) => network = network_id,
+                Err(_) => exit_with_usage(1),
+            },
+            _ if arg.starts_with("--") => exit_with_usage(1),
+            _ => positional.push(arg),
+        }
+    }
+    let [data_dir, score_type, start, end] = positional.as_slice() else { exit_with_usage(1) };
+    let (Ok(start), Ok(end)) = (start.parse(), end.parse()) else { exit_with_usage(1) };
+    let range = match score_type.as_str() {
+        "daa" => DagExportRange::DaaScore { start, end },
+        "blue" => DagExportRange::BlueScore { start, end },
+        _ => exit_with_usage(1),
+    };
+
+    let data_dir = PathBuf::from(data_dir);
+    let meta_db_dir = data_dir.join(META_DB);
+    if !meta_db_dir.exists() {
+        eprintln!("No meta DB found at {}", meta_db_dir.display());
+        exit(1);
+    }
+    let meta_db = ConnBuilder::default().with_db_path(meta_db_dir).with_create_if_missing(false).with_files_limit(8).build().unwrap();
+    let Some(consensus_dir_name) = MultiConsensusManagementStore::new(meta_db).active_consensus_dir_name().unwrap() else {
+        eprintln!("The data directory holds no active consensus");
+        exit(1);
+    };
+    let consensus_db_dir = data_dir.join(CONSENSUS_DB).join(consensus_dir_name);
+    // Progress is reported to stderr so that the export can be piped from stdout
+    eprintln!("Reading consensus DB {}", consensus_db_dir.display());
+    let db = ConnBuilder::default().with_db_path(consensus_db_dir).with_create_if_missing(false).with_files_limit(64).build().unwrap();
+
+    let config = Arc::new(ConfigBuilder::new(network.into()).build());
+    let storage = ConsensusStorage::new(db, config);
+    let blocks = match storage.export_dag(range, usize::MAX) {
+        Ok(blocks) => blocks,
+        Err(err) => {
+            eprintln!("DAG export failed: {err}");
+            exit(1);
+        }
+    };
+    eprintln!("Exporting {} blocks ({} chain blocks)", blocks.len(), blocks.iter().filter(|block| block.is_chain_block).count());
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => match File::create(&path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(err) => {
+                eprintln!("Failed creating {}: {err}", path.display());
+                exit(1);
+            }
+        },
+        None => Box::new(BufWriter::new(std::io::stdout().lock())),
+    };
+    let result = match format {
+        Format::Jsonl => write_jsonl(&mut writer, &blocks),
+        Format::Dot => write_dot(&mut writer, &blocks),
+    };
+    if let Err(err) = result.and_then(|_| writer.flush()) {
+        eprintln!("Failed writing the export: {err}");
+        exit(1);
+    }
+}
+
+/// Prints the usage to stderr, keeping stdout for the export itself, and exits with `code`
+fn exit_with_usage(code: i32) -> ! {
+    eprintln!("{USAGE}");
+    exit(code)
+}
@@ -0,0 +1,188 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    process::exit,
+    str::FromStr,
+    sync::Arc,
+};
+use waglayla_consensus::consensus::Consensus;
+use waglayla_consensus_core::{
+    api::ConsensusApi,
+    config::ConfigBuilder,
+    hashing,
+    network::NetworkId,
+    pruning::{diff_pruning_point_proofs, PruningPointProof},
+    BlockHashSet,
+};
+use waglayla_consensus_notify::root::ConsensusNotificationRoot;
+use waglayla_database::{create_temp_db, prelude::ConnBuilder};
+use waglayla_grpc_client::GrpcClient;
+use waglayla_rpc_core::api::rpc::RpcApi;
+
+const USAGE: &str = "\
+Usage: waglayla_pruning_proof <COMMAND> [OPTIONS]
+
+Inspects pruning point proofs in order to debug proof rejections during IBD.
+
+Commands:
+  fetch <RPC_SERVER> <FILE>  Fetches the pruning point proof of a node and saves it as JSON
+  verify <PROOF>             Verifies a proof in isolation against the genesis and params of the network
+                             and prints statistics of each proof level
+  diff <PROOF> <PROOF>       Compares two proofs level by level and prints where they diverge. Exits
+                             with code 1 if the proofs differ
+
+A <PROOF> is either a file saved by `fetch` or the gRPC address of a node, e.g. grpc://127.0.0.1:12110.
+
+Options:
+  --network=<NETWORK_ID>  Network of the proofs (default: mainnet)";
+
+#[tokio::main]
+async fn main() {
+    let mut positional = Vec::new();
+    let mut network = NetworkId::from_str("mainnet").unwrap();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--help" => exit_with_usage(0),
+            _ if arg.starts_with("--network=") => match NetworkId::from_str(&arg["--network=".len()..]) {
+                Ok(network_id) => network = network_id,
+                Err(_) => exit_with_usage(1),
+            },
+            _ if arg.starts_with("--") => exit_with_usage(1),
+            _ => positional.push(arg),
+        }
+    }
+
+    let result = match positional.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["fetch", rpc_server, path] => fetch(rpc_server, path).await,
+        ["verify", source] => verify(network, source).await,
+        ["diff", first, second] => diff(first, second).await,
+        _ => exit_with_usage(1),
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => exit(1),
+        Err(err) => {
+            eprintln!("{err}");
+            exit(1);
+        }
+    }
+}
+
+async fn fetch(rpc_server: &str, path: &str) -> Result<bool, String> {
+    let proof = load_proof(rpc_server).await?;
+    let mut writer = BufWriter::new(File::create(path).map_err(|err| format!("Failed creating {path}: {err}"))?);
+    serde_json::to_writer(&mut writer, &proof)
+        .map_err(|err| err.to_string())
+        .and_then(|_| writer.flush().map_err(|err| err.to_string()))
+        .map_err(|err| format!("Failed writing the proof: {err}"))?;
+    eprintln!("Saved the proof to {path}");
+    Ok(true)
+}
+
+async fn verify(network: NetworkId, source: &str) -> Result<bool, String> {
+    let proof = load_proof(source).await?;
+    let Some(pruning_point) = proof.first().and_then(|level| level.last()) else {
+        println!("The proof is empty, i.e., the pruning point is genesis");
+        return Ok(true);
+    };
+    println!("Pruning point {} (DAA score {}, blue score {})", pruning_point.hash, pruning_point.daa_score, pruning_point.blue_score);
+
+    // The proof is validated by a fresh consensus at genesis, exactly as done by a syncing node
+    let config = Arc::new(ConfigBuilder::new(network.into()).build());
+    let (db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    let (dummy_notification_sender, _) = async_channel::unbounded();
+    let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
+    let consensus = Consensus::new(db, config, Default::default(), notification_root, Default::default(), Default::default(), 0);
+
+    let stats = consensus.inspect_pruning_proof(&proof);
+    let verdict = match &stats {
+        Ok(_) => consensus.validate_pruning_proof(&proof),
+        Err(_) => Ok(()),
+    };
+    drop(consensus);
+    drop(db_lifetime);
+
+    let stats = stats.map_err(|err| format!("The proof is invalid: {err}"))?;
+    for level in stats {
+        println!(
+            "level {:>3}: {:>5} headers, DAA scores {}..{}, selected tip {} (blue score {}, blue work {})",
+            level.level,
+            level.header_count,
+            level.lowest_daa_score,
+            level.highest_daa_score,
+            level.selected_tip,
+            level.selected_tip_blue_score,
+            level.selected_tip_blue_work
+        );
+    }
+    verdict.map_err(|err| format!("The proof is valid in isolation but is rejected by a syncing node: {err}"))?;
+    println!("The proof is valid");
+    Ok(true)
+}
+
+async fn diff(first_source: &str, second_source: &str) -> Result<bool, String> {
+    let first = load_proof(first_source).await?;
+    let second = load_proof(second_source).await?;
+    let pruning_points = (first.first().and_then(|level| level.last()), second.first().and_then(|level| level.last()));
+    match pruning_points {
+        (Some(first_pp), Some(second_pp)) if first_pp.hash == second_pp.hash => println!("Same pruning point {}", first_pp.hash),
+        (first_pp, second_pp) => println!(
+            "Different pruning points: {} vs {}",
+            first_pp.map_or("genesis".to_string(), |header| header.hash.to_string()),
+            second_pp.map_or("genesis".to_string(), |header| header.hash.to_string())
+        ),
+    }
+
+    let diffs = diff_pruning_point_proofs(&first, &second);
+    for level in diffs.iter() {
+        let (first_count, second_count) = level.header_counts;
+        if level.is_identical() {
+            println!("level {:>3}: identical ({} headers)", level.level, first_count);
+            continue;
+        }
+        println!(
+            "level {:>3}: diverges after {} common headers ({} vs {} headers, {} only in first, {} only in second)",
+            level.level,
+            level.common_prefix_len,
+            first_count,
+            second_count,
+            level.only_in_first.len(),
+            level.only_in_second.len()
+        );
+        let first_divergent = first.get(level.level as usize).and_then(|headers| headers.get(level.common_prefix_len));
+        let second_divergent = second.get(level.level as usize).and_then(|headers| headers.get(level.common_prefix_len));
+        for (name, header) in [("first", first_divergent), ("second", second_divergent)] {
+            if let Some(header) = header {
+                println!("    first divergent header in {name}: {} (DAA score {})", header.hash, header.daa_score);
+            }
+        }
+    }
+    Ok(diffs.iter().all(|level| level.is_identical()))
+}
+
+async fn load_proof(source: &str) -> Result<PruningPointProof, String> {
+    let proof: PruningPointProof = if source.starts_with("grpc://") {
+        let client = GrpcClient::connect(source.to_string()).await.map_err(|err| format!("Failed connecting to {source}: {err}"))?;
+        let levels =
+            client.get_pruning_point_proof().await.map_err(|err| format!("Failed fetching the proof from {source}: {err}"))?;
+        client.disconnect().await.ok();
+        levels.into_iter().map(|level| level.into_iter().map(Arc::new).collect()).collect()
+    } else {
+        let file = File::open(source).map_err(|err| format!("Failed opening {source}: {err}"))?;
+        serde_json::from_reader(BufReader::new(file)).map_err(|err| format!("Failed parsing {source}: {err}"))?
+    };
+
+    // Proof files might have been edited by hand, so cached hashes are not trusted
+    if let Some(header) = proof.iter().flatten().find(|header| hashing::header::hash(header) != header.hash) {
+        return Err(format!("Header {} of {source} does not match its hash", header.hash));
+    }
+    let unique = proof.iter().flatten().map(|header| header.hash).collect::<BlockHashSet>().len();
+    eprintln!("Loaded a proof with {} levels and {unique} unique headers from {source}", proof.len());
+    Ok(proof)
+}
+
+/// Prints the usage to stderr, keeping stdout for the command output, and exits with `code`
+fn exit_with_usage(code: i32) -> ! {
+    eprintln!("{USAGE}");
+    exit(code)
+}
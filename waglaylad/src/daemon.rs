@@ -1,4 +1,10 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+    sync::Arc,
+    time::Duration,
+};
 
 use async_channel::unbounded;
 use waglayla_consensus_core::{
@@ -30,6 +36,7 @@ use waglayla_mining::{
     MiningCounters,
 };
 use waglayla_p2p_flows::{flow_context::FlowContext, service::P2pService};
+use waglayla_p2p_lib::{NodeIdentity, PinnedPeer, TransportConfig};
 
 use waglayla_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
 use waglayla_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
@@ -47,6 +54,8 @@ pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 use crate::args::Args;
 
 const DEFAULT_DATA_DIR: &str = "datadir";
+/// Light nodes keep a separate database since it lacks block bodies and the UTXO set
+const DEFAULT_LIGHT_DATA_DIR: &str = "light-datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
 const META_DB: &str = "meta";
@@ -95,9 +104,40 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.light && args.archival {
+        return Err(ConfigError::MixedLightAndArchival);
+    }
+    if args.light && args.utxoindex {
+        return Err(ConfigError::MixedLightAndUtxoIndex);
+    }
+    if args.network_params.is_some() && (args.testnet || args.devnet || args.simnet) {
+        return Err(ConfigError::MixedNetworkParamsAndNetwork);
+    }
+    // Entries coming from a config file do not go through the command line parser
+    for pinned in args.p2p_pin.iter() {
+        pinned.parse::<PinnedPeer>().map_err(|err| ConfigError::InvalidPinnedPeer(pinned.clone(), err))?;
+    }
     Ok(())
 }
 
+/// Loads (or creates) the P2P identity key and builds the encrypted transport settings
+pub fn load_transport_config(args: &Args) -> ConfigResult<TransportConfig> {
+    let identity = args
+        .p2p_identity_key
+        .as_ref()
+        .map(|path| {
+            NodeIdentity::load_or_create(Path::new(path))
+                .map_err(|err| ConfigError::InvalidP2pIdentityKey(path.clone(), err.to_string()))
+        })
+        .transpose()?;
+    let pinned = args
+        .p2p_pin
+        .iter()
+        .map(|pinned| pinned.parse::<PinnedPeer>().map_err(|err| ConfigError::InvalidPinnedPeer(pinned.clone(), err)))
+        .collect::<ConfigResult<Vec<_>>>()?;
+    Ok(TransportConfig::new(identity, pinned))
+}
+
 fn get_user_approval_or_exit(message: &str, approve: bool) {
     if approve {
         return;
@@ -213,9 +253,16 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
         println!("{}", err);
         exit(1);
     }
+    let transport_config = match load_transport_config(args) {
+        Ok(transport_config) => Arc::new(transport_config),
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+    };
 
     let config = Arc::new(
-        ConfigBuilder::new(network.into())
+        ConfigBuilder::new(args.custom_params.clone().unwrap_or_else(|| network.into()))
             .adjust_perf_params_to_consensus_params()
             .apply_args(|config| args.apply_to_config(config))
             .build(),
@@ -224,7 +271,7 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     // TODO: Validate `config` forms a valid set of properties
 
     let app_dir = get_app_dir_from_args(args);
-    let db_dir = app_dir.join(network.to_prefixed()).join(DEFAULT_DATA_DIR);
+    let db_dir = app_dir.join(network.to_prefixed()).join(if args.light { DEFAULT_LIGHT_DATA_DIR } else { DEFAULT_DATA_DIR });
 
     // Print package name and version
     info!("{} v{}", env!("CARGO_PKG_NAME"), version());
@@ -240,6 +287,15 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
             info!("Logs to console only");
         }
     }
+    if let Some(path) = args.network_params.as_ref() {
+        info!("Custom network {} loaded from {} (BPS: {}, genesis: {})", network, path, config.bps(), config.genesis.hash);
+    }
+    if let Some(identity) = transport_config.identity.as_ref() {
+        info!("P2P identity key: {}", identity.public_key());
+    }
+    if let Some(assume_valid) = config.assume_valid {
+        info!("Assume-valid block: {} (transaction scripts along its selected chain are not verified during sync)", assume_valid);
+    }
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
@@ -351,6 +407,7 @@ do you confirm? (answer y/n or pass --yes to the Waglaylad command line to confi
     let p2p_server_addr = args.listen.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_p2p_port());
     // connect_peers means no DNS seeding and no outbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
+    let block_relay_only_target = if connect_peers.is_empty() { args.block_relay_only_target } else { 0 };
     let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
@@ -436,6 +493,7 @@ do you confirm? (answer y/n or pass --yes to the Waglaylad command line to confi
         mining_manager.clone(),
         tick_service.clone(),
         notification_root,
+        transport_config,
     ));
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
@@ -443,6 +501,7 @@ do you confirm? (answer y/n or pass --yes to the Waglaylad command line to confi
         add_peers,
         p2p_server_addr,
         outbound_target,
+        block_relay_only_target,
         args.inbound_limit,
         dns_seeders,
         config.default_p2p_port(),
@@ -39,7 +39,11 @@ pub fn main() {
         }
     }
 
-    let fd_total_budget = fd_budget::limit() - args.rpc_max_clients as i32 - args.inbound_limit as i32 - args.outbound_target as i32;
+    let fd_total_budget = fd_budget::limit()
+        - args.rpc_max_clients as i32
+        - args.inbound_limit as i32
+        - args.outbound_target as i32
+        - args.block_relay_only_target as i32;
     let (core, _) = create_core(args, fd_total_budget);
 
     // Bind the keyboard signal to the core
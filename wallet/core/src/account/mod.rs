@@ -16,17 +16,26 @@ use crate::storage::account::AccountSettings;
 use crate::storage::AccountMetadata;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
-use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
+use crate::tx::{
+    pay_payout_recipients, FeeBumpSummary, Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PayoutReceipt,
+    PayoutRecipient, PayoutSummary, PendingTransaction, Signer, UtxoSelection,
+};
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
-use crate::utxo::UtxoContextBinding;
+use crate::utils::sompi_to_waglayla_string;
+use crate::utxo::{ConsolidationPolicy, ConsolidationSummary, UtxoContextBinding};
 use waglayla_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey, PrivateKeyBytes};
 use waglayla_consensus_client::UtxoEntryReference;
+use waglayla_txscript::extract_script_pub_key_address;
 use waglayla_wallet_keys::derivation::gen0::WalletDerivationManagerV0;
 use workflow_core::abortable::Abortable;
 
 /// Notification callback type used by [`Account::sweep`] and [`Account::send`].
 /// Allows tracking in-flight transactions during transaction generation.
 pub type GenerationNotifier = Arc<dyn Fn(&PendingTransaction) + Send + Sync>;
+/// Notification callback type used by [`Account::payout`]. Invoked as each
+/// payout transaction is submitted with its id and the receipts of the recipients
+/// it pays, which are empty for transactions compounding UTXOs ahead of the payment.
+pub type PayoutNotifier = Arc<dyn Fn(TransactionId, &[PayoutReceipt]) + Send + Sync>;
 /// Scan notification callback type used by [`DerivationCapableAccount::derivation_scan`].
 /// Provides derivation discovery scan progress information.
 pub type ScanNotifier = Arc<dyn Fn(usize, usize, u64, Option<TransactionId>) + Send + Sync>;
@@ -153,6 +162,33 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(())
     }
 
+    fn consolidation_policy(&self) -> Option<ConsolidationPolicy> {
+        self.context().settings.consolidation.clone()
+    }
+
+    /// Store the automatic UTXO consolidation policy of the account (or clear it
+    /// if `policy` is `None`) and register it with the [`UtxoProcessor`].
+    async fn set_consolidation_policy(&self, wallet_secret: &Secret, policy: Option<ConsolidationPolicy>) -> Result<()> {
+        {
+            let mut context = self.context();
+            context.settings.consolidation.clone_from(&policy);
+        }
+
+        let account = self.to_storage()?;
+        self.wallet().store().as_account_store()?.store_single(&account, None).await?;
+        self.wallet().store().commit(wallet_secret).await?;
+
+        let processor = self.utxo_context().processor();
+        match policy {
+            Some(policy) if self.wallet().active_accounts().contains(self.id()) => {
+                processor.register_consolidation_policy(self.utxo_context(), policy)
+            }
+            _ => processor.unregister_consolidation_policy(self.utxo_context().id_as_ref()),
+        }
+
+        Ok(())
+    }
+
     fn get_list_string(&self) -> Result<String> {
         let name = style(self.name_with_id()).blue();
         let balance = self.balance_as_strings(None)?;
@@ -270,6 +306,9 @@ pub trait Account: AnySync + Send + Sync + 'static {
     /// handle connection event
     async fn connect(self: Arc<Self>) -> Result<()> {
         let vacated = self.wallet().active_accounts().insert(self.clone().as_dyn_arc());
+        if let Some(policy) = self.consolidation_policy() {
+            self.utxo_context().processor().register_consolidation_policy(self.utxo_context(), policy);
+        }
         if vacated.is_none() && self.wallet().is_connected() {
             self.scan(None, None).await?;
         }
@@ -278,12 +317,27 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
     /// handle disconnection event
     async fn disconnect(&self) -> Result<()> {
+        self.utxo_context().processor().unregister_consolidation_policy(self.utxo_context().id_as_ref());
         self.wallet().active_accounts().remove(self.id());
         Ok(())
     }
 
     fn as_dyn_arc(self: Arc<Self>) -> Arc<dyn Account>;
 
+    /// Create [`GeneratorSettings`] spending the account UTXOs, skipping the UTXOs
+    /// frozen in the wallet and applying the optional manual [`UtxoSelection`].
+    async fn generator_settings(
+        self: Arc<Self>,
+        destination: PaymentDestination,
+        priority_fee_sompi: Fees,
+        payload: Option<Vec<u8>>,
+        utxo_selection: Option<UtxoSelection>,
+    ) -> Result<GeneratorSettings> {
+        let frozen = self.wallet().frozen_utxos().await?;
+        GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, priority_fee_sompi, payload)?
+            .with_utxo_selection(&utxo_selection.unwrap_or_default(), frozen)
+    }
+
     /// Aggregate all account UTXOs into the change address.
     /// Also known as "compounding".
     async fn sweep(
@@ -295,8 +349,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
     ) -> Result<(GeneratorSummary, Vec<waglayla_hashes::Hash>)> {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
-        let settings =
-            GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), PaymentDestination::Change, Fees::None, None)?;
+        let settings = self.clone().generator_settings(PaymentDestination::Change, Fees::None, None, None).await?;
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
         let mut stream = generator.stream();
@@ -314,13 +367,169 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
+    /// Consolidate the mature account UTXOs selected by the [`ConsolidationPolicy`]
+    /// into the change address. UTXOs frozen in the wallet are not consolidated.
+    /// Returns `None` if the UTXOs do not meet the policy thresholds and fails if
+    /// the consolidation fees exceed the policy maximum fee rate.
+    async fn consolidate(
+        self: Arc<Self>,
+        policy: &ConsolidationPolicy,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+    ) -> Result<Option<ConsolidationSummary>> {
+        // the account generator settings skip the UTXOs frozen in the wallet
+        let available = self.clone().generator_settings(PaymentDestination::Change, Fees::None, None, None).await?.utxo_iterator;
+        let Some(utxos) = policy.select(available.collect::<Vec<_>>().iter()) else {
+            return Ok(None);
+        };
+
+        let aggregate_input_value = utxos.iter().map(|entry| entry.amount()).sum::<u64>();
+        let selection =
+            UtxoSelection { include: utxos.iter().map(|entry| entry.utxo.outpoint.clone().into()).collect(), exclude: vec![] };
+
+        let estimate = self.clone().estimate(PaymentDestination::Change, Fees::None, None, Some(selection.clone()), abortable).await?;
+        if !policy.is_fee_acceptable(estimate.aggregated_fees, aggregate_input_value) {
+            return Err(Error::ConsolidationFeeRateExceeded(
+                sompi_to_waglayla_string(estimate.aggregated_fees),
+                sompi_to_waglayla_string(aggregate_input_value),
+            ));
+        }
+
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let settings =
+            self.clone().generator_settings(PaymentDestination::Change, Fees::None, None, Some(selection)).await?.with_consolidation();
+        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+
+        let mut stream = generator.stream();
+        let mut ids = vec![];
+        while let Some(transaction) = stream.try_next().await? {
+            transaction.try_sign()?;
+            ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
+            yield_executor().await;
+        }
+
+        Ok(Some(ConsolidationSummary {
+            utxo_count: utxos.len(),
+            aggregate_input_value,
+            aggregated_fees: generator.summary().aggregated_fees,
+            transaction_ids: ids,
+        }))
+    }
+
+    /// Raise the fee rate of the unconfirmed transaction `transaction_id` by creating
+    /// a child transaction (child-pays-for-parent) spending the parent outputs that
+    /// belong to this account into the change address. The child fees bring the fee
+    /// rate of the parent and child package up to `fee_rate` (in SOMPI per gram).
+    async fn fee_bump(
+        self: Arc<Self>,
+        transaction_id: TransactionId,
+        fee_rate: f64,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+    ) -> Result<FeeBumpSummary> {
+        let utxo_context = self.utxo_context().clone();
+
+        // parent outputs already tracked in the pending UTXO set, excluding
+        // outputs spent by other outgoing transactions (including previous bumps)
+        let (outgoing, mut utxo_entries) = {
+            let context = utxo_context.context();
+            let spent = context.outgoing.values().flat_map(|tx| tx.utxo_entries().keys().cloned()).collect::<AHashSet<_>>();
+            let pending = context
+                .pending
+                .values()
+                .filter(|entry| entry.transaction_id() == transaction_id && !spent.contains(&entry.id()))
+                .cloned()
+                .collect::<Vec<_>>();
+            (context.outgoing.get(&transaction_id).cloned(), pending)
+        };
+
+        let (parent_mass, parent_fees, outputs) = match outgoing {
+            Some(outgoing) if !outgoing.is_accepted() => {
+                let pending = outgoing.pending_transaction();
+                (pending.mass(), pending.fees(), pending.change_utxo_entry_reference().into_iter().collect::<Vec<_>>())
+            }
+            _ => {
+                let entry = self
+                    .wallet()
+                    .rpc_api()
+                    .get_mempool_entry(transaction_id, false, false)
+                    .await
+                    .map_err(|_| Error::FeeBumpTransactionNotPending(transaction_id))?;
+                let prefix = Prefix::from(self.wallet().network_id()?);
+                let addresses = utxo_context.addresses();
+                let outputs = entry
+                    .transaction
+                    .outputs
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, output)| {
+                        let address = extract_script_pub_key_address(&output.script_public_key, prefix).ok()?;
+                        addresses.contains(&address).then(|| {
+                            Generator::create_utxo_entry_reference(
+                                transaction_id,
+                                index as TransactionIndexType,
+                                output.value,
+                                output.script_public_key.clone(),
+                                &address,
+                            )
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (entry.transaction.mass, entry.fee, outputs)
+            }
+        };
+
+        for entry in outputs {
+            if !utxo_entries.iter().any(|pending| pending.id() == entry.id()) {
+                utxo_entries.push(entry);
+            }
+        }
+
+        if utxo_entries.is_empty() {
+            return Err(Error::FeeBumpNoSpendableOutputs(transaction_id));
+        }
+
+        if parent_mass > 0 {
+            let parent_fee_rate = parent_fees as f64 / parent_mass as f64;
+            if parent_fee_rate >= fee_rate {
+                return Err(Error::FeeBumpNotRequired(parent_fee_rate, fee_rate));
+            }
+        }
+
+        let utxo_count = utxo_entries.len();
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let settings =
+            GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), PaymentDestination::Change, Fees::None, None)?;
+        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+
+        let transaction = generator.generate_fee_bump_transaction(utxo_entries, parent_mass, parent_fees, fee_rate)?;
+        transaction.try_sign()?;
+        let id = transaction.try_submit(&self.wallet().rpc_api()).await?;
+
+        Ok(FeeBumpSummary {
+            parent_transaction_id: transaction_id,
+            parent_mass,
+            parent_fees,
+            transaction_id: id,
+            mass: transaction.mass(),
+            fees: transaction.fees(),
+            utxo_count,
+        })
+    }
+
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
+    /// The spent UTXOs can be restricted using the `utxo_selection`.
     async fn send(
         self: Arc<Self>,
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        utxo_selection: Option<UtxoSelection>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
         abortable: &Abortable,
@@ -329,7 +538,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
 
-        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = self.clone().generator_settings(destination, priority_fee_sompi, payload, utxo_selection).await?;
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -348,6 +557,42 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
+    /// Pay a list of recipients, packing their outputs into as few transactions as
+    /// the transaction mass limit allows. The `priority_fee_sompi` applies to each
+    /// generated payout transaction. If the payout is interrupted after some of the
+    /// recipients have been paid, the returned [`PayoutSummary`] contains the error
+    /// and the receipts of the paid recipients.
+    async fn payout(
+        self: Arc<Self>,
+        recipients: Vec<PayoutRecipient>,
+        priority_fee_sompi: Fees,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+        notifier: Option<PayoutNotifier>,
+    ) -> Result<PayoutSummary> {
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let rpc = self.wallet().rpc_api();
+
+        pay_payout_recipients(
+            &self.wallet().network_id()?,
+            &recipients,
+            |destination| self.clone().generator_settings(destination, priority_fee_sompi.clone(), None, None),
+            |transaction| {
+                let rpc = rpc.clone();
+                async move {
+                    transaction.try_sign()?;
+                    transaction.try_submit(&rpc).await
+                }
+            },
+            Some(signer),
+            abortable,
+            notifier,
+        )
+        .await
+    }
+
     async fn mnemonic(&self, wallet_secret: Secret) -> Result<String> {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let mnemonic = keydata.as_mnemonic(None)?.unwrap();
@@ -420,13 +665,11 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let final_transaction_destination = PaymentDestination::from(PaymentOutput::new(destination_address, transfer_amount_sompi));
         let final_transaction_payload = None;
 
-        let settings = GeneratorSettings::try_new_with_account(
-            self.clone().as_dyn_arc(),
-            final_transaction_destination,
-            priority_fee_sompi,
-            final_transaction_payload,
-        )?
-        .utxo_context_transfer(destination_account.utxo_context());
+        let settings = self
+            .clone()
+            .generator_settings(final_transaction_destination, priority_fee_sompi, final_transaction_payload, None)
+            .await?
+            .utxo_context_transfer(destination_account.utxo_context());
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -450,9 +693,10 @@ pub trait Account: AnySync + Send + Sync + 'static {
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        utxo_selection: Option<UtxoSelection>,
         abortable: &Abortable,
     ) -> Result<GeneratorSummary> {
-        let settings = GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = self.generator_settings(destination, priority_fee_sompi, payload, utxo_selection).await?;
 
         let generator = Generator::try_new(settings, None, Some(abortable))?;
 
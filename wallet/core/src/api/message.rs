@@ -6,8 +6,12 @@
 //!
 
 use crate::imports::*;
-use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
+use crate::tx::{
+    FeeBumpSummary, Fees, GeneratorSummary, PaymentDestination, PaymentRequest, PayoutRecipient, PayoutSummary, UtxoSelection,
+};
+use crate::utxo::ConsolidationPolicy;
 use waglayla_addresses::Address;
+use waglayla_consensus_core::tx::TransactionOutpoint;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
@@ -242,6 +246,19 @@ pub struct PrvKeyDataCreateRequest {
 #[serde(rename_all = "camelCase")]
 pub struct PrvKeyDataCreateResponse {
     pub prv_key_data_id: PrvKeyDataId,
+    pub mnemonic_shares: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrvKeyDataVerifySharesRequest {
+    pub shares: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrvKeyDataVerifySharesResponse {
+    pub share_set: MnemonicShareSetInfo,
 }
 
 // TODO
@@ -451,6 +468,9 @@ pub struct AccountsSendRequest {
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    /// Manual coin control, UTXOs are selected automatically if `None`
+    #[serde(default)]
+    pub utxo_selection: Option<UtxoSelection>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -460,6 +480,60 @@ pub struct AccountsSendResponse {
     pub transaction_ids: Vec<TransactionId>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPayoutRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+    pub recipients: Vec<PayoutRecipient>,
+    /// Priority fee applied to each payout transaction
+    pub priority_fee_sompi: Fees,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPayoutResponse {
+    pub payout_summary: PayoutSummary,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsConsolidationRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+    /// Consolidation policy, `None` disables the automatic consolidation
+    pub policy: Option<ConsolidationPolicy>,
+    /// Retain the secrets in memory (until the wallet is closed or its secret is changed)
+    /// so that the wallet can sign the consolidation transactions. If `false`, the policy
+    /// is stored but the automatic consolidation remains inactive.
+    #[serde(default)]
+    pub retain_secrets: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsConsolidationResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsFeeBumpRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+    /// Unconfirmed (parent) transaction
+    pub transaction_id: TransactionId,
+    /// Target fee rate of the parent and child package (SOMPI per gram)
+    pub fee_rate: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsFeeBumpResponse {
+    pub fee_bump_summary: FeeBumpSummary,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountsMnemonicRequest {
@@ -541,6 +615,9 @@ pub struct AccountsEstimateRequest {
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    /// Manual coin control, UTXOs are selected automatically if `None`
+    #[serde(default)]
+    pub utxo_selection: Option<UtxoSelection>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -600,6 +677,30 @@ pub struct TransactionsReplaceMetadataRequest {
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsReplaceMetadataResponse {}
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsExportRequest {
+    pub account_id: AccountId,
+    pub network_id: NetworkId,
+    pub format: TransactionExportFormat,
+    pub filter: Option<Vec<TransactionKind>>,
+    /// Include only transactions estimated to occur at or after this unix time (in milliseconds)
+    pub from_unixtime_msec: Option<u64>,
+    /// Include only transactions estimated to occur before this unix time (in milliseconds)
+    pub to_unixtime_msec: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsExportResponse {
+    pub account_id: AccountId,
+    pub format: TransactionExportFormat,
+    /// Number of exported transactions
+    pub total: u64,
+    /// Exported data in the requested format
+    pub data: String,
+}
+
 // #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 // #[serde(rename_all = "camelCase")]
 // pub struct TransactionGetRequest {}
@@ -610,12 +711,146 @@ pub struct TransactionsReplaceMetadataResponse {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AddressBookEnumerateRequest {}
+pub struct AddressBookEnumerateRequest {
+    pub network_id: Option<NetworkId>,
+    pub search: Option<String>,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AddressBookEnumerateResponse {}
+pub struct AddressBookEnumerateResponse {
+    pub entries: Vec<AddressBookEntry>,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WalletNotification {}
+pub struct AddressBookAddRequest {
+    pub wallet_secret: Secret,
+    pub entry: AddressBookEntry,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookAddResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookUpdateRequest {
+    pub wallet_secret: Secret,
+    pub network_id: NetworkId,
+    pub alias: String,
+    pub entry: AddressBookEntry,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookUpdateResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookRemoveRequest {
+    pub wallet_secret: Secret,
+    pub network_id: NetworkId,
+    pub alias: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookRemoveResponse {}
+
+/// UTXO owned by an account along with its coin control metadata.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoDescriptor {
+    pub outpoint: TransactionOutpoint,
+    pub address: Option<Address>,
+    pub amount: u64,
+    pub block_daa_score: u64,
+    pub is_coinbase: bool,
+    /// Only mature UTXOs can be spent
+    pub is_mature: bool,
+    pub frozen: bool,
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosEnumerateRequest {
+    pub account_id: AccountId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosEnumerateResponse {
+    pub utxos: Vec<UtxoDescriptor>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosFreezeRequest {
+    pub outpoints: Vec<TransactionOutpoint>,
+    pub frozen: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosFreezeResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosLabelRequest {
+    pub outpoint: TransactionOutpoint,
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxosLabelResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestCreateRequest {
+    pub account_id: AccountId,
+    /// Requested amount in SOMPI
+    pub amount: Option<u64>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    /// Expiration time (unix time in seconds)
+    pub expires: Option<u64>,
+    pub payload: Option<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestCreateResponse {
+    pub payment_request: PaymentRequest,
+    pub uri: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestParseRequest {
+    pub uri: String,
+    /// Network the request is validated against (defaults to the wallet network)
+    pub network_id: Option<NetworkId>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentRequestParseResponse {
+    pub payment_request: PaymentRequest,
+}
+
+/// Wallet [`Events`] delivered to the channels registered via
+/// [`WalletApi::register_notifications`](super::traits::WalletApi::register_notifications).
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletNotification {
+    pub event: Box<Events>,
+}
+
+impl From<Box<Events>> for WalletNotification {
+    fn from(event: Box<Events>) -> Self {
+        Self { event }
+    }
+}
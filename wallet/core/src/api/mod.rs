@@ -5,7 +5,12 @@
 pub mod message;
 pub use message::*;
 
+pub mod notifications;
+pub use notifications::*;
+
 pub mod traits;
 pub use traits::*;
 
 pub mod transport;
+
+pub mod wrpc;
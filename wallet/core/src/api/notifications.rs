@@ -0,0 +1,94 @@
+//!
+//! Registry of channels receiving [`WalletNotification`] messages.
+//!
+//! Both the runtime [`Wallet`] and the [`WalletClient`](super::transport::WalletClient)
+//! use [`NotificationChannels`] to multiplex wallet [`Events`] to the listeners
+//! registered via [`WalletApi::register_notifications`](super::traits::WalletApi::register_notifications).
+//!
+
+use crate::api::message::WalletNotification;
+use crate::imports::*;
+use workflow_core::channel::{Sender, TrySendError};
+
+#[derive(Default)]
+pub struct NotificationChannels {
+    next_id: AtomicU64,
+    channels: Mutex<AHashMap<u64, Sender<WalletNotification>>>,
+}
+
+impl NotificationChannels {
+    /// Registers a channel and returns its id, used for unregistering it.
+    pub fn register(&self, channel: Sender<WalletNotification>) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.channels.lock().unwrap().insert(id, channel);
+        id
+    }
+
+    pub fn unregister(&self, id: u64) -> Result<()> {
+        self.channels
+            .lock()
+            .unwrap()
+            .remove(&id)
+            .map(|_| ())
+            .ok_or_else(|| Error::custom(format!("unknown notification channel id {id}")))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channels.lock().unwrap().is_empty()
+    }
+
+    /// Delivers a notification to all registered channels without waiting
+    /// for them: the notification is dropped for channels which are full,
+    /// and channels closed by their receiving end are unregistered.
+    pub fn notify(&self, notification: WalletNotification) {
+        self.channels.lock().unwrap().retain(|id, channel| match channel.try_send(notification.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log_warn!("Wallet notification channel {id} is full, dropping a notification");
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workflow_core::channel::{bounded, unbounded};
+
+    #[test]
+    fn test_notification_channels() {
+        let channels = NotificationChannels::default();
+        let (first_sender, first) = unbounded();
+        let (second_sender, second) = unbounded();
+        let first_id = channels.register(first_sender);
+        let second_id = channels.register(second_sender);
+        assert_ne!(first_id, second_id);
+
+        channels.notify(Box::new(Events::WalletClose).into());
+        assert!(matches!(*first.try_recv().unwrap().event, Events::WalletClose));
+        assert!(matches!(*second.try_recv().unwrap().event, Events::WalletClose));
+
+        channels.unregister(first_id).unwrap();
+        assert!(channels.unregister(first_id).is_err());
+        channels.notify(Box::new(Events::UtxoProcStart).into());
+        assert!(first.try_recv().is_err());
+        assert!(matches!(*second.try_recv().unwrap().event, Events::UtxoProcStart));
+
+        // A full channel misses notifications but stays registered
+        let (full_sender, full) = bounded(1);
+        channels.register(full_sender);
+        channels.notify(Box::new(Events::WalletPing).into());
+        channels.notify(Box::new(Events::WalletClose).into());
+        assert!(matches!(*full.try_recv().unwrap().event, Events::WalletPing));
+        assert!(full.try_recv().is_err());
+        assert!(matches!(*second.try_recv().unwrap().event, Events::WalletPing));
+
+        // Dropping the receiving end unregisters the channel on the next notification
+        drop(second);
+        drop(full);
+        channels.notify(Box::new(Events::UtxoProcStop).into());
+        assert!(channels.is_empty());
+    }
+}
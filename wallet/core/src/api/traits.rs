@@ -9,16 +9,20 @@
 
 use crate::api::message::*;
 use crate::imports::*;
-use crate::storage::{PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, WalletDescriptor};
-use crate::tx::GeneratorSummary;
-use workflow_core::channel::Receiver;
+use crate::storage::{MnemonicShareSetInfo, PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, WalletDescriptor};
+use crate::tx::{FeeBumpSummary, GeneratorSummary, PaymentRequest, PayoutSummary};
+use workflow_core::channel::Sender;
 
 ///
 ///  API trait for interfacing with the Waglayla wallet subsystem.
 ///
 #[async_trait]
 pub trait WalletApi: Send + Sync + AnySync {
-    async fn register_notifications(self: Arc<Self>, channel: Receiver<WalletNotification>) -> Result<u64>;
+    /// Registers a channel receiving all wallet [`Events`] (balance, pending,
+    /// maturity, sync state etc.) as [`WalletNotification`] messages. Returns
+    /// the channel id to be supplied to [`unregister_notifications()`](Self::unregister_notifications).
+    /// A channel is also unregistered once its receiving end is closed.
+    async fn register_notifications(self: Arc<Self>, channel: Sender<WalletNotification>) -> Result<u64>;
     async fn unregister_notifications(self: Arc<Self>, channel_id: u64) -> Result<()>;
 
     async fn retain_context(self: Arc<Self>, name: &str, data: Option<Vec<u8>>) -> Result<()> {
@@ -239,6 +243,21 @@ pub trait WalletApi: Send + Sync + AnySync {
     /// this call.
     async fn prv_key_data_create_call(self: Arc<Self>, request: PrvKeyDataCreateRequest) -> Result<PrvKeyDataCreateResponse>;
 
+    /// Wrapper around [`prv_key_data_verify_shares_call()`](Self::prv_key_data_verify_shares_call)
+    async fn prv_key_data_verify_shares(self: Arc<Self>, shares: Vec<String>) -> Result<MnemonicShareSetInfo> {
+        Ok(self.prv_key_data_verify_shares_call(PrvKeyDataVerifySharesRequest { shares }).await?.share_set)
+    }
+    /// Verify a set of mnemonic shares (created by supplying `shares` in
+    /// [`PrvKeyDataCreateArgs`]) without revealing the secret. The call
+    /// checks each share and, if the threshold is met, the digest of the
+    /// recovered secret. The returned [`MnemonicShareSetInfo`] contains the
+    /// [`PrvKeyDataId`] of the recovered key, allowing the set to be matched
+    /// against the private keys stored in the wallet.
+    async fn prv_key_data_verify_shares_call(
+        self: Arc<Self>,
+        request: PrvKeyDataVerifySharesRequest,
+    ) -> Result<PrvKeyDataVerifySharesResponse>;
+
     /// Not implemented
     async fn prv_key_data_remove_call(self: Arc<Self>, request: PrvKeyDataRemoveRequest) -> Result<PrvKeyDataRemoveResponse>;
 
@@ -383,6 +402,42 @@ pub trait WalletApi: Send + Sync + AnySync {
     /// well `transaction_ids` containing a list of submitted transaction ids.
     async fn accounts_send_call(self: Arc<Self>, request: AccountsSendRequest) -> Result<AccountsSendResponse>;
 
+    /// Wrapper around [`Self::accounts_payout_call()`](Self::accounts_payout_call)
+    async fn accounts_payout(self: Arc<Self>, request: AccountsPayoutRequest) -> Result<PayoutSummary> {
+        Ok(self.accounts_payout_call(request).await?.payout_summary)
+    }
+    /// Pay a list of recipients from an account, packing the payments into as
+    /// few transactions as possible. Returns an [`AccountsPayoutResponse`] with a
+    /// [`PayoutSummary`] containing the transaction id paying each recipient. If
+    /// the payout fails after some recipients have been paid, the summary contains
+    /// the error and the unpaid recipients can be resubmitted.
+    async fn accounts_payout_call(self: Arc<Self>, request: AccountsPayoutRequest) -> Result<AccountsPayoutResponse>;
+
+    /// Wrapper around [`Self::accounts_consolidation_call()`](Self::accounts_consolidation_call)
+    async fn accounts_consolidation(self: Arc<Self>, request: AccountsConsolidationRequest) -> Result<()> {
+        self.accounts_consolidation_call(request).await?;
+        Ok(())
+    }
+    /// Set or clear the automatic UTXO consolidation policy of an account. The policy
+    /// is stored in the account settings. Only if `retain_secrets` is set, the supplied
+    /// secrets are retained in memory (until the wallet is closed or its secret is changed)
+    /// allowing the wallet to sign consolidation transactions. The consolidation must be
+    /// re-enabled after the wallet is reopened.
+    async fn accounts_consolidation_call(
+        self: Arc<Self>,
+        request: AccountsConsolidationRequest,
+    ) -> Result<AccountsConsolidationResponse>;
+
+    /// Wrapper around [`Self::accounts_fee_bump_call()`](Self::accounts_fee_bump_call)
+    async fn accounts_fee_bump(self: Arc<Self>, request: AccountsFeeBumpRequest) -> Result<FeeBumpSummary> {
+        Ok(self.accounts_fee_bump_call(request).await?.fee_bump_summary)
+    }
+    /// Bump the fee rate of an unconfirmed transaction using a child-pays-for-parent
+    /// transaction spending the transaction outputs belonging to the account. The
+    /// child fees bring the fee rate of the parent and child package up to the
+    /// requested fee rate (see [`crate::tx::fee_bump`]).
+    async fn accounts_fee_bump_call(self: Arc<Self>, request: AccountsFeeBumpRequest) -> Result<AccountsFeeBumpResponse>;
+
     /// Wrapper around [`Self::accounts_mnemonic_call()`](Self::accounts_mnemonic_call)
     async fn accounts_mnemonic(self: Arc<Self>, request: AccountsMnemonicRequest) -> Result<String> {
         Ok(self.accounts_mnemonic_call(request).await?.mnemonic)
@@ -463,10 +518,65 @@ pub trait WalletApi: Send + Sync + AnySync {
         request: TransactionsReplaceMetadataRequest,
     ) -> Result<TransactionsReplaceMetadataResponse>;
 
+    /// Exports the transaction history of an account in CSV or JSON format,
+    /// ordered by DAA score. Each exported record contains the DAA score and
+    /// the estimated timestamp of the transaction, its kind, value, fees,
+    /// addresses, note and metadata. Transactions can be filtered by kind
+    /// and by a unix time range; timestamp estimates are resolved via the
+    /// connected node.
+    async fn transactions_export_call(self: Arc<Self>, request: TransactionsExportRequest) -> Result<TransactionsExportResponse>;
+
+    /// Enumerates address book entries, optionally restricted to the
+    /// addresses of a network. If a `search` string is supplied, only
+    /// entries fuzzy-matching it by alias, title, label or address are
+    /// returned, best matches first.
     async fn address_book_enumerate_call(
         self: Arc<Self>,
         request: AddressBookEnumerateRequest,
     ) -> Result<AddressBookEnumerateResponse>;
+
+    /// Adds a new entry to the address book. Aliases are unique
+    /// (case-insensitive) within the network of the entry address.
+    async fn address_book_add_call(self: Arc<Self>, request: AddressBookAddRequest) -> Result<AddressBookAddResponse>;
+
+    /// Replaces the address book entry identified by `alias` on the
+    /// network `network_id` with a new entry, which may carry a new alias.
+    async fn address_book_update_call(self: Arc<Self>, request: AddressBookUpdateRequest) -> Result<AddressBookUpdateResponse>;
+
+    /// Removes the address book entry identified by `alias` on the network `network_id`.
+    async fn address_book_remove_call(self: Arc<Self>, request: AddressBookRemoveRequest) -> Result<AddressBookRemoveResponse>;
+
+    /// Returns the mature and pending UTXOs of an active account along
+    /// with their coin control metadata (frozen state and label).
+    async fn utxos_enumerate_call(self: Arc<Self>, request: UtxosEnumerateRequest) -> Result<UtxosEnumerateResponse>;
+
+    /// Freezes or unfreezes UTXOs. Frozen UTXOs are never spent by the
+    /// transaction generator, including when explicitly selected using
+    /// a [`UtxoSelection`](crate::tx::UtxoSelection). The frozen state is
+    /// persisted in the wallet metadata and does not require the wallet secret.
+    async fn utxos_freeze_call(self: Arc<Self>, request: UtxosFreezeRequest) -> Result<UtxosFreezeResponse>;
+
+    /// Replaces the label of a UTXO. Supply [`Option::None`] to remove the label.
+    async fn utxos_label_call(self: Arc<Self>, request: UtxosLabelRequest) -> Result<UtxosLabelResponse>;
+
+    /// Wrapper around [`Self::payment_request_create_call()`](Self::payment_request_create_call)
+    async fn payment_request_create(self: Arc<Self>, request: PaymentRequestCreateRequest) -> Result<String> {
+        Ok(self.payment_request_create_call(request).await?.uri)
+    }
+    /// Creates a payment request paying to the current receive address of
+    /// the account, returning the request along with its URI.
+    async fn payment_request_create_call(
+        self: Arc<Self>,
+        request: PaymentRequestCreateRequest,
+    ) -> Result<PaymentRequestCreateResponse>;
+
+    /// Wrapper around [`Self::payment_request_parse_call()`](Self::payment_request_parse_call)
+    async fn payment_request_parse(self: Arc<Self>, request: PaymentRequestParseRequest) -> Result<PaymentRequest> {
+        Ok(self.payment_request_parse_call(request).await?.payment_request)
+    }
+    /// Parses a payment request URI, ensuring the request address belongs
+    /// to the network and the request has not expired.
+    async fn payment_request_parse_call(self: Arc<Self>, request: PaymentRequestParseRequest) -> Result<PaymentRequestParseResponse>;
 }
 
 /// alias for `Arc<dyn WalletApi + Send + Sync + 'static>`
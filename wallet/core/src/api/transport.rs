@@ -11,6 +11,7 @@
 //!
 
 use super::message::*;
+use super::notifications::NotificationChannels;
 use super::traits::WalletApi;
 use crate::error::Error;
 use crate::events::Events;
@@ -41,27 +42,79 @@ pub enum Codec {
     Serde(Arc<dyn SerdeCodec>),
 }
 
+/// Op of the [`WalletNotification`] messages posted by servers using Borsh encoding
+pub const WALLET_NOTIFICATION_BORSH_OP: u64 = 0;
+/// Op of the [`WalletNotification`] messages posted by servers using Serde JSON encoding
+pub const WALLET_NOTIFICATION_SERDE_OP: &str = "wallet-notification";
+/// Op of the method authenticating a connection to a server using Borsh encoding
+pub const AUTHENTICATE_BORSH_OP: u64 = 1;
+/// Op of the method authenticating a connection to a server using Serde JSON encoding
+pub const AUTHENTICATE_SERDE_OP: &str = "authenticate";
+
+/// A JSON value carried by transport messages, which are required to be
+/// Borsh-serializable regardless of the encoding in use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonValue(pub serde_json::Value);
+
+impl BorshSerialize for JsonValue {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.0.to_string(), writer)
+    }
+}
+
+impl BorshDeserialize for JsonValue {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let json: String = BorshDeserialize::deserialize(buf)?;
+        serde_json::from_str(&json).map(Self).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
 /// [`WalletClient`] is a client-side transport interface declaring
 /// API methods that can be invoked via WalletApi method calls.
 /// [`WalletClient`] is a counter-part to [`WalletServer`].
+///
+/// Notifications pushed by the server are not requested by the client:
+/// the transport is expected to relay them to [`WalletClient::handle_notification()`]
+/// (or its Borsh and Serde JSON counterparts), which multiplexes them to
+/// the channels registered via [`WalletApi::register_notifications()`].
+/// See [`WrpcCodec`](super::wrpc::WrpcCodec) for the wRPC transport.
 pub struct WalletClient {
     pub codec: Codec,
+    notification_channels: NotificationChannels,
 }
 
 impl WalletClient {
     pub fn new(codec: Codec) -> Self {
-        Self { codec }
+        Self { codec, notification_channels: NotificationChannels::default() }
+    }
+
+    /// Delivers a notification received from the [`WalletServer`] to the registered channels.
+    pub fn handle_notification(&self, notification: WalletNotification) {
+        self.notification_channels.notify(notification);
+    }
+
+    /// Decodes and delivers a Borsh-serialized [`WalletNotification`].
+    pub fn handle_borsh_notification(&self, data: &[u8]) -> Result<()> {
+        self.handle_notification(WalletNotification::try_from_slice(data)?);
+        Ok(())
+    }
+
+    /// Decodes and delivers a Serde JSON-serialized [`WalletNotification`].
+    pub fn handle_serde_notification(&self, json: &str) -> Result<()> {
+        self.handle_notification(serde_json::from_str(json)?);
+        Ok(())
     }
 }
 
-use workflow_core::channel::{DuplexChannel, Receiver};
+use workflow_core::channel::{DuplexChannel, Sender};
 #[async_trait]
 impl WalletApi for WalletClient {
-    async fn register_notifications(self: Arc<Self>, _channel: Receiver<WalletNotification>) -> Result<u64> {
-        todo!()
+    async fn register_notifications(self: Arc<Self>, channel: Sender<WalletNotification>) -> Result<u64> {
+        Ok(self.notification_channels.register(channel))
     }
-    async fn unregister_notifications(self: Arc<Self>, _channel_id: u64) -> Result<()> {
-        todo!()
+    async fn unregister_notifications(self: Arc<Self>, channel_id: u64) -> Result<()> {
+        self.notification_channels.unregister(channel_id)
     }
 
     build_wallet_client_transport_interface! {[
@@ -108,11 +161,27 @@ impl WalletApi for WalletClient {
         TransactionsReplaceNote,
         TransactionsReplaceMetadata,
         AddressBookEnumerate,
+        AddressBookAdd,
+        AddressBookUpdate,
+        AddressBookRemove,
+        UtxosEnumerate,
+        UtxosFreeze,
+        UtxosLabel,
+        AccountsPayout,
+        AccountsConsolidation,
+        TransactionsExport,
+        AccountsFeeBump,
+        PaymentRequestCreate,
+        PaymentRequestParse,
+        PrvKeyDataVerifyShares,
     ]}
 }
 
 // ----------------------------
 
+/// Server-side handler of wallet [`Events`], responsible for pushing them
+/// to the connected clients, typically as a serialized [`WalletNotification`]
+/// relayed to [`WalletClient::handle_notification()`] on the client side.
 #[async_trait]
 pub trait EventHandler: Send + Sync {
     // pub trait EventHandler {
@@ -188,6 +257,19 @@ impl WalletServer {
         TransactionsReplaceNote,
         TransactionsReplaceMetadata,
         AddressBookEnumerate,
+        AddressBookAdd,
+        AddressBookUpdate,
+        AddressBookRemove,
+        UtxosEnumerate,
+        UtxosFreeze,
+        UtxosLabel,
+        AccountsPayout,
+        AccountsConsolidation,
+        TransactionsExport,
+        AccountsFeeBump,
+        PaymentRequestCreate,
+        PaymentRequestParse,
+        PrvKeyDataVerifyShares,
     ]}
 }
 
@@ -230,3 +312,16 @@ impl WalletServer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_value_borsh() {
+        let value = JsonValue(serde_json::json!({ "accountId": "abc", "amount": 100 }));
+        let bytes = value.try_to_vec().unwrap();
+        assert_eq!(JsonValue::try_from_slice(&bytes).unwrap().0, value.0);
+        assert!(JsonValue::try_from_slice(&String::from("{").try_to_vec().unwrap()).is_err());
+    }
+}
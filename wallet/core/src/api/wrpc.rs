@@ -0,0 +1,157 @@
+//!
+//! wRPC transport connecting a [`WalletClient`] to a remote [`WalletServer`](super::transport::WalletServer),
+//! such as the one exposed by the wallet daemon.
+//!
+//! Requests, responses and notifications are relayed as they are received,
+//! leaving their decoding to the [`WalletClient`].
+//!
+
+use super::transport::*;
+use crate::imports::*;
+use serde::de::DeserializeOwned;
+use std::io::Result as IoResult;
+use workflow_core::channel::{unbounded, Receiver, Sender};
+use workflow_core::task::spawn;
+use workflow_rpc::client::prelude::{ConnectOptions, ConnectStrategy, Encoding, RpcClient, RpcClientOptions};
+use workflow_rpc::client::{Interface, Notification};
+
+/// The bytes of a Borsh-serialized message, kept undecoded
+#[derive(Deserialize)]
+struct RawBorsh(Vec<u8>);
+
+impl BorshDeserialize for RawBorsh {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let data = buf.to_vec();
+        *buf = &[];
+        Ok(Self(data))
+    }
+}
+
+/// A notification received from the server
+enum Payload {
+    Borsh(Vec<u8>),
+    Serde(String),
+}
+
+enum Rpc {
+    Borsh(RpcClient<u64>),
+    Serde(RpcClient<String>),
+}
+
+/// wRPC [`Codec`] of a [`WalletClient`], either Borsh or Serde JSON encoded.
+///
+/// ```ignore
+/// let codec = WrpcCodec::connect("ws://127.0.0.1:8210", Encoding::Borsh, token).await?;
+/// let client = Arc::new(WalletClient::new(codec.codec()));
+/// codec.relay_notifications(&client);
+/// ```
+pub struct WrpcCodec {
+    rpc: Rpc,
+    notifications: Receiver<Payload>,
+}
+
+impl WrpcCodec {
+    /// Connects to the wallet wRPC server at `url` and authenticates the connection with the RPC `token`.
+    pub async fn connect(url: &str, encoding: Encoding, token: &str) -> Result<Arc<Self>> {
+        let (sender, notifications) = unbounded();
+        let options = ConnectOptions {
+            block_async_connect: true,
+            strategy: ConnectStrategy::Fallback,
+            url: Some(url.to_string()),
+            ..Default::default()
+        };
+        let rpc = match encoding {
+            Encoding::Borsh => {
+                let mut interface = Interface::<u64>::new();
+                interface.notification(WALLET_NOTIFICATION_BORSH_OP, relay(sender, |data: RawBorsh| Payload::Borsh(data.0)));
+                let rpc = RpcClient::new_with_encoding(encoding, interface.into(), RpcClientOptions::new(), None)?;
+                rpc.connect(options).await?;
+                rpc.call::<String, bool>(AUTHENTICATE_BORSH_OP, token.to_string()).await?;
+                Rpc::Borsh(rpc)
+            }
+            Encoding::SerdeJson => {
+                let mut interface = Interface::<String>::new();
+                interface.notification(
+                    WALLET_NOTIFICATION_SERDE_OP.to_string(),
+                    relay(sender, |value: JsonValue| Payload::Serde(value.0.to_string())),
+                );
+                let rpc = RpcClient::new_with_encoding(encoding, interface.into(), RpcClientOptions::new(), None)?;
+                rpc.connect(options).await?;
+                rpc.call::<String, bool>(AUTHENTICATE_SERDE_OP.to_string(), token.to_string()).await?;
+                Rpc::Serde(rpc)
+            }
+        };
+        Ok(Arc::new(Self { rpc, notifications }))
+    }
+
+    /// Returns the [`Codec`] for constructing a [`WalletClient`].
+    pub fn codec(self: &Arc<Self>) -> Codec {
+        match self.rpc {
+            Rpc::Borsh(_) => Codec::Borsh(self.clone()),
+            Rpc::Serde(_) => Codec::Serde(self.clone()),
+        }
+    }
+
+    /// Relays the notifications posted by the server to the channels registered with `client`,
+    /// until the client is dropped or the connection is shut down.
+    pub fn relay_notifications(&self, client: &Arc<WalletClient>) {
+        let client = Arc::downgrade(client);
+        let notifications = self.notifications.clone();
+        spawn(async move {
+            while let Ok(payload) = notifications.recv().await {
+                let Some(client) = client.upgrade() else { break };
+                let result = match payload {
+                    Payload::Borsh(data) => client.handle_borsh_notification(&data),
+                    Payload::Serde(json) => client.handle_serde_notification(&json),
+                };
+                if let Err(err) = result {
+                    log_warn!("Unable to decode a wallet notification: {err}");
+                }
+            }
+        });
+    }
+
+    pub async fn shutdown(&self) -> Result<()> {
+        match &self.rpc {
+            Rpc::Borsh(rpc) => rpc.shutdown().await?,
+            Rpc::Serde(rpc) => rpc.shutdown().await?,
+        }
+        self.notifications.close();
+        Ok(())
+    }
+}
+
+/// Creates a notification handler posting the notifications to `sender`
+fn relay<Msg>(sender: Sender<Payload>, payload: fn(Msg) -> Payload) -> Notification<Msg>
+where
+    Msg: BorshDeserialize + DeserializeOwned + Send + Sync + 'static,
+{
+    Notification::new(move |msg: Msg| {
+        // The relay is unbounded, so it only fails once the codec is shut down
+        sender.try_send(payload(msg)).ok();
+        Box::pin(async { Ok(()) })
+    })
+}
+
+#[async_trait]
+impl BorshCodec for WrpcCodec {
+    async fn call(&self, op: u64, request: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.rpc {
+            Rpc::Borsh(rpc) => Ok(rpc.call(op, request).await?),
+            Rpc::Serde(_) => Err(Error::custom("the wRPC connection is not Borsh encoded")),
+        }
+    }
+}
+
+#[async_trait]
+impl SerdeCodec for WrpcCodec {
+    async fn call(&self, op: &str, request: &str) -> Result<String> {
+        match &self.rpc {
+            Rpc::Serde(rpc) => {
+                let response: JsonValue = rpc.call(op.to_string(), JsonValue(serde_json::from_str(request)?)).await?;
+                Ok(response.0.to_string())
+            }
+            Rpc::Borsh(_) => Err(Error::custom("the wRPC connection is not Serde JSON encoded")),
+        }
+    }
+}
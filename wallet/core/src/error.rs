@@ -6,7 +6,9 @@ use crate::imports::{AccountId, AccountKind, AssocPrvKeyDataIds, PrvKeyDataId};
 use base64::DecodeError;
 use downcast::DowncastError;
 use waglayla_bip32::Error as BIP32Error;
+use waglayla_consensus_client::UtxoEntryId;
 use waglayla_consensus_core::sign::Error as CoreSignError;
+use waglayla_consensus_core::tx::TransactionId;
 use waglayla_rpc_core::RpcError as WaglaylaRpcError;
 use waglayla_wrpc_client::error::Error as WaglaylaWorkflowRpcError;
 use std::sync::PoisonError;
@@ -222,6 +224,57 @@ pub enum Error {
     #[error("No records found")]
     NoRecordsFound,
 
+    #[error("Invalid address book alias '{0}' (aliases must not be empty, contain whitespace or be an address)")]
+    InvalidAddressBookAlias(String),
+
+    #[error("Address book entry not found: {0}")]
+    AddressBookEntryNotFound(String),
+
+    #[error("Address book entry already exists: {0}")]
+    AddressBookEntryExists(String),
+
+    #[error("UTXO {0} is frozen")]
+    UtxoFrozen(UtxoEntryId),
+
+    #[error("UTXO {0} is not available for spending")]
+    UtxoNotAvailable(UtxoEntryId),
+
+    #[error("Consolidation fees {0} exceed the maximum fee rate for the consolidated value {1}")]
+    ConsolidationFeeRateExceeded(String, String),
+
+    #[error("Transaction {0} is not pending in the mempool")]
+    FeeBumpTransactionNotPending(TransactionId),
+
+    #[error("Transaction {0} has no unspent outputs belonging to the account")]
+    FeeBumpNoSpendableOutputs(TransactionId),
+
+    #[error("Transaction fee rate {0:.2} already meets the target fee rate {1:.2}")]
+    FeeBumpNotRequired(f64, f64),
+
+    #[error("Spendable transaction outputs ({0}) are insufficient to pay the fee bump fees {1}")]
+    FeeBumpInsufficientValue(String, String),
+
+    #[error("Invalid payment request: {0}")]
+    InvalidPaymentRequest(String),
+
+    #[error("Payment request address {0} does not belong to network {1}")]
+    PaymentRequestNetworkMismatch(String, waglayla_consensus_core::network::NetworkId),
+
+    #[error("Payment request has expired")]
+    PaymentRequestExpired,
+
+    #[error("Invalid share set {0}-of-{1} (the threshold must be between 1 and the share count, at most {2} shares)")]
+    InvalidMnemonicShareArgs(u8, u8, u8),
+
+    #[error("Invalid mnemonic share: {0}")]
+    InvalidMnemonicShare(String),
+
+    #[error("Insufficient mnemonic shares: {0} supplied, {1} required")]
+    InsufficientMnemonicShares(usize, u8),
+
+    #[error("Mnemonic shares do not recover a valid secret")]
+    MnemonicShareDigestMismatch,
+
     #[error("The feature is not supported")]
     NotImplemented,
 
@@ -270,6 +323,9 @@ pub enum Error {
     #[error("Transaction exceeds the maximum allowed mass")]
     GeneratorTransactionIsTooHeavy,
 
+    #[error("Generator has already produced the final transaction")]
+    GeneratorIsDone,
+
     #[error("Storage mass exceeds maximum")]
     StorageMassExceedsMaximumTransactionMass { storage_mass: u64 },
 
@@ -7,10 +7,11 @@
 use crate::imports::*;
 use crate::storage::{Hint, PrvKeyDataInfo, StorageDescriptor, TransactionRecord, WalletDescriptor};
 use crate::utxo::context::UtxoContextId;
+use crate::utxo::ConsolidationSummary;
 use transaction::TransactionRecordNotification;
 
 /// Sync state of the waglaylad node
-#[derive(Clone, Debug, Serialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "kebab-case")]
 #[serde(tag = "type", content = "data")]
 pub enum SyncState {
@@ -49,7 +50,7 @@ impl SyncState {
 }
 
 /// Events emitted by the wallet framework
-#[derive(Clone, Debug, Serialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "kebab-case")]
 #[serde(tag = "type", content = "data")]
 pub enum Events {
@@ -213,6 +214,19 @@ pub enum Events {
         /// contain a developer-assigned internal id.
         id: UtxoContextId,
     },
+    /// Automatic UTXO consolidation of an account has been submitted
+    /// in accordance with the account consolidation policy.
+    Consolidation {
+        id: UtxoContextId,
+        summary: ConsolidationSummary,
+    },
+    /// Automatic UTXO consolidation of an account has failed or
+    /// has been skipped (for example, if the fees exceed the policy
+    /// maximum fee rate).
+    ConsolidationError {
+        id: UtxoContextId,
+        message: String,
+    },
     /// Periodic metrics updates (on-request)
     Metrics {
         #[serde(rename = "networkId")]
@@ -277,6 +291,8 @@ pub enum EventKind {
     Maturity,
     Discovery,
     Balance,
+    Consolidation,
+    ConsolidationError,
     Metrics,
     Error,
 }
@@ -313,6 +329,8 @@ impl From<&Events> for EventKind {
             Events::Maturity { .. } => EventKind::Maturity,
             Events::Discovery { .. } => EventKind::Discovery,
             Events::Balance { .. } => EventKind::Balance,
+            Events::Consolidation { .. } => EventKind::Consolidation,
+            Events::ConsolidationError { .. } => EventKind::ConsolidationError,
             Events::Metrics { .. } => EventKind::Metrics,
             Events::Error { .. } => EventKind::Error,
         }
@@ -352,6 +370,8 @@ impl FromStr for EventKind {
             "maturity" => Ok(EventKind::Maturity),
             "discovery" => Ok(EventKind::Discovery),
             "balance" => Ok(EventKind::Balance),
+            "consolidation" => Ok(EventKind::Consolidation),
+            "consolidation-error" => Ok(EventKind::ConsolidationError),
             "metrics" => Ok(EventKind::Metrics),
             "error" => Ok(EventKind::Error),
             _ => Err(Error::custom("Invalid event kind")),
@@ -399,6 +419,8 @@ impl std::fmt::Display for EventKind {
             EventKind::Maturity => "maturity",
             EventKind::Discovery => "discovery",
             EventKind::Balance => "balance",
+            EventKind::Consolidation => "consolidation",
+            EventKind::ConsolidationError => "consolidation-error",
             EventKind::Metrics => "metrics",
             EventKind::Error => "error",
         };
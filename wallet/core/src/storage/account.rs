@@ -3,8 +3,9 @@
 //!
 
 use crate::imports::*;
+use crate::utxo::ConsolidationPolicy;
 
-const ACCOUNT_SETTINGS_VERSION: u32 = 0;
+const ACCOUNT_SETTINGS_VERSION: u32 = 1;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,6 +14,9 @@ pub struct AccountSettings {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Vec<u8>>,
+    /// Automatic UTXO consolidation policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consolidation: Option<ConsolidationPolicy>,
 }
 
 impl BorshSerialize for AccountSettings {
@@ -20,6 +24,7 @@ impl BorshSerialize for AccountSettings {
         BorshSerialize::serialize(&ACCOUNT_SETTINGS_VERSION, writer)?;
         BorshSerialize::serialize(&self.name, writer)?;
         BorshSerialize::serialize(&self.meta, writer)?;
+        BorshSerialize::serialize(&self.consolidation, writer)?;
 
         Ok(())
     }
@@ -27,11 +32,12 @@ impl BorshSerialize for AccountSettings {
 
 impl BorshDeserialize for AccountSettings {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let _version: u32 = BorshDeserialize::deserialize(buf)?;
+        let version: u32 = BorshDeserialize::deserialize(buf)?;
         let name = BorshDeserialize::deserialize(buf)?;
         let meta = BorshDeserialize::deserialize(buf)?;
+        let consolidation = if version > 0 { BorshDeserialize::deserialize(buf)? } else { None };
 
-        Ok(Self { name, meta })
+        Ok(Self { name, meta, consolidation })
     }
 }
 
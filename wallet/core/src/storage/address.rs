@@ -1,13 +1,189 @@
 //!
 //! Wallet address book.
 //!
+//! Address book entries are stored within the encrypted wallet
+//! payload. Each entry is scoped to the network of its address
+//! and is identified by an alias unique within that network.
+//!
 
 use crate::imports::*;
 
-// TODO
-#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddressBookEntry {
     pub alias: String,
     pub title: String,
     pub address: Address,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl AddressBookEntry {
+    const STORAGE_MAGIC: u32 = 0x4b4f4241;
+    const STORAGE_VERSION: u32 = 0;
+
+    pub fn try_new(alias: &str, title: &str, address: Address, labels: Vec<String>) -> Result<Self> {
+        let alias = alias.trim();
+        if !Self::is_valid_alias(alias) {
+            return Err(Error::InvalidAddressBookAlias(alias.to_string()));
+        }
+        let labels = labels.into_iter().map(|label| label.trim().to_string()).filter(|label| !label.is_empty()).collect();
+        Ok(Self { alias: alias.to_string(), title: title.trim().to_string(), address, labels })
+    }
+
+    /// Aliases must be non-empty, must not contain whitespace (so they
+    /// can be supplied as a single command line argument) and must not
+    /// be parsable as an address.
+    pub fn is_valid_alias(alias: &str) -> bool {
+        !alias.is_empty() && !alias.chars().any(char::is_whitespace) && Address::try_from(alias).is_err()
+    }
+
+    /// Returns `true` if the entry belongs to the network identified by `network_id`.
+    pub fn is_network(&self, network_id: &NetworkId) -> bool {
+        self.address.prefix == Prefix::from(*network_id)
+    }
+
+    /// Returns `true` if the entry is identified by `alias` on the network
+    /// of the address `prefix`. Aliases are case-insensitive.
+    pub fn is(&self, prefix: Prefix, alias: &str) -> bool {
+        self.address.prefix == prefix && self.alias.eq_ignore_ascii_case(alias)
+    }
+
+    /// Scores the entry against a search string, matching the alias, title,
+    /// labels and address. Returns `None` if the entry does not match.
+    /// Higher scores indicate better matches.
+    pub fn search_score(&self, search: &str) -> Option<u32> {
+        let search = search.trim().to_lowercase();
+        if search.is_empty() {
+            return Some(0);
+        }
+
+        let address = self.address.to_string();
+        [
+            fuzzy_score(&search, &self.alias).map(|score| score * 4),
+            fuzzy_score(&search, &self.title).map(|score| score * 2),
+            self.labels.iter().filter_map(|label| fuzzy_score(&search, label)).max().map(|score| score * 2),
+            address.contains(&search).then_some(SUBSTRING_SCORE),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+    }
+}
+
+const EXACT_SCORE: u32 = 1000;
+const PREFIX_SCORE: u32 = 800;
+const SUBSTRING_SCORE: u32 = 600;
+const SUBSEQUENCE_SCORE: u32 = 400;
+
+/// Case-insensitive fuzzy match of a lowercase `search` string against `text`,
+/// preferring exact, prefix and substring matches over subsequence matches.
+/// Subsequence matches are penalized by the number of skipped characters.
+fn fuzzy_score(search: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if text == search {
+        Some(EXACT_SCORE)
+    } else if text.starts_with(search) {
+        Some(PREFIX_SCORE)
+    } else if text.contains(search) {
+        Some(SUBSTRING_SCORE)
+    } else {
+        let mut chars = text.chars();
+        let mut skipped = 0u32;
+        for c in search.chars() {
+            loop {
+                match chars.next() {
+                    Some(t) if t == c => break,
+                    Some(_) => skipped += 1,
+                    None => return None,
+                }
+            }
+        }
+        Some(SUBSEQUENCE_SCORE.saturating_sub(skipped * 10).max(1))
+    }
+}
+
+impl BorshSerialize for AddressBookEntry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        StorageHeader::new(Self::STORAGE_MAGIC, Self::STORAGE_VERSION).serialize(writer)?;
+        BorshSerialize::serialize(&self.alias, writer)?;
+        BorshSerialize::serialize(&self.title, writer)?;
+        BorshSerialize::serialize(&self.address, writer)?;
+        BorshSerialize::serialize(&self.labels, writer)?;
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for AddressBookEntry {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let StorageHeader { version: _, .. } =
+            StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
+
+        let alias = BorshDeserialize::deserialize(buf)?;
+        let title = BorshDeserialize::deserialize(buf)?;
+        let address = BorshDeserialize::deserialize(buf)?;
+        let labels = BorshDeserialize::deserialize(buf)?;
+
+        Ok(Self { alias, title, address, labels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use waglayla_addresses::Version;
+
+    fn entry(alias: &str, title: &str, labels: &[&str]) -> AddressBookEntry {
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[0x5a; 32]);
+        AddressBookEntry::try_new(alias, title, address, labels.iter().map(|label| label.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_storage_address_book_entry() -> Result<()> {
+        let storable_in = entry("exchange", "Exchange deposit", &["trading"]);
+        let guard = StorageGuard::new(&storable_in);
+        let storable_out = guard.validate()?;
+        assert_eq!(storable_in.alias, storable_out.alias);
+        assert_eq!(storable_in.labels, storable_out.labels);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_address_book_entry_without_labels() {
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[0x5a; 32]);
+        let json = serde_json::json!({ "alias": "alice", "title": "Alice", "address": address.to_string() });
+        let entry: AddressBookEntry = serde_json::from_value(json).unwrap();
+        assert_eq!(entry.address, address);
+        assert!(entry.labels.is_empty());
+    }
+
+    #[test]
+    fn test_address_book_entry_alias() {
+        assert!(AddressBookEntry::is_valid_alias("alice"));
+        assert!(!AddressBookEntry::is_valid_alias(""));
+        assert!(!AddressBookEntry::is_valid_alias("alice smith"));
+        assert!(!AddressBookEntry::is_valid_alias(&Address::new(Prefix::Mainnet, Version::PubKey, &[0x5a; 32]).to_string()));
+
+        let entry = entry("Alice", "", &[]);
+        assert!(entry.is(Prefix::Mainnet, "alice"));
+        assert!(!entry.is(Prefix::Testnet, "alice"));
+        assert!(entry.is_network(&NetworkId::new(NetworkType::Mainnet)));
+        assert!(!entry.is_network(&NetworkId::with_suffix(NetworkType::Testnet, 10)));
+    }
+
+    #[test]
+    fn test_address_book_search() {
+        let alice = entry("alice", "Alice Smith", &["friends"]);
+        let exchange = entry("exchange", "Exchange deposit", &["trading", "alerts"]);
+
+        assert_eq!(alice.search_score("alice"), Some(EXACT_SCORE * 4));
+        assert!(alice.search_score("alc").is_some());
+        assert!(alice.search_score("ALICE").unwrap() > exchange.search_score("ale").unwrap_or(0));
+        assert!(exchange.search_score("trad").is_some());
+        assert!(exchange.search_score("xyz").is_none());
+        assert!(alice.search_score("smith").unwrap() > alice.search_score("smh").unwrap());
+    }
 }
@@ -5,6 +5,7 @@
 use crate::imports::*;
 use async_trait::async_trait;
 use downcast::{downcast_sync, AnySync};
+use waglayla_consensus_core::tx::TransactionOutpoint;
 
 #[derive(Debug, Clone)]
 pub struct WalletExportOptions {
@@ -100,15 +101,25 @@ pub trait AccountStore: Send + Sync {
 
 #[async_trait]
 pub trait AddressBookStore: Send + Sync {
-    async fn is_empty(&self) -> Result<bool> {
-        Err(Error::NotImplemented)
-    }
-    async fn iter(&self) -> Result<StorageStream<Arc<AddressBookEntry>>> {
-        Err(Error::NotImplemented)
-    }
-    async fn search(&self, _search: &str) -> Result<Vec<Arc<AddressBookEntry>>> {
-        Err(Error::NotImplemented)
-    }
+    async fn is_empty(&self) -> Result<bool>;
+    async fn iter(&self) -> Result<StorageStream<Arc<AddressBookEntry>>>;
+    /// fuzzy search over aliases, titles, labels and addresses, best matches first
+    async fn search(&self, search: &str) -> Result<Vec<Arc<AddressBookEntry>>>;
+    /// load an entry by alias within the network of the address `prefix`
+    async fn load(&self, prefix: Prefix, alias: &str) -> Result<Option<Arc<AddressBookEntry>>>;
+    /// store an entry, replacing an existing entry with the same alias on the same network
+    async fn store(&self, entry: AddressBookEntry) -> Result<()>;
+    async fn remove(&self, prefix: Prefix, alias: &str) -> Result<()>;
+}
+
+#[async_trait]
+pub trait UtxoMetadataStore: Send + Sync {
+    async fn load_all(&self) -> Result<Vec<Arc<UtxoMetadata>>>;
+    async fn load_single(&self, outpoint: &TransactionOutpoint) -> Result<Option<Arc<UtxoMetadata>>>;
+    /// store UTXO metadata, removing the entries that are empty
+    async fn store(&self, metadata: Vec<UtxoMetadata>) -> Result<()>;
+    /// remove the metadata of spent UTXOs
+    async fn remove(&self, outpoints: &[TransactionOutpoint]) -> Result<()>;
 }
 
 pub struct TransactionRangeResult {
@@ -247,6 +258,7 @@ pub trait Interface: Send + Sync + AnySync {
     fn as_prv_key_data_store(&self) -> Result<Arc<dyn PrvKeyDataStore>>;
     fn as_account_store(&self) -> Result<Arc<dyn AccountStore>>;
     fn as_address_book_store(&self) -> Result<Arc<dyn AddressBookStore>>;
+    fn as_utxo_metadata_store(&self) -> Result<Arc<dyn UtxoMetadataStore>>;
     fn as_transaction_record_store(&self) -> Result<Arc<dyn TransactionRecordStore>>;
 }
 
@@ -13,3 +13,6 @@ pub use data::*;
 
 pub mod info;
 pub use info::*;
+
+pub mod shares;
+pub use shares::*;
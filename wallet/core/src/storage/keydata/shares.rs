@@ -0,0 +1,403 @@
+//!
+//! Shamir secret sharing of mnemonic entropy.
+//!
+//! The entropy of a BIP39 mnemonic is split into a set of `count` shares, any
+//! `threshold` of which recover the mnemonic, while fewer shares reveal no
+//! information about it. The sharing scheme follows SLIP-0039:
+//!
+//! - arithmetic is performed in GF(256) with the reduction polynomial
+//!   `x^8 + x^4 + x^3 + x + 1`, share values are evaluated byte-wise using
+//!   Lagrange interpolation;
+//! - the secret is the polynomial value at `x = 255` and a digest of the secret
+//!   (`HMAC-SHA256(key: R, msg: secret)[..4] || R`, where `R` is random) is the
+//!   value at `x = 254`, allowing the recovered secret to be verified;
+//! - shares `0..threshold - 2` are random, the remaining shares are interpolated.
+//!
+//! Unlike SLIP-0039, a set has a single group and the secret is not encrypted
+//! (the BIP39 passphrase, if any, is still required to derive the keys). Each
+//! share is serialized as `identifier (2 bytes) || threshold || index || value ||
+//! checksum (4 bytes)`, where the checksum is the truncated SHA-256 of the
+//! preceding bytes, and encoded as words of the BIP39 English word list
+//! (11 bits per word) - 18 words for a 12-word mnemonic and 30 words for a
+//! 24-word mnemonic.
+//!
+
+use super::{PrvKeyDataId, PrvKeyDataVariant};
+use crate::imports::*;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use waglayla_bip32::{Language, Mnemonic};
+
+/// Maximum number of shares in a share set.
+pub const MAX_MNEMONIC_SHARE_COUNT: u8 = 16;
+
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+const DIGEST_LENGTH: usize = 4;
+const CHECKSUM_LENGTH: usize = 4;
+const CHECKSUM_CUSTOMIZATION: &[u8] = b"waglayla-mnemonic-share";
+// identifier, threshold and index
+const HEADER_LENGTH: usize = 4;
+
+/// Parameters of a `threshold`-of-`count` share set.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MnemonicShareArgs {
+    pub threshold: u8,
+    pub count: u8,
+}
+
+impl MnemonicShareArgs {
+    pub fn new(threshold: u8, count: u8) -> Self {
+        Self { threshold, count }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.threshold == 0 || self.threshold > self.count || self.count > MAX_MNEMONIC_SHARE_COUNT {
+            return Err(Error::InvalidMnemonicShareArgs(self.threshold, self.count, MAX_MNEMONIC_SHARE_COUNT));
+        }
+        Ok(())
+    }
+}
+
+/// Result of a share set verification.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MnemonicShareSetInfo {
+    /// Random identifier common to all shares of the set
+    pub identifier: u16,
+    pub threshold: u8,
+    /// Number of distinct shares verified
+    pub share_count: usize,
+    /// Id of the private key data the shares recover
+    pub prv_key_data_id: PrvKeyDataId,
+}
+
+/// A single share of a mnemonic share set.
+#[derive(Clone)]
+pub struct MnemonicShare {
+    identifier: u16,
+    threshold: u8,
+    index: u8,
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl MnemonicShare {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Share number within the set (starting from 1).
+    pub fn number(&self) -> u8 {
+        self.index + 1
+    }
+
+    fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(HEADER_LENGTH + self.value.len() + CHECKSUM_LENGTH));
+        bytes.extend_from_slice(&self.identifier.to_be_bytes());
+        bytes.push(self.threshold);
+        bytes.push(self.index);
+        bytes.extend_from_slice(&self.value);
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        bytes
+    }
+
+    /// Encodes the share as a space separated list of words.
+    pub fn to_phrase(&self) -> Zeroizing<String> {
+        let wordlist = Language::English.wordlist();
+        let bytes = self.to_bytes();
+        let words = bytes.len() * 8 / 11 + usize::from(bytes.len() * 8 % 11 != 0);
+
+        let mut phrase = Zeroizing::new(String::new());
+        for word in 0..words {
+            let bits = (0..11).fold(0u16, |bits, bit| {
+                let position = word * 11 + bit;
+                let value = bytes.get(position / 8).map(|byte| (byte >> (7 - position % 8)) & 1).unwrap_or(0);
+                (bits << 1) | value as u16
+            });
+            if word > 0 {
+                phrase.push(' ');
+            }
+            phrase.push_str(wordlist.get_word(bits.into()));
+        }
+        phrase
+    }
+
+    /// Decodes a share from its word list encoding.
+    pub fn try_from_phrase(phrase: &str) -> Result<Self> {
+        let wordlist = Language::English.wordlist();
+        let words = phrase.split_whitespace().map(|word| word.to_lowercase()).collect::<Vec<_>>();
+        let value_length = match words.len() {
+            18 => 16,
+            30 => 32,
+            length => return Err(Error::InvalidMnemonicShare(format!("expected 18 or 30 words, got {length}"))),
+        };
+
+        let length = HEADER_LENGTH + value_length + CHECKSUM_LENGTH;
+        let mut bytes = Zeroizing::new(vec![0u8; length]);
+        for (index, word) in words.iter().enumerate() {
+            let bits = wordlist
+                .iter()
+                .position(|candidate| candidate == word)
+                .ok_or_else(|| Error::InvalidMnemonicShare(format!("unknown word '{word}'")))?;
+            for bit in 0..11 {
+                if (bits >> (10 - bit)) & 1 == 1 {
+                    let position = index * 11 + bit;
+                    // padding bits must be zero
+                    let byte =
+                        bytes.get_mut(position / 8).ok_or_else(|| Error::InvalidMnemonicShare("invalid padding".to_string()))?;
+                    *byte |= 1 << (7 - position % 8);
+                }
+            }
+        }
+
+        let (data, expected) = bytes.split_at(length - CHECKSUM_LENGTH);
+        if checksum(data) != expected {
+            return Err(Error::InvalidMnemonicShare("checksum mismatch".to_string()));
+        }
+
+        let share = MnemonicShare {
+            identifier: u16::from_be_bytes([data[0], data[1]]),
+            threshold: data[2],
+            index: data[3],
+            value: Zeroizing::new(data[HEADER_LENGTH..].to_vec()),
+        };
+        if share.threshold == 0 || share.threshold > MAX_MNEMONIC_SHARE_COUNT || share.index >= MAX_MNEMONIC_SHARE_COUNT {
+            return Err(Error::InvalidMnemonicShare("invalid threshold or index".to_string()));
+        }
+
+        Ok(share)
+    }
+}
+
+impl FromStr for MnemonicShare {
+    type Err = Error;
+    fn from_str(phrase: &str) -> Result<Self> {
+        Self::try_from_phrase(phrase)
+    }
+}
+
+/// Splits the entropy of the `mnemonic` into a share set.
+pub fn split_mnemonic(mnemonic: &Mnemonic, args: &MnemonicShareArgs) -> Result<Vec<MnemonicShare>> {
+    args.validate()?;
+
+    let mut rng = rand::thread_rng();
+    let identifier = rng.next_u32() as u16;
+    let secret = mnemonic.entropy();
+    let share = |index: u8, value: Zeroizing<Vec<u8>>| MnemonicShare { identifier, threshold: args.threshold, index, value };
+
+    if args.threshold == 1 {
+        return Ok((0..args.count).map(|index| share(index, Zeroizing::new(secret.clone()))).collect());
+    }
+
+    let mut random = Zeroizing::new(vec![0u8; secret.len() - DIGEST_LENGTH]);
+    rng.fill_bytes(&mut random);
+    let mut digest = Zeroizing::new(create_digest(&random, secret)?);
+    digest.extend_from_slice(&random);
+
+    let mut points = (0..args.threshold - 2)
+        .map(|index| {
+            let mut value = Zeroizing::new(vec![0u8; secret.len()]);
+            rng.fill_bytes(&mut value);
+            (index, value)
+        })
+        .collect::<Vec<_>>();
+    points.push((DIGEST_INDEX, digest));
+    points.push((SECRET_INDEX, Zeroizing::new(secret.clone())));
+
+    Ok((0..args.count).map(|index| share(index, interpolate(&points, index))).collect())
+}
+
+/// Recovers the mnemonic from the `shares` (at least `threshold` shares of the same set).
+pub fn combine_mnemonic_shares(shares: &[MnemonicShare]) -> Result<Mnemonic> {
+    let (secret, _) = recover_secret(shares)?;
+    Ok(Mnemonic::from_entropy(secret.to_vec(), Language::English)?)
+}
+
+/// Verifies that the `shares` belong to the same set and recover a valid secret,
+/// without revealing it. All supplied shares (including the shares exceeding
+/// the threshold) must be consistent with the recovered secret.
+pub fn verify_mnemonic_shares(shares: &[MnemonicShare]) -> Result<MnemonicShareSetInfo> {
+    let (secret, share_count) = recover_secret(shares)?;
+    let mnemonic = Mnemonic::from_entropy(secret.to_vec(), Language::English)?;
+    let prv_key_data_id = PrvKeyDataVariant::from_mnemonic(mnemonic).id();
+
+    Ok(MnemonicShareSetInfo { identifier: shares[0].identifier, threshold: shares[0].threshold, share_count, prv_key_data_id })
+}
+
+/// Returns the recovered secret and the number of distinct shares.
+fn recover_secret(shares: &[MnemonicShare]) -> Result<(Zeroizing<Vec<u8>>, usize)> {
+    let first = shares.first().ok_or(Error::InsufficientMnemonicShares(0, 1))?;
+    let mut distinct = Vec::<&MnemonicShare>::new();
+    for share in shares {
+        if share.identifier != first.identifier || share.threshold != first.threshold || share.value.len() != first.value.len() {
+            return Err(Error::InvalidMnemonicShare(format!("share {} does not belong to the share set", share.number())));
+        }
+        match distinct.iter().find(|other| other.index == share.index) {
+            Some(other) if other.value != share.value => {
+                return Err(Error::InvalidMnemonicShare(format!("conflicting copies of share {}", share.number())));
+            }
+            Some(_) => {}
+            None => distinct.push(share),
+        }
+    }
+
+    let threshold = first.threshold as usize;
+    if distinct.len() < threshold {
+        return Err(Error::InsufficientMnemonicShares(distinct.len(), first.threshold));
+    }
+
+    let points = distinct[..threshold].iter().map(|share| (share.index, share.value.clone())).collect::<Vec<_>>();
+    let secret = if threshold == 1 {
+        points[0].1.clone()
+    } else {
+        let secret = interpolate(&points, SECRET_INDEX);
+        let digest = interpolate(&points, DIGEST_INDEX);
+        let (expected, random) = digest.split_at(DIGEST_LENGTH);
+        if create_digest(random, &secret)? != expected {
+            return Err(Error::MnemonicShareDigestMismatch);
+        }
+        secret
+    };
+
+    for share in &distinct[threshold..] {
+        let value = if threshold == 1 { points[0].1.clone() } else { interpolate(&points, share.index) };
+        if value != share.value {
+            return Err(Error::InvalidMnemonicShare(format!("share {} does not belong to the share set", share.number())));
+        }
+    }
+
+    Ok((secret, distinct.len()))
+}
+
+fn create_digest(random: &[u8], secret: &[u8]) -> Result<Vec<u8>> {
+    let mut hmac = Hmac::<Sha256>::new_from_slice(random).map_err(|err| Error::custom(err.to_string()))?;
+    hmac.update(secret);
+    Ok(hmac.finalize().into_bytes()[..DIGEST_LENGTH].to_vec())
+}
+
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let mut hasher = Sha256::new();
+    hasher.update(CHECKSUM_CUSTOMIZATION);
+    hasher.update(data);
+    let hash = hasher.finalize();
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// GF(256) exponent and logarithm tables for the generator `x + 1`
+const GF256_TABLES: ([u8; 255], [u8; 256]) = {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut value: u16 = 1;
+    let mut power = 0;
+    while power < 255 {
+        exp[power] = value as u8;
+        log[value as usize] = power as u8;
+        value ^= value << 1;
+        if value & 0x100 != 0 {
+            value ^= 0x11b;
+        }
+        power += 1;
+    }
+    (exp, log)
+};
+
+fn gf256_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = &GF256_TABLES;
+    exp[(log[a as usize] as usize + log[b as usize] as usize) % 255]
+}
+
+fn gf256_div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+    let (exp, log) = &GF256_TABLES;
+    exp[(log[a as usize] as usize + 255 - log[b as usize] as usize) % 255]
+}
+
+/// Evaluates at `x` the polynomial passing through the `points` (with distinct indices).
+fn interpolate(points: &[(u8, Zeroizing<Vec<u8>>)], x: u8) -> Zeroizing<Vec<u8>> {
+    if let Some((_, value)) = points.iter().find(|(index, _)| *index == x) {
+        return value.clone();
+    }
+
+    let mut result = Zeroizing::new(vec![0u8; points[0].1.len()]);
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        // subtraction in GF(256) is xor
+        let basis = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(1u8, |basis, (_, (xj, _))| gf256_mul(basis, gf256_div(x ^ xj, xi ^ xj)));
+        result.iter_mut().zip(yi.iter()).for_each(|(result, y)| *result ^= gf256_mul(basis, *y));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use waglayla_bip32::WordCount;
+
+    #[test]
+    fn test_mnemonic_shares() -> Result<()> {
+        for word_count in [WordCount::Words12, WordCount::Words24] {
+            let mnemonic = Mnemonic::random(word_count, Language::English)?;
+            let shares = split_mnemonic(&mnemonic, &MnemonicShareArgs::new(3, 5))?;
+            assert_eq!(shares.len(), 5);
+
+            // shares survive the word encoding
+            let shares = shares.iter().map(|share| MnemonicShare::try_from_phrase(&share.to_phrase())).collect::<Result<Vec<_>>>()?;
+
+            // any 3 shares recover the mnemonic
+            for selection in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+                let selected = selection.iter().map(|index| shares[*index].clone()).collect::<Vec<_>>();
+                assert_eq!(combine_mnemonic_shares(&selected)?.phrase(), mnemonic.phrase());
+            }
+
+            let info = verify_mnemonic_shares(&shares)?;
+            assert_eq!(info.share_count, 5);
+            assert_eq!(info.prv_key_data_id, PrvKeyDataVariant::from_mnemonic(mnemonic.clone()).id());
+
+            assert!(matches!(combine_mnemonic_shares(&shares[..2]), Err(Error::InsufficientMnemonicShares(2, 3))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_mnemonic_shares_validation() -> Result<()> {
+        let mnemonic = Mnemonic::random(WordCount::Words12, Language::English)?;
+        assert!(split_mnemonic(&mnemonic, &MnemonicShareArgs::new(0, 3)).is_err());
+        assert!(split_mnemonic(&mnemonic, &MnemonicShareArgs::new(4, 3)).is_err());
+        assert!(split_mnemonic(&mnemonic, &MnemonicShareArgs::new(2, 17)).is_err());
+
+        let single = split_mnemonic(&mnemonic, &MnemonicShareArgs::new(1, 2))?;
+        assert_eq!(combine_mnemonic_shares(&single[1..])?.phrase(), mnemonic.phrase());
+
+        // a corrupted word fails the checksum
+        let shares = split_mnemonic(&mnemonic, &MnemonicShareArgs::new(2, 3))?;
+        let phrase = shares[0].to_phrase();
+        let mut words = phrase.split(' ').collect::<Vec<_>>();
+        words[5] = if words[5] == "abandon" { "ability" } else { "abandon" };
+        assert!(MnemonicShare::try_from_phrase(&words.join(" ")).is_err());
+
+        // shares of different sets can not be combined
+        let other = split_mnemonic(&mnemonic, &MnemonicShareArgs::new(2, 3))?;
+        assert!(combine_mnemonic_shares(&[shares[0].clone(), other[1].clone()]).is_err());
+
+        // a tampered share value fails the digest verification
+        let mut tampered = shares[1].clone();
+        tampered.value[0] ^= 1;
+        assert!(matches!(combine_mnemonic_shares(&[shares[0].clone(), tampered]), Err(Error::MnemonicShareDigestMismatch)));
+        Ok(())
+    }
+}
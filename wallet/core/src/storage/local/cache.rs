@@ -16,6 +16,7 @@ pub struct Cache {
     pub accounts: Collection<AccountId, AccountStorage>,
     pub metadata: Collection<AccountId, AccountMetadata>,
     pub address_book: Vec<AddressBookEntry>,
+    pub utxo_metadata: Vec<UtxoMetadata>,
 }
 
 impl Cache {
@@ -34,8 +35,19 @@ impl Cache {
         let user_hint = wallet.user_hint;
         let wallet_title = wallet.title;
         let address_book = payload.0.address_book.into_iter().collect();
+        let utxo_metadata = wallet.utxo_metadata;
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            utxo_metadata,
+        })
     }
 
     pub fn from_payload(
@@ -53,8 +65,19 @@ impl Cache {
         let accounts: Collection<AccountId, AccountStorage> = payload.accounts.try_into()?;
         let metadata: Collection<AccountId, AccountMetadata> = Collection::default();
         let address_book = payload.address_book.into_iter().collect();
+        let utxo_metadata = vec![];
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            utxo_metadata,
+        })
     }
 
     pub fn to_wallet(
@@ -77,6 +100,7 @@ impl Cache {
             user_hint: self.user_hint.clone(),
             title: self.wallet_title.clone(),
             transactions,
+            utxo_metadata: self.utxo_metadata.clone(),
         })
     }
 }
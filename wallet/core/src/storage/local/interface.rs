@@ -15,6 +15,7 @@ use crate::storage::local::wallet::WalletStorage;
 use crate::storage::local::Payload;
 use crate::storage::local::Storage;
 use slugify_rs::slugify;
+use waglayla_consensus_core::tx::TransactionOutpoint;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
@@ -183,9 +184,13 @@ impl LocalStoreInner {
             Store::Storage(ref storage) => {
                 // take current metadata, load wallet, replace metadata, store wallet
                 // this bypasses the cache payload and wallet encryption
-                let metadata: Vec<AccountMetadata> = (&self.cache.read().unwrap().metadata).try_into()?;
+                let (metadata, utxo_metadata) = {
+                    let cache = self.cache.read().unwrap();
+                    let metadata: Vec<AccountMetadata> = (&cache.metadata).try_into()?;
+                    (metadata, cache.utxo_metadata.clone())
+                };
                 let mut wallet = WalletStorage::try_load(storage).await?;
-                wallet.replace_metadata(metadata);
+                wallet.replace_metadata(metadata, utxo_metadata);
                 wallet.try_store(storage).await?;
                 Ok(())
             }
@@ -334,6 +339,10 @@ impl Interface for LocalStore {
         Ok(self.inner()?)
     }
 
+    fn as_utxo_metadata_store(&self) -> Result<Arc<dyn UtxoMetadataStore>> {
+        Ok(self.inner()?)
+    }
+
     fn as_transaction_record_store(&self) -> Result<Arc<dyn TransactionRecordStore>> {
         Ok(self.inner()?.transactions.clone())
     }
@@ -609,20 +618,96 @@ impl AccountStore for LocalStoreInner {
 
 #[async_trait]
 impl AddressBookStore for LocalStoreInner {
+    async fn is_empty(&self) -> Result<bool> {
+        Ok(self.cache.read().unwrap().address_book.is_empty())
+    }
+
     async fn iter(&self) -> Result<StorageStream<Arc<AddressBookEntry>>> {
         Ok(Box::pin(AddressBookEntryStream::new(self.cache.clone())))
     }
 
     async fn search(&self, search: &str) -> Result<Vec<Arc<AddressBookEntry>>> {
-        let matches = self
+        let mut matches = self
             .cache
             .read()
             .unwrap()
             .address_book
             .iter()
-            .filter_map(|entry| if entry.alias.contains(search) { Some(Arc::new(entry.clone())) } else { None })
-            .collect();
+            .filter_map(|entry| entry.search_score(search).map(|score| (score, entry)))
+            .map(|(score, entry)| (score, Arc::new(entry.clone())))
+            .collect::<Vec<_>>();
+        matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        Ok(matches.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    async fn load(&self, prefix: Prefix, alias: &str) -> Result<Option<Arc<AddressBookEntry>>> {
+        let cache = self.cache.read().unwrap();
+        Ok(cache.address_book.iter().find(|entry| entry.is(prefix, alias)).cloned().map(Arc::new))
+    }
+
+    async fn store(&self, entry: AddressBookEntry) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        if let Some(existing) = cache.address_book.iter_mut().find(|existing| existing.is(entry.address.prefix, &entry.alias)) {
+            *existing = entry;
+        } else {
+            cache.address_book.push(entry);
+        }
+
+        self.set_modified(true);
+
+        Ok(())
+    }
+
+    async fn remove(&self, prefix: Prefix, alias: &str) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        let len = cache.address_book.len();
+        cache.address_book.retain(|entry| !entry.is(prefix, alias));
+        if cache.address_book.len() == len {
+            return Err(Error::AddressBookEntryNotFound(alias.to_string()));
+        }
+
+        self.set_modified(true);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UtxoMetadataStore for LocalStoreInner {
+    async fn load_all(&self) -> Result<Vec<Arc<UtxoMetadata>>> {
+        Ok(self.cache.read().unwrap().utxo_metadata.iter().cloned().map(Arc::new).collect())
+    }
 
-        Ok(matches)
+    async fn load_single(&self, outpoint: &TransactionOutpoint) -> Result<Option<Arc<UtxoMetadata>>> {
+        let cache = self.cache.read().unwrap();
+        Ok(cache.utxo_metadata.iter().find(|metadata| metadata.outpoint == *outpoint).cloned().map(Arc::new))
+    }
+
+    async fn store(&self, metadata: Vec<UtxoMetadata>) -> Result<()> {
+        {
+            let mut cache = self.cache.write().unwrap();
+            let outpoints = metadata.iter().map(|metadata| metadata.outpoint).collect::<HashSet<_>>();
+            cache.utxo_metadata.retain(|existing| !outpoints.contains(&existing.outpoint));
+            cache.utxo_metadata.extend(metadata.into_iter().filter(|metadata| !metadata.is_empty()));
+        }
+
+        self.update_stored_metadata().await
+    }
+
+    async fn remove(&self, outpoints: &[TransactionOutpoint]) -> Result<()> {
+        let removed = {
+            let mut cache = self.cache.write().unwrap();
+            let len = cache.utxo_metadata.len();
+            cache.utxo_metadata.retain(|metadata| !outpoints.contains(&metadata.outpoint));
+            cache.utxo_metadata.len() != len
+        };
+
+        // spent UTXOs are rarely annotated, avoid rewriting the wallet file for each of them
+        if removed {
+            self.update_stored_metadata().await?;
+        }
+
+        Ok(())
     }
 }
@@ -7,7 +7,7 @@ use crate::storage::local::Payload;
 use crate::storage::local::Storage;
 use crate::storage::Encryptable;
 use crate::storage::TransactionRecord;
-use crate::storage::{AccountMetadata, Decrypted, Encrypted, Hint, PrvKeyData, PrvKeyDataId};
+use crate::storage::{AccountMetadata, Decrypted, Encrypted, Hint, PrvKeyData, PrvKeyDataId, UtxoMetadata};
 use workflow_store::fs;
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -21,11 +21,14 @@ pub struct WalletStorage {
     pub metadata: Vec<AccountMetadata>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transactions: Option<Encryptable<HashMap<AccountId, Vec<TransactionRecord>>>>,
+    #[serde(default)]
+    pub utxo_metadata: Vec<UtxoMetadata>,
 }
 
 impl WalletStorage {
     pub const STORAGE_MAGIC: u32 = 0x5753414b;
-    pub const STORAGE_VERSION: u32 = 0;
+    // version 1 adds `utxo_metadata`
+    pub const STORAGE_VERSION: u32 = 1;
 
     pub fn try_new(
         title: Option<String>,
@@ -36,7 +39,7 @@ impl WalletStorage {
         metadata: Vec<AccountMetadata>,
     ) -> Result<Self> {
         let payload = Decrypted::new(payload).encrypt(secret, encryption_kind)?;
-        Ok(Self { title, encryption_kind, payload, metadata, user_hint, transactions: None })
+        Ok(Self { title, encryption_kind, payload, metadata, user_hint, transactions: None, utxo_metadata: vec![] })
     }
 
     pub fn payload(&self, secret: &Secret) -> Result<Decrypted<Payload>> {
@@ -81,8 +84,9 @@ impl WalletStorage {
         Ok(keydata)
     }
 
-    pub fn replace_metadata(&mut self, metadata: Vec<AccountMetadata>) {
+    pub fn replace_metadata(&mut self, metadata: Vec<AccountMetadata>, utxo_metadata: Vec<UtxoMetadata>) {
         self.metadata = metadata;
+        self.utxo_metadata = utxo_metadata;
     }
 }
 
@@ -95,6 +99,7 @@ impl BorshSerialize for WalletStorage {
         BorshSerialize::serialize(&self.payload, writer)?;
         BorshSerialize::serialize(&self.metadata, writer)?;
         BorshSerialize::serialize(&self.transactions, writer)?;
+        BorshSerialize::serialize(&self.utxo_metadata, writer)?;
 
         Ok(())
     }
@@ -124,8 +129,9 @@ impl BorshDeserialize for WalletStorage {
         let payload = BorshDeserialize::deserialize(buf)?;
         let metadata = BorshDeserialize::deserialize(buf)?;
         let transactions = BorshDeserialize::deserialize(buf)?;
+        let utxo_metadata = if version > 0 { BorshDeserialize::deserialize(buf)? } else { vec![] };
 
-        Ok(Self { title, user_hint, encryption_kind, payload, metadata, transactions })
+        Ok(Self { title, user_hint, encryption_kind, payload, metadata, transactions, utxo_metadata })
     }
 }
 
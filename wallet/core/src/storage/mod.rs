@@ -15,6 +15,7 @@ pub mod local;
 pub mod metadata;
 pub mod storable;
 pub mod transaction;
+pub mod utxo;
 
 pub use account::{AccountSettings, AccountStorable, AccountStorage};
 pub use address::AddressBookEntry;
@@ -22,13 +23,20 @@ pub use binding::Binding;
 pub use hint::Hint;
 pub use id::IdT;
 pub use interface::{
-    AccountStore, Interface, PrvKeyDataStore, StorageDescriptor, TransactionRecordStore, WalletDescriptor, WalletExportOptions,
+    AccountStore, Interface, PrvKeyDataStore, StorageDescriptor, TransactionRecordStore, UtxoMetadataStore, WalletDescriptor,
+    WalletExportOptions,
+};
+pub use keydata::{
+    combine_mnemonic_shares, split_mnemonic, verify_mnemonic_shares, AssocPrvKeyDataIds, MnemonicShare, MnemonicShareArgs, MnemonicShareSetInfo,
+    PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, PrvKeyDataMap, PrvKeyDataPayload,
 };
-pub use keydata::{AssocPrvKeyDataIds, PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, PrvKeyDataMap, PrvKeyDataPayload};
 pub use local::interface::make_filename;
 pub use metadata::AccountMetadata;
 pub use storable::Storable;
-pub use transaction::{TransactionData, TransactionId, TransactionKind, TransactionRecord};
+pub use transaction::{
+    TransactionData, TransactionExportFormat, TransactionExportRecord, TransactionId, TransactionKind, TransactionRecord,
+};
+pub use utxo::UtxoMetadata;
 
 #[cfg(test)]
 mod tests {
@@ -119,6 +119,40 @@ pub enum TransactionData {
         #[serde(rename = "utxoEntries")]
         utxo_entries: Vec<UtxoRecord>,
     },
+    Consolidation {
+        fees: u64,
+        #[serde(rename = "inputValue")]
+        aggregate_input_value: u64,
+        #[serde(rename = "outputValue")]
+        aggregate_output_value: u64,
+        transaction: Transaction,
+        #[serde(rename = "paymentValue")]
+        payment_value: Option<u64>,
+        #[serde(rename = "changeValue")]
+        change_value: u64,
+        #[serde(rename = "acceptedDaaScore")]
+        accepted_daa_score: Option<u64>,
+        #[serde(rename = "utxoEntries")]
+        #[serde(default)]
+        utxo_entries: Vec<UtxoRecord>,
+    },
+    FeeBump {
+        fees: u64,
+        #[serde(rename = "inputValue")]
+        aggregate_input_value: u64,
+        #[serde(rename = "outputValue")]
+        aggregate_output_value: u64,
+        transaction: Transaction,
+        #[serde(rename = "paymentValue")]
+        payment_value: Option<u64>,
+        #[serde(rename = "changeValue")]
+        change_value: u64,
+        #[serde(rename = "acceptedDaaScore")]
+        accepted_daa_score: Option<u64>,
+        #[serde(rename = "utxoEntries")]
+        #[serde(default)]
+        utxo_entries: Vec<UtxoRecord>,
+    },
 }
 
 impl TransactionData {
@@ -136,6 +170,8 @@ impl TransactionData {
             TransactionData::TransferIncoming { .. } => TransactionKind::TransferIncoming,
             TransactionData::TransferOutgoing { .. } => TransactionKind::TransferOutgoing,
             TransactionData::Change { .. } => TransactionKind::Change,
+            TransactionData::Consolidation { .. } => TransactionKind::Consolidation,
+            TransactionData::FeeBump { .. } => TransactionKind::FeeBump,
         }
     }
 
@@ -154,6 +190,10 @@ impl TransactionData {
                 utxo_entries.iter().any(|utxo| utxo.address.as_ref() == Some(address))
             }
             TransactionData::Change { utxo_entries, .. } => utxo_entries.iter().any(|utxo| utxo.address.as_ref() == Some(address)),
+            TransactionData::Consolidation { utxo_entries, .. } => {
+                utxo_entries.iter().any(|utxo| utxo.address.as_ref() == Some(address))
+            }
+            TransactionData::FeeBump { utxo_entries, .. } => utxo_entries.iter().any(|utxo| utxo.address.as_ref() == Some(address)),
         }
     }
 }
@@ -275,6 +315,44 @@ impl BorshSerialize for TransactionData {
                 BorshSerialize::serialize(accepted_daa_score, writer)?;
                 BorshSerialize::serialize(utxo_entries, writer)?;
             }
+            TransactionData::Consolidation {
+                fees,
+                aggregate_input_value,
+                aggregate_output_value,
+                transaction,
+                payment_value,
+                change_value,
+                accepted_daa_score,
+                utxo_entries,
+            } => {
+                BorshSerialize::serialize(fees, writer)?;
+                BorshSerialize::serialize(aggregate_input_value, writer)?;
+                BorshSerialize::serialize(aggregate_output_value, writer)?;
+                BorshSerialize::serialize(transaction, writer)?;
+                BorshSerialize::serialize(payment_value, writer)?;
+                BorshSerialize::serialize(change_value, writer)?;
+                BorshSerialize::serialize(accepted_daa_score, writer)?;
+                BorshSerialize::serialize(utxo_entries, writer)?;
+            }
+            TransactionData::FeeBump {
+                fees,
+                aggregate_input_value,
+                aggregate_output_value,
+                transaction,
+                payment_value,
+                change_value,
+                accepted_daa_score,
+                utxo_entries,
+            } => {
+                BorshSerialize::serialize(fees, writer)?;
+                BorshSerialize::serialize(aggregate_input_value, writer)?;
+                BorshSerialize::serialize(aggregate_output_value, writer)?;
+                BorshSerialize::serialize(transaction, writer)?;
+                BorshSerialize::serialize(payment_value, writer)?;
+                BorshSerialize::serialize(change_value, writer)?;
+                BorshSerialize::serialize(accepted_daa_score, writer)?;
+                BorshSerialize::serialize(utxo_entries, writer)?;
+            }
         }
 
         Ok(())
@@ -407,6 +485,46 @@ impl BorshDeserialize for TransactionData {
                     utxo_entries,
                 })
             }
+            TransactionKind::Consolidation => {
+                let fees: u64 = BorshDeserialize::deserialize(buf)?;
+                let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
+                let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
+                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
+                let change_value: u64 = BorshDeserialize::deserialize(buf)?;
+                let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
+                let utxo_entries: Vec<UtxoRecord> = BorshDeserialize::deserialize(buf)?;
+                Ok(TransactionData::Consolidation {
+                    fees,
+                    aggregate_input_value,
+                    aggregate_output_value,
+                    transaction,
+                    payment_value,
+                    change_value,
+                    accepted_daa_score,
+                    utxo_entries,
+                })
+            }
+            TransactionKind::FeeBump => {
+                let fees: u64 = BorshDeserialize::deserialize(buf)?;
+                let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
+                let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
+                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
+                let change_value: u64 = BorshDeserialize::deserialize(buf)?;
+                let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
+                let utxo_entries: Vec<UtxoRecord> = BorshDeserialize::deserialize(buf)?;
+                Ok(TransactionData::FeeBump {
+                    fees,
+                    aggregate_input_value,
+                    aggregate_output_value,
+                    transaction,
+                    payment_value,
+                    change_value,
+                    accepted_daa_score,
+                    utxo_entries,
+                })
+            }
         }
     }
 }
@@ -0,0 +1,370 @@
+//!
+//! Transaction history export in CSV and JSON formats.
+//!
+
+use super::{TransactionData, TransactionKind, TransactionRecord};
+use crate::imports::*;
+use waglayla_consensus_core::tx::TransactionId;
+use waglayla_txscript::extract_script_pub_key_address;
+
+const MSEC_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// Format of the transaction history export.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl TransactionExportFormat {
+    /// File extension associated with the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TransactionExportFormat::Csv => "csv",
+            TransactionExportFormat::Json => "json",
+        }
+    }
+
+    /// Serializes `records` into the export format.
+    pub fn export(&self, records: &[TransactionExportRecord]) -> Result<String> {
+        match self {
+            TransactionExportFormat::Csv => {
+                let mut text = String::from("id,kind,daaScore,unixtimeMsec,date,value,fees,destinationAddresses,note,metadata\n");
+                records.iter().for_each(|record| {
+                    text.push_str(&record.to_csv());
+                    text.push('\n');
+                });
+                Ok(text)
+            }
+            TransactionExportFormat::Json => Ok(serde_json::to_string_pretty(records)?),
+        }
+    }
+}
+
+impl std::fmt::Display for TransactionExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for TransactionExportFormat {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(TransactionExportFormat::Csv),
+            "json" => Ok(TransactionExportFormat::Json),
+            _ => Err(Error::custom(format!("invalid export format '{s}', expected 'csv' or 'json'"))),
+        }
+    }
+}
+
+/// Flat representation of a [`TransactionRecord`] used by the history export.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionExportRecord {
+    pub id: TransactionId,
+    pub kind: TransactionKind,
+    pub daa_score: u64,
+    /// Estimated unix time in milliseconds, if available
+    pub unixtime_msec: Option<u64>,
+    /// Transaction value in SOMPI
+    pub value: u64,
+    /// Fees paid by the account (outgoing transactions only)
+    pub fees: Option<u64>,
+    /// Addresses receiving the value: payment destinations of outgoing
+    /// transactions (excluding the change address) or the account's own
+    /// receive addresses of incoming transactions. Senders of incoming
+    /// transactions are not known to the wallet and are not exported.
+    pub destination_addresses: Vec<Address>,
+    pub note: Option<String>,
+    pub metadata: Option<String>,
+}
+
+impl TransactionExportRecord {
+    pub fn new(record: &TransactionRecord, unixtime_msec: Option<u64>) -> Self {
+        let prefix = Prefix::from(*record.network_id());
+
+        let (fees, destination_addresses) = match record.transaction_data() {
+            TransactionData::Outgoing { fees, transaction, change_value, .. }
+            | TransactionData::Batch { fees, transaction, change_value, .. }
+            | TransactionData::TransferOutgoing { fees, transaction, change_value, .. } => {
+                // the change output, if any, is the last output of the transaction
+                let payment_outputs = transaction.outputs.len().saturating_sub(if *change_value > 0 { 1 } else { 0 });
+                let addresses = transaction.outputs[..payment_outputs]
+                    .iter()
+                    .filter_map(|output| extract_script_pub_key_address(&output.script_public_key, prefix).ok())
+                    .collect();
+                (Some(*fees), addresses)
+            }
+            TransactionData::Consolidation { fees, .. } | TransactionData::FeeBump { fees, .. } => (Some(*fees), vec![]),
+            TransactionData::Reorg { utxo_entries, .. }
+            | TransactionData::Incoming { utxo_entries, .. }
+            | TransactionData::Stasis { utxo_entries, .. }
+            | TransactionData::External { utxo_entries, .. }
+            | TransactionData::TransferIncoming { utxo_entries, .. }
+            | TransactionData::Change { utxo_entries, .. } => {
+                let mut addresses = Vec::<Address>::new();
+                utxo_entries.iter().filter_map(|entry| entry.address.as_ref()).for_each(|address| {
+                    if !addresses.contains(address) {
+                        addresses.push(address.clone());
+                    }
+                });
+                (None, addresses)
+            }
+        };
+
+        Self {
+            id: *record.id(),
+            kind: record.kind(),
+            daa_score: record.block_daa_score(),
+            unixtime_msec: unixtime_msec.or(record.unixtime_msec()),
+            value: record.value(),
+            fees,
+            destination_addresses,
+            note: record.note.clone(),
+            metadata: record.metadata.clone(),
+        }
+    }
+
+    /// Formats the record as a CSV line (without the line terminator).
+    pub fn to_csv(&self) -> String {
+        let addresses = self.destination_addresses.iter().map(|address| address.to_string()).collect::<Vec<_>>();
+        [
+            self.id.to_string(),
+            self.kind.to_string(),
+            self.daa_score.to_string(),
+            self.unixtime_msec.map(|unixtime| unixtime.to_string()).unwrap_or_default(),
+            self.unixtime_msec.map(unixtime_to_utc_string).unwrap_or_default(),
+            self.value.to_string(),
+            self.fees.map(|fees| fees.to_string()).unwrap_or_default(),
+            addresses.join(" "),
+            self.note.clone().unwrap_or_default(),
+            self.metadata.clone().unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| escape_csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    // spreadsheets evaluate cells starting with these characters as formulas
+    let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) { format!("'{field}") } else { field.to_string() };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Formats unix time in milliseconds as `YYYY-MM-DD HH:MM:SS` (UTC).
+pub fn unixtime_to_utc_string(unixtime_msec: u64) -> String {
+    let days = (unixtime_msec / MSEC_PER_DAY) as i64;
+    let secs = (unixtime_msec % MSEC_PER_DAY) / 1000;
+
+    // civil date from days since the unix epoch
+    // (http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Parses a `YYYY-MM-DD` UTC date returning unix time in milliseconds
+/// at the start of the day.
+pub fn utc_date_to_unixtime(date: &str) -> Result<u64> {
+    let invalid = || Error::custom(format!("invalid date '{date}', expected YYYY-MM-DD"));
+    let mut parts = date.trim().splitn(3, '-').map(|part| part.parse::<i64>().map_err(|_| invalid()));
+    let (year, month, day) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(year), Some(month), Some(day)) => (year?, month?, day?),
+        _ => return Err(invalid()),
+    };
+    if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+
+    // days since the unix epoch from a civil date
+    // (http://howardhinnant.github.io/date_algorithms.html#days_from_civil)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    Ok(days as u64 * MSEC_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::UtxoRecord;
+    use super::*;
+    use crate::api::{message::*, WalletApi};
+    use waglayla_addresses::Version;
+    use waglayla_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use waglayla_consensus_core::tx::{Transaction, TransactionOutput};
+    use waglayla_hashes::Hash;
+    use waglayla_txscript::pay_to_address_script;
+
+    const NETWORK_ID: NetworkId = NetworkId::with_suffix(NetworkType::Testnet, 10);
+
+    fn address(seed: u8) -> Address {
+        Address::new(Prefix::Testnet, Version::PubKey, &[seed; 32])
+    }
+
+    fn record(account_id: AccountId, seed: u64, unixtime_msec: u64, transaction_data: TransactionData) -> TransactionRecord {
+        TransactionRecord {
+            id: TransactionId::from_u64_word(seed),
+            unixtime_msec: Some(unixtime_msec),
+            value: 1_000,
+            binding: Binding::Account(account_id),
+            block_daa_score: seed,
+            network_id: NETWORK_ID,
+            transaction_data,
+            note: None,
+            metadata: None,
+        }
+    }
+
+    fn outgoing(destinations: &[Address], change: Option<&Address>) -> TransactionData {
+        let outputs = destinations
+            .iter()
+            .chain(change)
+            .map(|address| TransactionOutput::new(1_000, pay_to_address_script(address)))
+            .collect::<Vec<_>>();
+        TransactionData::Outgoing {
+            fees: 10,
+            aggregate_input_value: 1_000 * outputs.len() as u64 + 10,
+            aggregate_output_value: 1_000 * outputs.len() as u64,
+            transaction: Transaction::new(0, vec![], outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]),
+            payment_value: Some(1_000 * destinations.len() as u64),
+            change_value: if change.is_some() { 1_000 } else { 0 },
+            accepted_daa_score: None,
+            utxo_entries: vec![],
+        }
+    }
+
+    fn incoming(receive: &Address) -> TransactionData {
+        let utxo_entry = UtxoRecord {
+            address: Some(receive.clone()),
+            index: 0,
+            amount: 1_000,
+            script_public_key: pay_to_address_script(receive),
+            is_coinbase: false,
+        };
+        TransactionData::Incoming { utxo_entries: vec![utxo_entry.clone(), utxo_entry], aggregate_input_value: 2_000 }
+    }
+
+    #[test]
+    fn test_transaction_export_record() {
+        let account_id = AccountId(Hash::from_u64_word(1));
+        let (destination, change, receive) = (address(1), address(2), address(3));
+
+        let export = TransactionExportRecord::new(&record(account_id, 1, 0, outgoing(&[destination.clone()], Some(&change))), None);
+        assert_eq!(export.destination_addresses, vec![destination.clone()]);
+        assert_eq!(export.fees, Some(10));
+
+        let export =
+            TransactionExportRecord::new(&record(account_id, 2, 0, outgoing(&[destination.clone(), change.clone()], None)), None);
+        assert_eq!(export.destination_addresses, vec![destination, change]);
+
+        let export = TransactionExportRecord::new(&record(account_id, 3, 0, incoming(&receive)), Some(1_000));
+        assert_eq!(export.destination_addresses, vec![receive]);
+        assert_eq!(export.fees, None);
+        assert_eq!(export.unixtime_msec, Some(1_000));
+    }
+
+    #[test]
+    fn test_transaction_export_dates() -> Result<()> {
+        assert_eq!(unixtime_to_utc_string(0), "1970-01-01 00:00:00");
+        assert_eq!(unixtime_to_utc_string(1_709_210_096_000), "2024-02-29 12:34:56");
+        assert_eq!(utc_date_to_unixtime("1970-01-01")?, 0);
+        assert_eq!(utc_date_to_unixtime("2024-02-29")?, 1_709_164_800_000);
+        assert_eq!(unixtime_to_utc_string(utc_date_to_unixtime("2026-12-31")?), "2026-12-31 00:00:00");
+        assert!(utc_date_to_unixtime("2024-13-01").is_err());
+        assert!(utc_date_to_unixtime("2024-02").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_transaction_export_csv_escaping() {
+        assert_eq!(escape_csv_field("plain"), "plain");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("=SUM(A1:A2)"), "'=SUM(A1:A2)");
+        assert_eq!(escape_csv_field("-1+2"), "'-1+2");
+        assert_eq!(escape_csv_field("@cmd"), "'@cmd");
+        assert_eq!(escape_csv_field("\tnote"), "'\tnote");
+        assert_eq!(escape_csv_field("+1,2"), "\"'+1,2\"");
+        assert_eq!(escape_csv_field("a=b"), "a=b");
+        assert_eq!("JSON".parse::<TransactionExportFormat>().unwrap(), TransactionExportFormat::Json);
+        assert!("xml".parse::<TransactionExportFormat>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transactions_export_call_filters() -> Result<()> {
+        let wallet = Arc::new(Wallet::try_new(Wallet::resident_store()?, None, Some(NETWORK_ID))?);
+        let args = WalletCreateArgs {
+            title: None,
+            filename: None,
+            encryption_kind: EncryptionKind::XChaCha20Poly1305,
+            user_hint: None,
+            overwrite_wallet_storage: false,
+        };
+        wallet.create_wallet(&Secret::new(vec![]), args).await?;
+
+        let account_id = AccountId(Hash::from_u64_word(0x5854_4578_706f_7274));
+        let day = MSEC_PER_DAY;
+        let records = [
+            record(account_id, 1, day, incoming(&address(3))),
+            record(account_id, 2, 2 * day, outgoing(&[address(1)], Some(&address(2)))),
+            record(account_id, 3, 3 * day, incoming(&address(3))),
+        ];
+        let store = wallet.store().as_transaction_record_store()?;
+        store.store(&records.iter().collect::<Vec<_>>()).await?;
+
+        let export = |filter: Option<Vec<TransactionKind>>, from_unixtime_msec: Option<u64>, to_unixtime_msec: Option<u64>| {
+            let request = TransactionsExportRequest {
+                account_id,
+                network_id: NETWORK_ID,
+                format: TransactionExportFormat::Json,
+                filter,
+                from_unixtime_msec,
+                to_unixtime_msec,
+            };
+            let wallet = wallet.clone();
+            async move {
+                let response = wallet.transactions_export_call(request).await?;
+                let records = serde_json::from_str::<Vec<TransactionExportRecord>>(&response.data)?;
+                assert_eq!(response.total, records.len() as u64);
+                Ok::<_, Error>(records.iter().map(|record| record.daa_score).collect::<Vec<_>>())
+            }
+        };
+
+        let result = async {
+            assert_eq!(export(None, None, None).await?, vec![1, 2, 3]);
+            assert_eq!(export(Some(vec![TransactionKind::Incoming]), None, None).await?, vec![1, 3]);
+            assert_eq!(export(Some(vec![TransactionKind::Outgoing]), None, None).await?, vec![2]);
+            // the from bound is inclusive and the to bound is exclusive
+            assert_eq!(export(None, Some(2 * day), None).await?, vec![2, 3]);
+            assert_eq!(export(None, None, Some(3 * day)).await?, vec![1, 2]);
+            assert_eq!(export(Some(vec![TransactionKind::Incoming]), Some(2 * day), Some(4 * day)).await?, vec![3]);
+            Ok::<_, Error>(())
+        }
+        .await;
+
+        let ids = records.iter().map(|record| record.id()).collect::<Vec<_>>();
+        store.remove(&Binding::Account(account_id), &NETWORK_ID, &ids).await?;
+        result
+    }
+}
@@ -23,11 +23,13 @@ export enum TransactionKind {
     External = "external",
     TransferIncoming = "transfer-incoming",
     TransferOutgoing = "transfer-outgoing",
+    Consolidation = "consolidation",
+    FeeBump = "fee-bump",
 }
 "#;
 
 // Do not change the order of the variants in this enum.
-seal! { 0x93c6, {
+seal! { 0x10ee, {
         #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, Eq, PartialEq)]
         #[serde(rename_all = "kebab-case")]
         pub enum TransactionKind {
@@ -65,6 +67,15 @@ seal! { 0x93c6, {
             /// Outgoing transfer transaction. A transfer between multiple
             /// accounts managed by the wallet runtime.
             TransferOutgoing,
+            /// Consolidation (sweep) transaction compounding account
+            /// UTXOs into the account change address. This is the final
+            /// transaction in the transaction tree, generated manually
+            /// or by the automatic consolidation policy.
+            Consolidation,
+            /// Child-pays-for-parent transaction spending outputs of an
+            /// unconfirmed transaction into the account change address,
+            /// raising the fee rate of the parent and child package.
+            FeeBump,
         }
     }
 }
@@ -83,6 +94,8 @@ impl TransactionKind {
             TransactionKind::TransferIncoming => "",
             TransactionKind::TransferOutgoing => "",
             TransactionKind::Change => "",
+            TransactionKind::Consolidation => "",
+            TransactionKind::FeeBump => "",
         }
         .to_string()
     }
@@ -100,27 +113,38 @@ impl std::fmt::Display for TransactionKind {
             TransactionKind::TransferIncoming => "transfer-incoming",
             TransactionKind::TransferOutgoing => "transfer-outgoing",
             TransactionKind::Change => "change",
+            TransactionKind::Consolidation => "consolidation",
+            TransactionKind::FeeBump => "fee-bump",
         };
         write!(f, "{s}")
     }
 }
 
+impl FromStr for TransactionKind {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "incoming" => Ok(TransactionKind::Incoming),
+            "outgoing" => Ok(TransactionKind::Outgoing),
+            "external" => Ok(TransactionKind::External),
+            "batch" => Ok(TransactionKind::Batch),
+            "reorg" => Ok(TransactionKind::Reorg),
+            "stasis" => Ok(TransactionKind::Stasis),
+            "transfer-incoming" => Ok(TransactionKind::TransferIncoming),
+            "transfer-outgoing" => Ok(TransactionKind::TransferOutgoing),
+            "change" => Ok(TransactionKind::Change),
+            "consolidation" => Ok(TransactionKind::Consolidation),
+            "fee-bump" => Ok(TransactionKind::FeeBump),
+            _ => Err(Error::InvalidTransactionKind(s.to_string())),
+        }
+    }
+}
+
 impl TryFrom<JsValue> for TransactionKind {
     type Error = Error;
     fn try_from(js_value: JsValue) -> std::result::Result<Self, Self::Error> {
         if let Some(s) = js_value.as_string() {
-            match s.as_str() {
-                "incoming" => Ok(TransactionKind::Incoming),
-                "outgoing" => Ok(TransactionKind::Outgoing),
-                "external" => Ok(TransactionKind::External),
-                "batch" => Ok(TransactionKind::Batch),
-                "reorg" => Ok(TransactionKind::Reorg),
-                "stasis" => Ok(TransactionKind::Stasis),
-                "transfer-incoming" => Ok(TransactionKind::TransferIncoming),
-                "transfer-outgoing" => Ok(TransactionKind::TransferOutgoing),
-                "change" => Ok(TransactionKind::Change),
-                _ => Err(Error::InvalidTransactionKind(s)),
-            }
+            s.parse()
         } else {
             Err(Error::InvalidTransactionKind(format!("{:?}", js_value)))
         }
@@ -3,11 +3,13 @@
 //!
 
 pub mod data;
+pub mod export;
 pub mod kind;
 pub mod record;
 pub mod utxo;
 
 pub use data::*;
+pub use export::*;
 pub use kind::*;
 pub use record::*;
 pub use utxo::*;
@@ -84,6 +84,16 @@ export enum TransactionDataType {
      * @see {@link ITransactionDataChange}
      */
     Change = "change",
+    /**
+     * Transaction is a consolidation (sweep) transaction compounding account UTXOs to the account change address.
+     * @see {@link ITransactionDataConsolidation}
+     */
+    Consolidation = "consolidation",
+    /**
+     * Transaction is a child-pays-for-parent transaction spending outputs of an unconfirmed transaction.
+     * @see {@link ITransactionDataFeeBump}
+     */
+    FeeBump = "fee-bump",
 }
 
 /**
@@ -204,6 +214,38 @@ export interface ITransactionDataChange {
     utxoEntries: IUtxoRecord[];
 }
 
+/**
+ * Consolidation transaction data (final transaction of a manual
+ * or automatic UTXO consolidation).
+ * @category Wallet SDK
+ */
+export interface ITransactionDataConsolidation {
+    fees: bigint;
+    inputValue: bigint;
+    outputValue: bigint;
+    transaction: ITransaction;
+    paymentValue: bigint;
+    changeValue: bigint;
+    acceptedDaaScore?: bigint;
+    utxoEntries: IUtxoRecord[];
+}
+
+/**
+ * Child-pays-for-parent transaction data. The parent transaction
+ * is referenced by the outpoints of the transaction inputs.
+ * @category Wallet SDK
+ */
+export interface ITransactionDataFeeBump {
+    fees: bigint;
+    inputValue: bigint;
+    outputValue: bigint;
+    transaction: ITransaction;
+    paymentValue: bigint;
+    changeValue: bigint;
+    acceptedDaaScore?: bigint;
+    utxoEntries: IUtxoRecord[];
+}
+
 /**
  * Transaction record data variants.
  * @category Wallet SDK
@@ -217,7 +259,9 @@ export type ITransactionDataVariant =
     | ITransactionDataBatch
     | ITransactionDataTransferIncoming
     | ITransactionDataTransferOutgoing
-    | ITransactionDataChange;
+    | ITransactionDataChange
+    | ITransactionDataConsolidation
+    | ITransactionDataFeeBump;
 
 /**
  * Internal transaction data contained within the transaction record.
@@ -375,7 +419,8 @@ impl TransactionRecord {
     }
 
     pub fn maturity(&self, current_daa_score: u64) -> Maturity {
-        let params = NetworkParams::from(self.network_id);
+        let params =
+            <&NetworkParams>::try_from(self.network_id).expect("transaction records are only created on networks with known params");
 
         let maturity = if self.is_coinbase() {
             params.coinbase_transaction_maturity_period_daa
@@ -417,6 +462,14 @@ impl TransactionRecord {
         matches!(&self.transaction_data, TransactionData::Batch { .. })
     }
 
+    pub fn is_consolidation(&self) -> bool {
+        matches!(&self.transaction_data, TransactionData::Consolidation { .. })
+    }
+
+    pub fn is_fee_bump(&self) -> bool {
+        matches!(&self.transaction_data, TransactionData::FeeBump { .. })
+    }
+
     pub fn is_transfer(&self) -> bool {
         matches!(&self.transaction_data, TransactionData::TransferIncoming { .. } | TransactionData::TransferOutgoing { .. })
     }
@@ -429,7 +482,8 @@ impl TransactionRecord {
     // a progress value based on the pending period. It is assumed
     // that transactions in stasis are not visible to the user.
     pub fn maturity_progress(&self, current_daa_score: u64) -> Option<f64> {
-        let params = NetworkParams::from(self.network_id);
+        let params =
+            <&NetworkParams>::try_from(self.network_id).expect("transaction records are only created on networks with known params");
         let maturity = if self.is_coinbase() {
             params.coinbase_transaction_maturity_period_daa
         } else {
@@ -453,7 +507,9 @@ impl TransactionRecord {
             | TransactionData::Batch { aggregate_input_value, .. }
             | TransactionData::TransferIncoming { aggregate_input_value, .. }
             | TransactionData::TransferOutgoing { aggregate_input_value, .. }
-            | TransactionData::Change { aggregate_input_value, .. } => *aggregate_input_value,
+            | TransactionData::Change { aggregate_input_value, .. }
+            | TransactionData::Consolidation { aggregate_input_value, .. }
+            | TransactionData::FeeBump { aggregate_input_value, .. } => *aggregate_input_value,
         }
     }
 
@@ -629,6 +685,106 @@ impl TransactionRecord {
         })
     }
 
+    pub fn new_consolidation(
+        utxo_context: &UtxoContext,
+        outgoing_tx: &OutgoingTransaction,
+        accepted_daa_score: Option<u64>,
+    ) -> Result<Self> {
+        let binding = Binding::from(utxo_context.binding());
+        let block_daa_score =
+            utxo_context.processor().current_daa_score().ok_or(Error::MissingDaaScore("TransactionRecord::new_consolidation()"))?;
+
+        let utxo_entries = outgoing_tx.utxo_entries().values().map(UtxoRecord::from).collect::<Vec<_>>();
+
+        let unixtime = unixtime_as_millis_u64();
+
+        let PendingTransactionInner {
+            signable_tx,
+            fees,
+            aggregate_input_value,
+            aggregate_output_value,
+            payment_value,
+            change_output_value,
+            ..
+        } = &*outgoing_tx.pending_transaction().inner;
+
+        let transaction = signable_tx.lock().unwrap().tx.clone();
+        let id = transaction.id();
+
+        let transaction_data = TransactionData::Consolidation {
+            fees: *fees,
+            aggregate_input_value: *aggregate_input_value,
+            aggregate_output_value: *aggregate_output_value,
+            transaction,
+            payment_value: *payment_value,
+            change_value: *change_output_value,
+            accepted_daa_score,
+            utxo_entries,
+        };
+
+        Ok(TransactionRecord {
+            id,
+            unixtime_msec: Some(unixtime),
+            value: *aggregate_input_value,
+            binding,
+            transaction_data,
+            block_daa_score,
+            network_id: utxo_context.processor().network_id().expect("network expected for transaction record generation"),
+            metadata: None,
+            note: None,
+        })
+    }
+
+    pub fn new_fee_bump(
+        utxo_context: &UtxoContext,
+        outgoing_tx: &OutgoingTransaction,
+        accepted_daa_score: Option<u64>,
+    ) -> Result<Self> {
+        let binding = Binding::from(utxo_context.binding());
+        let block_daa_score =
+            utxo_context.processor().current_daa_score().ok_or(Error::MissingDaaScore("TransactionRecord::new_fee_bump()"))?;
+
+        let utxo_entries = outgoing_tx.utxo_entries().values().map(UtxoRecord::from).collect::<Vec<_>>();
+
+        let unixtime = unixtime_as_millis_u64();
+
+        let PendingTransactionInner {
+            signable_tx,
+            fees,
+            aggregate_input_value,
+            aggregate_output_value,
+            payment_value,
+            change_output_value,
+            ..
+        } = &*outgoing_tx.pending_transaction().inner;
+
+        let transaction = signable_tx.lock().unwrap().tx.clone();
+        let id = transaction.id();
+
+        let transaction_data = TransactionData::FeeBump {
+            fees: *fees,
+            aggregate_input_value: *aggregate_input_value,
+            aggregate_output_value: *aggregate_output_value,
+            transaction,
+            payment_value: *payment_value,
+            change_value: *change_output_value,
+            accepted_daa_score,
+            utxo_entries,
+        };
+
+        Ok(TransactionRecord {
+            id,
+            unixtime_msec: Some(unixtime),
+            value: *fees,
+            binding,
+            transaction_data,
+            block_daa_score,
+            network_id: utxo_context.processor().network_id().expect("network expected for transaction record generation"),
+            metadata: None,
+            note: None,
+        })
+    }
+
     pub fn new_transfer_incoming(
         utxo_context: &UtxoContext,
         outgoing_tx: &OutgoingTransaction,
@@ -0,0 +1,82 @@
+//!
+//! UTXO metadata used for manual coin control. A frozen UTXO
+//! is never spent by the transaction generator, a label is
+//! a user-supplied string describing the UTXO. This structure
+//! is not encrypted and is stored in plain text alongside
+//! the [`AccountMetadata`](super::AccountMetadata), allowing
+//! it to be updated without the wallet secret.
+//!
+
+use crate::imports::*;
+use waglayla_consensus_core::tx::TransactionOutpoint;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoMetadata {
+    pub outpoint: TransactionOutpoint,
+    pub frozen: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl UtxoMetadata {
+    const STORAGE_MAGIC: u32 = 0x4f585455;
+    const STORAGE_VERSION: u32 = 0;
+
+    pub fn new(outpoint: TransactionOutpoint) -> Self {
+        Self { outpoint, frozen: false, label: None }
+    }
+
+    /// Metadata that neither freezes nor labels the UTXO does not need to be stored.
+    pub fn is_empty(&self) -> bool {
+        !self.frozen && self.label.is_none()
+    }
+}
+
+impl BorshSerialize for UtxoMetadata {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        StorageHeader::new(Self::STORAGE_MAGIC, Self::STORAGE_VERSION).serialize(writer)?;
+        BorshSerialize::serialize(&self.outpoint, writer)?;
+        BorshSerialize::serialize(&self.frozen, writer)?;
+        BorshSerialize::serialize(&self.label, writer)?;
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for UtxoMetadata {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let StorageHeader { version: _, .. } =
+            StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
+
+        let outpoint = BorshDeserialize::deserialize(buf)?;
+        let frozen = BorshDeserialize::deserialize(buf)?;
+        let label = BorshDeserialize::deserialize(buf)?;
+
+        Ok(Self { outpoint, frozen, label })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use waglayla_consensus_core::tx::TransactionId;
+
+    #[test]
+    fn test_storage_utxo_metadata() -> Result<()> {
+        let mut storable_in = UtxoMetadata::new(TransactionOutpoint::new(TransactionId::from_bytes([0xa5; 32]), 1));
+        assert!(storable_in.is_empty());
+        storable_in.frozen = true;
+        storable_in.label = Some("cold storage".to_string());
+        assert!(!storable_in.is_empty());
+
+        let guard = StorageGuard::new(&storable_in);
+        let storable_out = guard.validate()?;
+        assert_eq!(storable_in.outpoint, storable_out.outpoint);
+        assert_eq!(storable_in.frozen, storable_out.frozen);
+        assert_eq!(storable_in.label, storable_out.label);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//!
+//! Child-pays-for-parent (CPFP) fee bumping.
+//!
+//! An unconfirmed transaction can not be replaced in the mempool, however its
+//! outputs belonging to the account can be spent by a child transaction paying
+//! higher fees. The child fees are chosen so that the fee rate of the
+//! parent and child package (the aggregate fees divided by the aggregate mass)
+//! reaches the requested target fee rate.
+//!
+//! Nodes prioritize the parent in block templates by the fee rate of the
+//! package made of the parent and its descendants in the mempool. Since the
+//! transactions of a block are validated independently of each other, the
+//! child itself is included in a later block, once the parent is accepted.
+//!
+
+use crate::imports::*;
+use crate::utils::sompi_to_waglayla_string;
+use waglayla_consensus_core::tx::TransactionId;
+
+/// Returns the fees the child transaction of the `child_mass` must pay for the
+/// parent and child package to reach the `fee_rate` (in SOMPI per gram). The
+/// result is never lower than the `minimum_fees` required to relay the child.
+pub fn calc_fee_bump_fees(parent_mass: u64, parent_fees: u64, child_mass: u64, minimum_fees: u64, fee_rate: f64) -> u64 {
+    let package_fees = ((parent_mass + child_mass) as f64 * fee_rate).ceil() as u64;
+    package_fees.saturating_sub(parent_fees).max(minimum_fees)
+}
+
+/// Result of a child-pays-for-parent fee bump.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBumpSummary {
+    pub parent_transaction_id: TransactionId,
+    pub parent_mass: u64,
+    pub parent_fees: u64,
+    pub transaction_id: TransactionId,
+    pub mass: u64,
+    pub fees: u64,
+    /// Number of parent outputs spent by the child transaction
+    pub utxo_count: usize,
+}
+
+fn fee_rate(fees: u64, mass: u64) -> Option<f64> {
+    (mass > 0).then(|| fees as f64 / mass as f64)
+}
+
+fn fee_rate_string(fee_rate: Option<f64>) -> String {
+    fee_rate.map(|fee_rate| format!("{fee_rate:.2}")).unwrap_or_else(|| "n/a".to_string())
+}
+
+impl FeeBumpSummary {
+    /// Fee rate (in SOMPI per gram) of the parent and child package,
+    /// `None` if the mass is not known.
+    pub fn package_fee_rate(&self) -> Option<f64> {
+        fee_rate(self.parent_fees + self.fees, self.parent_mass + self.mass)
+    }
+
+    /// Fee rate (in SOMPI per gram) of the parent transaction, `None` if
+    /// the parent mass is not known.
+    pub fn parent_fee_rate(&self) -> Option<f64> {
+        fee_rate(self.parent_fees, self.parent_mass)
+    }
+}
+
+impl std::fmt::Display for FeeBumpSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Child: {} Fees: {} UTXOs: {} Package fee rate: {} (parent: {})",
+            self.transaction_id,
+            sompi_to_waglayla_string(self.fees),
+            self.utxo_count,
+            fee_rate_string(self.package_fee_rate()),
+            fee_rate_string(self.parent_fee_rate())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calc_fee_bump_fees() {
+        // package of 3000 grams at 10 SOMPI/gram with parent paying 2000
+        assert_eq!(calc_fee_bump_fees(2000, 2000, 1000, 1000, 10.0), 28000);
+        // fractional rate is rounded up
+        assert_eq!(calc_fee_bump_fees(2000, 2000, 1001, 1001, 1.5), 2502);
+        // parent already pays above the target, child pays the minimum fees
+        assert_eq!(calc_fee_bump_fees(2000, 50000, 1000, 1000, 10.0), 1000);
+    }
+
+    #[test]
+    fn test_fee_bump_summary_unknown_mass() {
+        let summary = FeeBumpSummary {
+            parent_transaction_id: TransactionId::default(),
+            parent_mass: 0,
+            parent_fees: 0,
+            transaction_id: TransactionId::default(),
+            mass: 0,
+            fees: 0,
+            utxo_count: 1,
+        };
+        assert_eq!(summary.package_fee_rate(), None);
+        assert_eq!(summary.parent_fee_rate(), None);
+        assert!(summary.to_string().ends_with("Package fee rate: n/a (parent: n/a)"));
+
+        let summary = FeeBumpSummary { mass: 1000, fees: 20000, ..summary };
+        assert_eq!(summary.package_fee_rate(), Some(20.0));
+        assert_eq!(summary.parent_fee_rate(), None);
+    }
+}
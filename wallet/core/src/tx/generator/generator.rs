@@ -59,14 +59,15 @@
 use crate::imports::*;
 use crate::result::Result;
 use crate::tx::{
-    mass::*, Fees, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, PendingTransactionIterator,
-    PendingTransactionStream,
+    calc_fee_bump_fees, mass::*, Fees, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction,
+    PendingTransactionIterator, PendingTransactionStream,
 };
+use crate::utils::sompi_to_waglayla_string;
 use crate::utxo::{NetworkParams, UtxoContext, UtxoEntryReference};
 use waglayla_consensus_client::UtxoEntry;
 use waglayla_consensus_core::constants::UNACCEPTED_DAA_SCORE;
 use waglayla_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
-use waglayla_consensus_core::tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput};
+use waglayla_consensus_core::tx::{Transaction, TransactionIndexType, TransactionInput, TransactionOutpoint, TransactionOutput};
 use waglayla_txscript::pay_to_address_script;
 use std::collections::VecDeque;
 
@@ -170,11 +171,17 @@ pub enum DataKind {
     /// Final transaction combining the entire aggregated UTXO set
     /// into a single set of supplied outputs.
     Final,
+    /// Final child-pays-for-parent transaction spending outputs
+    /// of an unconfirmed transaction into the change address.
+    FeeBump,
 }
 
 impl DataKind {
     pub fn is_final(&self) -> bool {
-        matches!(self, DataKind::Final)
+        matches!(self, DataKind::Final | DataKind::FeeBump)
+    }
+    pub fn is_fee_bump(&self) -> bool {
+        matches!(self, DataKind::FeeBump)
     }
     pub fn is_stage_node(&self) -> bool {
         matches!(self, DataKind::Node)
@@ -266,6 +273,8 @@ struct Inner {
     source_utxo_context: Option<UtxoContext>,
     // Destination Utxo Context (Used only during transfer transactions)
     destination_utxo_context: Option<UtxoContext>,
+    // Final transaction is a consolidation (set by the consolidation policy)
+    consolidation: bool,
     // Event multiplexer
     multiplexer: Option<Multiplexer<Box<Events>>>,
     // typically a number of keys required to sign the transaction
@@ -346,10 +355,11 @@ impl Generator {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context,
+            consolidation,
         } = settings;
 
         let network_type = NetworkType::from(network_id);
-        let network_params = NetworkParams::from(network_id);
+        let network_params = NetworkParams::try_from(network_id)?;
         let mass_calculator = MassCalculator::new(&network_id.into(), &network_params);
 
         let (final_transaction_outputs, final_transaction_amount) = match final_transaction_destination {
@@ -447,6 +457,7 @@ impl Generator {
             final_transaction_payload,
             final_transaction_payload_mass,
             destination_utxo_context,
+            consolidation,
         };
 
         Ok(Self { inner: Arc::new(inner) })
@@ -467,6 +478,11 @@ impl Generator {
         &self.inner.network_params
     }
 
+    /// The address receiving the change of generated transactions.
+    pub fn change_address(&self) -> &Address {
+        &self.inner.change_address
+    }
+
     /// The underlying [`UtxoContext`] (if available).
     pub fn source_utxo_context(&self) -> &Option<UtxoContext> {
         &self.inner.source_utxo_context
@@ -477,6 +493,11 @@ impl Generator {
         &self.inner.destination_utxo_context
     }
 
+    /// Signifies that the final transaction is a consolidation
+    pub fn is_consolidation(&self) -> bool {
+        self.inner.consolidation
+    }
+
     /// Core [`Multiplexer<Events>`] (if available)
     pub fn multiplexer(&self) -> &Option<Multiplexer<Box<Events>>> {
         &self.inner.multiplexer
@@ -1025,7 +1046,7 @@ impl Generator {
                 context.number_of_transactions += 1;
 
                 let utxo_entry_reference =
-                    Self::create_batch_utxo_entry_reference(tx.id(), output_value, script_public_key, &self.inner.change_address);
+                    Self::create_utxo_entry_reference(tx.id(), 0, output_value, script_public_key, &self.inner.change_address);
 
                 match kind {
                     DataKind::Node => {
@@ -1061,13 +1082,103 @@ impl Generator {
         }
     }
 
-    fn create_batch_utxo_entry_reference(
+    /// Generates a child-pays-for-parent transaction spending `utxo_entries` (outputs
+    /// of an unconfirmed parent transaction) into the change address. The child fees
+    /// are chosen so that the package of the parent (having `parent_mass` and paying
+    /// `parent_fees`) and the child reaches the `fee_rate` (in SOMPI per gram).
+    /// UTXOs supplied by the generator settings are not used.
+    pub fn generate_fee_bump_transaction(
+        &self,
+        utxo_entries: Vec<UtxoEntryReference>,
+        parent_mass: u64,
+        parent_fees: u64,
+        fee_rate: f64,
+    ) -> Result<PendingTransaction> {
+        let calc = &self.inner.mass_calculator;
+        let mut context = self.context();
+
+        if context.is_done {
+            return Err(Error::GeneratorIsDone);
+        }
+
+        let inputs = utxo_entries
+            .iter()
+            .map(|entry| TransactionInput::new(entry.utxo.outpoint.clone().into(), vec![], 0, self.inner.sig_op_count))
+            .collect::<Vec<_>>();
+        let addresses = utxo_entries.iter().filter_map(|entry| entry.utxo.address.clone()).collect::<HashSet<_>>();
+        let aggregate_input_value = utxo_entries.iter().map(|entry| entry.amount()).sum::<u64>();
+        let script_public_key = pay_to_address_script(&self.inner.change_address);
+
+        let mut tx = Transaction::new_non_finalized(
+            0,
+            inputs,
+            vec![TransactionOutput::new(aggregate_input_value, script_public_key)],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let compute_mass = calc.calc_mass_for_signed_transaction(&tx, self.inner.minimum_signatures);
+
+        // storage mass depends on the output value, which in turn
+        // depends on the fees, iterate until the fees settle
+        let (mut mass, mut fees) = (compute_mass, 0);
+        for _ in 0..4 {
+            tx.outputs[0].value = aggregate_input_value.saturating_sub(fees);
+            let storage_mass = calc.calc_storage_mass_for_transaction(false, &utxo_entries, &tx.outputs).unwrap_or(u64::MAX);
+            mass = calc.combine_mass(compute_mass, storage_mass);
+            let minimum_fees = calc.calc_minimum_transaction_fee_from_mass(mass);
+            let child_fees = calc_fee_bump_fees(parent_mass, parent_fees, mass, minimum_fees, fee_rate);
+            if child_fees == fees {
+                break;
+            }
+            fees = child_fees;
+        }
+
+        if mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
+            return Err(Error::GeneratorTransactionIsTooHeavy);
+        }
+
+        let output_value = aggregate_input_value.saturating_sub(fees);
+        if fees >= aggregate_input_value || calc.is_dust(output_value) {
+            return Err(Error::FeeBumpInsufficientValue(
+                sompi_to_waglayla_string(aggregate_input_value),
+                sompi_to_waglayla_string(fees),
+            ));
+        }
+        tx.outputs[0].value = output_value;
+        tx.finalize();
+
+        context.is_done = true;
+        context.stage.take();
+        context.aggregated_utxos += utxo_entries.len();
+        context.aggregate_fees += fees;
+        context.number_of_transactions += 1;
+        context.final_transaction_id = Some(tx.id());
+
+        PendingTransaction::try_new(
+            self,
+            tx,
+            utxo_entries,
+            addresses.into_iter().collect(),
+            None,
+            output_value,
+            aggregate_input_value,
+            output_value,
+            mass,
+            fees,
+            DataKind::FeeBump,
+        )
+    }
+
+    pub(crate) fn create_utxo_entry_reference(
         txid: TransactionId,
+        index: TransactionIndexType,
         amount: u64,
         script_public_key: ScriptPublicKey,
         address: &Address,
     ) -> UtxoEntryReference {
-        let outpoint = TransactionOutpoint::new(txid, 0);
+        let outpoint = TransactionOutpoint::new(txid, index);
         let utxo = UtxoEntry {
             address: Some(address.clone()),
             outpoint: outpoint.into(),
@@ -9,7 +9,7 @@ use crate::rpc::DynRpcApi;
 use crate::tx::{DataKind, Generator};
 use crate::utxo::{UtxoContext, UtxoEntryId, UtxoEntryReference};
 use waglayla_consensus_core::sign::sign_with_multiple_v2;
-use waglayla_consensus_core::tx::{SignableTransaction, Transaction, TransactionId};
+use waglayla_consensus_core::tx::{SignableTransaction, Transaction, TransactionId, TransactionIndexType};
 use waglayla_rpc_core::{RpcTransaction, RpcTransactionId};
 
 pub(crate) struct PendingTransactionInner {
@@ -134,6 +134,10 @@ impl PendingTransaction {
         self.inner.fees
     }
 
+    pub fn mass(&self) -> u64 {
+        self.inner.mass
+    }
+
     pub fn aggregate_input_value(&self) -> u64 {
         self.inner.aggregate_input_value
     }
@@ -150,6 +154,28 @@ impl PendingTransaction {
         self.inner.change_output_value
     }
 
+    /// Change output of the final transaction as a [`UtxoEntryReference`], allowing
+    /// the change to be spent by subsequent transactions before it is accepted
+    /// by the network. Returns `None` for batch transactions or if the final
+    /// transaction has no change output.
+    pub fn change_utxo_entry_reference(&self) -> Option<UtxoEntryReference> {
+        if !self.is_final() || self.inner.change_output_value == 0 {
+            return None;
+        }
+
+        let tx = self.transaction();
+        // the change output is always the last output of the final transaction
+        let index = tx.outputs.len() - 1;
+        let output = &tx.outputs[index];
+        Some(Generator::create_utxo_entry_reference(
+            self.id(),
+            index as TransactionIndexType,
+            output.value,
+            output.script_public_key.clone(),
+            self.generator().change_address(),
+        ))
+    }
+
     pub fn is_final(&self) -> bool {
         self.inner.kind.is_final()
     }
@@ -158,6 +184,17 @@ impl PendingTransaction {
         !self.inner.kind.is_final()
     }
 
+    /// Final transaction compounding UTXOs selected by a consolidation
+    /// policy (see [`GeneratorSettings::with_consolidation`](crate::tx::GeneratorSettings::with_consolidation)).
+    pub fn is_consolidation(&self) -> bool {
+        self.is_final() && !self.is_fee_bump() && self.generator().is_consolidation()
+    }
+
+    /// Child-pays-for-parent transaction spending outputs of an unconfirmed transaction.
+    pub fn is_fee_bump(&self) -> bool {
+        self.inner.kind.is_fee_bump()
+    }
+
     pub fn network_type(&self) -> NetworkType {
         self.inner.generator.network_type()
     }
@@ -7,10 +7,67 @@ use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
 use crate::tx::{Fees, PaymentDestination};
-use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
+use crate::utxo::{UtxoContext, UtxoEntryId, UtxoEntryReference, UtxoIterator};
+use std::collections::HashSet;
 use waglayla_addresses::Address;
+use waglayla_consensus_core::tx::TransactionOutpoint;
 use workflow_core::channel::Multiplexer;
 
+/// Manual coin control applied to the UTXOs spent by the [`Generator`](crate::tx::Generator).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoSelection {
+    /// If not empty, only these UTXOs are spent
+    #[serde(default)]
+    pub include: Vec<TransactionOutpoint>,
+    /// UTXOs that must not be spent
+    #[serde(default)]
+    pub exclude: Vec<TransactionOutpoint>,
+}
+
+impl UtxoSelection {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Restricts the UTXOs yielded by `utxo_iterator`. UTXOs that are `frozen` or
+    /// excluded by the selection are skipped and, if the selection includes UTXOs,
+    /// only those are yielded. Included UTXOs must be available and not frozen.
+    pub fn apply(
+        &self,
+        utxo_iterator: Box<dyn Iterator<Item = UtxoEntryReference> + Send + Sync + 'static>,
+        frozen: HashSet<UtxoEntryId>,
+    ) -> Result<Box<dyn Iterator<Item = UtxoEntryReference> + Send + Sync + 'static>> {
+        if self.is_empty() && frozen.is_empty() {
+            return Ok(utxo_iterator);
+        }
+
+        let excluded = self.exclude.iter().copied().map(UtxoEntryId::from).collect::<HashSet<_>>();
+        if self.include.is_empty() {
+            Ok(Box::new(
+                utxo_iterator.filter(move |entry| !frozen.contains(entry.id_as_ref()) && !excluded.contains(entry.id_as_ref())),
+            ))
+        } else {
+            let mut available = utxo_iterator.map(|entry| (entry.id(), entry)).collect::<HashMap<_, _>>();
+            let included = self
+                .include
+                .iter()
+                .map(|outpoint| {
+                    let id = UtxoEntryId::from(*outpoint);
+                    if frozen.contains(&id) {
+                        Err(Error::UtxoFrozen(id))
+                    } else if excluded.contains(&id) {
+                        Err(Error::UtxoNotAvailable(id))
+                    } else {
+                        available.remove(&id).ok_or(Error::UtxoNotAvailable(id))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Box::new(included.into_iter()))
+        }
+    }
+}
+
 pub struct GeneratorSettings {
     // Network type
     pub network_id: NetworkId,
@@ -34,6 +91,8 @@ pub struct GeneratorSettings {
     pub final_transaction_payload: Option<Vec<u8>>,
     // transaction is a transfer between accounts
     pub destination_utxo_context: Option<UtxoContext>,
+    // transaction compounds UTXOs selected by a consolidation policy
+    pub consolidation: bool,
 }
 
 // impl std::fmt::Debug for GeneratorSettings {
@@ -82,6 +141,7 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            consolidation: false,
         };
 
         Ok(settings)
@@ -113,6 +173,7 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            consolidation: false,
         };
 
         Ok(settings)
@@ -142,13 +203,28 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            consolidation: false,
         };
 
         Ok(settings)
     }
 
+    /// Restricts the UTXOs spent by the generator (see [`UtxoSelection::apply`]).
+    pub fn with_utxo_selection(mut self, selection: &UtxoSelection, frozen: HashSet<UtxoEntryId>) -> Result<Self> {
+        let utxo_iterator = std::mem::replace(&mut self.utxo_iterator, Box::new(std::iter::empty()));
+        self.utxo_iterator = selection.apply(utxo_iterator, frozen)?;
+        Ok(self)
+    }
+
     pub fn utxo_context_transfer(mut self, destination_utxo_context: &UtxoContext) -> Self {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Marks the final transaction as a consolidation of the UTXOs selected
+    /// by a [`ConsolidationPolicy`](crate::utxo::ConsolidationPolicy).
+    pub fn with_consolidation(mut self) -> Self {
+        self.consolidation = true;
+        self
+    }
 }
@@ -5,11 +5,12 @@ use crate::result::Result;
 use crate::tx::{Fees, MassCalculator, PaymentDestination};
 use crate::utxo::UtxoEntryReference;
 use crate::{tx::PaymentOutputs, utils::waglayla_to_sompi};
-use waglayla_addresses::Address;
+use waglayla_addresses::{Address, Prefix, Version};
 use waglayla_consensus_core::network::{NetworkId, NetworkType};
-use waglayla_consensus_core::tx::Transaction;
+use waglayla_consensus_core::tx::{Transaction, TransactionOutpoint};
 use rand::prelude::*;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::rc::Rc;
 use workflow_log::style;
@@ -387,7 +388,25 @@ where
     let mut values = head.to_vec();
     values.extend(tail);
 
-    let utxo_entries: Vec<UtxoEntryReference> = values.into_iter().map(waglayla_to_sompi).map(UtxoEntryReference::simulated).collect();
+    let utxo_entries = simulated_utxo_entries(&values);
+    let settings = make_generator_settings(network_id, utxo_entries, fees, change_address, final_transaction_destination);
+    Generator::try_new(settings, None, None)
+}
+
+pub(crate) fn simulated_utxo_entries(values: &[f64]) -> Vec<UtxoEntryReference> {
+    values.iter().copied().map(waglayla_to_sompi).map(UtxoEntryReference::simulated).collect()
+}
+
+pub(crate) fn make_generator_settings<F>(
+    network_id: NetworkId,
+    utxo_entries: Vec<UtxoEntryReference>,
+    fees: Fees,
+    change_address: F,
+    final_transaction_destination: PaymentDestination,
+) -> GeneratorSettings
+where
+    F: FnOnce(NetworkType) -> Address,
+{
     let multiplexer = None;
     let sig_op_count = 1;
     let minimum_signatures = 1;
@@ -398,7 +417,7 @@ where
     let final_transaction_payload = None;
     let change_address = change_address(network_id.into());
 
-    let settings = GeneratorSettings {
+    GeneratorSettings {
         network_id,
         multiplexer,
         sig_op_count,
@@ -410,9 +429,8 @@ where
         final_transaction_priority_fee: final_priority_fee,
         final_transaction_destination,
         final_transaction_payload,
-    };
-
-    Generator::try_new(settings, None, None)
+        consolidation: false,
+    }
 }
 
 pub(crate) fn change_address(network_type: NetworkType) -> Address {
@@ -680,3 +698,113 @@ fn test_generator_inputs_903_outputs_2_fees_exclude() -> Result<()> {
 
     Ok(())
 }
+
+fn outpoint(entry: &UtxoEntryReference) -> TransactionOutpoint {
+    entry.utxo.outpoint.clone().into()
+}
+
+fn utxo_selection_generator(
+    utxo_entries: &[UtxoEntryReference],
+    selection: &UtxoSelection,
+    frozen: &[&UtxoEntryReference],
+) -> Result<Generator> {
+    let frozen = frozen.iter().map(|entry| entry.id()).collect::<HashSet<_>>();
+    let change_address = |_| Address::new(Prefix::Testnet, Version::PubKey, &[0x5a; 32]);
+    let settings =
+        make_generator_settings(test_network_id(), utxo_entries.to_vec(), Fees::None, change_address, PaymentDestination::Change)
+            .with_utxo_selection(selection, frozen)?;
+    Generator::try_new(settings, None, None)
+}
+
+#[test]
+fn test_generator_utxo_selection_include() -> Result<()> {
+    let utxo_entries = simulated_utxo_entries(&[10.0, 20.0, 30.0, 40.0]);
+    let selection = UtxoSelection { include: vec![outpoint(&utxo_entries[1]), outpoint(&utxo_entries[3])], exclude: vec![] };
+    utxo_selection_generator(&utxo_entries, &selection, &[])?
+        .harness()
+        .fetch(&Expected {
+            is_final: true,
+            input_count: 2,
+            aggregate_input_value: Waglayla(60.0),
+            output_count: 1,
+            priority_fees: FeesExpected::None,
+        })
+        .finalize();
+    Ok(())
+}
+
+#[test]
+fn test_generator_utxo_selection_exclude_frozen() -> Result<()> {
+    let utxo_entries = simulated_utxo_entries(&[10.0, 20.0, 30.0, 40.0]);
+    let selection = UtxoSelection { include: vec![], exclude: vec![outpoint(&utxo_entries[0])] };
+    utxo_selection_generator(&utxo_entries, &selection, &[&utxo_entries[3]])?
+        .harness()
+        .fetch(&Expected {
+            is_final: true,
+            input_count: 2,
+            aggregate_input_value: Waglayla(50.0),
+            output_count: 1,
+            priority_fees: FeesExpected::None,
+        })
+        .finalize();
+
+    // frozen UTXOs are skipped even if the selection is empty
+    utxo_selection_generator(&utxo_entries, &UtxoSelection::default(), &[&utxo_entries[0], &utxo_entries[1]])?
+        .harness()
+        .fetch(&Expected {
+            is_final: true,
+            input_count: 2,
+            aggregate_input_value: Waglayla(70.0),
+            output_count: 1,
+            priority_fees: FeesExpected::None,
+        })
+        .finalize();
+    Ok(())
+}
+
+#[test]
+fn test_generator_utxo_selection_errors() {
+    let utxo_entries = simulated_utxo_entries(&[10.0, 20.0]);
+    let unknown = simulated_utxo_entries(&[30.0]).remove(0);
+
+    let selection = UtxoSelection { include: vec![outpoint(&utxo_entries[0])], exclude: vec![] };
+    let err = utxo_selection_generator(&utxo_entries, &selection, &[&utxo_entries[0]]).err();
+    assert!(
+        matches!(&err, Some(Error::UtxoFrozen(id)) if *id == utxo_entries[0].id()),
+        "expecting frozen UTXO error, received: {err:?}"
+    );
+
+    let selection = UtxoSelection { include: vec![outpoint(&utxo_entries[1])], exclude: vec![outpoint(&utxo_entries[1])] };
+    let err = utxo_selection_generator(&utxo_entries, &selection, &[]).err();
+    assert!(
+        matches!(&err, Some(Error::UtxoNotAvailable(id)) if *id == utxo_entries[1].id()),
+        "expecting unavailable UTXO error, received: {err:?}"
+    );
+
+    let selection = UtxoSelection { include: vec![outpoint(&utxo_entries[0]), outpoint(&unknown)], exclude: vec![] };
+    let err = utxo_selection_generator(&utxo_entries, &selection, &[]).err();
+    assert!(
+        matches!(&err, Some(Error::UtxoNotAvailable(id)) if *id == unknown.id()),
+        "expecting unavailable UTXO error, received: {err:?}"
+    );
+}
+
+#[test]
+fn test_generator_sweep_and_consolidation_classification() -> Result<()> {
+    let utxo_entries = simulated_utxo_entries(&[10.0, 20.0, 30.0]);
+    let change_address = |_| Address::new(Prefix::Testnet, Version::PubKey, &[0x5a; 32]);
+
+    // identical transactions, only the consolidation flag set by the policy differs
+    let sweep = make_generator_settings(test_network_id(), utxo_entries.clone(), Fees::None, change_address, PaymentDestination::Change);
+    let sweep = Generator::try_new(sweep, None, None)?.generate_transaction()?.expect("sweep transaction");
+    assert!(sweep.is_final() && !sweep.is_consolidation(), "sweep must not be classified as consolidation");
+
+    let consolidation = make_generator_settings(test_network_id(), utxo_entries, Fees::None, change_address, PaymentDestination::Change)
+        .with_consolidation();
+    let consolidation = Generator::try_new(consolidation, None, None)?.generate_transaction()?.expect("consolidation transaction");
+    assert!(consolidation.is_final() && consolidation.is_consolidation(), "expecting consolidation");
+
+    assert_eq!(sweep.id(), consolidation.id());
+
+    Ok(())
+}
@@ -3,13 +3,19 @@
 //!
 
 pub mod consensus;
+pub mod fee_bump;
 pub mod fees;
 pub mod generator;
 pub mod mass;
 pub mod payment;
+pub mod payment_request;
+pub mod payout;
 
 pub use self::consensus::*;
+pub use self::fee_bump::*;
 pub use self::fees::*;
 pub use self::generator::*;
 pub use self::mass::*;
 pub use self::payment::*;
+pub use self::payment_request::*;
+pub use self::payout::*;
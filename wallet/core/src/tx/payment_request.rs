@@ -0,0 +1,337 @@
+//!
+//! Payment request URIs.
+//!
+//! A payment request URI carries a destination address along with optional
+//! payment details, for example:
+//!
+//! `waglaylatest:qr0...?amount=12.5&label=Shop&message=Order%20%2342&expires=1735689600&payload=0badf00d`
+//!
+//! The URI scheme is the address prefix, as such a bare address is a valid
+//! payment request. The `amount` is expressed in WALA, `expires` is the unix
+//! time in seconds after which the request should no longer be paid and
+//! `payload` is the hex-encoded transaction payload. As in BIP-21, unknown
+//! parameters are ignored unless they are prefixed with `req-`.
+//!
+
+use crate::imports::*;
+use crate::utils::try_waglayla_str_to_sompi;
+use waglayla_consensus_core::constants::SOMPI_PER_WAGLAYLA;
+use waglayla_consensus_core::network::NetworkIdT;
+use waglayla_wasm_core::types::HexString;
+use workflow_core::time::unixtime_as_millis_u64;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_PAYMENT_REQUEST: &'static str = r#"
+/**
+ *
+ * Payment request properties.
+ *
+ * @see {@link PaymentRequest}
+ * @category Wallet SDK
+ */
+export interface IPaymentRequest {
+    /**
+     * Destination address.
+     */
+    address: Address | string;
+    /**
+     * Requested amount in SOMPI.
+     */
+    amount?: bigint;
+    /**
+     * Label of the payment recipient.
+     */
+    label?: string;
+    /**
+     * Message describing the payment.
+     */
+    message?: string;
+    /**
+     * Expiration time (unix time in seconds).
+     */
+    expires?: number | bigint;
+    /**
+     * Transaction payload.
+     */
+    payload?: Uint8Array | HexString;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "IPaymentRequest | string")]
+    pub type IPaymentRequest;
+}
+
+/// Payment request that can be exchanged as a `waglayla:` URI
+/// (e.g. displayed as a QR code).
+/// @category Wallet SDK
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, CastFromJs)]
+#[serde(rename_all = "camelCase")]
+#[wasm_bindgen(inspectable)]
+pub struct PaymentRequest {
+    #[wasm_bindgen(getter_with_clone)]
+    pub address: Address,
+    /// Requested amount in SOMPI
+    pub amount: Option<u64>,
+    #[wasm_bindgen(getter_with_clone)]
+    pub label: Option<String>,
+    #[wasm_bindgen(getter_with_clone)]
+    pub message: Option<String>,
+    /// Expiration time (unix time in seconds)
+    pub expires: Option<u64>,
+    #[wasm_bindgen(skip)]
+    pub payload: Option<Vec<u8>>,
+}
+
+impl PaymentRequest {
+    pub fn new(address: Address, amount: Option<u64>) -> Self {
+        Self { address, amount, label: None, message: None, expires: None, payload: None }
+    }
+
+    /// Returns `true` if the request expiration time has passed.
+    pub fn is_expired(&self) -> bool {
+        self.expires.map(|expires| unixtime_as_millis_u64() / 1000 >= expires).unwrap_or(false)
+    }
+
+    /// Ensures the request can be paid on the network `network_id`: the address
+    /// must belong to the network and the request must not be expired.
+    pub fn validate(&self, network_id: &NetworkId) -> Result<()> {
+        if self.address.prefix != Prefix::from(*network_id) {
+            return Err(Error::PaymentRequestNetworkMismatch(self.address.to_string(), *network_id));
+        }
+        if self.amount == Some(0) {
+            return Err(Error::InvalidPaymentRequest("amount must be greater than zero".to_string()));
+        }
+        if self.is_expired() {
+            return Err(Error::PaymentRequestExpired);
+        }
+        Ok(())
+    }
+
+    /// Formats the request as a payment request URI.
+    pub fn to_uri(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut params = vec![];
+        if let Some(amount) = self.amount {
+            params.push(format!("amount={}", format_amount(amount)));
+        }
+        if let Some(label) = &self.label {
+            params.push(format!("label={}", percent_encode(label)));
+        }
+        if let Some(message) = &self.message {
+            params.push(format!("message={}", percent_encode(message)));
+        }
+        if let Some(expires) = self.expires {
+            params.push(format!("expires={expires}"));
+        }
+        if let Some(payload) = &self.payload {
+            params.push(format!("payload={}", payload.to_hex()));
+        }
+
+        if params.is_empty() {
+            write!(f, "{}", self.address)
+        } else {
+            write!(f, "{}?{}", self.address, params.join("&"))
+        }
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = Error;
+    fn from_str(uri: &str) -> Result<Self> {
+        let uri = uri.trim();
+        let (address, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let mut request = PaymentRequest::new(Address::try_from(address)?, None);
+
+        let mut keys = AHashSet::new();
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) =
+                param.split_once('=').ok_or_else(|| Error::InvalidPaymentRequest(format!("parameter '{param}' has no value")))?;
+            if !keys.insert(key) {
+                return Err(Error::InvalidPaymentRequest(format!("duplicate parameter '{key}'")));
+            }
+
+            let invalid = |err: &dyn std::fmt::Display| Error::InvalidPaymentRequest(format!("invalid {key} '{value}': {err}"));
+            match key {
+                "amount" => {
+                    let amount = try_waglayla_str_to_sompi(value).map_err(|err| invalid(&err))?;
+                    request.amount =
+                        Some(amount.filter(|amount| *amount > 0).ok_or_else(|| invalid(&"amount must be greater than zero"))?);
+                }
+                "label" => request.label = Some(percent_decode(value)?),
+                "message" => request.message = Some(percent_decode(value)?),
+                "expires" => request.expires = Some(value.parse().map_err(|err| invalid(&err))?),
+                "payload" => request.payload = Some(Vec::<u8>::from_hex(value).map_err(|err| invalid(&err))?),
+                _ if key.starts_with("req-") => {
+                    return Err(Error::InvalidPaymentRequest(format!("unsupported required parameter '{key}'")));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(request)
+    }
+}
+
+impl TryFrom<&str> for PaymentRequest {
+    type Error = Error;
+    fn try_from(uri: &str) -> Result<Self> {
+        uri.parse()
+    }
+}
+
+/// Formats SOMPI as a WALA amount without thousands separators or trailing zeros.
+fn format_amount(sompi: u64) -> String {
+    let integer = sompi / SOMPI_PER_WAGLAYLA;
+    let decimal = sompi % SOMPI_PER_WAGLAYLA;
+    if decimal == 0 {
+        integer.to_string()
+    } else {
+        format!("{integer}.{decimal:08}").trim_end_matches('0').to_string()
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Result<String> {
+    let invalid = || Error::InvalidPaymentRequest(format!("invalid percent-encoding '{value}'"));
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next().ok_or_else(invalid)?, iter.next().ok_or_else(invalid)?];
+            let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+impl TryCastFromJs for PaymentRequest {
+    type Error = Error;
+    fn try_cast_from(value: impl AsRef<JsValue>) -> Result<Cast<Self>, Self::Error> {
+        Self::resolve(&value, || {
+            if let Some(uri) = value.as_ref().as_string() {
+                uri.parse()
+            } else if let Some(object) = Object::try_from(value.as_ref()) {
+                let address = object.get_cast::<Address>("address")?.into_owned();
+                let amount = object.try_get_value("amount")?.map(|v| v.try_as_u64()).transpose()?;
+                let label = object.try_get_string("label")?;
+                let message = object.try_get_string("message")?;
+                let expires = object.try_get_value("expires")?.map(|v| v.try_as_u64()).transpose()?;
+                let payload = object.try_get_value("payload")?.map(|v| v.try_as_vec_u8()).transpose()?;
+                Ok(Self { address, amount, label, message, expires, payload })
+            } else {
+                Err(Error::InvalidPaymentRequest("expected a payment request URI or object".to_string()))
+            }
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl PaymentRequest {
+    #[wasm_bindgen(constructor)]
+    pub fn constructor(args: IPaymentRequest) -> Result<PaymentRequest> {
+        Self::try_owned_from(args)
+    }
+
+    /// Parses a payment request URI.
+    #[wasm_bindgen(js_name = "parse")]
+    pub fn js_parse(uri: &str) -> Result<PaymentRequest> {
+        uri.parse()
+    }
+
+    /// Ensures the request can be paid on the given network.
+    #[wasm_bindgen(js_name = "validate")]
+    pub fn js_validate(&self, network_id: &NetworkIdT) -> Result<()> {
+        self.validate(NetworkId::try_cast_from(network_id)?.as_ref())
+    }
+
+    #[wasm_bindgen(getter, js_name = "isExpired")]
+    pub fn js_is_expired(&self) -> bool {
+        self.is_expired()
+    }
+
+    #[wasm_bindgen(getter, js_name = "payload")]
+    pub fn js_payload(&self) -> Option<HexString> {
+        self.payload.as_ref().map(|payload| payload.to_hex().into())
+    }
+
+    /// Formats the request as a payment request URI (suitable for QR codes).
+    #[wasm_bindgen(js_name = "toURI")]
+    pub fn js_to_uri(&self) -> String {
+        self.to_uri()
+    }
+
+    #[wasm_bindgen(js_name = "toString")]
+    pub fn js_to_string(&self) -> String {
+        self.to_uri()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use waglayla_addresses::Version;
+
+    fn address() -> Address {
+        Address::new(Prefix::Testnet, Version::PubKey, &[0x2a; 32])
+    }
+
+    #[test]
+    fn test_payment_request_uri() -> Result<()> {
+        let address = address();
+        let mut request = PaymentRequest::new(address.clone(), Some(1_250_000_000));
+        request.label = Some("Shop".to_string());
+        request.message = Some("Order #42 & more".to_string());
+        request.expires = Some(1_735_689_600);
+        request.payload = Some(vec![0x0b, 0xad, 0xf0, 0x0d]);
+
+        let uri = request.to_uri();
+        assert_eq!(
+            uri,
+            format!("{address}?amount=12.5&label=Shop&message=Order%20%2342%20%26%20more&expires=1735689600&payload=0badf00d")
+        );
+        assert_eq!(uri.parse::<PaymentRequest>()?, request);
+
+        // bare address
+        let request = address.to_string().parse::<PaymentRequest>()?;
+        assert_eq!(request, PaymentRequest::new(address.clone(), None));
+        assert_eq!(request.to_uri(), address.to_string());
+
+        // unknown parameters are ignored unless required
+        assert!(format!("{address}?amount=1&foo=bar").parse::<PaymentRequest>().is_ok());
+        assert!(format!("{address}?amount=1&req-foo=bar").parse::<PaymentRequest>().is_err());
+        assert!(format!("{address}?amount=1&amount=2").parse::<PaymentRequest>().is_err());
+        assert!(format!("{address}?amount=0").parse::<PaymentRequest>().is_err());
+        assert!(format!("{address}?message=%zz").parse::<PaymentRequest>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_payment_request_validate() -> Result<()> {
+        let mut request = PaymentRequest::new(address(), Some(1));
+        request.validate(&NetworkId::from_str("testnet-10")?)?;
+        assert!(matches!(request.validate(&NetworkId::from_str("mainnet")?), Err(Error::PaymentRequestNetworkMismatch(..))));
+
+        request.expires = Some(1);
+        assert!(matches!(request.validate(&NetworkId::from_str("testnet-10")?), Err(Error::PaymentRequestExpired)));
+        Ok(())
+    }
+}
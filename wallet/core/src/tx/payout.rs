@@ -0,0 +1,356 @@
+//!
+//! Batch payouts to a large number of recipients.
+//!
+//! Payout recipients are packed into groups whose outputs fit within the
+//! standard transaction mass limit. Each group is paid by a single final
+//! transaction, spending the change of the previous group's transaction.
+//! Payout progress is reported per recipient via [`PayoutReceipt`], allowing
+//! a partially failed payout to be resumed by paying the remaining recipients.
+//!
+
+use crate::account::PayoutNotifier;
+use crate::imports::*;
+use crate::result::Result;
+use crate::tx::{
+    Generator, GeneratorSettings, MassCalculator, PaymentDestination, PaymentOutput, PaymentOutputs, PendingTransaction, SignerT,
+    MAXIMUM_STANDARD_TRANSACTION_MASS,
+};
+use crate::utils::sompi_to_waglayla_string;
+use crate::utxo::NetworkParams;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::ops::Range;
+use waglayla_consensus_core::tx::{TransactionId, TransactionOutput};
+use waglayla_hashes::Hash;
+use waglayla_txscript::pay_to_address_script;
+use workflow_core::abortable::Abortable;
+
+/// Mass available to the payment outputs of a single payout transaction.
+/// The remaining mass is reserved for the transaction inputs and change.
+const PAYOUT_OUTPUTS_MASS_BUDGET: u64 = MAXIMUM_STANDARD_TRANSACTION_MASS / 2;
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutRecipient {
+    pub address: Address,
+    pub amount: u64,
+    /// Optional note (not included in the transaction)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl PayoutRecipient {
+    pub fn new(address: Address, amount: u64, note: Option<String>) -> Self {
+        Self { address, amount, note }
+    }
+
+    /// Identifies the recipient across payout runs by hashing its address, amount and note,
+    /// so that receipts remain valid if the recipient list is reordered or edited.
+    pub fn id(&self) -> Hash {
+        let address = self.address.to_string();
+        let note = self.note.as_deref().unwrap_or_default();
+        let mut hasher = Sha256::new();
+        hasher.update((address.len() as u64).to_le_bytes());
+        hasher.update(address.as_bytes());
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update(note.as_bytes());
+        Hash::from_slice(hasher.finalize().as_slice())
+    }
+}
+
+impl From<&PayoutRecipient> for PaymentOutput {
+    fn from(recipient: &PayoutRecipient) -> Self {
+        PaymentOutput::new(recipient.address.clone(), recipient.amount)
+    }
+}
+
+/// Transaction paying the recipient at `index` of the payout recipient list.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutReceipt {
+    pub index: u32,
+    /// [`PayoutRecipient::id()`] of the paid recipient
+    pub recipient_id: Hash,
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutSummary {
+    /// Receipts of all paid recipients
+    pub receipts: Vec<PayoutReceipt>,
+    /// Ids of all submitted transactions, including those compounding
+    /// UTXOs ahead of the transactions paying the recipients
+    pub transaction_ids: Vec<TransactionId>,
+    pub aggregated_fees: u64,
+    pub number_of_generated_transactions: usize,
+    /// Error that interrupted the payout. Recipients without
+    /// a receipt have not been paid and can be resubmitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PayoutSummary {
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl std::fmt::Display for PayoutSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Paid recipients: {} Transactions: {} Fees: {}",
+            self.receipts.len(),
+            self.number_of_generated_transactions,
+            sompi_to_waglayla_string(self.aggregated_fees)
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, " Error: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Groups payout recipients into index ranges, each of which can be paid by a
+/// single transaction. Recipients are packed in order, starting a new group
+/// once the combined compute and storage mass of the group's outputs exceeds
+/// [`PAYOUT_OUTPUTS_MASS_BUDGET`].
+pub fn pack_payout_recipients(network_id: &NetworkId, recipients: &[PayoutRecipient]) -> Result<Vec<Range<usize>>> {
+    let network_params = NetworkParams::try_from(*network_id)?;
+    let calc = MassCalculator::new(&(*network_id).into(), &network_params);
+    let prefix = Prefix::from(*network_id);
+
+    let mut groups = vec![];
+    let mut start = 0;
+    let mut compute_mass = 0;
+    let mut storage_mass = 0u64;
+    for (index, recipient) in recipients.iter().enumerate() {
+        if recipient.address.prefix != prefix {
+            return Err(Error::GeneratorPaymentOutputNetworkTypeMismatch);
+        }
+        if recipient.amount == 0 {
+            return Err(Error::GeneratorPaymentOutputZeroAmount);
+        }
+
+        let output = TransactionOutput::new(recipient.amount, pay_to_address_script(&recipient.address));
+        let output_compute_mass = calc.calc_mass_for_output(&output);
+        let output_storage_mass = calc.calc_storage_mass_output_harmonic_single(recipient.amount);
+        let mass = calc.combine_mass(compute_mass + output_compute_mass, storage_mass.saturating_add(output_storage_mass));
+        if index > start && mass > PAYOUT_OUTPUTS_MASS_BUDGET {
+            groups.push(start..index);
+            start = index;
+            compute_mass = 0;
+            storage_mass = 0;
+        }
+
+        compute_mass += output_compute_mass;
+        storage_mass = storage_mass.saturating_add(output_storage_mass);
+    }
+
+    if start < recipients.len() {
+        groups.push(start..recipients.len());
+    }
+
+    Ok(groups)
+}
+
+/// Creates [`PaymentOutputs`] for a group of payout recipients.
+pub fn payout_outputs(recipients: &[PayoutRecipient]) -> PaymentOutputs {
+    PaymentOutputs { outputs: recipients.iter().map(PaymentOutput::from).collect() }
+}
+
+/// Pays the recipients grouped by [`pack_payout_recipients()`]. The transactions of each group
+/// are generated from the `settings` created for its outputs and are passed to `submit` for
+/// signing and submission. The change of each group's final transaction is spent first by the
+/// next group, which would otherwise have to wait for it to be reflected in the UTXO set.
+///
+/// The `notifier` is invoked as soon as each transaction is submitted. If the payout is
+/// interrupted after some of the recipients have been paid, the returned [`PayoutSummary`]
+/// contains the error and the receipts of the paid recipients.
+pub async fn pay_payout_recipients<S, SF, T, TF>(
+    network_id: &NetworkId,
+    recipients: &[PayoutRecipient],
+    settings: S,
+    submit: T,
+    signer: Option<Arc<dyn SignerT>>,
+    abortable: &Abortable,
+    notifier: Option<PayoutNotifier>,
+) -> Result<PayoutSummary>
+where
+    S: Fn(PaymentDestination) -> SF,
+    SF: Future<Output = Result<GeneratorSettings>>,
+    T: Fn(PendingTransaction) -> TF,
+    TF: Future<Output = Result<TransactionId>>,
+{
+    let groups = pack_payout_recipients(network_id, recipients)?;
+
+    let mut summary = PayoutSummary::default();
+    // change of the previous payout transaction, spent by the next one
+    let mut change = None;
+    for group in groups {
+        let result: Result<()> = async {
+            let mut settings = settings(payout_outputs(&recipients[group.clone()]).into()).await?;
+            if let Some(change) = change.take() {
+                settings.utxo_iterator = Box::new(std::iter::once(change).chain(settings.utxo_iterator));
+            }
+            let generator = Generator::try_new(settings, signer.clone(), Some(abortable))?;
+
+            let mut stream = generator.stream();
+            while let Some(transaction) = stream.try_next().await? {
+                let fees = transaction.fees();
+                let is_final = transaction.is_final();
+                let transaction_change = transaction.change_utxo_entry_reference();
+                let transaction_id = submit(transaction).await?;
+
+                let receipts = if is_final {
+                    group
+                        .clone()
+                        .map(|index| PayoutReceipt { index: index as u32, recipient_id: recipients[index].id(), transaction_id })
+                        .collect::<Vec<_>>()
+                } else {
+                    vec![]
+                };
+                if let Some(notifier) = notifier.as_ref() {
+                    notifier(transaction_id, &receipts);
+                }
+
+                summary.transaction_ids.push(transaction_id);
+                summary.number_of_generated_transactions += 1;
+                summary.aggregated_fees += fees;
+                summary.receipts.extend(receipts);
+                if is_final {
+                    change = transaction_change;
+                }
+                yield_executor().await;
+            }
+
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            if summary.receipts.is_empty() {
+                return Err(err);
+            }
+            summary.error = Some(err.to_string());
+            break;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tx::generator::test::make_generator_settings;
+    use crate::tx::Fees;
+    use crate::utxo::UtxoEntryReference;
+    use waglayla_addresses::Version;
+    use waglayla_consensus_core::constants::SOMPI_PER_WAGLAYLA;
+    use waglayla_consensus_core::tx::TransactionOutpoint;
+
+    fn recipients(amounts: &[u64]) -> Vec<PayoutRecipient> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(index, amount)| {
+                let address = Address::new(Prefix::Mainnet, Version::PubKey, &[index as u8; 32]);
+                PayoutRecipient::new(address, *amount, None)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pack_payout_recipients() -> Result<()> {
+        let network_id = NetworkId::new(NetworkType::Mainnet);
+
+        let groups = pack_payout_recipients(&network_id, &recipients(&[SOMPI_PER_WAGLAYLA * 1_000; 3]))?;
+        assert_eq!(groups, vec![0..3]);
+
+        let groups = pack_payout_recipients(&network_id, &recipients(&[SOMPI_PER_WAGLAYLA * 1_000; 250]))?;
+        assert!(groups.len() > 1);
+        assert_eq!(groups.first().unwrap().start, 0);
+        assert_eq!(groups.last().unwrap().end, 250);
+        assert!(groups.windows(2).all(|pair| pair[0].end == pair[1].start));
+
+        // a recipient too small to share a transaction is paid separately
+        let groups = pack_payout_recipients(&network_id, &recipients(&[SOMPI_PER_WAGLAYLA * 1_000, 100_000]))?;
+        assert_eq!(groups, vec![0..1, 1..2]);
+
+        assert!(pack_payout_recipients(&network_id, &recipients(&[0])).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pay_payout_recipients_change_chaining() -> Result<()> {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let recipients = (0..250)
+            .map(|index| {
+                let address = Address::new(Prefix::Testnet, Version::PubKey, &[index as u8; 32]);
+                PayoutRecipient::new(address, SOMPI_PER_WAGLAYLA * 1_000, Some(format!("recipient {index}")))
+            })
+            .collect::<Vec<_>>();
+        let groups = pack_payout_recipients(&network_id, &recipients)?;
+        assert!(groups.len() > 1);
+
+        // the only UTXO is spent by the first transaction, the following
+        // transactions can only be funded by the change of their predecessor
+        let utxo_entries = Mutex::new(vec![UtxoEntryReference::simulated(SOMPI_PER_WAGLAYLA * 300_000)]);
+        let submitted = Mutex::new(vec![]);
+        let notifications = Arc::new(Mutex::new(vec![]));
+        let notifications_ = notifications.clone();
+        let change_address = |_| Address::new(Prefix::Testnet, Version::PubKey, &[0xff; 32]);
+
+        let summary = pay_payout_recipients(
+            &network_id,
+            &recipients,
+            |destination| {
+                let utxo_entries = std::mem::take(&mut *utxo_entries.lock().unwrap());
+                async move { Ok(make_generator_settings(network_id, utxo_entries, Fees::SenderPays(0), change_address, destination)) }
+            },
+            |transaction| {
+                let transaction_id = transaction.id();
+                submitted.lock().unwrap().push(transaction.transaction());
+                async move { Ok(transaction_id) }
+            },
+            None,
+            &Abortable::default(),
+            Some(Arc::new(move |transaction_id, receipts: &[PayoutReceipt]| {
+                notifications_.lock().unwrap().push((transaction_id, receipts.len()));
+            })),
+        )
+        .await?;
+
+        assert!(summary.is_complete());
+        assert_eq!(summary.number_of_generated_transactions, groups.len());
+        assert_eq!(summary.receipts.len(), recipients.len());
+        assert!(summary.receipts.iter().all(|receipt| receipt.recipient_id == recipients[receipt.index as usize].id()));
+
+        let submitted = submitted.into_inner().unwrap();
+        assert_eq!(summary.transaction_ids, submitted.iter().map(|transaction| transaction.id()).collect::<Vec<_>>());
+        for pair in submitted.windows(2) {
+            let change = TransactionOutpoint::new(pair[0].id(), pair[0].outputs.len() as u32 - 1);
+            assert_eq!(pair[1].inputs.len(), 1);
+            assert_eq!(pair[1].inputs[0].previous_outpoint, change);
+        }
+
+        let notifications = notifications.lock().unwrap();
+        let expected = summary.transaction_ids.iter().copied().zip(groups.iter().map(|group| group.len())).collect::<Vec<_>>();
+        assert_eq!(*notifications, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_payout_recipient_id() {
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[0x5a; 32]);
+        let recipient = PayoutRecipient::new(address.clone(), 100, Some("note".to_string()));
+        assert_eq!(recipient.id(), PayoutRecipient::new(address.clone(), 100, Some("note".to_string())).id());
+        assert_ne!(recipient.id(), PayoutRecipient::new(address.clone(), 101, Some("note".to_string())).id());
+        assert_ne!(recipient.id(), PayoutRecipient::new(address.clone(), 100, None).id());
+        assert_ne!(recipient.id(), PayoutRecipient::new(Address::new(Prefix::Mainnet, Version::PubKey, &[0xa5; 32]), 100, None).id());
+    }
+}
@@ -0,0 +1,212 @@
+//!
+//! Automatic UTXO consolidation policy.
+//!
+//! Accounts receiving a large number of small payments (for example coinbase
+//! or mining pool payouts) accumulate UTXOs that eventually make outgoing
+//! transactions exceed the transaction mass limit. A [`ConsolidationPolicy`]
+//! registered with the [`UtxoProcessor`](crate::utxo::UtxoProcessor) allows
+//! such UTXOs to be periodically compounded into the account change address.
+//!
+
+use crate::imports::*;
+use crate::utils::sompi_to_waglayla_string;
+use crate::utxo::UtxoEntryReference;
+use waglayla_consensus_core::tx::TransactionId;
+
+const MSEC_PER_HOUR: u64 = 60 * 60 * 1000;
+
+/// Time window (in UTC hours) during which consolidation is allowed.
+/// A window where `start_hour` is greater than `end_hour` wraps around
+/// midnight (e.g. `22..4`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationWindow {
+    /// First hour of the window (inclusive, `0..24`)
+    pub start_hour: u8,
+    /// Last hour of the window (exclusive, `0..24`)
+    pub end_hour: u8,
+}
+
+impl ConsolidationWindow {
+    pub fn new(start_hour: u8, end_hour: u8) -> Self {
+        Self { start_hour, end_hour }
+    }
+
+    /// Returns `true` if the UTC `hour` falls within this window.
+    pub fn contains(&self, hour: u8) -> bool {
+        if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+impl std::fmt::Display for ConsolidationWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:00-{:02}:00 UTC", self.start_hour, self.end_hour)
+    }
+}
+
+impl FromStr for ConsolidationWindow {
+    type Err = Error;
+    /// Parses a window specified as `<start hour>-<end hour>` (e.g. `22-4`).
+    fn from_str(s: &str) -> Result<Self> {
+        let (start_hour, end_hour) =
+            s.split_once('-').ok_or_else(|| Error::custom(format!("invalid consolidation window '{s}', expected <start>-<end>")))?;
+        let parse_hour = |hour: &str| -> Result<u8> {
+            match hour.trim().parse::<u8>() {
+                Ok(hour) if hour < 24 => Ok(hour),
+                _ => Err(Error::custom(format!("invalid consolidation window hour '{hour}'"))),
+            }
+        };
+        Ok(Self::new(parse_hour(start_hour)?, parse_hour(end_hour)?))
+    }
+}
+
+/// Per-account automatic UTXO consolidation policy. The policy is stored in
+/// the account settings and evaluated periodically by the
+/// [`UtxoProcessor`](crate::utxo::UtxoProcessor) against the mature UTXOs
+/// of the account.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationPolicy {
+    /// Minimum number of eligible UTXOs triggering the consolidation
+    pub utxo_count_threshold: usize,
+    /// Only UTXOs with a value less than or equal to this amount (in SOMPI)
+    /// are consolidated. If not set, all mature UTXOs are eligible.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_utxo_value: Option<u64>,
+    /// Minimum aggregate value (in SOMPI) of the eligible UTXOs
+    pub min_value: u64,
+    /// Maximum fees relative to the consolidated value (e.g. `0.01` for 1%)
+    pub max_fee_rate: f64,
+    /// UTC time windows during which the consolidation can occur.
+    /// If empty, the consolidation can occur at any time.
+    #[serde(default)]
+    pub windows: Vec<ConsolidationWindow>,
+}
+
+impl Default for ConsolidationPolicy {
+    fn default() -> Self {
+        Self { utxo_count_threshold: 100, max_utxo_value: None, min_value: 0, max_fee_rate: 0.01, windows: vec![] }
+    }
+}
+
+impl ConsolidationPolicy {
+    /// Returns `true` if the consolidation is allowed at the given unix time (in milliseconds).
+    pub fn is_within_window(&self, unixtime_msec: u64) -> bool {
+        let hour = ((unixtime_msec / MSEC_PER_HOUR) % 24) as u8;
+        self.windows.is_empty() || self.windows.iter().any(|window| window.contains(hour))
+    }
+
+    /// Selects UTXOs eligible for consolidation from the supplied `entries`.
+    /// Returns `None` if the number or the aggregate value of the eligible
+    /// UTXOs does not reach the policy thresholds.
+    pub fn select<'a>(&self, entries: impl Iterator<Item = &'a UtxoEntryReference>) -> Option<Vec<UtxoEntryReference>> {
+        let selected = entries
+            .filter(|entry| self.max_utxo_value.map(|max_utxo_value| entry.amount() <= max_utxo_value).unwrap_or(true))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let value = selected.iter().map(|entry| entry.amount()).sum::<u64>();
+        (selected.len() >= self.utxo_count_threshold.max(2) && value >= self.min_value).then_some(selected)
+    }
+
+    /// Returns `true` if `fees` do not exceed the maximum fee rate for the consolidated `value`.
+    pub fn is_fee_acceptable(&self, fees: u64, value: u64) -> bool {
+        fees as f64 <= value as f64 * self.max_fee_rate
+    }
+}
+
+impl std::fmt::Display for ConsolidationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UTXO count threshold: {}", self.utxo_count_threshold)?;
+        if let Some(max_utxo_value) = self.max_utxo_value {
+            write!(f, " Max UTXO value: {}", sompi_to_waglayla_string(max_utxo_value))?;
+        }
+        write!(f, " Min value: {} Max fee rate: {}%", sompi_to_waglayla_string(self.min_value), self.max_fee_rate * 100.0)?;
+        if !self.windows.is_empty() {
+            let windows = self.windows.iter().map(|window| window.to_string()).collect::<Vec<_>>();
+            write!(f, " Windows: {}", windows.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of an automatic UTXO consolidation.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationSummary {
+    /// Number of consolidated UTXOs
+    pub utxo_count: usize,
+    /// Aggregate value of the consolidated UTXOs
+    pub aggregate_input_value: u64,
+    pub aggregated_fees: u64,
+    /// Ids of all submitted transactions (the last one being the final transaction)
+    pub transaction_ids: Vec<TransactionId>,
+}
+
+impl std::fmt::Display for ConsolidationSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Consolidated UTXOs: {} Value: {} Transactions: {} Fees: {}",
+            self.utxo_count,
+            sompi_to_waglayla_string(self.aggregate_input_value),
+            self.transaction_ids.len(),
+            sompi_to_waglayla_string(self.aggregated_fees)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consolidation_window() -> Result<()> {
+        let window = ConsolidationWindow::new(2, 6);
+        assert!(!window.contains(1));
+        assert!(window.contains(2));
+        assert!(window.contains(5));
+        assert!(!window.contains(6));
+
+        let window = ConsolidationWindow::from_str("22-4")?;
+        assert_eq!(window, ConsolidationWindow::new(22, 4));
+        assert!(window.contains(23));
+        assert!(window.contains(0));
+        assert!(!window.contains(4));
+        assert!(!window.contains(12));
+
+        assert!(ConsolidationWindow::from_str("22-24").is_err());
+        assert!(ConsolidationWindow::from_str("22").is_err());
+
+        let policy = ConsolidationPolicy { windows: vec![ConsolidationWindow::new(22, 4)], ..Default::default() };
+        assert!(policy.is_within_window(23 * MSEC_PER_HOUR));
+        assert!(policy.is_within_window(24 * MSEC_PER_HOUR + MSEC_PER_HOUR));
+        assert!(!policy.is_within_window(12 * MSEC_PER_HOUR));
+        assert!(ConsolidationPolicy::default().is_within_window(12 * MSEC_PER_HOUR));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_consolidation_policy_select() {
+        let entries = [1_000, 2_000, 3_000, 50_000].into_iter().map(UtxoEntryReference::simulated).collect::<Vec<_>>();
+
+        let policy = ConsolidationPolicy { utxo_count_threshold: 3, max_utxo_value: Some(3_000), ..Default::default() };
+        let selected = policy.select(entries.iter()).expect("consolidation selection");
+        assert_eq!(selected.iter().map(|entry| entry.amount()).sum::<u64>(), 6_000);
+
+        let policy = ConsolidationPolicy { utxo_count_threshold: 4, max_utxo_value: Some(3_000), ..Default::default() };
+        assert!(policy.select(entries.iter()).is_none());
+
+        let policy = ConsolidationPolicy { utxo_count_threshold: 3, min_value: 100_000, ..Default::default() };
+        assert!(policy.select(entries.iter()).is_none());
+
+        let policy = ConsolidationPolicy { max_fee_rate: 0.01, ..Default::default() };
+        assert!(policy.is_fee_acceptable(1_000, 100_000));
+        assert!(!policy.is_fee_acceptable(1_001, 100_000));
+    }
+}
@@ -266,6 +266,12 @@ impl UtxoContext {
         if pending_tx.is_batch() {
             let record = TransactionRecord::new_batch(self, &outgoing_tx, None)?;
             self.processor().notify(Events::Pending { record }).await?;
+        } else if pending_tx.is_fee_bump() {
+            let record = TransactionRecord::new_fee_bump(self, &outgoing_tx, None)?;
+            self.processor().notify(Events::Pending { record }).await?;
+        } else if pending_tx.is_consolidation() {
+            let record = TransactionRecord::new_consolidation(self, &outgoing_tx, None)?;
+            self.processor().notify(Events::Pending { record }).await?;
         } else {
             let record = TransactionRecord::new_outgoing(self, &outgoing_tx, None)?;
             self.processor().notify(Events::Pending { record }).await?;
@@ -298,7 +304,7 @@ impl UtxoContext {
             if force_maturity {
                 context.mature.sorted_insert_binary_asc_by_key(utxo_entry.clone(), |entry| entry.amount_as_ref());
             } else {
-                let params = NetworkParams::from(self.processor().network_id()?);
+                let params = NetworkParams::try_from(self.processor().network_id()?)?;
                 match utxo_entry.maturity(&params, current_daa_score) {
                     Maturity::Stasis => {
                         context.stasis.insert(utxo_entry.id().clone(), utxo_entry.clone());
@@ -423,7 +429,7 @@ impl UtxoContext {
             let mut pending = vec![];
             let mut mature = vec![];
 
-            let params = NetworkParams::from(self.processor().network_id()?);
+            let params = NetworkParams::try_from(self.processor().network_id()?)?;
 
             for utxo_entry in utxo_entries.into_iter() {
                 if let std::collections::hash_map::Entry::Vacant(e) = context.map.entry(utxo_entry.id()) {
@@ -514,7 +520,7 @@ impl UtxoContext {
     pub(crate) async fn handle_utxo_added(&self, utxos: Vec<UtxoEntryReference>, current_daa_score: u64) -> Result<()> {
         // add UTXOs to account set
 
-        let params = NetworkParams::from(self.processor().network_id()?);
+        let params = NetworkParams::try_from(self.processor().network_id()?)?;
 
         let mut accepted_outgoing_transactions = AHashSet::new();
 
@@ -542,6 +548,12 @@ impl UtxoContext {
                 if outgoing_transaction.is_batch() {
                     let record = TransactionRecord::new_batch(self, &outgoing_transaction, Some(current_daa_score))?;
                     self.processor().notify(Events::Maturity { record }).await?;
+                } else if outgoing_transaction.is_fee_bump() {
+                    let record = TransactionRecord::new_fee_bump(self, &outgoing_transaction, Some(current_daa_score))?;
+                    self.processor().notify(Events::Maturity { record }).await?;
+                } else if outgoing_transaction.is_consolidation() {
+                    let record = TransactionRecord::new_consolidation(self, &outgoing_transaction, Some(current_daa_score))?;
+                    self.processor().notify(Events::Maturity { record }).await?;
                 } else if outgoing_transaction.originating_context() == self {
                     let record = TransactionRecord::new_change(self, &outgoing_transaction, Some(current_daa_score), &utxos)?;
                     self.processor().notify(Events::Maturity { record }).await?;
@@ -569,11 +581,13 @@ impl UtxoContext {
 
         let outgoing_transactions = self.processor().outgoing();
         let mut accepted_outgoing_transactions = HashSet::<OutgoingTransaction>::new();
+        let mut spent = vec![];
 
         utxos.retain(|utxo| {
             for outgoing_transaction in outgoing_transactions.iter() {
                 if outgoing_transaction.utxo_entries().contains_key(&utxo.id()) {
                     accepted_outgoing_transactions.insert((*outgoing_transaction).clone());
+                    spent.push(utxo.clone());
                     return false;
                 }
             }
@@ -584,6 +598,12 @@ impl UtxoContext {
             if accepted_outgoing_transaction.is_batch() {
                 let record = TransactionRecord::new_batch(self, &accepted_outgoing_transaction, Some(current_daa_score))?;
                 self.processor().notify(Events::Maturity { record }).await?;
+            } else if accepted_outgoing_transaction.is_fee_bump() {
+                let record = TransactionRecord::new_fee_bump(self, &accepted_outgoing_transaction, Some(current_daa_score))?;
+                self.processor().notify(Events::Maturity { record }).await?;
+            } else if accepted_outgoing_transaction.is_consolidation() {
+                let record = TransactionRecord::new_consolidation(self, &accepted_outgoing_transaction, Some(current_daa_score))?;
+                self.processor().notify(Events::Maturity { record }).await?;
             } else if accepted_outgoing_transaction.destination_context().is_some() {
                 let record =
                     TransactionRecord::new_transfer_outgoing(self, &accepted_outgoing_transaction, Some(current_daa_score), &utxos)?;
@@ -595,7 +615,7 @@ impl UtxoContext {
         }
 
         if utxos.is_empty() {
-            return Ok(());
+            return self.processor().handle_utxos_spent(&spent).await;
         }
 
         let removed = self.remove(utxos).await?;
@@ -616,6 +636,10 @@ impl UtxoContext {
             }
         });
 
+        // pending and stasis UTXOs are removed by reorgs and may reappear
+        spent.extend(mature.iter().cloned());
+        self.processor().handle_utxos_spent(&spent).await?;
+
         let mature = HashMap::group_from(mature.into_iter().map(|utxo| (utxo.transaction_id(), utxo)));
         let pending = HashMap::group_from(pending.into_iter().map(|utxo| (utxo.transaction_id(), utxo)));
         let stasis = HashMap::group_from(stasis.into_iter().map(|utxo| (utxo.transaction_id(), utxo)));
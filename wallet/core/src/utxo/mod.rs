@@ -4,6 +4,7 @@
 
 pub mod balance;
 pub mod binding;
+pub mod consolidation;
 pub mod context;
 pub mod iterator;
 pub mod outgoing;
@@ -17,6 +18,7 @@ pub mod sync;
 
 pub use balance::Balance;
 pub use binding::UtxoContextBinding;
+pub use consolidation::{ConsolidationPolicy, ConsolidationSummary, ConsolidationWindow};
 pub use context::{UtxoContext, UtxoContextId};
 pub use iterator::UtxoIterator;
 pub use waglayla_consensus_client::UtxoEntryId;
@@ -81,6 +81,14 @@ impl OutgoingTransaction {
         self.inner.pending_transaction.is_batch()
     }
 
+    pub fn is_consolidation(&self) -> bool {
+        self.inner.pending_transaction.is_consolidation()
+    }
+
+    pub fn is_fee_bump(&self) -> bool {
+        self.inner.pending_transaction.is_fee_bump()
+    }
+
     pub fn utxo_entries(&self) -> &AHashMap<UtxoEntryId, UtxoEntryReference> {
         self.inner.pending_transaction.utxo_entries()
     }
@@ -22,11 +22,14 @@ use waglayla_rpc_core::{
 use waglayla_wrpc_client::WaglaylaRpcClient;
 use workflow_core::channel::{Channel, DuplexChannel};
 use workflow_core::task::spawn;
+use workflow_core::time::unixtime_as_millis_u64;
 
 use crate::events::Events;
 use crate::result::Result;
+use crate::tx::UtxoSelection;
 use crate::utxo::{
-    Maturity, OutgoingTransaction, PendingUtxoEntryReference, SyncMonitor, UtxoContext, UtxoEntryId, UtxoEntryReference,
+    ConsolidationPolicy, Maturity, OutgoingTransaction, PendingUtxoEntryReference, SyncMonitor, UtxoContext, UtxoContextId,
+    UtxoEntryId, UtxoEntryReference, UtxoIterator,
 };
 use crate::wallet::WalletBusMessage;
 use waglayla_rpc_core::{
@@ -36,6 +39,9 @@ use waglayla_rpc_core::{
 // use workflow_core::task;
 // use waglayla_metrics_core::{Metrics,Metric};
 
+/// Interval between evaluations of the registered [`ConsolidationPolicy`] instances.
+const CONSOLIDATION_CHECK_INTERVAL_MSEC: u64 = 10 * 60 * 1000;
+
 pub struct Inner {
     /// Coinbase UTXOs in stasis
     stasis: DashMap<UtxoEntryId, PendingUtxoEntryReference>,
@@ -46,6 +52,11 @@ pub struct Inner {
     /// Address to UtxoContext map (maps all addresses used by
     /// all UtxoContexts to their respective UtxoContexts)
     address_to_utxo_context_map: DashMap<Arc<Address>, UtxoContext>,
+    /// UTXO consolidation policies of the registered UtxoContexts
+    consolidation_policies: DashMap<UtxoContextId, (UtxoContext, ConsolidationPolicy)>,
+    last_consolidation_check: AtomicU64,
+    /// UTXOs frozen in the wallet, excluded from the consolidation
+    frozen_utxos: Mutex<HashSet<UtxoEntryId>>,
     // ---
     current_daa_score: Arc<AtomicU64>,
     network_id: Arc<Mutex<Option<NetworkId>>>,
@@ -76,6 +87,9 @@ impl Inner {
             pending: DashMap::new(),
             outgoing: DashMap::new(),
             address_to_utxo_context_map: DashMap::new(),
+            consolidation_policies: DashMap::new(),
+            last_consolidation_check: AtomicU64::new(0),
+            frozen_utxos: Mutex::new(HashSet::new()),
             current_daa_score: Arc::new(AtomicU64::new(0)),
             network_id: Arc::new(Mutex::new(network_id)),
             rpc: Mutex::new(rpc.clone()),
@@ -181,7 +195,7 @@ impl UtxoProcessor {
 
     pub fn network_params(&self) -> Result<&'static NetworkParams> {
         let network_id = (*self.inner.network_id.lock().unwrap()).ok_or(Error::MissingNetworkId)?;
-        Ok(network_id.into())
+        network_id.try_into()
     }
 
     pub fn pending(&self) -> &DashMap<UtxoEntryId, PendingUtxoEntryReference> {
@@ -242,6 +256,27 @@ impl UtxoProcessor {
         Ok(())
     }
 
+    /// Register a [`ConsolidationPolicy`] for the supplied [`UtxoContext`], replacing
+    /// any previously registered policy. The policy is evaluated periodically and,
+    /// if met, the consolidation is requested from the wallet via the wallet bus.
+    pub fn register_consolidation_policy(&self, utxo_context: &UtxoContext, policy: ConsolidationPolicy) {
+        self.inner.consolidation_policies.insert(utxo_context.id(), (utxo_context.clone(), policy));
+    }
+
+    pub fn unregister_consolidation_policy(&self, id: &UtxoContextId) {
+        self.inner.consolidation_policies.remove(id);
+    }
+
+    pub fn consolidation_policy(&self, id: &UtxoContextId) -> Option<ConsolidationPolicy> {
+        self.inner.consolidation_policies.get(id).map(|entry| entry.value().1.clone())
+    }
+
+    /// Update the set of UTXOs frozen in the wallet, which are not
+    /// considered when evaluating the consolidation policies.
+    pub fn set_frozen_utxos(&self, frozen: HashSet<UtxoEntryId>) {
+        *self.inner.frozen_utxos.lock().unwrap() = frozen;
+    }
+
     pub async fn notify(&self, event: Events) -> Result<()> {
         self.multiplexer()
             .try_broadcast(Box::new(event))
@@ -261,6 +296,7 @@ impl UtxoProcessor {
         self.notify(Events::DaaScoreChange { current_daa_score }).await?;
         self.handle_pending(current_daa_score).await?;
         self.handle_outgoing(current_daa_score).await?;
+        self.handle_consolidation().await?;
         Ok(())
     }
 
@@ -343,6 +379,49 @@ impl UtxoProcessor {
         Ok(())
     }
 
+    async fn handle_consolidation(&self) -> Result<()> {
+        // consolidation transactions can only be signed by the wallet
+        let Some(wallet_bus) = self.wallet_bus() else {
+            return Ok(());
+        };
+
+        if self.inner.consolidation_policies.is_empty() || !self.is_synced() {
+            return Ok(());
+        }
+
+        let now = unixtime_as_millis_u64();
+        if now < self.inner.last_consolidation_check.load(Ordering::SeqCst) + CONSOLIDATION_CHECK_INTERVAL_MSEC {
+            return Ok(());
+        }
+        self.inner.last_consolidation_check.store(now, Ordering::SeqCst);
+
+        let frozen = self.inner.frozen_utxos.lock().unwrap().clone();
+        let ids = self
+            .inner
+            .consolidation_policies
+            .iter()
+            .filter_map(|entry| {
+                let (utxo_context, policy) = entry.value();
+                if !policy.is_within_window(now) {
+                    return None;
+                }
+                // do not consolidate while outgoing transactions are in flight
+                if !utxo_context.context().outgoing.is_empty() {
+                    return None;
+                }
+                // frozen UTXOs are skipped the same way the transaction generator skips them
+                let available = UtxoSelection::default().apply(Box::new(UtxoIterator::new(utxo_context)), frozen.clone()).ok()?;
+                policy.select(available.collect::<Vec<_>>().iter()).is_some().then_some(*entry.key())
+            })
+            .collect::<Vec<_>>();
+
+        for id in ids {
+            wallet_bus.sender.send(WalletBusMessage::Consolidation { id }).await?;
+        }
+
+        Ok(())
+    }
+
     pub fn register_outgoing_transaction(&self, outgoing_transaction: OutgoingTransaction) {
         self.inner.outgoing.insert(outgoing_transaction.id(), outgoing_transaction);
     }
@@ -351,6 +430,18 @@ impl UtxoProcessor {
         self.inner.outgoing.remove(&transaction_id);
     }
 
+    /// Cascades the spent UTXOs to the wallet, if any, so that it can prune their metadata.
+    pub(crate) async fn handle_utxos_spent(&self, utxos: &[UtxoEntryReference]) -> Result<()> {
+        if let Some(wallet_bus) = self.wallet_bus() {
+            if !utxos.is_empty() {
+                let outpoints = utxos.iter().map(|utxo| utxo.utxo.outpoint.clone().into()).collect();
+                wallet_bus.sender.send(WalletBusMessage::UtxosSpent { outpoints }).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn handle_discovery(&self, record: TransactionRecord) -> Result<()> {
         if let Some(wallet_bus) = self.wallet_bus() {
             // if UtxoProcessor has an associated wallet_bus installed
@@ -455,6 +546,9 @@ impl UtxoProcessor {
             return Err(Error::InvalidNetworkType(network_id.to_string(), server_network_id.to_string()));
         }
 
+        // refuse networks without known maturity and mass params (custom node networks)
+        self.network_params()?;
+
         if rpc_api_version[0] > RPC_API_VERSION[0] || rpc_api_version[1] > RPC_API_VERSION[1] {
             let current = RPC_API_VERSION.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(".");
             let connected = rpc_api_version.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(".");
@@ -525,6 +619,8 @@ impl UtxoProcessor {
         self.inner.stasis.clear();
         self.inner.outgoing.clear();
         self.inner.address_to_utxo_context_map.clear();
+        self.inner.consolidation_policies.clear();
+        self.inner.frozen_utxos.lock().unwrap().clear();
         Ok(())
     }
 
@@ -5,7 +5,7 @@
 
 use crate::imports::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NetworkParams {
     pub coinbase_transaction_maturity_period_daa: u64,
     pub coinbase_transaction_stasis_period_daa: u64,
@@ -54,34 +54,41 @@ pub const SIMNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
     additional_compound_transaction_mass: 0,
 };
 
-impl From<NetworkId> for &'static NetworkParams {
-    fn from(value: NetworkId) -> Self {
+// Custom networks defined by a node params file have no built-in wallet params
+// and are rejected instead of being mapped onto the params of another network.
+impl TryFrom<NetworkId> for &'static NetworkParams {
+    type Error = Error;
+    fn try_from(value: NetworkId) -> Result<Self> {
         match value.network_type {
-            NetworkType::Mainnet => &MAINNET_NETWORK_PARAMS,
+            NetworkType::Mainnet => Ok(&MAINNET_NETWORK_PARAMS),
             NetworkType::Testnet => match value.suffix {
-                Some(10) => &TESTNET10_NETWORK_PARAMS,
-                Some(11) => &TESTNET11_NETWORK_PARAMS,
-                Some(x) => panic!("Testnet suffix {} is not supported", x),
-                None => panic!("Testnet suffix not provided"),
+                Some(10) => Ok(&TESTNET10_NETWORK_PARAMS),
+                Some(11) => Ok(&TESTNET11_NETWORK_PARAMS),
+                Some(_) => Err(Error::InvalidNetworkId(value.to_string())),
+                None => Err(Error::MissingNetworkSuffix(value.to_string())),
             },
-            NetworkType::Devnet => &DEVNET_NETWORK_PARAMS,
-            NetworkType::Simnet => &SIMNET_NETWORK_PARAMS,
+            NetworkType::Devnet => Ok(&DEVNET_NETWORK_PARAMS),
+            NetworkType::Simnet => Ok(&SIMNET_NETWORK_PARAMS),
         }
     }
 }
 
-impl From<NetworkId> for NetworkParams {
-    fn from(value: NetworkId) -> Self {
-        match value.network_type {
-            NetworkType::Mainnet => MAINNET_NETWORK_PARAMS,
-            NetworkType::Testnet => match value.suffix {
-                Some(10) => TESTNET10_NETWORK_PARAMS,
-                Some(11) => TESTNET11_NETWORK_PARAMS,
-                Some(x) => panic!("Testnet suffix {} is not supported", x),
-                None => panic!("Testnet suffix not provided"),
-            },
-            NetworkType::Devnet => DEVNET_NETWORK_PARAMS,
-            NetworkType::Simnet => SIMNET_NETWORK_PARAMS,
-        }
+impl TryFrom<NetworkId> for NetworkParams {
+    type Error = Error;
+    fn try_from(value: NetworkId) -> Result<Self> {
+        <&'static NetworkParams>::try_from(value).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_params_custom_network() {
+        let params = NetworkParams::try_from(NetworkId::with_suffix(NetworkType::Testnet, 11)).unwrap();
+        assert_eq!(params.coinbase_transaction_maturity_period_daa, TESTNET11_NETWORK_PARAMS.coinbase_transaction_maturity_period_daa);
+        let custom = NetworkId::with_suffix(NetworkType::Testnet, 42);
+        assert!(matches!(NetworkParams::try_from(custom), Err(Error::InvalidNetworkId(_))));
     }
 }
@@ -6,17 +6,19 @@ use crate::api::{message::*, traits::WalletApi};
 use crate::imports::*;
 use crate::result::Result;
 use crate::storage::interface::TransactionRangeResult;
-use crate::storage::Binding;
-use crate::tx::Fees;
-use workflow_core::channel::Receiver;
+use crate::storage::{Binding, UtxoMetadata};
+use waglayla_consensus_core::tx::TransactionOutpoint;
+use crate::tx::{Fees, PaymentRequest};
+use waglayla_bip32::{Language, Mnemonic};
+use workflow_core::channel::Sender;
 
 #[async_trait]
 impl WalletApi for super::Wallet {
-    async fn register_notifications(self: Arc<Self>, _channel: Receiver<WalletNotification>) -> Result<u64> {
-        todo!()
+    async fn register_notifications(self: Arc<Self>, channel: Sender<WalletNotification>) -> Result<u64> {
+        Ok(self.inner.notification_channels.register(channel))
     }
-    async fn unregister_notifications(self: Arc<Self>, _channel_id: u64) -> Result<()> {
-        todo!()
+    async fn unregister_notifications(self: Arc<Self>, channel_id: u64) -> Result<()> {
+        self.inner.notification_channels.unregister(channel_id)
     }
 
     async fn get_status_call(self: Arc<Self>, request: GetStatusRequest) -> Result<GetStatusResponse> {
@@ -172,6 +174,8 @@ impl WalletApi for super::Wallet {
     async fn wallet_change_secret_call(self: Arc<Self>, request: WalletChangeSecretRequest) -> Result<WalletChangeSecretResponse> {
         let WalletChangeSecretRequest { old_wallet_secret, new_wallet_secret } = request;
         self.store().change_secret(&old_wallet_secret, &new_wallet_secret).await?;
+        // retained consolidation secrets are no longer valid
+        self.disarm_all_consolidations();
         Ok(WalletChangeSecretResponse {})
     }
 
@@ -202,8 +206,25 @@ impl WalletApi for super::Wallet {
 
     async fn prv_key_data_create_call(self: Arc<Self>, request: PrvKeyDataCreateRequest) -> Result<PrvKeyDataCreateResponse> {
         let PrvKeyDataCreateRequest { wallet_secret, prv_key_data_args } = request;
+        // split before storing so that invalid share arguments do not leave a key behind
+        let mnemonic_shares = if let Some(shares) = prv_key_data_args.shares.as_ref() {
+            let mnemonic = Mnemonic::new(prv_key_data_args.mnemonic.as_str()?, Language::default())?;
+            let shares = split_mnemonic(&mnemonic, shares)?;
+            Some(shares.iter().map(|share| share.to_phrase().to_string()).collect())
+        } else {
+            None
+        };
         let prv_key_data_id = self.create_prv_key_data(&wallet_secret, prv_key_data_args).await?;
-        Ok(PrvKeyDataCreateResponse { prv_key_data_id })
+        Ok(PrvKeyDataCreateResponse { prv_key_data_id, mnemonic_shares })
+    }
+
+    async fn prv_key_data_verify_shares_call(
+        self: Arc<Self>,
+        request: PrvKeyDataVerifySharesRequest,
+    ) -> Result<PrvKeyDataVerifySharesResponse> {
+        let shares = request.shares.iter().map(|share| MnemonicShare::try_from_phrase(share)).collect::<Result<Vec<_>>>()?;
+        let share_set = verify_mnemonic_shares(&shares)?;
+        Ok(PrvKeyDataVerifySharesResponse { share_set })
     }
 
     async fn prv_key_data_remove_call(self: Arc<Self>, _request: PrvKeyDataRemoveRequest) -> Result<PrvKeyDataRemoveResponse> {
@@ -343,17 +364,69 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_send_call(self: Arc<Self>, request: AccountsSendRequest) -> Result<AccountsSendResponse> {
-        let AccountsSendRequest { account_id, wallet_secret, payment_secret, destination, priority_fee_sompi, payload } = request;
+        let AccountsSendRequest {
+            account_id,
+            wallet_secret,
+            payment_secret,
+            destination,
+            priority_fee_sompi,
+            payload,
+            utxo_selection,
+        } = request;
 
         let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
 
         let abortable = Abortable::new();
-        let (generator_summary, transaction_ids) =
-            account.send(destination, priority_fee_sompi, payload, wallet_secret, payment_secret, &abortable, None).await?;
+        let (generator_summary, transaction_ids) = account
+            .send(destination, priority_fee_sompi, payload, utxo_selection, wallet_secret, payment_secret, &abortable, None)
+            .await?;
 
         Ok(AccountsSendResponse { generator_summary, transaction_ids })
     }
 
+    async fn accounts_payout_call(self: Arc<Self>, request: AccountsPayoutRequest) -> Result<AccountsPayoutResponse> {
+        let AccountsPayoutRequest { account_id, wallet_secret, payment_secret, recipients, priority_fee_sompi } = request;
+
+        let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let abortable = Abortable::new();
+        let payout_summary = account.payout(recipients, priority_fee_sompi, wallet_secret, payment_secret, &abortable, None).await?;
+
+        Ok(AccountsPayoutResponse { payout_summary })
+    }
+
+    async fn accounts_consolidation_call(
+        self: Arc<Self>,
+        request: AccountsConsolidationRequest,
+    ) -> Result<AccountsConsolidationResponse> {
+        let AccountsConsolidationRequest { account_id, wallet_secret, payment_secret, policy, retain_secrets } = request;
+
+        let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        if policy.is_some() && retain_secrets {
+            // ensure the account can be signed with the supplied secret before retaining it
+            account.prv_key_data(wallet_secret.clone()).await?;
+            account.set_consolidation_policy(&wallet_secret, policy).await?;
+            self.arm_consolidation(&account_id, wallet_secret, payment_secret);
+        } else {
+            account.set_consolidation_policy(&wallet_secret, policy).await?;
+            self.disarm_consolidation(&account_id);
+        }
+
+        Ok(AccountsConsolidationResponse {})
+    }
+
+    async fn accounts_fee_bump_call(self: Arc<Self>, request: AccountsFeeBumpRequest) -> Result<AccountsFeeBumpResponse> {
+        let AccountsFeeBumpRequest { account_id, wallet_secret, payment_secret, transaction_id, fee_rate } = request;
+
+        let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let abortable = Abortable::new();
+        let fee_bump_summary = account.fee_bump(transaction_id, fee_rate, wallet_secret, payment_secret, &abortable).await?;
+
+        Ok(AccountsFeeBumpResponse { fee_bump_summary })
+    }
+
     async fn accounts_mnemonic_call(self: Arc<Self>, request: AccountsMnemonicRequest) -> Result<AccountsMnemonicResponse> {
         let account_id = request.account_id.clone();
         let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
@@ -423,7 +496,7 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_estimate_call(self: Arc<Self>, request: AccountsEstimateRequest) -> Result<AccountsEstimateResponse> {
-        let AccountsEstimateRequest { account_id, destination, priority_fee_sompi, payload } = request;
+        let AccountsEstimateRequest { account_id, destination, priority_fee_sompi, payload, utxo_selection } = request;
 
         let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
 
@@ -440,7 +513,7 @@ impl WalletApi for super::Wallet {
 
         let abortable = Abortable::new();
         self.inner.estimation_abortables.lock().unwrap().insert(account_id, abortable.clone());
-        let result = account.estimate(destination, priority_fee_sompi, payload, &abortable).await;
+        let result = account.estimate(destination, priority_fee_sompi, payload, utxo_selection, &abortable).await;
         self.inner.estimation_abortables.lock().unwrap().remove(&account_id);
 
         Ok(AccountsEstimateResponse { generator_summary: result? })
@@ -489,10 +562,214 @@ impl WalletApi for super::Wallet {
         Ok(TransactionsReplaceMetadataResponse {})
     }
 
+    async fn transactions_export_call(self: Arc<Self>, request: TransactionsExportRequest) -> Result<TransactionsExportResponse> {
+        let TransactionsExportRequest { account_id, network_id, format, filter, from_unixtime_msec, to_unixtime_msec } = request;
+
+        let binding = Binding::Account(account_id);
+        let store = self.store().as_transaction_record_store()?;
+        let mut records = vec![];
+        match store.transaction_data_iter(&binding, &network_id).await {
+            Ok(mut iter) => {
+                while let Some(record) = iter.try_next().await? {
+                    if filter.as_ref().map(|filter| filter.contains(&record.kind())).unwrap_or(true) {
+                        records.push(record);
+                    }
+                }
+            }
+            Err(Error::NoRecordsFound) => {}
+            Err(err) => return Err(err),
+        }
+        records.sort_by_key(|record| record.block_daa_score());
+
+        // resolve timestamps of records that were stored without them
+        let mut timestamps = HashMap::<u64, u64>::new();
+        let daa_scores =
+            records.iter().filter(|record| record.unixtime_msec().is_none()).map(|record| record.block_daa_score()).collect::<Vec<_>>();
+        if !daa_scores.is_empty() {
+            if self.is_connected() {
+                for daa_scores in daa_scores.chunks(1024) {
+                    let estimates = self.rpc_api().get_daa_score_timestamp_estimate(daa_scores.to_vec()).await?;
+                    timestamps.extend(daa_scores.iter().cloned().zip(estimates));
+                }
+            } else if from_unixtime_msec.is_some() || to_unixtime_msec.is_some() {
+                return Err(Error::NotConnected);
+            }
+        }
+
+        let records = records
+            .iter()
+            .map(|record| TransactionExportRecord::new(record, timestamps.get(&record.block_daa_score()).cloned()))
+            .filter(|record| match record.unixtime_msec {
+                Some(unixtime_msec) => {
+                    from_unixtime_msec.map(|from| unixtime_msec >= from).unwrap_or(true)
+                        && to_unixtime_msec.map(|to| unixtime_msec < to).unwrap_or(true)
+                }
+                None => from_unixtime_msec.is_none() && to_unixtime_msec.is_none(),
+            })
+            .collect::<Vec<_>>();
+
+        let data = format.export(&records)?;
+        Ok(TransactionsExportResponse { account_id, format, total: records.len() as u64, data })
+    }
+
     async fn address_book_enumerate_call(
         self: Arc<Self>,
-        _request: AddressBookEnumerateRequest,
+        request: AddressBookEnumerateRequest,
     ) -> Result<AddressBookEnumerateResponse> {
-        return Err(Error::NotImplemented);
+        let AddressBookEnumerateRequest { network_id, search } = request;
+
+        let store = self.store().as_address_book_store()?;
+        let entries = if let Some(search) = search {
+            store.search(&search).await?
+        } else {
+            store.iter().await?.try_collect::<Vec<_>>().await?
+        };
+        let entries = entries
+            .into_iter()
+            .filter(|entry| network_id.as_ref().map(|network_id| entry.is_network(network_id)).unwrap_or(true))
+            .map(|entry| (*entry).clone())
+            .collect();
+
+        Ok(AddressBookEnumerateResponse { entries })
+    }
+
+    async fn address_book_add_call(self: Arc<Self>, request: AddressBookAddRequest) -> Result<AddressBookAddResponse> {
+        let AddressBookAddRequest { wallet_secret, entry } = request;
+
+        let entry = AddressBookEntry::try_new(&entry.alias, &entry.title, entry.address, entry.labels)?;
+        let store = self.store().as_address_book_store()?;
+        if store.load(entry.address.prefix, &entry.alias).await?.is_some() {
+            return Err(Error::AddressBookEntryExists(entry.alias));
+        }
+        store.store(entry).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(AddressBookAddResponse {})
+    }
+
+    async fn address_book_update_call(self: Arc<Self>, request: AddressBookUpdateRequest) -> Result<AddressBookUpdateResponse> {
+        let AddressBookUpdateRequest { wallet_secret, network_id, alias, entry } = request;
+
+        let entry = AddressBookEntry::try_new(&entry.alias, &entry.title, entry.address, entry.labels)?;
+        let prefix = Prefix::from(network_id);
+        let store = self.store().as_address_book_store()?;
+        let existing = store.load(prefix, &alias).await?.ok_or_else(|| Error::AddressBookEntryNotFound(alias.clone()))?;
+        // the entry may be re-keyed by changing its alias or moving its address to another network
+        if !existing.is(entry.address.prefix, &entry.alias) {
+            if store.load(entry.address.prefix, &entry.alias).await?.is_some() {
+                return Err(Error::AddressBookEntryExists(entry.alias));
+            }
+            store.remove(prefix, &alias).await?;
+        }
+        store.store(entry).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(AddressBookUpdateResponse {})
+    }
+
+    async fn address_book_remove_call(self: Arc<Self>, request: AddressBookRemoveRequest) -> Result<AddressBookRemoveResponse> {
+        let AddressBookRemoveRequest { wallet_secret, network_id, alias } = request;
+
+        self.store().as_address_book_store()?.remove(Prefix::from(network_id), &alias).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(AddressBookRemoveResponse {})
+    }
+
+    async fn utxos_enumerate_call(self: Arc<Self>, request: UtxosEnumerateRequest) -> Result<UtxosEnumerateResponse> {
+        let UtxosEnumerateRequest { account_id } = request;
+
+        let account = self.active_accounts().get(&account_id).ok_or(Error::AccountNotActive(account_id))?;
+        let metadata = self.store().as_utxo_metadata_store()?.load_all().await?;
+        let metadata = metadata.iter().map(|metadata| (metadata.outpoint, metadata)).collect::<HashMap<_, _>>();
+
+        let entries = {
+            let context = account.utxo_context().context();
+            let mature = context.mature.iter().map(|entry| (entry.clone(), true));
+            let immature = context.pending.values().chain(context.stasis.values()).map(|entry| (entry.clone(), false));
+            mature.chain(immature).collect::<Vec<_>>()
+        };
+
+        let utxos = entries
+            .into_iter()
+            .map(|(entry, is_mature)| {
+                let utxo = entry.as_ref();
+                let outpoint = TransactionOutpoint::from(utxo.outpoint.clone());
+                let metadata = metadata.get(&outpoint);
+                UtxoDescriptor {
+                    outpoint,
+                    address: utxo.address.clone(),
+                    amount: utxo.amount,
+                    block_daa_score: utxo.block_daa_score,
+                    is_coinbase: utxo.is_coinbase,
+                    is_mature,
+                    frozen: metadata.map(|metadata| metadata.frozen).unwrap_or_default(),
+                    label: metadata.and_then(|metadata| metadata.label.clone()),
+                }
+            })
+            .collect();
+
+        Ok(UtxosEnumerateResponse { utxos })
+    }
+
+    async fn utxos_freeze_call(self: Arc<Self>, request: UtxosFreezeRequest) -> Result<UtxosFreezeResponse> {
+        let UtxosFreezeRequest { outpoints, frozen } = request;
+
+        let store = self.store().as_utxo_metadata_store()?;
+        let mut updates = Vec::with_capacity(outpoints.len());
+        for outpoint in outpoints {
+            let mut metadata =
+                store.load_single(&outpoint).await?.map(|metadata| (*metadata).clone()).unwrap_or_else(|| UtxoMetadata::new(outpoint));
+            metadata.frozen = frozen;
+            updates.push(metadata);
+        }
+        store.store(updates).await?;
+        self.sync_frozen_utxos().await?;
+
+        Ok(UtxosFreezeResponse {})
+    }
+
+    async fn utxos_label_call(self: Arc<Self>, request: UtxosLabelRequest) -> Result<UtxosLabelResponse> {
+        let UtxosLabelRequest { outpoint, label } = request;
+
+        let store = self.store().as_utxo_metadata_store()?;
+        let mut metadata =
+            store.load_single(&outpoint).await?.map(|metadata| (*metadata).clone()).unwrap_or_else(|| UtxoMetadata::new(outpoint));
+        metadata.label = label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty());
+        store.store(vec![metadata]).await?;
+
+        Ok(UtxosLabelResponse {})
+    }
+
+    async fn payment_request_create_call(
+        self: Arc<Self>,
+        request: PaymentRequestCreateRequest,
+    ) -> Result<PaymentRequestCreateResponse> {
+        let PaymentRequestCreateRequest { account_id, amount, label, message, expires, payload } = request;
+
+        let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let payment_request = PaymentRequest {
+            address: account.receive_address()?,
+            amount,
+            label: label.filter(|label| !label.is_empty()),
+            message: message.filter(|message| !message.is_empty()),
+            expires,
+            payload,
+        };
+        payment_request.validate(&self.network_id()?)?;
+        let uri = payment_request.to_uri();
+
+        Ok(PaymentRequestCreateResponse { payment_request, uri })
+    }
+
+    async fn payment_request_parse_call(self: Arc<Self>, request: PaymentRequestParseRequest) -> Result<PaymentRequestParseResponse> {
+        let PaymentRequestParseRequest { uri, network_id } = request;
+
+        let network_id = if let Some(network_id) = network_id { network_id } else { self.network_id()? };
+        let payment_request = uri.parse::<PaymentRequest>()?;
+        payment_request.validate(&network_id)?;
+
+        Ok(PaymentRequestParseResponse { payment_request })
     }
 }
@@ -5,7 +5,7 @@
 use crate::imports::*;
 // use crate::secret::Secret;
 use crate::storage::interface::CreateArgs;
-use crate::storage::{Hint, PrvKeyDataId};
+use crate::storage::{Hint, MnemonicShareArgs, PrvKeyDataId};
 use borsh::{BorshDeserialize, BorshSerialize};
 use zeroize::Zeroize;
 
@@ -64,11 +64,19 @@ pub struct PrvKeyDataCreateArgs {
     pub name: Option<String>,
     pub payment_secret: Option<Secret>,
     pub mnemonic: Secret,
+    /// If set, the mnemonic is additionally split into a share set
+    /// returned with the creation response.
+    pub shares: Option<MnemonicShareArgs>,
 }
 
 impl PrvKeyDataCreateArgs {
     pub fn new(name: Option<String>, payment_secret: Option<Secret>, mnemonic: Secret) -> Self {
-        Self { name, payment_secret, mnemonic }
+        Self { name, payment_secret, mnemonic, shares: None }
+    }
+
+    pub fn with_shares(mut self, shares: MnemonicShareArgs) -> Self {
+        self.shares = Some(shares);
+        self
     }
 }
 
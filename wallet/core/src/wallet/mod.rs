@@ -7,6 +7,7 @@ pub mod maps;
 pub use args::*;
 
 use crate::account::ScanNotifier;
+use crate::api::NotificationChannels;
 use crate::compat::gen1::decrypt_mnemonic;
 use crate::error::Error::Custom;
 use crate::factory::try_load_account;
@@ -15,7 +16,9 @@ use crate::settings::{SettingsStore, WalletSettings};
 use crate::storage::interface::{OpenArgs, StorageDescriptor};
 use crate::storage::local::interface::LocalStore;
 use crate::storage::local::Storage;
+use crate::utxo::{UtxoContextId, UtxoEntryId};
 use crate::wallet::maps::ActiveAccountMap;
+use waglayla_consensus_core::tx::TransactionOutpoint;
 use waglayla_bip32::{ExtendedKey, Language, Mnemonic, Prefix as KeyPrefix, WordCount};
 use waglayla_notify::{
     listener::ListenerId,
@@ -75,6 +78,11 @@ impl<'a, T: AsRef<[u8]>> MultisigWalletFileV1<'a, T> {
 #[derive(Clone)]
 pub enum WalletBusMessage {
     Discovery { record: TransactionRecord },
+    /// Posted by the [`UtxoProcessor`] when UTXOs of the [`UtxoContext`]
+    /// meet the thresholds of its [`ConsolidationPolicy`](crate::utxo::ConsolidationPolicy).
+    Consolidation { id: UtxoContextId },
+    /// Posted by the [`UtxoProcessor`] when UTXOs are spent, allowing their metadata to be pruned.
+    UtxosSpent { outpoints: Vec<TransactionOutpoint> },
 }
 
 pub struct Inner {
@@ -90,6 +98,10 @@ pub struct Inner {
     wallet_bus: Channel<WalletBusMessage>,
     estimation_abortables: Mutex<HashMap<AccountId, Abortable>>,
     retained_contexts: Mutex<HashMap<String, Arc<Vec<u8>>>>,
+    notification_channels: NotificationChannels,
+    /// Secrets of accounts armed for automatic UTXO consolidation
+    consolidation_secrets: Mutex<HashMap<AccountId, (Secret, Option<Secret>)>>,
+    consolidations_in_progress: Mutex<HashSet<AccountId>>,
 }
 
 ///
@@ -155,6 +167,9 @@ impl Wallet {
                 wallet_bus,
                 estimation_abortables: Mutex::new(HashMap::new()),
                 retained_contexts: Mutex::new(HashMap::new()),
+                notification_channels: NotificationChannels::default(),
+                consolidation_secrets: Mutex::new(HashMap::new()),
+                consolidations_in_progress: Mutex::new(HashSet::new()),
             }),
         };
 
@@ -192,6 +207,7 @@ impl Wallet {
         self.utxo_processor().cleanup().await?;
 
         self.select(None).await?;
+        self.disarm_all_consolidations();
 
         let accounts = self.active_accounts().collect();
         let futures = accounts.into_iter().map(|account| account.stop());
@@ -218,6 +234,7 @@ impl Wallet {
 
             // reset utxo processor
             self.utxo_processor().cleanup().await?;
+            self.sync_frozen_utxos().await?;
 
             // notify reload event
             self.notify(Events::WalletReload { wallet_descriptor, account_descriptors }).await?;
@@ -307,6 +324,7 @@ impl Wallet {
 
         // reset current state only after we have successfully opened another wallet
         self.reset(true).await?;
+        self.sync_frozen_utxos().await?;
 
         let accounts: Option<Vec<Arc<dyn Account>>> = if args.load_account_descriptors() {
             let stored_accounts = self.inner.store.as_account_store().unwrap().iter(None).await?.try_collect::<Vec<_>>().await?;
@@ -847,6 +865,18 @@ impl Wallet {
         Ok((wallet_descriptor, storage_descriptor, mnemonic, account))
     }
 
+    /// UTXOs frozen in the wallet metadata, which are never spent by the transaction generator.
+    pub async fn frozen_utxos(&self) -> Result<HashSet<UtxoEntryId>> {
+        let metadata = self.store().as_utxo_metadata_store()?.load_all().await?;
+        Ok(metadata.iter().filter(|metadata| metadata.frozen).map(|metadata| UtxoEntryId::from(metadata.outpoint)).collect())
+    }
+
+    /// Mirror the frozen UTXOs into the [`UtxoProcessor`], excluding them from the consolidation policies.
+    pub(crate) async fn sync_frozen_utxos(&self) -> Result<()> {
+        self.utxo_processor().set_frozen_utxos(self.frozen_utxos().await?);
+        Ok(())
+    }
+
     pub async fn get_account_by_id(self: &Arc<Self>, account_id: &AccountId) -> Result<Option<Arc<dyn Account>>> {
         if let Some(account) = self.active_accounts().get(account_id) {
             Ok(Some(account.clone()))
@@ -909,11 +939,73 @@ impl Wallet {
         Ok(())
     }
 
+    /// Arm automatic UTXO consolidation of the account. This is an explicit opt-in
+    /// (see [`AccountsConsolidationRequest::retain_secrets`](crate::api::AccountsConsolidationRequest::retain_secrets)):
+    /// the account secrets are retained in memory until the consolidation is disarmed,
+    /// the wallet secret is changed or the wallet is closed. Secrets are zeroized when dropped.
+    pub fn arm_consolidation(&self, account_id: &AccountId, wallet_secret: Secret, payment_secret: Option<Secret>) {
+        self.inner.consolidation_secrets.lock().unwrap().insert(*account_id, (wallet_secret, payment_secret));
+    }
+
+    pub fn disarm_consolidation(&self, account_id: &AccountId) {
+        self.inner.consolidation_secrets.lock().unwrap().remove(account_id);
+    }
+
+    /// Disarm the automatic UTXO consolidation of all accounts, dropping the retained secrets.
+    pub fn disarm_all_consolidations(&self) {
+        self.inner.consolidation_secrets.lock().unwrap().clear();
+    }
+
+    pub fn is_consolidation_armed(&self, account_id: &AccountId) -> bool {
+        self.inner.consolidation_secrets.lock().unwrap().contains_key(account_id)
+    }
+
+    async fn handle_consolidation(self: &Arc<Self>, id: UtxoContextId) -> Result<()> {
+        let account_id = AccountId::from(id);
+        // consolidation policies of accounts that have not been armed are ignored
+        let Some((wallet_secret, payment_secret)) = self.inner.consolidation_secrets.lock().unwrap().get(&account_id).cloned() else {
+            return Ok(());
+        };
+        let Some(account) = self.active_accounts().get(&account_id) else {
+            return Ok(());
+        };
+        let Some(policy) = account.consolidation_policy() else {
+            return Ok(());
+        };
+        if !self.inner.consolidations_in_progress.lock().unwrap().insert(account_id) {
+            return Ok(());
+        }
+
+        let this = self.clone();
+        spawn(async move {
+            let abortable = Abortable::default();
+            let event = match account.consolidate(&policy, wallet_secret, payment_secret, &abortable).await {
+                Ok(Some(summary)) => Some(Events::Consolidation { id, summary }),
+                Ok(None) => None,
+                Err(err) => Some(Events::ConsolidationError { id, message: err.to_string() }),
+            };
+            this.inner.consolidations_in_progress.lock().unwrap().remove(&account_id);
+
+            if let Some(event) = event {
+                this.notify(event).await.unwrap_or_else(|err| log_error!("Wallet::handle_consolidation() notify error: {err}"));
+            }
+        });
+
+        Ok(())
+    }
+
     async fn handle_wallet_bus(self: &Arc<Self>, message: WalletBusMessage) -> Result<()> {
         match message {
             WalletBusMessage::Discovery { record } => {
                 self.handle_discovery(record).await?;
             }
+            WalletBusMessage::Consolidation { id } => {
+                self.handle_consolidation(id).await?;
+            }
+            WalletBusMessage::UtxosSpent { outpoints } => {
+                self.store().as_utxo_metadata_store()?.remove(&outpoints).await?;
+                self.sync_frozen_utxos().await?;
+            }
         }
         Ok(())
     }
@@ -929,6 +1021,10 @@ impl Wallet {
             _ => {}
         }
 
+        if !self.inner.notification_channels.is_empty() {
+            self.inner.notification_channels.notify(event.into());
+        }
+
         Ok(())
     }
 
@@ -6,6 +6,7 @@ pub trait WalletApiObjectExtension {
     fn get_secret(&self, key: &str) -> Result<Secret>;
     fn try_get_secret(&self, key: &str) -> Result<Option<Secret>>;
     fn get_network_id(&self, key: &str) -> Result<NetworkId>;
+    fn try_get_network_id(&self, key: &str) -> Result<Option<NetworkId>>;
     fn try_get_prv_key_data_id(&self, key: &str) -> Result<Option<PrvKeyDataId>>;
     fn get_prv_key_data_id(&self, key: &str) -> Result<PrvKeyDataId>;
     fn get_account_id(&self, key: &str) -> Result<AccountId>;
@@ -41,6 +42,14 @@ impl WalletApiObjectExtension for Object {
         Ok(NetworkId::try_from(value)?)
     }
 
+    fn try_get_network_id(&self, key: &str) -> Result<Option<NetworkId>> {
+        if let Some(value) = self.try_get_value(key)? {
+            Ok(Some(NetworkId::try_from(value)?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn try_get_prv_key_data_id(&self, key: &str) -> Result<Option<PrvKeyDataId>> {
         if let Some(value) = self.try_get_value(key)? {
             Ok(Some(PrvKeyDataId::try_from(&value)?))
@@ -4,7 +4,8 @@ use super::extensions::*;
 use crate::account::descriptor::IAccountDescriptor;
 use crate::api::message::*;
 use crate::imports::*;
-use crate::tx::{Fees, PaymentDestination, PaymentOutputs};
+use crate::tx::{Fees, PaymentDestination, PaymentOutputs, PayoutRecipient, UtxoSelection};
+use crate::utxo::ConsolidationPolicy;
 use crate::wasm::tx::fees::IFees;
 use crate::wasm::tx::GeneratorSummary;
 use js_sys::Array;
@@ -740,6 +741,12 @@ declare! {
         paymentSecret? : string;
         /** BIP39 mnemonic phrase (12 or 24 words)*/
         mnemonic : string;
+        /**
+         * Optional `threshold`-of-`count` Shamir share set to
+         * generate from the mnemonic (up to 16 shares).
+         * The shares are returned in the response.
+         */
+        shares? : { threshold : number, count : number };
     }
     "#,
 }
@@ -749,11 +756,13 @@ try_from! ( args: IPrvKeyDataCreateRequest, PrvKeyDataCreateRequest, {
     let name = args.try_get_string("name")?;
     let payment_secret = args.try_get_secret("paymentSecret")?;
     let mnemonic = args.get_secret("mnemonic")?;
+    let shares = args.try_get_value("shares")?.map(from_value::<MnemonicShareArgs>).transpose()?;
 
     let prv_key_data_args = PrvKeyDataCreateArgs {
         name,
         payment_secret,
         mnemonic,
+        shares,
     };
 
     Ok(PrvKeyDataCreateRequest { wallet_secret, prv_key_data_args })
@@ -770,6 +779,8 @@ declare! {
      */
     export interface IPrvKeyDataCreateResponse {
         prvKeyDataId: HexString;
+        /** Share phrases, if `shares` was supplied in the request */
+        mnemonicShares? : string[];
     }
     "#,
 }
@@ -780,6 +791,51 @@ try_from!(args: PrvKeyDataCreateResponse, IPrvKeyDataCreateResponse, {
 
 // ---
 
+declare! {
+    IPrvKeyDataVerifySharesRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPrvKeyDataVerifySharesRequest {
+        /** Share phrases (at least the threshold of the share set) */
+        shares : string[];
+    }
+    "#,
+}
+
+try_from! ( args: IPrvKeyDataVerifySharesRequest, PrvKeyDataVerifySharesRequest, {
+    Ok(from_value::<PrvKeyDataVerifySharesRequest>(args.into())?)
+});
+
+declare! {
+    IPrvKeyDataVerifySharesResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPrvKeyDataVerifySharesResponse {
+        shareSet : {
+            identifier : number;
+            threshold : number;
+            shareCount : number;
+            /** Id of the private key recovered from the shares */
+            prvKeyDataId : HexString;
+        };
+    }
+    "#,
+}
+
+try_from!(args: PrvKeyDataVerifySharesResponse, IPrvKeyDataVerifySharesResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IPrvKeyDataRemoveRequest,
     r#"
@@ -1373,6 +1429,10 @@ declare! {
          * If not supplied, the destination will be the change address resulting in a UTXO compound transaction.
          */
         destination? : IPaymentOutput[];
+        /**
+         * Manual coin control. If not supplied, UTXOs are selected automatically.
+         */
+        utxoSelection? : IUtxoSelection;
     }
     "#,
 }
@@ -1387,8 +1447,9 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
+    let utxo_selection = args.try_get_value("utxoSelection")?.map(from_value::<UtxoSelection>).transpose()?;
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload })
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload, utxo_selection })
 });
 
 declare! {
@@ -1704,6 +1765,7 @@ declare! {
         destination : IPaymentOutput[];
         priorityFeeSompi : IFees | bigint;
         payload? : Uint8Array | string;
+        utxoSelection? : IUtxoSelection;
     }
     "#,
 }
@@ -1716,8 +1778,9 @@ try_from! ( args: IAccountsEstimateRequest, AccountsEstimateRequest, {
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
+    let utxo_selection = args.try_get_value("utxoSelection")?.map(from_value::<UtxoSelection>).transpose()?;
 
-    Ok(AccountsEstimateRequest { account_id, priority_fee_sompi, destination, payload })
+    Ok(AccountsEstimateRequest { account_id, priority_fee_sompi, destination, payload, utxo_selection })
 });
 
 declare! {
@@ -1933,8 +1996,92 @@ try_from! ( _args: TransactionsReplaceMetadataResponse, ITransactionsReplaceMeta
     Ok(ITransactionsReplaceMetadataResponse::default())
 });
 
+declare! {
+    ITransactionsExportRequest,
+    r#"
+    /**
+     * Exports the transaction history of an account.
+     *  
+     * @category Wallet API
+     */
+    export interface ITransactionsExportRequest {
+        accountId : HexString;
+        networkId : NetworkId | string;
+        /**
+         * Export format, `csv` if not supplied.
+         */
+        format? : "csv" | "json";
+        filter? : TransactionKind[];
+        /**
+         * Include only transactions estimated to occur at or after this unix time (in milliseconds).
+         */
+        fromUnixtimeMsec? : bigint;
+        /**
+         * Include only transactions estimated to occur before this unix time (in milliseconds).
+         */
+        toUnixtimeMsec? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: ITransactionsExportRequest, TransactionsExportRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let network_id = args.get_network_id("networkId")?;
+    let format = args.try_get_string("format")?.map(|format| format.parse::<TransactionExportFormat>()).transpose()?.unwrap_or_default();
+    let filter = args.get_vec("filter").ok().map(|filter| {
+        filter.into_iter().map(TransactionKind::try_from).collect::<Result<Vec<TransactionKind>>>()
+    }).transpose()?;
+    let from_unixtime_msec = args.get_u64("fromUnixtimeMsec").ok();
+    let to_unixtime_msec = args.get_u64("toUnixtimeMsec").ok();
+
+    Ok(TransactionsExportRequest { account_id, network_id, format, filter, from_unixtime_msec, to_unixtime_msec })
+});
+
+declare! {
+    ITransactionsExportResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ITransactionsExportResponse {
+        accountId : HexString;
+        format : "csv" | "json";
+        /**
+         * Number of exported transactions.
+         */
+        total : bigint;
+        /**
+         * Exported data in the requested format.
+         */
+        data : string;
+    }
+    "#,
+}
+
+try_from! ( args: TransactionsExportResponse, ITransactionsExportResponse, {
+    Ok(to_value(&args)?.into())
+});
+
 // ---
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ADDRESS_BOOK_ENTRY: &'static str = r#"
+/**
+ * Address book entry, identified by an alias unique
+ * within the network of the entry address.
+ * 
+ * @category Wallet API
+ */
+export interface IAddressBookEntry {
+    alias: string;
+    title: string;
+    address: Address | string;
+    labels: string[];
+}
+"#;
+
 declare! {
     IAddressBookEnumerateRequest,
     r#"
@@ -1943,12 +2090,24 @@ declare! {
      *  
      * @category Wallet API
      */
-    export interface IAddressBookEnumerateRequest { }
+    export interface IAddressBookEnumerateRequest {
+        /**
+         * Only return entries for addresses of this network.
+         */
+        networkId?: NetworkId | string;
+        /**
+         * Fuzzy search string matching aliases, titles, labels and addresses.
+         * Best matches are returned first.
+         */
+        search?: string;
+    }
     "#,
 }
 
-try_from! ( _args: IAddressBookEnumerateRequest, AddressBookEnumerateRequest, {
-    Ok(AddressBookEnumerateRequest { })
+try_from! ( args: IAddressBookEnumerateRequest, AddressBookEnumerateRequest, {
+    let network_id = args.try_get_network_id("networkId")?;
+    let search = args.try_get_string("search")?;
+    Ok(AddressBookEnumerateRequest { network_id, search })
 });
 
 declare! {
@@ -1960,13 +2119,683 @@ declare! {
      * @category Wallet API
      */
     export interface IAddressBookEnumerateResponse {
-        // TODO
+        entries: IAddressBookEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: AddressBookEnumerateResponse, IAddressBookEnumerateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IAddressBookAddRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookAddRequest {
+        walletSecret: string;
+        entry: IAddressBookEntry;
+    }
+    "#,
+}
+
+try_from! ( args: IAddressBookAddRequest, AddressBookAddRequest, {
+    Ok(from_value::<AddressBookAddRequest>(args.into())?)
+});
+
+declare! {
+    IAddressBookAddResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookAddResponse { }
+    "#,
+}
+
+try_from! ( _args: AddressBookAddResponse, IAddressBookAddResponse, {
+    Ok(IAddressBookAddResponse::default())
+});
+
+// ---
+
+declare! {
+    IAddressBookUpdateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookUpdateRequest {
+        walletSecret: string;
+        networkId: NetworkId | string;
+        alias: string;
+        entry: IAddressBookEntry;
+    }
+    "#,
+}
+
+try_from! ( args: IAddressBookUpdateRequest, AddressBookUpdateRequest, {
+    Ok(from_value::<AddressBookUpdateRequest>(args.into())?)
+});
+
+declare! {
+    IAddressBookUpdateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookUpdateResponse { }
+    "#,
+}
+
+try_from! ( _args: AddressBookUpdateResponse, IAddressBookUpdateResponse, {
+    Ok(IAddressBookUpdateResponse::default())
+});
+
+// ---
+
+declare! {
+    IAddressBookRemoveRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookRemoveRequest {
+        walletSecret: string;
+        networkId: NetworkId | string;
+        alias: string;
+    }
+    "#,
+}
+
+try_from! ( args: IAddressBookRemoveRequest, AddressBookRemoveRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let network_id = args.get_network_id("networkId")?;
+    let alias = args.get_string("alias")?;
+    Ok(AddressBookRemoveRequest { wallet_secret, network_id, alias })
+});
+
+declare! {
+    IAddressBookRemoveResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookRemoveResponse { }
+    "#,
+}
+
+try_from! ( _args: AddressBookRemoveResponse, IAddressBookRemoveResponse, {
+    Ok(IAddressBookRemoveResponse::default())
+});
+
+// ---
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_UTXO_SELECTION: &'static str = r#"
+/**
+ * Manual coin control applied to the UTXOs spent by a transaction.
+ * Frozen UTXOs are never spent.
+ * 
+ * @category Wallet API
+ */
+export interface IUtxoSelection {
+    /**
+     * If not empty, only these UTXOs are spent.
+     */
+    include?: ITransactionOutpoint[];
+    /**
+     * UTXOs that must not be spent.
+     */
+    exclude?: ITransactionOutpoint[];
+}
+
+/**
+ * UTXO owned by an account along with its coin control metadata.
+ * 
+ * @category Wallet API
+ */
+export interface IUtxoDescriptor {
+    outpoint: ITransactionOutpoint;
+    address?: Address | string;
+    amount: bigint;
+    blockDaaScore: bigint;
+    isCoinbase: boolean;
+    isMature: boolean;
+    frozen: boolean;
+    label?: string;
+}
+"#;
+
+declare! {
+    IUtxosEnumerateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IUtxosEnumerateRequest {
+        accountId : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IUtxosEnumerateRequest, UtxosEnumerateRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    Ok(UtxosEnumerateRequest { account_id })
+});
+
+declare! {
+    IUtxosEnumerateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IUtxosEnumerateResponse {
+        utxos : IUtxoDescriptor[];
+    }
+    "#,
+}
+
+try_from! ( args: UtxosEnumerateResponse, IUtxosEnumerateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IUtxosFreezeRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IUtxosFreezeRequest {
+        outpoints : ITransactionOutpoint[];
+        frozen : boolean;
     }
     "#,
 }
 
-try_from! ( _args: AddressBookEnumerateResponse, IAddressBookEnumerateResponse, {
-    Err(Error::NotImplemented)
+try_from! ( args: IUtxosFreezeRequest, UtxosFreezeRequest, {
+    Ok(from_value::<UtxosFreezeRequest>(args.into())?)
+});
+
+declare! {
+    IUtxosFreezeResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IUtxosFreezeResponse { }
+    "#,
+}
+
+try_from! ( _args: UtxosFreezeResponse, IUtxosFreezeResponse, {
+    Ok(IUtxosFreezeResponse::default())
 });
 
 // ---
+
+declare! {
+    IUtxosLabelRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IUtxosLabelRequest {
+        outpoint : ITransactionOutpoint;
+        /**
+         * The label is removed if not supplied.
+         */
+        label? : string;
+    }
+    "#,
+}
+
+try_from! ( args: IUtxosLabelRequest, UtxosLabelRequest, {
+    Ok(from_value::<UtxosLabelRequest>(args.into())?)
+});
+
+declare! {
+    IUtxosLabelResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IUtxosLabelResponse { }
+    "#,
+}
+
+try_from! ( _args: UtxosLabelResponse, IUtxosLabelResponse, {
+    Ok(IUtxosLabelResponse::default())
+});
+
+// ---
+
+// ---
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_PAYOUT: &'static str = r#"
+/**
+ * Payout recipient. The note is not included in the transaction.
+ * 
+ * @category Wallet API
+ */
+export interface IPayoutRecipient {
+    address: Address | string;
+    amount: bigint;
+    note?: string;
+}
+
+/**
+ * Transaction paying the recipient at `index` of the payout recipient list.
+ * 
+ * @category Wallet API
+ */
+export interface IPayoutReceipt {
+    index: number;
+    recipientId: HexString;
+    transactionId: HexString;
+}
+
+/**
+ * Payout summary. If `error` is set, the payout has been interrupted
+ * and recipients without a receipt have not been paid.
+ * 
+ * @category Wallet API
+ */
+export interface IPayoutSummary {
+    receipts: IPayoutReceipt[];
+    transactionIds: HexString[];
+    aggregatedFees: bigint;
+    numberOfGeneratedTransactions: number;
+    error?: string;
+}
+"#;
+
+declare! {
+    IAccountsPayoutRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsPayoutRequest {
+        /**
+         * Hex identifier of the account.
+         */
+        accountId : HexString;
+        /**
+         * Wallet encryption secret.
+         */
+        walletSecret : string;
+        /**
+         * Optional key encryption secret or BIP39 passphrase.
+         */
+        paymentSecret? : string;
+        /**
+         * Priority fee applied to each payout transaction.
+         */
+        priorityFeeSompi? : IFees | bigint;
+        recipients : IPayoutRecipient[];
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsPayoutRequest, AccountsPayoutRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let payment_secret = args.try_get_secret("paymentSecret")?;
+    let priority_fee_sompi = args.get::<IFees>("priorityFeeSompi")?.try_into()?;
+    let recipients = from_value::<Vec<PayoutRecipient>>(args.get_value("recipients")?)?;
+
+    Ok(AccountsPayoutRequest { account_id, wallet_secret, payment_secret, recipients, priority_fee_sompi })
+});
+
+declare! {
+    IAccountsPayoutResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsPayoutResponse {
+        payoutSummary : IPayoutSummary;
+    }
+    "#,
+}
+
+try_from! ( args: AccountsPayoutResponse, IAccountsPayoutResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_CONSOLIDATION_POLICY: &'static str = r#"
+/**
+ * UTC time window (hours) during which the automatic UTXO consolidation can occur.
+ * A window with `startHour` greater than `endHour` wraps around midnight.
+ * 
+ * @category Wallet API
+ */
+export interface IConsolidationWindow {
+    startHour : number;
+    endHour : number;
+}
+
+/**
+ * Automatic UTXO consolidation policy of an account.
+ * 
+ * @category Wallet API
+ */
+export interface IConsolidationPolicy {
+    /**
+     * Minimum number of eligible UTXOs triggering the consolidation.
+     */
+    utxoCountThreshold : number;
+    /**
+     * Only UTXOs with a value less than or equal to this amount are consolidated.
+     */
+    maxUtxoValue? : bigint;
+    /**
+     * Minimum aggregate value of the eligible UTXOs.
+     */
+    minValue : bigint;
+    /**
+     * Maximum fees relative to the consolidated value (e.g. `0.01` for 1%).
+     */
+    maxFeeRate : number;
+    /**
+     * UTC time windows during which the consolidation can occur (any time if empty).
+     */
+    windows? : IConsolidationWindow[];
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_FEE_BUMP_SUMMARY: &'static str = r#"
+/**
+ * Child-pays-for-parent fee bump summary.
+ * 
+ * @category Wallet API
+ */
+export interface IFeeBumpSummary {
+    parentTransactionId: HexString;
+    parentMass: bigint;
+    parentFees: bigint;
+    transactionId: HexString;
+    mass: bigint;
+    fees: bigint;
+    utxoCount: number;
+}
+"#;
+
+declare! {
+    IAccountsFeeBumpRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsFeeBumpRequest {
+        /**
+         * Hex identifier of the account.
+         */
+        accountId : HexString;
+        /**
+         * Wallet encryption secret.
+         */
+        walletSecret : string;
+        /**
+         * Optional key encryption secret or BIP39 passphrase.
+         */
+        paymentSecret? : string;
+        /**
+         * Id of the unconfirmed (parent) transaction.
+         */
+        transactionId : HexString;
+        /**
+         * Target fee rate of the parent and child package (SOMPI per gram).
+         */
+        feeRate : number;
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsFeeBumpRequest, AccountsFeeBumpRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let payment_secret = args.try_get_secret("paymentSecret")?;
+    let transaction_id = args.get_transaction_id("transactionId")?;
+    let fee_rate = args.get_value("feeRate")?.as_f64().ok_or_else(|| Error::custom("feeRate must be a number"))?;
+
+    Ok(AccountsFeeBumpRequest { account_id, wallet_secret, payment_secret, transaction_id, fee_rate })
+});
+
+declare! {
+    IAccountsFeeBumpResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsFeeBumpResponse {
+        feeBumpSummary : IFeeBumpSummary;
+    }
+    "#,
+}
+
+try_from! ( args: AccountsFeeBumpResponse, IAccountsFeeBumpResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+declare! {
+    IAccountsConsolidationRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsConsolidationRequest {
+        /**
+         * Hex identifier of the account.
+         */
+        accountId : HexString;
+        /**
+         * Wallet encryption secret.
+         */
+        walletSecret : string;
+        /**
+         * Optional key encryption secret or BIP39 passphrase.
+         */
+        paymentSecret? : string;
+        /**
+         * Consolidation policy, the automatic consolidation is disabled if not supplied.
+         */
+        policy? : IConsolidationPolicy;
+        /**
+         * Retain the secrets in memory (until the wallet is closed or its secret is changed)
+         * allowing the wallet to sign the consolidation transactions. The policy remains
+         * inactive if not set.
+         */
+        retainSecrets? : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsConsolidationRequest, AccountsConsolidationRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let payment_secret = args.try_get_secret("paymentSecret")?;
+    let policy = args.try_get_value("policy")?.map(from_value::<ConsolidationPolicy>).transpose()?;
+    let retain_secrets = args.try_get_bool("retainSecrets")?.unwrap_or(false);
+
+    Ok(AccountsConsolidationRequest { account_id, wallet_secret, payment_secret, policy, retain_secrets })
+});
+
+declare! {
+    IAccountsConsolidationResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsConsolidationResponse { }
+    "#,
+}
+
+try_from! ( _args: AccountsConsolidationResponse, IAccountsConsolidationResponse, {
+    Ok(IAccountsConsolidationResponse::default())
+});
+
+declare! {
+    IPaymentRequestCreateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentRequestCreateRequest {
+        /**
+         * Hex identifier of the account receiving the payment.
+         */
+        accountId : HexString;
+        /**
+         * Requested amount in SOMPI.
+         */
+        amount? : bigint;
+        /**
+         * Label of the payment recipient.
+         */
+        label? : string;
+        /**
+         * Message describing the payment.
+         */
+        message? : string;
+        /**
+         * Expiration time (unix time in seconds).
+         */
+        expires? : number | bigint;
+        /**
+         * Transaction payload.
+         */
+        payload? : Uint8Array | HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentRequestCreateRequest, PaymentRequestCreateRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let amount = args.try_get_value("amount")?.map(|v| v.try_as_u64()).transpose()?;
+    let label = args.try_get_string("label")?;
+    let message = args.try_get_string("message")?;
+    let expires = args.try_get_value("expires")?.map(|v| v.try_as_u64()).transpose()?;
+    let payload = args.try_get_value("payload")?.map(|v| v.try_as_vec_u8()).transpose()?;
+
+    Ok(PaymentRequestCreateRequest { account_id, amount, label, message, expires, payload })
+});
+
+declare! {
+    IPaymentRequestCreateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentRequestCreateResponse {
+        paymentRequest : PaymentRequest;
+        /**
+         * Payment request URI (suitable for QR codes).
+         */
+        uri : string;
+    }
+    "#,
+}
+
+try_from! ( args: PaymentRequestCreateResponse, IPaymentRequestCreateResponse, {
+    let response = IPaymentRequestCreateResponse::default();
+    response.set("paymentRequest", &args.payment_request.into())?;
+    response.set("uri", &args.uri.into())?;
+    Ok(response)
+});
+
+declare! {
+    IPaymentRequestParseRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentRequestParseRequest {
+        /**
+         * Payment request URI.
+         */
+        uri : string;
+        /**
+         * Network the request is validated against (defaults to the wallet network).
+         */
+        networkId? : NetworkId | string;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentRequestParseRequest, PaymentRequestParseRequest, {
+    let uri = args.get_string("uri")?;
+    let network_id = args.try_get_network_id("networkId")?;
+    Ok(PaymentRequestParseRequest { uri, network_id })
+});
+
+declare! {
+    IPaymentRequestParseResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentRequestParseResponse {
+        paymentRequest : PaymentRequest;
+    }
+    "#,
+}
+
+try_from! ( args: PaymentRequestParseResponse, IPaymentRequestParseResponse, {
+    let response = IPaymentRequestParseResponse::default();
+    response.set("paymentRequest", &args.payment_request.into())?;
+    Ok(response)
+});
@@ -56,4 +56,17 @@ declare_wasm_handlers!([
     TransactionsReplaceNote,
     TransactionsReplaceMetadata,
     AddressBookEnumerate,
+    AddressBookAdd,
+    AddressBookUpdate,
+    AddressBookRemove,
+    UtxosEnumerate,
+    UtxosFreeze,
+    UtxosLabel,
+    AccountsPayout,
+    AccountsConsolidation,
+    TransactionsExport,
+    AccountsFeeBump,
+    PaymentRequestCreate,
+    PaymentRequestParse,
+    PrvKeyDataVerifyShares,
 ]);
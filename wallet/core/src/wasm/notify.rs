@@ -147,6 +147,8 @@ cfg_if! {
             Maturity = "maturity",
             Discovery = "discovery",
             Balance = "balance",
+            Consolidation = "consolidation",
+            ConsolidationError = "consolidation-error",
             Error = "error",
         }
 
@@ -182,6 +184,8 @@ cfg_if! {
             | IMaturityEvent
             | IDiscoveryEvent
             | IBalanceEvent
+            | IConsolidationEvent
+            | IConsolidationErrorEvent
             | IErrorEvent
             | undefined
             ;
@@ -219,6 +223,8 @@ cfg_if! {
              "maturity": IMaturityEvent,
              "discovery": IDiscoveryEvent,
              "balance": IBalanceEvent,
+             "consolidation": IConsolidationEvent,
+             "consolidation-error": IConsolidationErrorEvent,
              "error": IErrorEvent,
         }
         
@@ -642,6 +648,43 @@ declare! {
     "#,
 }
 
+declare! {
+    IConsolidationEvent,
+    r#"
+    /**
+     * Emitted by {@link Wallet} when an automatic UTXO consolidation
+     * of an account has been submitted.
+     * 
+     * @category Wallet Events
+     */
+    export interface IConsolidationEvent {
+        id : HexString;
+        summary : {
+            utxoCount : number;
+            aggregateInputValue : bigint;
+            aggregatedFees : bigint;
+            transactionIds : HexString[];
+        };
+    }
+    "#,
+}
+
+declare! {
+    IConsolidationErrorEvent,
+    r#"
+    /**
+     * Emitted by {@link Wallet} when an automatic UTXO consolidation
+     * of an account has failed or has been skipped.
+     * 
+     * @category Wallet Events
+     */
+    export interface IConsolidationErrorEvent {
+        id : HexString;
+        message : string;
+    }
+    "#,
+}
+
 declare! {
     IErrorEvent,
     r#"
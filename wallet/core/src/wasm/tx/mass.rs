@@ -18,10 +18,10 @@ pub struct MassCalculator {
 #[wasm_bindgen]
 impl MassCalculator {
     #[wasm_bindgen(constructor)]
-    pub fn new(cp: ConsensusParams) -> Self {
+    pub fn new(cp: ConsensusParams) -> Result<MassCalculator> {
         let consensus_params = Params::from(cp);
-        let network_params = NetworkParams::from(consensus_params.net);
-        Self { mc: Arc::new(mass::MassCalculator::new(&consensus_params, &network_params)) }
+        let network_params = NetworkParams::try_from(consensus_params.net)?;
+        Ok(Self { mc: Arc::new(mass::MassCalculator::new(&consensus_params, &network_params)) })
     }
 
     #[wasm_bindgen(js_name=isDust)]
@@ -25,7 +25,7 @@ pub fn create_transaction_js(
 ) -> crate::result::Result<Transaction> {
     let change_address = Address::try_cast_from(change_address)?;
     let params = get_consensus_params_by_address(change_address.as_ref());
-    let mc = MassCalculator::new(params);
+    let mc = MassCalculator::new(params)?;
 
     let utxo_entries = if let Some(utxo_entries) = utxo_entry_source.dyn_ref::<js_sys::Array>() {
         utxo_entries.to_vec().iter().map(UtxoEntryReference::try_cast_from).collect::<Result<Vec<_>, _>>()?
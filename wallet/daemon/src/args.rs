@@ -0,0 +1,42 @@
+pub use clap::Parser;
+use waglayla_consensus_core::network::NetworkId;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+pub struct Args {
+    /// Wallet file to open (default: the default wallet file)
+    #[arg(long)]
+    pub wallet: Option<String>,
+
+    /// File containing the wallet password. Alternatively, the password can be supplied via the `WAGLAYLA_WALLET_SECRET` environment variable
+    #[arg(long = "wallet-secret-file", value_name = "FILE")]
+    pub wallet_secret_file: Option<String>,
+
+    /// Network of the wallet
+    #[arg(long, default_value = "mainnet")]
+    pub network: NetworkId,
+
+    /// wRPC Borsh URL of the node (default: the local node)
+    #[arg(long)]
+    pub server: Option<String>,
+
+    /// File containing the token clients must supply in order to authenticate
+    #[arg(long = "rpc-token-file", value_name = "FILE")]
+    pub rpc_token_file: String,
+
+    /// Interface:port of the JSON-RPC (HTTP) server
+    #[arg(long, default_value = "127.0.0.1:8180")]
+    pub rpclisten: String,
+
+    /// Interface:port of the wRPC Borsh server (disabled by default)
+    #[arg(long = "rpclisten-borsh", value_name = "ADDRESS")]
+    pub rpclisten_borsh: Option<String>,
+
+    /// Interface:port of the wRPC JSON server (disabled by default)
+    #[arg(long = "rpclisten-json", value_name = "ADDRESS")]
+    pub rpclisten_json: Option<String>,
+
+    /// Verbose mode
+    #[arg(short, long, default_value = "false")]
+    pub verbose: bool,
+}
@@ -0,0 +1,50 @@
+use crate::error::Error;
+use crate::result::Result;
+use subtle::ConstantTimeEq;
+
+/// Token shared with the RPC clients of the daemon, loaded from the `--rpc-token-file`
+pub struct AuthToken(String);
+
+impl AuthToken {
+    pub fn load(path: &str) -> Result<Self> {
+        let token = std::fs::read_to_string(path)?.trim().to_string();
+        if token.is_empty() {
+            return Err(Error::EmptyRpcToken(path.to_string()));
+        }
+        Ok(Self(token))
+    }
+
+    /// Compares the supplied token in constant time
+    pub fn verify(&self, token: &str) -> bool {
+        self.0.as_bytes().ct_eq(token.as_bytes()).into()
+    }
+
+    /// Verifies the value of an HTTP `Authorization: Bearer <token>` header
+    pub fn verify_bearer(&self, header: Option<&str>) -> bool {
+        header.and_then(|header| header.strip_prefix("Bearer ")).is_some_and(|token| self.verify(token.trim()))
+    }
+}
+
+#[cfg(test)]
+impl From<&str> for AuthToken {
+    fn from(token: &str) -> Self {
+        Self(token.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_token() {
+        let token = AuthToken::from("secret-token");
+        assert!(token.verify("secret-token"));
+        assert!(!token.verify("secret-token2"));
+        assert!(!token.verify(""));
+        assert!(token.verify_bearer(Some("Bearer secret-token")));
+        assert!(!token.verify_bearer(Some("Basic secret-token")));
+        assert!(!token.verify_bearer(Some("secret-token")));
+        assert!(!token.verify_bearer(None));
+    }
+}
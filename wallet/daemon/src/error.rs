@@ -0,0 +1,21 @@
+use thiserror::Error;
+use waglayla_wallet_core::error::Error as WalletError;
+use workflow_rpc::server::WebSocketError;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Wallet(#[from] WalletError),
+
+    #[error("IO Error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("wRPC server error: {0}")]
+    WebSocket(#[from] WebSocketError),
+
+    #[error("Missing wallet secret, use --wallet-secret-file or the {0} environment variable")]
+    MissingWalletSecret(&'static str),
+
+    #[error("The RPC token file {0} is empty")]
+    EmptyRpcToken(String),
+}
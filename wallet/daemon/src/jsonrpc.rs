@@ -0,0 +1,153 @@
+//!
+//! JSON-RPC 2.0 server exposing the [`WalletApi`](waglayla_wallet_core::api::WalletApi) over HTTP.
+//!
+//! Requests are posted to `/` and are authenticated with an `Authorization: Bearer <token>`
+//! header. Methods are named after the Wallet API operations in kebab case (e.g. `accounts-send`)
+//! and their `params` are the corresponding camel case request messages. Notifications are not
+//! available over HTTP, use a wRPC listener instead.
+//!
+
+use crate::auth::AuthToken;
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use waglayla_wallet_core::{api::transport::WalletServer, error::Error as WalletError};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const WALLET_ERROR: i64 = -32000;
+
+#[derive(Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    #[serde(default)]
+    id: Value,
+    method: String,
+    params: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Serialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl JsonRpcResponse {
+    fn result(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    fn error(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(JsonRpcError { code, message: message.into() }) }
+    }
+}
+
+#[derive(Clone)]
+struct JsonRpcState {
+    server: Arc<WalletServer>,
+    token: Arc<AuthToken>,
+}
+
+pub fn router(server: Arc<WalletServer>, token: Arc<AuthToken>) -> Router {
+    Router::new().route("/", post(handle_request)).with_state(JsonRpcState { server, token })
+}
+
+async fn handle_request(State(state): State<JsonRpcState>, headers: HeaderMap, body: String) -> Response {
+    let authorization = headers.get(header::AUTHORIZATION).and_then(|value| value.to_str().ok());
+    if !state.token.verify_bearer(authorization) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    Json(dispatch(&state.server, &body).await).into_response()
+}
+
+async fn dispatch(server: &WalletServer, body: &str) -> JsonRpcResponse {
+    let request = match serde_json::from_str::<Value>(body) {
+        Ok(request) => request,
+        Err(err) => return JsonRpcResponse::error(Value::Null, PARSE_ERROR, err.to_string()),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let request = match serde_json::from_value::<JsonRpcRequest>(request) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        Ok(_) => return JsonRpcResponse::error(id, INVALID_REQUEST, "unsupported JSON-RPC version, expected 2.0"),
+        Err(err) => return JsonRpcResponse::error(id, INVALID_REQUEST, err.to_string()),
+    };
+
+    if !WalletServer::SERDE_OPS.contains(&request.method.as_str()) {
+        return JsonRpcResponse::error(request.id, METHOD_NOT_FOUND, format!("unknown method `{}`", request.method));
+    }
+    // Requests without fields, such as `ping` or `get-status`, may omit the params
+    let params = request.params.unwrap_or_else(|| Value::Object(Default::default()));
+    match server.call_with_serde(&request.method, &params.to_string()).await {
+        Ok(response) => match serde_json::from_str(&response) {
+            Ok(result) => JsonRpcResponse::result(request.id, result),
+            Err(err) => JsonRpcResponse::error(request.id, WALLET_ERROR, err.to_string()),
+        },
+        Err(WalletError::SerdeJson(err)) => JsonRpcResponse::error(request.id, INVALID_PARAMS, err.to_string()),
+        Err(err) => JsonRpcResponse::error(request.id, WALLET_ERROR, err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrpc::Connections;
+    use axum::http::HeaderValue;
+    use waglayla_wallet_core::wallet::Wallet;
+
+    const TOKEN: &str = "secret-token";
+
+    fn state() -> JsonRpcState {
+        let wallet = Arc::new(Wallet::try_new(Wallet::resident_store().unwrap(), None, None).unwrap());
+        let server = Arc::new(WalletServer::new(wallet, Arc::new(Connections::default())));
+        JsonRpcState { server, token: Arc::new(AuthToken::from(TOKEN)) }
+    }
+
+    async fn post(state: JsonRpcState, authorization: Option<&str>, body: &str) -> (StatusCode, Option<Value>) {
+        let mut headers = HeaderMap::new();
+        if let Some(authorization) = authorization {
+            headers.insert(header::AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        }
+        let response = handle_request(State(state), headers, body.to_string()).await;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).ok())
+    }
+
+    #[tokio::test]
+    async fn test_authentication() {
+        let state = state();
+        let ping = r#"{"jsonrpc":"2.0","id":1,"method":"ping","params":{"message":"hello"}}"#;
+
+        for authorization in [None, Some("Bearer invalid-token"), Some(TOKEN)] {
+            let (status, body) = post(state.clone(), authorization, ping).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert!(body.is_none());
+        }
+
+        let (status, body) = post(state.clone(), Some(&format!("Bearer {TOKEN}")), ping).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.unwrap(), serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": { "message": "hello" } }));
+
+        let (_, body) = post(state, Some(&format!("Bearer {TOKEN}")), r#"{"jsonrpc":"2.0","id":2,"method":"unknown"}"#).await;
+        assert_eq!(body.unwrap()["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
@@ -0,0 +1,93 @@
+mod args;
+mod auth;
+mod error;
+mod jsonrpc;
+mod result;
+mod wrpc;
+
+use args::*;
+use auth::AuthToken;
+use error::Error;
+use result::Result;
+use std::sync::Arc;
+use waglayla_wallet_core::api::message::{AccountsActivateRequest, ConnectRequest, WalletOpenRequest};
+use waglayla_wallet_core::api::traits::WalletApi;
+use waglayla_wallet_core::api::transport::WalletServer;
+use waglayla_wallet_core::wallet::Wallet;
+use waglayla_wallet_keys::secret::Secret;
+use workflow_log::*;
+use workflow_rpc::server::Encoding;
+use wrpc::{Connections, WrpcServer};
+
+const WALLET_SECRET_ENV: &str = "WAGLAYLA_WALLET_SECRET";
+
+#[tokio::main]
+async fn main() {
+    if let Err(error) = run().await {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> Result<()> {
+    let args = Args::parse();
+
+    workflow_log::set_log_level(if args.verbose { LevelFilter::Debug } else { LevelFilter::Info });
+    log_info!("Waglayla wallet daemon v{} starting...", env!("CARGO_PKG_VERSION"));
+
+    let token = Arc::new(AuthToken::load(&args.rpc_token_file)?);
+    let wallet_secret = load_wallet_secret(&args)?;
+
+    let wallet = Arc::new(Wallet::try_new(Wallet::local_store()?, None, Some(args.network))?);
+    wallet.start().await?;
+    wallet.clone().connect_call(ConnectRequest { url: args.server.clone(), network_id: args.network }).await?;
+    wallet
+        .clone()
+        .wallet_open_call(WalletOpenRequest {
+            wallet_secret,
+            filename: args.wallet.clone(),
+            account_descriptors: false,
+            legacy_accounts: None,
+        })
+        .await?;
+    wallet.clone().accounts_activate_call(AccountsActivateRequest { account_ids: None }).await?;
+    log_info!("Opened wallet {} on {}", args.wallet.as_deref().unwrap_or("(default)"), args.network);
+
+    let connections = Arc::new(Connections::default());
+    let server = Arc::new(WalletServer::new(wallet.clone(), connections.clone()));
+    server.start();
+
+    let wrpc_servers = [(Encoding::Borsh, args.rpclisten_borsh.clone()), (Encoding::SerdeJson, args.rpclisten_json.clone())]
+        .into_iter()
+        .filter_map(|(encoding, listen_address)| listen_address.map(|listen_address| (encoding, listen_address)))
+        .map(|(encoding, listen_address)| {
+            Arc::new(WrpcServer::new(encoding, listen_address, server.clone(), connections.clone(), token.clone(), args.verbose))
+        })
+        .collect::<Vec<_>>();
+    wrpc_servers.iter().for_each(|wrpc_server| wrpc_server.start());
+
+    let listener = tokio::net::TcpListener::bind(&args.rpclisten).await?;
+    log_info!("JSON-RPC server listening on http://{}", args.rpclisten);
+    axum::serve(listener, jsonrpc::router(server.clone(), token))
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.unwrap_or_else(|err| log_error!("Unable to listen for the shutdown signal: {err}"));
+        })
+        .await?;
+
+    log_info!("Shutting down...");
+    for wrpc_server in wrpc_servers {
+        wrpc_server.stop().await?;
+    }
+    server.stop_task().await?;
+    wallet.close().await?;
+    wallet.stop().await?;
+    Ok(())
+}
+
+fn load_wallet_secret(args: &Args) -> Result<Secret> {
+    let secret = match args.wallet_secret_file.as_ref() {
+        Some(path) => std::fs::read_to_string(path)?.trim_end_matches(['\r', '\n']).to_string(),
+        None => std::env::var(WALLET_SECRET_ENV).map_err(|_| Error::MissingWalletSecret(WALLET_SECRET_ENV))?,
+    };
+    Ok(Secret::from(secret))
+}
@@ -0,0 +1,340 @@
+//!
+//! wRPC servers exposing the [`WalletApi`](waglayla_wallet_core::api::WalletApi) over WebSockets.
+//!
+//! Using Borsh encoding, methods are identified by the ops of [`WalletServer::BORSH_OPS`] and
+//! carry the Borsh-serialized request and response messages as byte vectors. Using JSON encoding,
+//! methods are named after [`WalletServer::SERDE_OPS`] and carry the JSON request and response
+//! messages.
+//!
+//! A connection must first call the `authenticate` method ([`AUTHENTICATE_BORSH_OP`] or
+//! [`AUTHENTICATE_SERDE_OP`]) with the RPC token, returning `true` on success. Authenticated
+//! connections then receive all wallet events as [`WalletNotification`] messages.
+//!
+
+use crate::auth::AuthToken;
+use crate::result::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use waglayla_wallet_core::api::message::WalletNotification;
+use waglayla_wallet_core::api::transport::{
+    EventHandler, JsonValue, WalletServer, AUTHENTICATE_BORSH_OP, AUTHENTICATE_SERDE_OP, WALLET_NOTIFICATION_BORSH_OP,
+    WALLET_NOTIFICATION_SERDE_OP,
+};
+use waglayla_wallet_core::events::Events;
+use workflow_log::*;
+use workflow_rpc::server::prelude::*;
+
+pub struct Connection {
+    id: u64,
+    peer: SocketAddr,
+    messenger: Arc<Messenger>,
+    authenticated: AtomicBool,
+}
+
+impl Connection {
+    fn authenticate(&self, token: &AuthToken, supplied: &str) -> ServerResult<bool> {
+        if !token.verify(supplied) {
+            log_warn!("wRPC client {} supplied an invalid RPC token", self.peer);
+            return Err(ServerError::Text("invalid RPC token".to_string()));
+        }
+        self.authenticated.store(true, Ordering::SeqCst);
+        Ok(true)
+    }
+
+    fn ensure_authenticated(&self) -> ServerResult<()> {
+        if self.is_authenticated() {
+            Ok(())
+        } else {
+            Err(ServerError::Text("the connection is not authenticated".to_string()))
+        }
+    }
+
+    fn is_authenticated(&self) -> bool {
+        self.authenticated.load(Ordering::SeqCst)
+    }
+
+    async fn notify(&self, notification: WalletNotification) -> workflow_rpc::server::result::Result<()> {
+        match self.messenger.encoding() {
+            Encoding::Borsh => self.messenger.notify(WALLET_NOTIFICATION_BORSH_OP, notification).await,
+            Encoding::SerdeJson => self.messenger.notify(WALLET_NOTIFICATION_SERDE_OP.to_string(), notification).await,
+        }
+    }
+}
+
+/// The wRPC connections of all listeners, receiving the wallet events as the [`EventHandler`] of the [`WalletServer`]
+#[derive(Default)]
+pub struct Connections {
+    next_id: AtomicU64,
+    connections: Mutex<HashMap<u64, Arc<Connection>>>,
+}
+
+impl Connections {
+    fn register(&self, peer: SocketAddr, messenger: Arc<Messenger>) -> Arc<Connection> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let connection = Arc::new(Connection { id, peer, messenger, authenticated: AtomicBool::new(false) });
+        self.connections.lock().unwrap().insert(id, connection.clone());
+        connection
+    }
+
+    fn unregister(&self, connection: &Connection) {
+        self.connections.lock().unwrap().remove(&connection.id);
+    }
+}
+
+#[async_trait]
+impl EventHandler for Connections {
+    async fn handle_event(&self, event: &Events) {
+        let connections =
+            self.connections.lock().unwrap().values().filter(|connection| connection.is_authenticated()).cloned().collect::<Vec<_>>();
+        let notification = WalletNotification::from(Box::new(event.clone()));
+        for connection in connections {
+            if let Err(err) = connection.notify(notification.clone()).await {
+                log_warn!("Failed posting a wallet notification to {}: {err}", connection.peer);
+            }
+        }
+    }
+}
+
+struct WrpcHandler {
+    connections: Arc<Connections>,
+    verbose: bool,
+}
+
+#[async_trait]
+impl RpcHandler for WrpcHandler {
+    type Context = Arc<Connection>;
+
+    async fn handshake(
+        self: Arc<Self>,
+        peer: &SocketAddr,
+        _sender: &mut WebSocketSender,
+        _receiver: &mut WebSocketReceiver,
+        messenger: Arc<Messenger>,
+    ) -> WebSocketResult<Self::Context> {
+        if self.verbose {
+            log_info!("wRPC client connected: {peer}");
+        }
+        Ok(self.connections.register(*peer, messenger))
+    }
+
+    async fn disconnect(self: Arc<Self>, connection: Self::Context, _result: WebSocketResult<()>) {
+        if self.verbose {
+            log_info!("wRPC client disconnected: {}", connection.peer);
+        }
+        self.connections.unregister(&connection);
+    }
+}
+
+fn borsh_interface(server: Arc<WalletServer>, token: Arc<AuthToken>) -> Interface<Arc<WalletServer>, Arc<Connection>, u64> {
+    let mut interface = Interface::new(server);
+    interface.method(
+        AUTHENTICATE_BORSH_OP,
+        Method::new(move |_, connection: Arc<Connection>, supplied: String| {
+            let token = token.clone();
+            Box::pin(async move { connection.authenticate(&token, &supplied) })
+        }),
+    );
+    for op in WalletServer::BORSH_OPS.iter().copied() {
+        interface.method(
+            op,
+            Method::new(move |server: Arc<WalletServer>, connection: Arc<Connection>, request: Vec<u8>| {
+                Box::pin(async move {
+                    connection.ensure_authenticated()?;
+                    server.call_with_borsh(op, &request).await.map_err(|err| ServerError::Text(err.to_string()))
+                })
+            }),
+        );
+    }
+    interface
+}
+
+fn serde_interface(server: Arc<WalletServer>, token: Arc<AuthToken>) -> Interface<Arc<WalletServer>, Arc<Connection>, String> {
+    let mut interface = Interface::new(server);
+    interface.method(
+        AUTHENTICATE_SERDE_OP.to_string(),
+        Method::new(move |_, connection: Arc<Connection>, supplied: String| {
+            let token = token.clone();
+            Box::pin(async move { connection.authenticate(&token, &supplied) })
+        }),
+    );
+    for op in WalletServer::SERDE_OPS.iter().copied() {
+        interface.method(
+            op.to_string(),
+            Method::new(move |server: Arc<WalletServer>, connection: Arc<Connection>, request: JsonValue| {
+                Box::pin(async move {
+                    connection.ensure_authenticated()?;
+                    let response =
+                        server.call_with_serde(op, &request.0.to_string()).await.map_err(|err| ServerError::Text(err.to_string()))?;
+                    serde_json::from_str(&response).map(JsonValue).map_err(|err| ServerError::Text(err.to_string()))
+                })
+            }),
+        );
+    }
+    interface
+}
+
+/// A wRPC listener, either Borsh or JSON encoded
+pub struct WrpcServer {
+    server: RpcServer,
+    listen_address: String,
+}
+
+impl WrpcServer {
+    pub fn new(
+        encoding: Encoding,
+        listen_address: String,
+        server: Arc<WalletServer>,
+        connections: Arc<Connections>,
+        token: Arc<AuthToken>,
+        verbose: bool,
+    ) -> Self {
+        let handler = Arc::new(WrpcHandler { connections, verbose });
+        let server = match encoding {
+            Encoding::Borsh => {
+                RpcServer::new_with_encoding::<_, _, _, Id64>(encoding, handler, Arc::new(borsh_interface(server, token)), None)
+            }
+            Encoding::SerdeJson => {
+                RpcServer::new_with_encoding::<_, _, _, Id64>(encoding, handler, Arc::new(serde_interface(server, token)), None)
+            }
+        };
+        Self { server, listen_address }
+    }
+
+    pub fn start(self: &Arc<Self>) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            log_info!("wRPC server listening on {}", this.listen_address);
+            if let Err(err) = this.server.listen(&this.listen_address, None).await {
+                log_error!("wRPC server {} stopped with error: {err}", this.listen_address);
+            }
+        });
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.server.stop_and_join().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+    use std::time::Duration;
+    use waglayla_wallet_core::api::message::PingRequest;
+    use waglayla_wallet_core::api::transport::WalletClient;
+    use waglayla_wallet_core::api::wrpc::WrpcCodec;
+    use waglayla_wallet_core::api::WalletApi;
+    use waglayla_wallet_core::wallet::Wallet;
+    use workflow_core::channel::unbounded;
+    use workflow_core::task::sleep;
+    use workflow_rpc::client::prelude::{ConnectOptions, ConnectStrategy, RpcClient, RpcClientOptions};
+    use workflow_rpc::client::Interface as ClientInterface;
+
+    const TOKEN: &str = "secret-token";
+
+    async fn start_server(encoding: Encoding, listen_address: &str) -> (Arc<Wallet>, Arc<WrpcServer>) {
+        let token = Arc::new(AuthToken::from(TOKEN));
+        let wallet = Arc::new(Wallet::try_new(Wallet::resident_store().unwrap(), None, None).unwrap());
+        let connections = Arc::new(Connections::default());
+        let server = Arc::new(WalletServer::new(wallet.clone(), connections.clone()));
+        server.start();
+        let wrpc = Arc::new(WrpcServer::new(encoding, listen_address.to_string(), server, connections, token, false));
+        wrpc.start();
+        // let the listener bind before connecting
+        sleep(Duration::from_millis(200)).await;
+        (wallet, wrpc)
+    }
+
+    async fn test_notification_round_trip(encoding: Encoding, listen_address: &str) {
+        let (wallet, wrpc) = start_server(encoding, listen_address).await;
+
+        let codec = WrpcCodec::connect(&format!("ws://{listen_address}"), encoding, TOKEN).await.unwrap();
+        let client = Arc::new(WalletClient::new(codec.codec()));
+        codec.relay_notifications(&client);
+        assert_eq!(client.clone().ping(Some("hello".to_string())).await.unwrap(), Some("hello".to_string()));
+
+        let (sender, receiver) = unbounded();
+        client.clone().register_notifications(sender).await.unwrap();
+        wallet.multiplexer().try_broadcast(Box::new(Events::WalletPing)).unwrap();
+        let notification = receiver.recv().await.unwrap();
+        assert!(matches!(*notification.event, Events::WalletPing));
+
+        codec.shutdown().await.unwrap();
+        wrpc.stop().await.unwrap();
+    }
+
+    async fn test_authentication(encoding: Encoding, listen_address: &str) {
+        let (_wallet, wrpc) = start_server(encoding, listen_address).await;
+        let url = format!("ws://{listen_address}");
+
+        assert!(WrpcCodec::connect(&url, encoding, "invalid-token").await.is_err());
+
+        // Wallet API calls are rejected until the connection is authenticated
+        let options = ConnectOptions {
+            block_async_connect: true,
+            strategy: ConnectStrategy::Fallback,
+            url: Some(url.clone()),
+            ..Default::default()
+        };
+        let ping = PingRequest { message: Some("hello".to_string()) };
+        match encoding {
+            Encoding::Borsh => {
+                let rpc =
+                    RpcClient::<u64>::new_with_encoding(encoding, ClientInterface::<u64>::new().into(), RpcClientOptions::new(), None)
+                        .unwrap();
+                rpc.connect(options).await.unwrap();
+                let request = ping.try_to_vec().unwrap();
+                assert!(rpc.call::<Vec<u8>, Vec<u8>>(WalletServer::BORSH_OPS[0], request.clone()).await.is_err());
+                assert!(rpc.call::<String, bool>(AUTHENTICATE_BORSH_OP, "invalid-token".to_string()).await.is_err());
+                assert!(rpc.call::<Vec<u8>, Vec<u8>>(WalletServer::BORSH_OPS[0], request).await.is_err());
+                rpc.shutdown().await.unwrap();
+            }
+            Encoding::SerdeJson => {
+                let rpc = RpcClient::<String>::new_with_encoding(
+                    encoding,
+                    ClientInterface::<String>::new().into(),
+                    RpcClientOptions::new(),
+                    None,
+                )
+                .unwrap();
+                rpc.connect(options).await.unwrap();
+                let request = JsonValue(serde_json::to_value(&ping).unwrap());
+                assert!(rpc.call::<JsonValue, JsonValue>(WalletServer::SERDE_OPS[0].to_string(), request.clone()).await.is_err());
+                assert!(rpc.call::<String, bool>(AUTHENTICATE_SERDE_OP.to_string(), "invalid-token".to_string()).await.is_err());
+                assert!(rpc.call::<JsonValue, JsonValue>(WalletServer::SERDE_OPS[0].to_string(), request).await.is_err());
+                rpc.shutdown().await.unwrap();
+            }
+        }
+
+        // Authenticated connections are dispatched to the wallet
+        let codec = WrpcCodec::connect(&url, encoding, TOKEN).await.unwrap();
+        let client = Arc::new(WalletClient::new(codec.codec()));
+        assert_eq!(client.ping(Some("hello".to_string())).await.unwrap(), Some("hello".to_string()));
+
+        codec.shutdown().await.unwrap();
+        wrpc.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_borsh_authentication() {
+        test_authentication(Encoding::Borsh, "127.0.0.1:18412").await;
+    }
+
+    #[tokio::test]
+    async fn test_serde_authentication() {
+        test_authentication(Encoding::SerdeJson, "127.0.0.1:18413").await;
+    }
+
+    #[tokio::test]
+    async fn test_borsh_notification_round_trip() {
+        test_notification_round_trip(Encoding::Borsh, "127.0.0.1:18410").await;
+    }
+
+    #[tokio::test]
+    async fn test_serde_notification_round_trip() {
+        test_notification_round_trip(Encoding::SerdeJson, "127.0.0.1:18411").await;
+    }
+}
@@ -32,9 +32,13 @@ impl ToTokens for RpcTable {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let mut targets_borsh = Vec::new();
         let mut targets_serde = Vec::new();
+        let mut ops_borsh = Vec::new();
+        let mut ops_serde = Vec::new();
 
         for handler in self.handlers.elems.iter() {
             let Handler { hash_64, ident, fn_call, request_type, .. } = Handler::new(handler);
+            ops_borsh.push(hash_64.clone());
+            ops_serde.push(ident.clone());
 
             targets_borsh.push(quote! {
                 #hash_64 => {
@@ -59,6 +63,12 @@ impl ToTokens for RpcTable {
 
         quote! {
 
+                /// Ops accepted by [`call_with_borsh()`](Self::call_with_borsh)
+                pub const BORSH_OPS: &'static [u64] = &[#(#ops_borsh),*];
+
+                /// Ops accepted by [`call_with_serde()`](Self::call_with_serde)
+                pub const SERDE_OPS: &'static [&'static str] = &[#(#ops_serde),*];
+
                 pub async fn call_with_borsh(&self, op: u64, request: &[u8]) -> Result<Vec<u8>> {
                     match op {
                         #(#targets_borsh)*